@@ -0,0 +1,350 @@
+//! Export the byte stream as raw PCM audio ("sonification")
+//!
+//! The bytes are dumped unchanged as mono samples: unsigned 8-bit or signed
+//! 16-bit little-endian. An optional minimal WAV header lets audio editors open
+//! the file directly; without it the output is headerless and must be imported
+//! as raw data with matching settings.
+
+use eframe::egui;
+
+use crate::ui::PointerCursor;
+
+use super::state::FileDialogResult;
+use super::{spawn_file_dialog, BendApp};
+
+/// Lowest sample rate offered in the export dialog
+const MIN_SAMPLE_RATE: u32 = 1_000;
+
+/// Highest sample rate offered in the export dialog
+const MAX_SAMPLE_RATE: u32 = 192_000;
+
+/// Size of the canonical PCM WAV header in bytes
+const WAV_HEADER_LEN: usize = 44;
+
+/// Sample width used when interpreting bytes as audio
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PcmBitDepth {
+    /// Unsigned 8-bit samples (one byte per sample)
+    Eight,
+    /// Signed 16-bit little-endian samples (two bytes per sample)
+    Sixteen,
+}
+
+impl PcmBitDepth {
+    fn bits(self) -> u16 {
+        match self {
+            PcmBitDepth::Eight => 8,
+            PcmBitDepth::Sixteen => 16,
+        }
+    }
+}
+
+/// Options for the "Export as Raw PCM" dialog
+pub struct AudioExportState {
+    /// Whether the options dialog is showing
+    pub dialog_open: bool,
+    pub bit_depth: PcmBitDepth,
+    pub sample_rate: u32,
+    /// Wrap the samples in a WAV header instead of writing them headerless
+    pub wav_header: bool,
+}
+
+impl Default for AudioExportState {
+    fn default() -> Self {
+        Self {
+            dialog_open: false,
+            bit_depth: PcmBitDepth::Eight,
+            sample_rate: 44_100,
+            wav_header: true,
+        }
+    }
+}
+
+/// Message shown when the samples don't fit in a WAV file
+const WAV_TOO_LARGE: &str = "Too large for a WAV file (4 GiB limit); export without the header";
+
+/// RIFF and data chunk sizes for `len` sample bytes, or None when they don't
+/// fit WAV's 32-bit size fields
+fn wav_chunk_sizes(len: usize, bit_depth: PcmBitDepth) -> Option<(u32, u32)> {
+    let data_len = len.checked_next_multiple_of((bit_depth.bits() / 8) as usize)?;
+    let riff_len = data_len.checked_add(36 + data_len % 2)?;
+    Some((u32::try_from(riff_len).ok()?, u32::try_from(data_len).ok()?))
+}
+
+/// Build the output file: the bytes as-is, optionally behind a mono PCM WAV header.
+///
+/// 16-bit WAV output is zero-padded to a whole sample, and the data chunk gets
+/// the RIFF pad byte when its length is odd. Fails when the WAV would be too
+/// large for its header.
+fn encode_pcm(
+    data: &[u8],
+    bit_depth: PcmBitDepth,
+    sample_rate: u32,
+    wav_header: bool,
+) -> Result<Vec<u8>, String> {
+    if !wav_header {
+        return Ok(data.to_vec());
+    }
+
+    let (riff_len, data_len) =
+        wav_chunk_sizes(data.len(), bit_depth).ok_or_else(|| WAV_TOO_LARGE.to_string())?;
+    let block_align = bit_depth.bits() / 8;
+    let file_len = WAV_HEADER_LEN + data_len as usize + data_len as usize % 2;
+    let mut out = Vec::with_capacity(file_len);
+
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&riff_len.to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&1u16.to_le_bytes()); // mono
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&bit_depth.bits().to_le_bytes());
+
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    out.extend_from_slice(data);
+    out.resize(file_len, 0);
+    Ok(out)
+}
+
+/// Sample bytes of a WAV file's `data` chunk, or None if `bytes` isn't a WAV file.
+///
+/// Lets an exported-and-edited WAV be imported back without its header.
+pub(super) fn wav_data_chunk(bytes: &[u8]) -> Option<&[u8]> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return None;
+    }
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().ok()?) as usize;
+        let body = pos + 8;
+        if id == b"data" {
+            return Some(&bytes[body..body.saturating_add(size).min(bytes.len())]);
+        }
+        // Chunks are padded to an even length
+        pos = body.saturating_add(size).saturating_add(size % 2);
+    }
+    None
+}
+
+impl BendApp {
+    /// Show the PCM export options dialog
+    pub(super) fn show_audio_export_dialog(&mut self, ctx: &egui::Context) {
+        if !self.ui.audio_export.dialog_open {
+            return;
+        }
+        let Some(editor) = &self.doc.editor else {
+            self.ui.audio_export.dialog_open = false;
+            return;
+        };
+        let ranges = editor.selections();
+        let selected: usize = ranges.iter().map(|(start, end)| end - start).sum();
+        let source = match ranges.len() {
+            0 => format!("Whole buffer ({} bytes)", editor.len()),
+            1 => format!("Selection ({} bytes)", selected),
+            n => format!("Selection ({} bytes in {} ranges, joined)", selected, n),
+        };
+
+        let mut open = true;
+        let mut should_export = false;
+        let state = &mut self.ui.audio_export;
+        egui::Window::new("Export as Raw PCM")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(source);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label("Sample format:");
+                    ui.radio_value(&mut state.bit_depth, PcmBitDepth::Eight, "8-bit unsigned");
+                    ui.radio_value(
+                        &mut state.bit_depth,
+                        PcmBitDepth::Sixteen,
+                        "16-bit signed LE",
+                    );
+                });
+                ui.checkbox(&mut state.wav_header, "Add WAV header");
+                ui.add_enabled_ui(state.wav_header, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Sample rate:");
+                        ui.add(
+                            egui::DragValue::new(&mut state.sample_rate)
+                                .range(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE)
+                                .speed(100)
+                                .suffix(" Hz"),
+                        );
+                    });
+                });
+                if !state.wav_header {
+                    ui.label("Headerless output: import it as raw data with the same format.");
+                }
+                ui.add_space(10.0);
+                if ui.button("Export...").pointer_cursor().clicked() {
+                    should_export = true;
+                }
+            });
+
+        if should_export {
+            self.export_audio(ctx);
+            self.ui.audio_export.dialog_open = false;
+        } else if !open {
+            self.ui.audio_export.dialog_open = false;
+        }
+    }
+
+    /// Write the selection (its ranges joined in order, or the whole buffer)
+    /// as PCM audio (non-blocking)
+    fn export_audio(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() {
+            return;
+        }
+        let ranges = self.target_ranges();
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        if ranges.is_empty() {
+            return;
+        }
+        let state = &self.ui.audio_export;
+        let (bit_depth, sample_rate, wav_header) =
+            (state.bit_depth, state.sample_rate, state.wav_header);
+        let total: usize = ranges.iter().map(|range| range.len()).sum();
+        if wav_header && wav_chunk_sizes(total, bit_depth).is_none() {
+            log::warn!("PCM export refused: {} bytes don't fit a WAV header", total);
+            self.ui.flag_job_notice(WAV_TOO_LARGE.to_string());
+            return;
+        }
+        let bytes: Vec<u8> = ranges
+            .into_iter()
+            .flat_map(|range| {
+                editor
+                    .bytes_in_range(range.start, range.end)
+                    .iter()
+                    .copied()
+            })
+            .collect();
+
+        let extension = if wav_header { "wav" } else { "raw" };
+        let default_name = self
+            .doc
+            .name_stem()
+            .map(|s| format!("{}.{}", s, extension))
+            .unwrap_or_else(|| format!("export.{}", extension));
+
+        let rx = spawn_file_dialog(ctx, move || {
+            let result = pollster::block_on(async {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name(default_name)
+                    .add_filter(if wav_header { "WAV" } else { "Raw PCM" }, &[extension])
+                    .save_file()
+                    .await
+            });
+            let Some(handle) = result else {
+                return FileDialogResult::Cancelled;
+            };
+
+            let encoded = match encode_pcm(&bytes, bit_depth, sample_rate, wav_header) {
+                Ok(encoded) => encoded,
+                Err(e) => return FileDialogResult::ExportError(e),
+            };
+            let path = handle.path().to_path_buf();
+            match std::fs::write(&path, &encoded) {
+                Ok(()) => FileDialogResult::ExportSuccess(path),
+                Err(e) => FileDialogResult::ExportError(e.to_string()),
+            }
+        });
+
+        self.io.export_dialog_rx = Some(rx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes(bytes[offset..offset + 2].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_wav_data_chunk_round_trip() {
+        let data = [9, 8, 7, 6, 5];
+        let wav = encode_pcm(&data, PcmBitDepth::Eight, 8000, true).unwrap();
+        assert_eq!(wav_data_chunk(&wav), Some(&data[..]));
+
+        // Unknown chunks before "data" are skipped, including their pad byte
+        let mut with_list = wav[..12].to_vec();
+        with_list.extend_from_slice(b"LIST");
+        with_list.extend_from_slice(&3u32.to_le_bytes());
+        with_list.extend_from_slice(&[1, 2, 3, 0]);
+        with_list.extend_from_slice(&wav[12..]);
+        assert_eq!(wav_data_chunk(&with_list), Some(&data[..]));
+
+        assert_eq!(wav_data_chunk(&data), None);
+    }
+
+    #[test]
+    fn test_encode_pcm_headerless_is_byte_dump() {
+        let data = [0x00, 0x7F, 0x80, 0xFF, 0x01];
+        assert_eq!(
+            encode_pcm(&data, PcmBitDepth::Sixteen, 8000, false),
+            Ok(data.to_vec())
+        );
+    }
+
+    #[test]
+    fn test_encode_pcm_wav_8bit() {
+        let data = [1, 2, 3];
+        let wav = encode_pcm(&data, PcmBitDepth::Eight, 22_050, true).unwrap();
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(u16_at(&wav, 22), 1); // channels
+        assert_eq!(u32_at(&wav, 24), 22_050);
+        assert_eq!(u32_at(&wav, 28), 22_050); // byte rate
+        assert_eq!(u16_at(&wav, 32), 1); // block align
+        assert_eq!(u16_at(&wav, 34), 8);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(&wav, 40), 3);
+        assert_eq!(&wav[44..47], &data);
+        // Odd-length chunk gets a RIFF pad byte
+        assert_eq!(wav.len(), 48);
+        assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+    }
+
+    #[test]
+    fn test_encode_pcm_wav_16bit_pads_to_whole_sample() {
+        let wav = encode_pcm(&[0xAA, 0xBB, 0xCC], PcmBitDepth::Sixteen, 44_100, true).unwrap();
+        assert_eq!(u32_at(&wav, 28), 88_200);
+        assert_eq!(u16_at(&wav, 32), 2);
+        assert_eq!(u16_at(&wav, 34), 16);
+        assert_eq!(u32_at(&wav, 40), 4);
+        assert_eq!(&wav[44..], &[0xAA, 0xBB, 0xCC, 0x00]);
+        assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+    }
+
+    #[test]
+    fn test_wav_chunk_sizes_refuse_overflow() {
+        assert_eq!(wav_chunk_sizes(3, PcmBitDepth::Eight), Some((40, 3)));
+        assert_eq!(wav_chunk_sizes(3, PcmBitDepth::Sixteen), Some((40, 4)));
+        // The RIFF size (data + 36) is the first field to run out of room
+        let max = (u32::MAX - 36) as usize;
+        assert_eq!(
+            wav_chunk_sizes(max - 1, PcmBitDepth::Eight),
+            Some((u32::MAX - 1, u32::MAX - 37))
+        );
+        assert_eq!(wav_chunk_sizes(max, PcmBitDepth::Eight), None);
+        assert_eq!(wav_chunk_sizes(u32::MAX as usize, PcmBitDepth::Eight), None);
+        assert_eq!(wav_chunk_sizes(usize::MAX, PcmBitDepth::Sixteen), None);
+    }
+}
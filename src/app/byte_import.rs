@@ -0,0 +1,132 @@
+//! Import bytes from another file over the selection
+//!
+//! Supports the round trip of exporting raw data, editing it in another tool
+//! (e.g. an audio editor), and writing it back. WAV files are unwrapped to
+//! their sample data so the header doesn't land in the image.
+
+use eframe::egui;
+
+use super::audio_export::wav_data_chunk;
+use super::state::FileDialogResult;
+use super::{spawn_file_dialog, BendApp};
+
+/// Truncate or zero-pad `bytes` to exactly `len` bytes
+fn fit_to_len(bytes: &[u8], len: usize) -> Vec<u8> {
+    let mut fitted = bytes[..bytes.len().min(len)].to_vec();
+    fitted.resize(len, 0);
+    fitted
+}
+
+impl BendApp {
+    /// Pick a file whose bytes will overwrite the selection (non-blocking)
+    pub(super) fn import_bytes_dialog(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() || self.doc.editor.is_none() {
+            return;
+        }
+
+        let rx = spawn_file_dialog(ctx, move || {
+            let result = pollster::block_on(async {
+                rfd::AsyncFileDialog::new()
+                    .add_filter("All files", &["*"])
+                    .add_filter("WAV", &["wav"])
+                    .pick_file()
+                    .await
+            });
+            let Some(handle) = result else {
+                return FileDialogResult::Cancelled;
+            };
+            match std::fs::read(handle.path()) {
+                Ok(bytes) => FileDialogResult::ImportBytes(bytes),
+                Err(e) => FileDialogResult::ImportError(e.to_string()),
+            }
+        });
+
+        self.io.import_dialog_rx = Some(rx);
+    }
+
+    /// Overwrite the selection with `bytes`, truncated or zero-padded to fit.
+    /// Several selected ranges are filled in order, as one undoable edit.
+    /// Without a selection, writes from the cursor up to the end of the buffer.
+    pub(super) fn import_bytes(&mut self, bytes: &[u8]) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        let bytes = wav_data_chunk(bytes).unwrap_or(bytes);
+        let mut ranges = editor.selections();
+        if ranges.is_empty() {
+            let cursor = editor.cursor();
+            let end = cursor + bytes.len().min(editor.len().saturating_sub(cursor));
+            ranges.push((cursor, end));
+        }
+        let len: usize = ranges.iter().map(|(start, end)| end - start).sum();
+        if len == 0 {
+            return;
+        }
+        if ranges
+            .iter()
+            .any(|&(start, end)| self.doc.is_range_protected(start, end - start))
+        {
+            log::warn!("Import blocked: target range overlaps a protected section");
+            return;
+        }
+
+        if bytes.len() != len {
+            log::info!(
+                "Imported {} bytes into a {}-byte range ({})",
+                bytes.len(),
+                len,
+                if bytes.len() > len {
+                    "truncated"
+                } else {
+                    "zero-padded"
+                }
+            );
+        }
+        if let Some(editor) = &mut self.doc.editor {
+            let fitted = fit_to_len(bytes, len);
+            if let [(start, _)] = ranges.as_slice() {
+                editor.replace_bytes(*start, &fitted);
+            } else {
+                let mut rest = fitted.as_slice();
+                let spans: Vec<_> = ranges
+                    .iter()
+                    .map(|&(start, end)| {
+                        let (chunk, tail) = rest.split_at(end - start);
+                        rest = tail;
+                        (start, end - start, chunk.to_vec())
+                    })
+                    .collect();
+                editor.replace_spans(&spans);
+            }
+            self.doc.preview.mark_dirty();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::EditorState;
+
+    #[test]
+    fn test_fit_to_len() {
+        assert_eq!(fit_to_len(&[1, 2, 3, 4], 2), vec![1, 2]);
+        assert_eq!(fit_to_len(&[1, 2], 4), vec![1, 2, 0, 0]);
+        assert_eq!(fit_to_len(&[1, 2], 2), vec![1, 2]);
+        assert!(fit_to_len(&[], 0).is_empty());
+    }
+
+    #[test]
+    fn test_import_fills_selected_ranges_in_order() {
+        let mut app = BendApp::default();
+        let mut editor = EditorState::new(vec![0; 8]);
+        editor.set_selections(&[(1, 3), (5, 7)]);
+        app.doc.editor = Some(editor);
+
+        app.import_bytes(&[1, 2, 3]);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), &[0, 1, 2, 0, 0, 3, 0, 0]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0; 8]);
+    }
+}
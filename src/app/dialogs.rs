@@ -1,8 +1,12 @@
 use crate::formats::RiskLevel;
+use crate::ui::hex_editor::{apply_paste_bytes, describe_paste, describe_tiled_paste};
 use crate::ui::PointerCursor;
 use eframe::egui;
+use std::collections::VecDeque;
 
-use super::BendApp;
+use super::large_file::PendingLargeOpen;
+use super::trim::PendingTrim;
+use super::{BendApp, DocumentId};
 
 /// State for close confirmation and high-risk edit warning dialogs
 #[derive(Default)]
@@ -17,6 +21,36 @@ pub struct DialogState {
     pub pending_high_risk_edit: Option<PendingEdit>,
     /// Checkbox state for "don't warn again" in high-risk dialog
     pub high_risk_dont_show: bool,
+    /// Whether the "Revert to Original" confirmation dialog is showing
+    pub show_revert_confirm: bool,
+    /// Large paste waiting for user confirmation
+    pub pending_paste: Option<PendingPaste>,
+    /// Whether the "export undecodable buffer" confirmation is showing
+    pub show_export_decode_warning: bool,
+    /// Checkbox state for "don't ask again" in the export decode warning
+    pub export_decode_dont_ask: bool,
+    /// Name being entered in the "Save Point As" dialog (None = closed)
+    pub pending_save_point_name: Option<String>,
+    /// Document whose tab is waiting for "close with unsaved changes" confirmation
+    pub pending_close_tab: Option<DocumentId>,
+    /// Trim waiting for confirmation
+    pub pending_trim: Option<PendingTrim>,
+    /// Files over the size warning threshold waiting for confirmation to open,
+    /// asked about one at a time (a multi-file drop can queue several)
+    pub pending_large_open: VecDeque<PendingLargeOpen>,
+}
+
+/// Widget id of the "Save Point As" name field, used to focus it on open
+const SAVE_POINT_NAME_ID: &str = "save_point_name_input";
+
+/// A paste that exceeded the confirmation threshold
+pub struct PendingPaste {
+    /// Offset the bytes will be written at
+    pub offset: usize,
+    /// Parsed clipboard bytes
+    pub bytes: Vec<u8>,
+    /// Repeat `bytes` across the selection instead of writing them once
+    pub tiled: bool,
 }
 
 /// Type of pending edit (hex nibble or ASCII character)
@@ -165,4 +199,197 @@ impl BendApp {
                 });
             });
     }
+
+    /// Show the "Revert to Original" confirmation dialog
+    pub(super) fn show_revert_dialog(&mut self, ctx: &egui::Context) {
+        if !self.ui.dialogs.show_revert_confirm {
+            return;
+        }
+
+        egui::Window::new("Revert to Original")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Discard all edits and restore the original file contents?");
+                ui.label("You can undo this afterwards.");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Revert").pointer_cursor().clicked() {
+                        if let Some(editor) = &mut self.doc.editor {
+                            let _ = editor.revert_to_original();
+                        }
+                        self.ui.dialogs.show_revert_confirm = false;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        self.ui.dialogs.show_revert_confirm = false;
+                    }
+                });
+            });
+    }
+
+    /// Show the "working buffer doesn't decode" export confirmation dialog
+    pub(super) fn show_export_decode_dialog(&mut self, ctx: &egui::Context) {
+        if !self.ui.dialogs.show_export_decode_warning {
+            return;
+        }
+
+        let mut should_export = false;
+        let mut should_cancel = false;
+        let colors = self.ui.colors;
+        egui::Window::new("Export Undecodable Image")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.colored_label(
+                    colors.warning_text,
+                    "Working buffer doesn't decode as an image — export anyway?",
+                );
+                ui.label(
+                    "Intentionally broken files are fine; this catches accidental header damage.",
+                );
+                ui.add_space(8.0);
+                ui.checkbox(
+                    &mut self.ui.dialogs.export_decode_dont_ask,
+                    "Don't ask again",
+                );
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Export Anyway").pointer_cursor().clicked() {
+                        should_export = true;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_export || should_cancel {
+            if should_export && self.ui.dialogs.export_decode_dont_ask {
+                self.config.settings.warn_on_undecodable_export = false;
+                self.config.settings.save();
+            }
+            self.ui.dialogs.export_decode_dont_ask = false;
+            self.ui.dialogs.show_export_decode_warning = false;
+        }
+        if should_export {
+            self.export_file_unchecked(ctx);
+        }
+    }
+
+    /// Open the "Save Point As" dialog prefilled with the default name
+    pub(super) fn open_save_point_name_dialog(&mut self, ctx: &egui::Context) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        self.ui.dialogs.pending_save_point_name = Some(editor.next_save_point_name());
+        ctx.memory_mut(|m| m.request_focus(egui::Id::new(SAVE_POINT_NAME_ID)));
+    }
+
+    /// Show the "Save Point As" name prompt
+    pub(super) fn show_save_point_name_dialog(&mut self, ctx: &egui::Context) {
+        let Some(name) = &mut self.ui.dialogs.pending_save_point_name else {
+            return;
+        };
+
+        let mut should_create = false;
+        let mut should_cancel = false;
+        egui::Window::new("Save Point As")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Name:");
+                let response =
+                    ui.add(egui::TextEdit::singleline(name).id(egui::Id::new(SAVE_POINT_NAME_ID)));
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    should_create = true;
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    should_cancel = true;
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Create").pointer_cursor().clicked() {
+                        should_create = true;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_create {
+            if let (Some(name), Some(editor)) = (
+                self.ui.dialogs.pending_save_point_name.take(),
+                &mut self.doc.editor,
+            ) {
+                let name = match name.trim() {
+                    "" => editor.next_save_point_name(),
+                    trimmed => trimmed.to_string(),
+                };
+                editor.create_save_point(name);
+            }
+        } else if should_cancel {
+            self.ui.dialogs.pending_save_point_name = None;
+        }
+    }
+
+    /// Show the large-paste confirmation dialog
+    pub(super) fn show_paste_confirm_dialog(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.ui.dialogs.pending_paste else {
+            return;
+        };
+        let Some(editor) = &self.doc.editor else {
+            self.ui.dialogs.pending_paste = None;
+            return;
+        };
+        let ranges = editor.selections();
+        let summary = match ranges.first().filter(|_| pending.tiled) {
+            Some(&(start, _)) => {
+                let len = ranges.iter().map(|(start, end)| end - start).sum();
+                describe_tiled_paste(pending.bytes.len(), start, len)
+            }
+            None => describe_paste(
+                pending.bytes.len(),
+                pending.offset,
+                editor.len(),
+                editor.write_mode(),
+            ),
+        };
+
+        let mut should_paste = false;
+        let mut should_cancel = false;
+        egui::Window::new("Confirm Paste")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "The clipboard holds {} bytes.",
+                    pending.bytes.len()
+                ));
+                ui.label(summary);
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Paste").pointer_cursor().clicked() {
+                        should_paste = true;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_paste {
+            if let (Some(pending), Some(editor)) =
+                (self.ui.dialogs.pending_paste.take(), &mut self.doc.editor)
+            {
+                apply_paste_bytes(editor, pending.offset, &pending.bytes, pending.tiled);
+            }
+        } else if should_cancel {
+            self.ui.dialogs.pending_paste = None;
+        }
+    }
 }
@@ -0,0 +1,658 @@
+//! Applying byte effects to the current selection or a file section
+//!
+//! Each effect runs over a copy of the target bytes and is written back as a
+//! single undoable range operation. Without a selection, effects apply to the
+//! whole buffer (see `BendApp::target_ranges`).
+
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::effects;
+use crate::formats::{BmpParser, ImageFormat, JpegParser};
+use crate::ui::PointerCursor;
+
+use super::jobs::{JobOutput, BACKGROUND_JOB_THRESHOLD};
+use super::macros::{MacroState, MacroStep};
+use super::state::overlaps_any;
+use super::BendApp;
+
+/// Parameters shared by the effects in the Effects menu
+pub struct EffectsState {
+    /// Corruption intensity in 0.0-1.0, read by effects when they are applied
+    pub intensity: f32,
+    /// Key used by the XOR effect (masked down by the intensity)
+    pub xor_key: u8,
+    /// Per-byte effects touch only every Nth selected byte (1 = every byte)
+    pub stride: usize,
+    /// Row width in bytes assumed by the scanline shift
+    pub scanline_row_width: usize,
+    /// Extra bytes each successive row is shifted by
+    pub scanline_step: usize,
+    /// Byte value the gradient fill starts at
+    pub gradient_start: u8,
+    /// Byte value the gradient fill ends at
+    pub gradient_end: u8,
+    /// Whether the "Gradient Fill" dialog is showing
+    pub gradient_dialog_open: bool,
+    /// Most recently applied selection effect, for "Repeat Last Effect"
+    pub last_applied: Option<AppliedEffect>,
+    /// Saved macros and the one being recorded
+    pub macros: MacroState,
+}
+
+impl Default for EffectsState {
+    fn default() -> Self {
+        Self {
+            intensity: 0.5,
+            xor_key: 0xFF,
+            stride: 1,
+            scanline_row_width: DEFAULT_SCANLINE_ROW_WIDTH,
+            scanline_step: 1,
+            gradient_start: 0x00,
+            gradient_end: 0xFF,
+            gradient_dialog_open: false,
+            last_applied: None,
+            macros: MacroState::default(),
+        }
+    }
+}
+
+impl EffectsState {
+    /// Remember a selection effect that was just applied (for "Repeat Last
+    /// Effect" and the macro recorder)
+    pub(super) fn note_applied(&mut self, effect: AppliedEffect) {
+        self.last_applied = Some(effect);
+        self.macros.record(MacroStep::Effect(effect));
+    }
+}
+
+/// Effects offered by the structure tree's "Glitch This Section" menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SectionEffect {
+    RandomFlips,
+    Shuffle,
+    Xor,
+}
+
+impl SectionEffect {
+    /// Every section effect, in menu order
+    pub const ALL: [SectionEffect; 3] = [Self::RandomFlips, Self::Shuffle, Self::Xor];
+
+    /// Menu label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::RandomFlips => "Random Bit Flips",
+            Self::Shuffle => "Shuffle Bytes",
+            Self::Xor => "XOR with Key",
+        }
+    }
+}
+
+/// A selection effect together with the parameters it was applied with,
+/// so it can be repeated exactly on another range
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AppliedEffect {
+    SwapEndian {
+        word: usize,
+    },
+    RandomFlip {
+        stride: usize,
+        intensity: f32,
+    },
+    RotateBits {
+        stride: usize,
+        intensity: f32,
+    },
+    Xor {
+        stride: usize,
+        key: u8,
+        intensity: f32,
+    },
+    Shuffle,
+    ScanlineShift {
+        row_width: usize,
+        step: usize,
+    },
+    GradientFill {
+        start: u8,
+        end: u8,
+    },
+}
+
+impl AppliedEffect {
+    /// Name used in menus and log messages
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SwapEndian { .. } => "Swap endianness",
+            Self::RandomFlip { .. } => "Random bit flip",
+            Self::RotateBits { .. } => "Rotate bits",
+            Self::Xor { .. } => "XOR",
+            Self::Shuffle => "Shuffle",
+            Self::ScanlineShift { .. } => "Scanline shift",
+            Self::GradientFill { .. } => "Gradient fill",
+        }
+    }
+
+    /// Run the effect over all of `bytes`; randomized effects draw from `seed`
+    pub(super) fn apply(self, bytes: &mut [u8], seed: u64) {
+        let len = bytes.len();
+        match self {
+            Self::SwapEndian { word } => effects::swap_endian(bytes, 0..len, word),
+            Self::RandomFlip { stride, intensity } => {
+                effects::random_flip(bytes, 0..len, stride, intensity, seed)
+            }
+            Self::RotateBits { stride, intensity } => {
+                effects::rotate_bits(bytes, 0..len, stride, intensity)
+            }
+            Self::Xor {
+                stride,
+                key,
+                intensity,
+            } => effects::xor_mask(bytes, 0..len, stride, key, intensity),
+            Self::Shuffle => effects::shuffle(bytes, 0..len, seed),
+            Self::ScanlineShift { row_width, step } => {
+                effects::scanline_shift(bytes, 0..len, row_width, step)
+            }
+            Self::GradientFill { start, end } => effects::gradient_fill(bytes, 0..len, start, end),
+        }
+    }
+}
+
+/// Scanline row width used until one is entered or read from a BMP header
+const DEFAULT_SCANLINE_ROW_WIDTH: usize = 256;
+
+/// Largest scanline row width (and per-row shift) offered in the Effects menu
+pub(super) const MAX_SCANLINE_ROW_WIDTH: usize = 1 << 20;
+
+/// Largest "every Nth byte" stride offered in the Effects menu
+pub(super) const MAX_EFFECT_STRIDE: usize = 64;
+
+/// Per-byte flip probability of the JPEG scan glitch at 100% intensity.
+/// Scan data is dense, so even a fraction of a percent melts the image.
+const JPEG_SCAN_MAX_FLIP_RATE: f32 = 0.01;
+
+/// Seed for randomized effects, different on every application
+pub(super) fn effect_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// The JPEG scan glitch over `ranges` (buffer offsets) of `bytes`, which
+/// starts at buffer offset `base`
+pub(super) fn flip_scan_ranges(
+    bytes: &mut [u8],
+    base: usize,
+    ranges: &[Range<usize>],
+    intensity: f32,
+    seed: u64,
+) {
+    let probability = intensity * JPEG_SCAN_MAX_FLIP_RATE;
+    for (i, range) in ranges.iter().enumerate() {
+        effects::jpeg_scan_flip(
+            bytes,
+            range.start - base..range.end - base,
+            probability,
+            seed.wrapping_add(i as u64),
+        );
+    }
+}
+
+/// Scan data ranges of JPEG `data` that don't overlap the sorted `protected`
+/// ranges (see `DocumentState::protected_ranges`)
+pub(super) fn editable_scan_ranges(data: &[u8], protected: &[Range<usize>]) -> Vec<Range<usize>> {
+    JpegParser::scan_data_ranges(data)
+        .into_iter()
+        .filter(|r| {
+            let protected = overlaps_any(protected, r.start, r.len());
+            if protected {
+                log::warn!("JPEG scan glitch skipped protected range {:?}", r);
+            }
+            !protected
+        })
+        .collect()
+}
+
+impl BendApp {
+    /// Every range effects work on: each selected range (several after
+    /// Ctrl+drag), or the whole buffer when nothing is selected
+    pub(super) fn target_ranges(&self) -> Vec<Range<usize>> {
+        let Some(editor) = self.doc.editor.as_ref() else {
+            return Vec::new();
+        };
+        let ranges = editor.selections();
+        if ranges.is_empty() {
+            return (editor.len() > 0)
+                .then_some(0..editor.len())
+                .into_iter()
+                .collect();
+        }
+        ranges.into_iter().map(|(start, end)| start..end).collect()
+    }
+
+    /// Replace each of `target_ranges()` with `f(working, range)`, all as one
+    /// undoable edit. Results may be a different length than their range, in
+    /// which case the sections are re-parsed. Blocked (with a log warning) when
+    /// a range overlaps a protected section or a background job is running.
+    /// Returns whether anything was applied.
+    pub(super) fn apply_range_effect(
+        &mut self,
+        label: &str,
+        mut f: impl FnMut(&[u8], Range<usize>) -> Vec<u8>,
+    ) -> bool {
+        let ranges = self.target_ranges();
+        if ranges.is_empty() {
+            return false;
+        }
+        if self.is_job_running() {
+            log::warn!("{} skipped: another operation is still running", label);
+            return false;
+        }
+        if ranges
+            .iter()
+            .any(|range| self.doc.is_range_protected(range.start, range.len()))
+        {
+            log::warn!("{} blocked: range overlaps a protected section", label);
+            return false;
+        }
+        let Some(editor) = &mut self.doc.editor else {
+            return false;
+        };
+        if let [range] = ranges.as_slice() {
+            let bytes = f(editor.working(), range.clone());
+            editor.replace_range_with(range.start, range.len(), &bytes);
+        } else {
+            let spans: Vec<_> = ranges
+                .into_iter()
+                .map(|range| (range.start, range.len(), f(editor.working(), range)))
+                .collect();
+            editor.replace_spans(&spans);
+        }
+        if editor.take_length_changed() {
+            self.doc.reparse_sections();
+        }
+        self.doc.preview.mark_dirty();
+        true
+    }
+
+    /// Run `effect` over the selection (each range of a multi-range
+    /// selection, or the whole buffer) as one undoable edit and remember it
+    /// for "Repeat Last Effect"
+    fn apply_selection_effect(&mut self, effect: AppliedEffect) {
+        let ranges = self.target_ranges();
+        if let [range] = ranges.as_slice() {
+            if range.len() >= BACKGROUND_JOB_THRESHOLD {
+                self.apply_effect_to_range(range.start, range.end, effect);
+                return;
+            }
+        }
+        let mut seed = effect_seed();
+        let applied = self.apply_range_effect(effect.label(), |data, range| {
+            let mut bytes = data[range].to_vec();
+            effect.apply(&mut bytes, seed);
+            seed = seed.wrapping_add(1);
+            bytes
+        });
+        if applied {
+            self.ui.effects.note_applied(effect);
+        }
+    }
+
+    /// Run `effect` over `start..end` as one undoable edit, unless the range
+    /// is empty or overlaps a protected section. Large ranges run in the background.
+    fn apply_effect_to_range(&mut self, start: usize, end: usize, effect: AppliedEffect) {
+        if start >= end {
+            return;
+        }
+        if self.is_job_running() {
+            log::warn!(
+                "{} skipped: another operation is still running",
+                effect.label()
+            );
+            return;
+        }
+        if self.doc.is_range_protected(start, end - start) {
+            log::warn!(
+                "{} blocked: range overlaps a protected section",
+                effect.label()
+            );
+            return;
+        }
+        if let Some(editor) = &mut self.doc.editor {
+            let mut bytes = editor.bytes_in_range(start, end).to_vec();
+            let seed = effect_seed();
+            if bytes.len() >= BACKGROUND_JOB_THRESHOLD {
+                self.spawn_job(effect.label().to_string(), move || {
+                    effect.apply(&mut bytes, seed);
+                    JobOutput::Effect {
+                        start,
+                        bytes,
+                        effect,
+                    }
+                });
+                return;
+            }
+            effect.apply(&mut bytes, seed);
+            editor.replace_bytes(start, &bytes);
+            self.doc.preview.mark_dirty();
+            self.ui.effects.note_applied(effect);
+        }
+    }
+
+    /// Apply the last effect again, with the same parameters, to the current
+    /// selection (or the whole buffer)
+    pub(super) fn repeat_last_effect(&mut self) {
+        if let Some(effect) = self.ui.effects.last_applied {
+            self.apply_selection_effect(effect);
+        }
+    }
+
+    /// Swap byte order within each `word`-byte word of the target range
+    pub(super) fn swap_endian_selection(&mut self, word: usize) {
+        self.apply_selection_effect(AppliedEffect::SwapEndian { word });
+    }
+
+    /// Flip random bits in the target range, as often as the intensity allows
+    pub(super) fn random_flip_selection(&mut self) {
+        let (stride, intensity) = (self.ui.effects.stride, self.ui.effects.intensity);
+        self.apply_selection_effect(AppliedEffect::RandomFlip { stride, intensity });
+    }
+
+    /// Rotate the bits of each target byte by an intensity-scaled amount
+    pub(super) fn rotate_bits_selection(&mut self) {
+        let (stride, intensity) = (self.ui.effects.stride, self.ui.effects.intensity);
+        self.apply_selection_effect(AppliedEffect::RotateBits { stride, intensity });
+    }
+
+    /// Shift each row of the target range sideways by an increasing amount
+    pub(super) fn scanline_shift_selection(&mut self) {
+        self.apply_selection_effect(AppliedEffect::ScanlineShift {
+            row_width: self.ui.effects.scanline_row_width,
+            step: self.ui.effects.scanline_step,
+        });
+    }
+
+    /// Replace the target range with a ramp between the dialog's endpoint values
+    pub(super) fn gradient_fill_selection(&mut self) {
+        self.apply_selection_effect(AppliedEffect::GradientFill {
+            start: self.ui.effects.gradient_start,
+            end: self.ui.effects.gradient_end,
+        });
+    }
+
+    /// Show the "Gradient Fill" dialog (start/end byte values)
+    pub(super) fn show_gradient_fill_dialog(&mut self, ctx: &egui::Context) {
+        if !self.ui.effects.gradient_dialog_open {
+            return;
+        }
+        let ranges = self.target_ranges();
+        if ranges.is_empty() {
+            self.ui.effects.gradient_dialog_open = false;
+            return;
+        }
+        let total: usize = ranges.iter().map(Range::len).sum();
+        let selected = self.doc.editor.as_ref().map_or(0, |e| e.selections().len());
+        let target = match selected {
+            0 => format!("Whole buffer ({} bytes)", total),
+            1 => format!("Selection ({} bytes)", total),
+            n => format!("{} selected ranges ({} bytes)", n, total),
+        };
+
+        let mut open = true;
+        let mut should_apply = false;
+        let state = &mut self.ui.effects;
+        egui::Window::new("Gradient Fill")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(target);
+                ui.add_space(6.0);
+                egui::Grid::new("gradient_fill_grid").show(ui, |ui| {
+                    ui.label("Start value:");
+                    ui.add(
+                        egui::DragValue::new(&mut state.gradient_start)
+                            .hexadecimal(2, false, true)
+                            .prefix("0x"),
+                    );
+                    ui.end_row();
+                    ui.label("End value:");
+                    ui.add(
+                        egui::DragValue::new(&mut state.gradient_end)
+                            .hexadecimal(2, false, true)
+                            .prefix("0x"),
+                    );
+                    ui.end_row();
+                });
+                ui.add_space(10.0);
+                if ui.button("Apply").pointer_cursor().clicked() {
+                    should_apply = true;
+                }
+            });
+
+        if should_apply {
+            self.gradient_fill_selection();
+            self.ui.effects.gradient_dialog_open = false;
+        } else if !open {
+            self.ui.effects.gradient_dialog_open = false;
+        }
+    }
+
+    /// Row stride of the open BMP (pixel width plus padding), if it is one
+    pub(super) fn bmp_row_stride(&self) -> Option<usize> {
+        self.doc
+            .editor
+            .as_ref()
+            .and_then(|e| BmpParser::row_stride(e.working()))
+    }
+
+    /// Whether the working buffer is a JPEG (gates the JPEG-only effects)
+    pub(super) fn is_jpeg(&self) -> bool {
+        self.doc
+            .editor
+            .as_ref()
+            .is_some_and(|e| JpegParser.can_parse(e.working()))
+    }
+
+    /// Flip random bits across every scan's entropy-coded data, avoiding
+    /// markers. Applies to the whole file as one undoable edit, no selection
+    /// needed; scans overlapping a protected section are left alone.
+    pub(super) fn jpeg_scan_glitch(&mut self) {
+        let intensity = self.ui.effects.intensity;
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        if !JpegParser.can_parse(editor.working()) {
+            return;
+        }
+        let ranges = editable_scan_ranges(editor.working(), self.doc.protected_ranges());
+        let (Some(first), Some(last)) = (ranges.first(), ranges.last()) else {
+            log::warn!("JPEG scan glitch: no editable scan data found");
+            return;
+        };
+        let (start, end) = (first.start, last.end);
+
+        // Edit one contiguous copy spanning all scans so the undo step is a single range
+        let mut bytes = editor.bytes_in_range(start, end).to_vec();
+        flip_scan_ranges(&mut bytes, start, &ranges, intensity, effect_seed());
+        if let Some(editor) = &mut self.doc.editor {
+            editor.replace_bytes(start, &bytes);
+            self.doc.preview.mark_dirty();
+            self.ui
+                .effects
+                .macros
+                .record(MacroStep::JpegScanGlitch { intensity });
+        }
+    }
+
+    /// XOR the target range with the configured key, masked by the intensity
+    pub(super) fn xor_selection(&mut self) {
+        self.apply_selection_effect(self.current_xor());
+    }
+
+    /// The XOR effect with the current key, stride and intensity
+    fn current_xor(&self) -> AppliedEffect {
+        AppliedEffect::Xor {
+            stride: self.ui.effects.stride,
+            key: self.ui.effects.xor_key,
+            intensity: self.ui.effects.intensity,
+        }
+    }
+
+    /// Apply a section effect to exactly `start..end` (a structure tree
+    /// section), using the current intensity, key and stride
+    pub(super) fn glitch_section(&mut self, start: usize, end: usize, effect: SectionEffect) {
+        let (stride, intensity) = (self.ui.effects.stride, self.ui.effects.intensity);
+        let effect = match effect {
+            SectionEffect::RandomFlips => AppliedEffect::RandomFlip { stride, intensity },
+            SectionEffect::Shuffle => AppliedEffect::Shuffle,
+            SectionEffect::Xor => self.current_xor(),
+        };
+        self.apply_effect_to_range(start, end, effect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::EditorState;
+    use crate::formats::{FileSection, RiskLevel};
+
+    #[test]
+    fn test_glitch_section_is_one_undo_and_respects_protection() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0u8; 16]));
+        app.doc.set_sections(Some(vec![
+            FileSection::new("Header", 0, 8, RiskLevel::High),
+            FileSection::new("Data", 8, 16, RiskLevel::Safe),
+        ]));
+        app.doc.header_protection = true;
+        app.ui.effects.intensity = 1.0;
+
+        app.glitch_section(0, 8, SectionEffect::Xor);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert!(!editor.is_modified());
+
+        app.glitch_section(8, 16, SectionEffect::Xor);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(&editor.working()[..8], &[0; 8]);
+        assert_eq!(&editor.working()[8..], &[0xFF; 8]);
+        assert!(editor.undo());
+        assert!(!editor.is_modified());
+    }
+
+    #[test]
+    fn test_repeat_last_effect_reuses_parameters() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0u8; 8]));
+        app.ui.effects.intensity = 1.0;
+        app.ui.effects.xor_key = 0x0F;
+
+        // Nothing to repeat yet
+        app.doc.editor.as_mut().unwrap().set_selection(0, 2);
+        app.repeat_last_effect();
+        assert!(!app.doc.editor.as_ref().unwrap().is_modified());
+
+        app.xor_selection();
+        // Parameters changed afterwards don't affect the repeat
+        app.ui.effects.xor_key = 0xFF;
+        app.doc.editor.as_mut().unwrap().set_selection(4, 6);
+        app.repeat_last_effect();
+        assert_eq!(
+            app.doc.editor.as_ref().unwrap().working(),
+            &[0x0F, 0x0F, 0, 0, 0x0F, 0x0F, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_effects_without_selection_cover_whole_buffer() {
+        let mut app = BendApp::default();
+        assert!(app.target_ranges().is_empty());
+        app.doc.editor = Some(EditorState::new(vec![0u8; 4]));
+        assert_eq!(app.target_ranges(), vec![0..4]);
+        app.doc.editor.as_mut().unwrap().set_selection(1, 3);
+        assert_eq!(app.target_ranges(), vec![1..3]);
+
+        app.doc.editor.as_mut().unwrap().clear_selection();
+        app.ui.effects.intensity = 1.0;
+        app.xor_selection();
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), &[0xFF; 4]);
+    }
+
+    #[test]
+    fn test_effect_covers_only_the_selected_ranges() {
+        let mut app = BendApp::default();
+        let mut editor = EditorState::new(vec![0u8; 8]);
+        editor.set_selection(0, 2);
+        editor.add_selection_at(4);
+        editor.extend_selection_to(5);
+        // A Ctrl+click without a drag leaves no active range
+        editor.add_selection_at(7);
+        app.doc.editor = Some(editor);
+        assert_eq!(app.target_ranges(), vec![0..2, 4..6]);
+
+        app.ui.effects.intensity = 1.0;
+        app.xor_selection();
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), &[0xFF, 0xFF, 0, 0, 0xFF, 0xFF, 0, 0]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0; 8]);
+    }
+
+    #[test]
+    fn test_gradient_fill_is_one_undo_with_configured_endpoints() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0x55; 10]));
+        app.doc.editor.as_mut().unwrap().set_selection(2, 8);
+        app.ui.effects.gradient_start = 0x20;
+        app.ui.effects.gradient_end = 0x80;
+
+        app.gradient_fill_selection();
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working()[2], 0x20);
+        assert_eq!(editor.working()[7], 0x80);
+        assert_eq!(editor.working()[8], 0x55);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x55; 10]);
+        assert!(!editor.can_undo());
+        assert_eq!(
+            app.ui.effects.last_applied,
+            Some(AppliedEffect::GradientFill {
+                start: 0x20,
+                end: 0x80
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_range_effect_resizes_and_respects_protection() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![1, 2, 3, 4, 5, 6]));
+        app.doc.editor.as_mut().unwrap().set_selection(2, 4);
+
+        // The result can be longer than the range it replaces
+        assert!(app.apply_range_effect("Double", |data, range| {
+            data[range].iter().flat_map(|&b| [b, b]).collect()
+        }));
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), &[1, 2, 3, 3, 4, 4, 5, 6]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[1, 2, 3, 4, 5, 6]);
+
+        app.doc.set_sections(Some(vec![FileSection::new(
+            "Header",
+            0,
+            3,
+            RiskLevel::High,
+        )]));
+        app.doc.header_protection = true;
+        assert!(!app.apply_range_effect("Zero", |_, range| vec![0; range.len()]));
+        assert_eq!(
+            app.doc.editor.as_ref().unwrap().working(),
+            &[1, 2, 3, 4, 5, 6]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Compare the working buffer against another file picked from disk
+
+use eframe::egui;
+
+use super::state::FileDialogResult;
+use super::{spawn_file_dialog, BendApp};
+
+impl BendApp {
+    /// Pick a file to compare against the working buffer (non-blocking)
+    pub(super) fn compare_with_file_dialog(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() || self.doc.editor.is_none() {
+            return;
+        }
+
+        let rx = spawn_file_dialog(ctx, move || {
+            let result = pollster::block_on(async {
+                rfd::AsyncFileDialog::new()
+                    .add_filter("All files", &["*"])
+                    .pick_file()
+                    .await
+            });
+            let Some(handle) = result else {
+                return FileDialogResult::Cancelled;
+            };
+            match std::fs::read(handle.path()) {
+                Ok(bytes) => FileDialogResult::CompareBytes(handle.file_name(), bytes),
+                Err(e) => FileDialogResult::CompareError(e.to_string()),
+            }
+        });
+
+        self.io.compare_dialog_rx = Some(rx);
+    }
+
+    /// Diff the working buffer against `bytes` and show the results window
+    pub(super) fn load_comparison(&mut self, name: String, bytes: Vec<u8>) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        self.ui
+            .file_diff
+            .load(name, bytes, editor.working(), editor.edit_generation());
+    }
+}
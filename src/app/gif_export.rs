@@ -0,0 +1,180 @@
+//! Export the save point chain as an animated GIF
+//!
+//! Each save point is resolved to its full buffer, decoded, and written as one
+//! frame. Frames that fail to decode are skipped (glitched states often do).
+
+use eframe::egui;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+
+use super::state::FileDialogResult;
+use super::{spawn_file_dialog, BendApp};
+
+/// Result of encoding save point frames
+struct EncodedGif {
+    /// GIF file bytes
+    bytes: Vec<u8>,
+    /// Number of frames written
+    frame_count: usize,
+    /// Names of save points that failed to decode and were skipped
+    skipped: Vec<String>,
+}
+
+/// Decode each `(name, bytes)` pair and encode the results as a looping GIF.
+///
+/// Every frame is resized to the first decoded frame's dimensions so the
+/// animation has a stable canvas even when a glitch changed the image size.
+fn encode_frames_as_gif(
+    sources: &[(String, Vec<u8>)],
+    delay_ms: u32,
+) -> Result<EncodedGif, String> {
+    let mut frames: Vec<RgbaImage> = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (name, bytes) in sources {
+        let Ok(img) = image::load_from_memory(bytes) else {
+            skipped.push(name.clone());
+            continue;
+        };
+        let mut rgba = img.to_rgba8();
+        if let Some(first) = frames.first() {
+            if rgba.dimensions() != first.dimensions() {
+                rgba = image::imageops::resize(
+                    &rgba,
+                    first.width(),
+                    first.height(),
+                    image::imageops::FilterType::Nearest,
+                );
+            }
+        }
+        frames.push(rgba);
+    }
+
+    if frames.is_empty() {
+        return Err("None of the save points could be decoded as an image".to_string());
+    }
+
+    let frame_count = frames.len();
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = GifEncoder::new(&mut bytes);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+        let delay = Delay::from_numer_denom_ms(delay_ms, 1);
+        encoder
+            .encode_frames(
+                frames
+                    .into_iter()
+                    .map(|buffer| Frame::from_parts(buffer, 0, 0, delay)),
+            )
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(EncodedGif {
+        bytes,
+        frame_count,
+        skipped,
+    })
+}
+
+impl BendApp {
+    /// Export every save point as a frame of an animated GIF (non-blocking)
+    pub(super) fn export_save_points_gif(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() {
+            return;
+        }
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+
+        // Resolve save point bytes on the main thread; decoding happens in the background
+        let sources: Vec<(String, Vec<u8>)> = editor
+            .save_points()
+            .iter()
+            .filter_map(|sp| Some((sp.name.clone(), editor.save_point_bytes(sp.id)?)))
+            .collect();
+        if sources.is_empty() {
+            return;
+        }
+
+        let delay_ms = self.config.settings.gif_frame_delay_ms;
+        let default_name = self
+            .doc
+            .name_stem()
+            .map(|s| format!("{}_save_points.gif", s))
+            .unwrap_or_else(|| "save_points.gif".to_string());
+
+        let rx = spawn_file_dialog(ctx, move || {
+            let result = pollster::block_on(async {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name(default_name)
+                    .add_filter("GIF", &["gif"])
+                    .save_file()
+                    .await
+            });
+            let Some(handle) = result else {
+                return FileDialogResult::Cancelled;
+            };
+
+            let encoded = match encode_frames_as_gif(&sources, delay_ms) {
+                Ok(encoded) => encoded,
+                Err(e) => return FileDialogResult::ExportError(e),
+            };
+            for name in &encoded.skipped {
+                log::warn!("Skipped save point \"{}\": unable to decode image", name);
+            }
+            log::info!("Encoded {} GIF frame(s)", encoded.frame_count);
+
+            let path = handle.path().to_path_buf();
+            match std::fs::write(&path, &encoded.bytes) {
+                Ok(()) => FileDialogResult::ExportSuccess(path),
+                Err(e) => FileDialogResult::ExportError(e.to_string()),
+            }
+        });
+
+        self.io.export_dialog_rx = Some(rx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::AnimationDecoder;
+    use std::io::Cursor;
+
+    fn png_bytes(width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+        let img = image::RgbImage::from_pixel(width, height, image::Rgb(color));
+        let mut out = Vec::new();
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(&mut Cursor::new(&mut out), image::ImageFormat::Png)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_encode_frames_skips_undecodable() {
+        let sources = vec![
+            ("A".to_string(), png_bytes(4, 4, [255, 0, 0])),
+            ("Broken".to_string(), vec![0x00, 0x01, 0x02]),
+            ("B".to_string(), png_bytes(8, 2, [0, 0, 255])),
+        ];
+
+        let encoded = encode_frames_as_gif(&sources, 100).unwrap();
+        assert_eq!(encoded.frame_count, 2);
+        assert_eq!(encoded.skipped, vec!["Broken".to_string()]);
+
+        let decoder = image::codecs::gif::GifDecoder::new(Cursor::new(&encoded.bytes)).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 2);
+        // Second frame was resized to the first frame's canvas
+        assert_eq!(frames[1].buffer().dimensions(), (4, 4));
+        assert_eq!(frames[0].delay().numer_denom_ms(), (100, 1));
+    }
+
+    #[test]
+    fn test_encode_frames_all_broken_is_error() {
+        let sources = vec![("Broken".to_string(), vec![0xFF; 8])];
+        assert!(encode_frames_as_gif(&sources, 100).is_err());
+    }
+}
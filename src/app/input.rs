@@ -1,63 +1,105 @@
 use eframe::egui;
 
 use crate::editor::buffer::EditMode;
+use crate::keybindings::ShortcutAction;
 
 use super::toolbar::InputActions;
 use super::BendApp;
 
+/// Whether the focused widget is a text field. Only `TextEdit`s store
+/// `TextEditState`, so a button focused with Tab doesn't count.
+fn text_edit_focused(ctx: &egui::Context) -> bool {
+    ctx.memory(|m| m.focused())
+        .is_some_and(|id| egui::TextEdit::load_state(ctx, id).is_some())
+}
+
 impl BendApp {
     /// Handle dropped files and keyboard shortcuts
     /// Returns flags for deferred actions
     pub(super) fn handle_input(&mut self, ctx: &egui::Context) -> InputActions {
         let mut actions = InputActions::default();
+        // A focused text field (Find box, Go to Offset, ...) gets Ctrl+Z/Ctrl+Y
+        // for its own undo, so buffer undo/redo must not fire underneath it
+        let text_input_focused = text_edit_focused(ctx);
 
         ctx.input(|i| {
-            for file in &i.raw.dropped_files {
-                if let Some(path) = &file.path {
-                    self.open_file(path.clone());
-                }
-            }
+            // Opened after input handling so a multi-file drop becomes one tab per file
+            actions.dropped_files = i
+                .raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect();
 
-            // Global keyboard shortcuts
-            let ctrl = i.modifiers.ctrl || i.modifiers.mac_cmd;
-            let shift = i.modifiers.shift;
-            if ctrl && i.key_pressed(egui::Key::O) {
+            // Global keyboard shortcuts (rebindable in Preferences); none fire
+            // while Preferences is waiting for a key to bind
+            if self.ui.settings_dialog_state.is_capturing_key() {
+                return;
+            }
+            let bindings = &self.config.settings.keybindings;
+            let pressed = |action| bindings.pressed(action, i);
+            let has_file = self.doc.editor.is_some();
+            if pressed(ShortcutAction::CommandPalette) {
+                actions.command_palette = true;
+            }
+            if pressed(ShortcutAction::Open) {
                 actions.open = true;
             }
-            if ctrl && i.key_pressed(egui::Key::E) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::Export) && has_file {
                 actions.export = true;
             }
-            if ctrl && i.key_pressed(egui::Key::F) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::QuickExport) && has_file {
+                actions.quick_export = true;
+            }
+            if pressed(ShortcutAction::FindReplace) && has_file {
                 actions.search = true;
             }
-            if ctrl && i.key_pressed(egui::Key::G) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::GoToOffset) && has_file {
                 actions.go_to = true;
             }
-            // Undo: Ctrl+Z / Cmd+Z
-            if ctrl && !shift && i.key_pressed(egui::Key::Z) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::Undo) && has_file && !text_input_focused {
                 actions.undo = true;
             }
-            // Redo: Ctrl+Shift+Z / Cmd+Shift+Z (or Ctrl+Y on some platforms)
-            if ctrl && shift && i.key_pressed(egui::Key::Z) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::Redo) && has_file && !text_input_focused {
                 actions.redo = true;
             }
-            if ctrl && i.key_pressed(egui::Key::Y) && self.doc.editor.is_some() {
-                actions.redo = true;
+            // Undo/redo: mouse back/forward side buttons (optional)
+            if self.config.settings.mouse_history_buttons && has_file {
+                if i.pointer.button_pressed(egui::PointerButton::Extra1) {
+                    actions.undo = true;
+                }
+                if i.pointer.button_pressed(egui::PointerButton::Extra2) {
+                    actions.redo = true;
+                }
             }
-            // Create save point: Ctrl+S / Cmd+S
-            if ctrl && i.key_pressed(egui::Key::S) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::CreateSavePoint) && has_file {
                 actions.create_save_point = true;
             }
-            // Add bookmark: Ctrl+D / Cmd+D
-            if ctrl && i.key_pressed(egui::Key::D) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::NameSavePoint) && has_file {
+                actions.name_save_point = true;
+            }
+            if pressed(ShortcutAction::AddBookmark) && has_file {
                 actions.add_bookmark = true;
             }
-            // Refresh preview: Ctrl+R / Cmd+R
-            if ctrl && i.key_pressed(egui::Key::R) && self.doc.editor.is_some() {
+            if pressed(ShortcutAction::RefreshPreview) && has_file {
                 actions.refresh_preview = true;
             }
-            // Toggle edit mode: Ctrl+M / Cmd+M
-            if ctrl && i.key_pressed(egui::Key::M) {
+            if pressed(ShortcutAction::RepeatLastEffect) && has_file {
+                actions.repeat_last_effect = true;
+            }
+            if pressed(ShortcutAction::ToggleComparison) && has_file {
+                actions.toggle_comparison = true;
+            }
+            if pressed(ShortcutAction::RepeatByteJumpNext) && has_file {
+                actions.repeat_byte_jump = Some(true);
+            }
+            if pressed(ShortcutAction::RepeatByteJumpPrevious) && has_file {
+                actions.repeat_byte_jump = Some(false);
+            }
+            if pressed(ShortcutAction::MatchingMarker) && has_file {
+                actions.matching_marker = true;
+            }
+            if pressed(ShortcutAction::ToggleEditMode) {
                 if let Some(editor) = self.doc.editor.as_ref() {
                     actions.set_edit_mode = Some(match editor.edit_mode() {
                         EditMode::Hex => EditMode::Ascii,
@@ -65,8 +107,16 @@ impl BendApp {
                     });
                 }
             }
-            // F1: Show keyboard shortcuts help
-            if i.key_pressed(egui::Key::F1) {
+            if pressed(ShortcutAction::NextTab) {
+                actions.cycle_tab = Some(true);
+            }
+            if pressed(ShortcutAction::PreviousTab) {
+                actions.cycle_tab = Some(false);
+            }
+            if pressed(ShortcutAction::CloseTab) && (has_file || self.tabs.len() > 1) {
+                actions.close_tab = true;
+            }
+            if pressed(ShortcutAction::ShortcutsHelp) {
                 self.ui.shortcuts_dialog_state.open();
             }
         });
@@ -74,3 +124,32 @@ impl BendApp {
         actions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_text_fields_count_as_text_input() {
+        let ctx = egui::Context::default();
+        let mut text = String::new();
+        let field = egui::Id::new("field");
+        let mut run = || {
+            let _ = ctx.run(egui::RawInput::default(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let _ = ui.button("Open");
+                    ui.add(egui::TextEdit::singleline(&mut text).id(field));
+                });
+            });
+        };
+        run();
+        assert!(!text_edit_focused(&ctx));
+        ctx.memory_mut(|m| m.request_focus(field));
+        run();
+        assert!(text_edit_focused(&ctx));
+
+        // A focused button (e.g. reached with Tab) doesn't block undo/redo
+        ctx.memory_mut(|m| m.request_focus(egui::Id::new("button")));
+        assert!(!text_edit_focused(&ctx));
+    }
+}
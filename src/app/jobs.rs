@@ -0,0 +1,295 @@
+//! Heavy edits on large buffers, run on a worker thread
+//!
+//! The worker only sees a copy of the bytes it needs and sends back the edited
+//! result; it is committed to the editor on the UI thread when it arrives. If
+//! the buffer changed in the meantime the result is dropped rather than
+//! applied over newer edits. Typing and undo/redo wait while a job runs, and
+//! a dropped or cancelled job is reported in the status bar.
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::editor::buffer::splice_ranges;
+use crate::editor::search::{non_overlapping_matches, SearchMessage};
+
+use super::effects::AppliedEffect;
+use super::macros::MacroStep;
+use super::BendApp;
+
+/// Effects, macros and Replace All over at least this many bytes run in the background
+pub(super) const BACKGROUND_JOB_THRESHOLD: usize = 8 * 1024 * 1024;
+
+/// Finished work from a background job, ready to commit
+pub(super) enum JobOutput {
+    /// `bytes` replace the range starting at `start`
+    Effect {
+        start: usize,
+        bytes: Vec<u8>,
+        effect: AppliedEffect,
+    },
+    /// A replayed macro: each `(start, bytes)` span overwrites as many bytes
+    Macro {
+        spans: Vec<(usize, Vec<u8>)>,
+        steps: Vec<MacroStep>,
+    },
+    /// A Replace All splice of the whole working buffer (see `splice_ranges`)
+    ReplaceAll {
+        spliced: Vec<u8>,
+        removed: Vec<(usize, Vec<u8>)>,
+        replacement: Vec<u8>,
+        /// Search dialog message to show once committed (e.g. skipped matches)
+        message: Option<SearchMessage>,
+    },
+}
+
+/// A running background job
+pub struct BackgroundJob {
+    /// What is running, shown in the status bar
+    label: String,
+    started: Instant,
+    /// Edit generation the job's input was taken at
+    generation: u64,
+    rx: mpsc::Receiver<JobOutput>,
+}
+
+impl BackgroundJob {
+    /// Status bar text and time spent so far
+    pub fn status(&self) -> (&str, Duration) {
+        (&self.label, self.started.elapsed())
+    }
+}
+
+impl BendApp {
+    /// Whether a background job is running (new edits wait for it)
+    pub(crate) fn is_job_running(&self) -> bool {
+        self.io.job.is_some()
+    }
+
+    /// Tell the user an edit was refused because a background job is running
+    pub(crate) fn note_edit_paused_for_job(&mut self) {
+        if let Some(job) = &self.io.job {
+            let text = format!("Editing paused until {} finishes", job.label);
+            self.ui.flag_job_notice(text);
+        }
+    }
+
+    /// Run `work` on a worker thread against the current edit generation
+    pub(super) fn spawn_job<F>(&mut self, label: String, work: F)
+    where
+        F: FnOnce() -> JobOutput + Send + 'static,
+    {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        let generation = editor.edit_generation();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(work());
+        });
+        self.io.job = Some(BackgroundJob {
+            label,
+            started: Instant::now(),
+            generation,
+            rx,
+        });
+    }
+
+    /// Start a resizing Replace All on a worker thread if the buffer is large
+    /// enough for it to matter. Returns false (doing nothing) for small buffers,
+    /// which the caller replaces inline.
+    pub fn replace_all_in_background(
+        &mut self,
+        offsets: &[usize],
+        old_len: usize,
+        replacement: &[u8],
+        message: Option<SearchMessage>,
+    ) -> bool {
+        let Some(editor) = &self.doc.editor else {
+            return false;
+        };
+        if editor.len() < BACKGROUND_JOB_THRESHOLD || self.is_job_running() {
+            return false;
+        }
+        let mut buffer = editor.working().to_vec();
+        let offsets = non_overlapping_matches(offsets, old_len);
+        let replacement = replacement.to_vec();
+        let count = offsets.len();
+        self.spawn_job(format!("Replacing {} matches", count), move || {
+            let removed = splice_ranges(&mut buffer, &offsets, old_len, &replacement);
+            let message = message
+                .unwrap_or_else(|| SearchMessage::Info(format!("Replaced {} matches", count)));
+            JobOutput::ReplaceAll {
+                spliced: buffer,
+                removed,
+                replacement,
+                message: Some(message),
+            }
+        });
+        self.ui.search_state.message = Some(SearchMessage::Info(format!(
+            "Replacing {} matches in the background...",
+            count
+        )));
+        true
+    }
+
+    /// Forget a running job (e.g. when the document it was editing goes away)
+    pub(super) fn cancel_job(&mut self) {
+        if let Some(job) = self.io.job.take() {
+            log::warn!("{} cancelled", job.label);
+            self.ui.flag_job_notice(format!("{} cancelled", job.label));
+        }
+    }
+
+    /// Commit the running job's result if it has finished
+    pub(super) fn poll_job(&mut self) {
+        let Some(job) = &self.io.job else {
+            return;
+        };
+        let output = match job.rx.try_recv() {
+            Ok(output) => output,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                log::error!("{} failed", job.label);
+                self.ui.flag_job_notice(format!("{} failed", job.label));
+                self.io.job = None;
+                return;
+            }
+        };
+        let job = self.io.job.take().expect("job checked above");
+        let Some(editor) = &mut self.doc.editor else {
+            return;
+        };
+        if editor.edit_generation() != job.generation {
+            log::warn!("{} discarded: the buffer changed while it ran", job.label);
+            self.ui.flag_job_notice(format!(
+                "{} discarded: the buffer changed while it ran",
+                job.label
+            ));
+            return;
+        }
+
+        match output {
+            JobOutput::Effect {
+                start,
+                bytes,
+                effect,
+            } => {
+                editor.replace_bytes(start, &bytes);
+                self.doc.preview.mark_dirty();
+                self.ui.effects.note_applied(effect);
+            }
+            JobOutput::Macro { spans, steps } => self.commit_macro(spans, steps),
+            JobOutput::ReplaceAll {
+                spliced,
+                removed,
+                replacement,
+                message,
+            } => {
+                editor.commit_spliced_ranges(spliced, removed, &replacement);
+                self.refresh_search();
+                if self.ui.search_state.message.is_none() {
+                    self.ui.search_state.message = message;
+                }
+            }
+        }
+        log::info!(
+            "{} finished in {:.1}s",
+            job.label,
+            job.started.elapsed().as_secs_f32()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::EditorState;
+
+    /// Wait for the job to finish and commit it
+    fn finish(app: &mut BendApp) {
+        while app.is_job_running() {
+            app.poll_job();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_job_result_is_committed_as_one_undo() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0; 8]));
+        app.spawn_job("Test".into(), || JobOutput::Effect {
+            start: 2,
+            bytes: vec![0xFF; 4],
+            effect: AppliedEffect::Shuffle,
+        });
+        finish(&mut app);
+
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), &[0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0; 8]);
+        assert_eq!(app.ui.effects.last_applied, Some(AppliedEffect::Shuffle));
+    }
+
+    #[test]
+    fn test_job_result_is_dropped_after_newer_edits() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0; 4]));
+        let (release, wait) = mpsc::channel::<()>();
+        app.spawn_job("Test".into(), move || {
+            let _ = wait.recv();
+            JobOutput::Effect {
+                start: 0,
+                bytes: vec![0xFF; 4],
+                effect: AppliedEffect::Shuffle,
+            }
+        });
+        app.doc.editor.as_mut().unwrap().edit_byte(3, 7);
+        release.send(()).unwrap();
+        finish(&mut app);
+
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), &[0, 0, 0, 7]);
+        assert_eq!(app.ui.effects.last_applied, None);
+        let (notice, _) = app.ui.recent_job_notice().unwrap();
+        assert!(notice.contains("discarded"));
+    }
+
+    #[test]
+    fn test_background_replace_all_skips_overlapping_matches() {
+        let mut app = BendApp::default();
+        let mut data = vec![0; BACKGROUND_JOB_THRESHOLD];
+        data[..4].copy_from_slice(b"aaaa");
+        app.doc.editor = Some(EditorState::new(data));
+
+        assert!(app.replace_all_in_background(&[0, 1], 3, b"b", None));
+        finish(&mut app);
+
+        let editor = app.doc.editor.as_ref().unwrap();
+        assert_eq!(editor.len(), BACKGROUND_JOB_THRESHOLD - 2);
+        assert_eq!(&editor.working()[..3], b"ba\0");
+    }
+
+    #[test]
+    fn test_undo_waits_for_running_job() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0; 4]));
+        app.doc.editor.as_mut().unwrap().edit_byte(0, 9);
+        let (release, wait) = mpsc::channel::<()>();
+        app.spawn_job("Test".into(), move || {
+            let _ = wait.recv();
+            JobOutput::Effect {
+                start: 1,
+                bytes: vec![0xFF],
+                effect: AppliedEffect::Shuffle,
+            }
+        });
+
+        app.do_undo();
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), &[9, 0, 0, 0]);
+        assert!(app.ui.recent_job_notice().is_some());
+
+        release.send(()).unwrap();
+        finish(&mut app);
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), &[9, 0xFF, 0, 0]);
+    }
+}
@@ -0,0 +1,188 @@
+//! Confirmation before opening a file above the size warning threshold
+//!
+//! Every document keeps both the original and the working buffer in memory,
+//! so a file costs about twice its size. Files larger than the "large file"
+//! setting wait for confirmation instead of loading straight away.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::ui::PointerCursor;
+
+use super::BendApp;
+
+/// Bytes per unit of the `large_file_warn_mb` setting
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// A file open waiting for confirmation because of its size
+pub struct PendingLargeOpen {
+    pub path: PathBuf,
+    /// Size on disk when the open was requested
+    pub size: u64,
+}
+
+/// Whether a `size`-byte file is over a `limit_mb` threshold (0 = never)
+fn exceeds_limit(size: u64, limit_mb: u64) -> bool {
+    limit_mb > 0 && size > limit_mb.saturating_mul(BYTES_PER_MB)
+}
+
+/// Human-readable size, e.g. "512.0 MB" or "1.5 GB"
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} bytes", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+impl BendApp {
+    /// Queue a confirmation if `path` is over the size threshold (behind any
+    /// already waiting). Returns whether the open has to wait for it.
+    pub(super) fn needs_large_file_confirmation(&mut self, path: &Path) -> bool {
+        let limit_mb = self.config.settings.large_file_warn_mb;
+        let Ok(size) = std::fs::metadata(path).map(|m| m.len()) else {
+            // Unreadable files fall through to the usual load error
+            return false;
+        };
+        if !exceeds_limit(size, limit_mb) {
+            return false;
+        }
+        let queue = &mut self.ui.dialogs.pending_large_open;
+        if !queue.iter().any(|pending| pending.path == path) {
+            queue.push_back(PendingLargeOpen {
+                path: path.to_path_buf(),
+                size,
+            });
+        }
+        true
+    }
+
+    /// Show the large-file confirmation dialog for the first queued file
+    pub(super) fn show_large_file_dialog(&mut self, ctx: &egui::Context) {
+        let queue = &self.ui.dialogs.pending_large_open;
+        let Some(pending) = queue.front() else {
+            return;
+        };
+        let waiting = queue.len() - 1;
+        let name = pending
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| pending.path.display().to_string());
+        let size = format_size(pending.size);
+        let memory = format_size(pending.size.saturating_mul(2));
+
+        let mut should_open = false;
+        let mut should_cancel = false;
+        let mut should_cancel_all = false;
+        egui::Window::new("Open Large File")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("{} is {}.", name, size));
+                ui.label(format!(
+                    "Bend keeps the original and an edited copy in memory, \
+                     so opening it needs about {}.",
+                    memory
+                ));
+                if waiting > 0 {
+                    ui.label(format!("{} more large file(s) after this one.", waiting));
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Open").pointer_cursor().clicked() {
+                        should_open = true;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                    if waiting > 0 && ui.button("Cancel All").pointer_cursor().clicked() {
+                        should_cancel_all = true;
+                    }
+                });
+            });
+
+        if should_open {
+            if let Some(pending) = self.ui.dialogs.pending_large_open.pop_front() {
+                self.open_file_unchecked(pending.path);
+            }
+        } else if should_cancel {
+            self.ui.dialogs.pending_large_open.pop_front();
+        } else if should_cancel_all {
+            self.ui.dialogs.pending_large_open.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeds_limit() {
+        assert!(!exceeds_limit(256 * BYTES_PER_MB, 256));
+        assert!(exceeds_limit(256 * BYTES_PER_MB + 1, 256));
+        // 0 turns the check off
+        assert!(!exceeds_limit(u64::MAX, 0));
+        assert!(!exceeds_limit(10, u64::MAX));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(256 * BYTES_PER_MB), "256.0 MB");
+        assert_eq!(format_size(3 * 1024 * BYTES_PER_MB / 2), "1.5 GB");
+    }
+
+    #[test]
+    fn test_large_file_waits_for_confirmation() {
+        let path = std::env::temp_dir().join(format!("bend_large_{}.bmp", std::process::id()));
+        std::fs::write(&path, vec![0u8; 2 * BYTES_PER_MB as usize]).unwrap();
+
+        let mut app = BendApp::default();
+        app.config.settings.large_file_warn_mb = 1;
+        assert!(app.needs_large_file_confirmation(&path));
+        let pending = app.ui.dialogs.pending_large_open.pop_front().unwrap();
+        assert_eq!(pending.size, 2 * BYTES_PER_MB);
+
+        app.config.settings.large_file_warn_mb = 2;
+        assert!(!app.needs_large_file_confirmation(&path));
+        assert!(app.ui.dialogs.pending_large_open.is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_large_files_from_one_drop_all_wait_in_order() {
+        let dir = std::env::temp_dir().join(format!("bend_large_drop_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths: Vec<_> = ["a.bmp", "b.bmp"].iter().map(|n| dir.join(n)).collect();
+        for path in &paths {
+            std::fs::write(path, vec![0u8; 2 * BYTES_PER_MB as usize]).unwrap();
+        }
+
+        let mut app = BendApp::default();
+        app.config.settings.large_file_warn_mb = 1;
+        app.open_files(paths.clone());
+        // Asking again for a queued file doesn't queue it twice
+        assert!(app.needs_large_file_confirmation(&paths[0]));
+        let queued: Vec<_> = app
+            .ui
+            .dialogs
+            .pending_large_open
+            .iter()
+            .map(|pending| pending.path.clone())
+            .collect();
+        assert_eq!(queued, paths);
+        assert!(app.doc.editor.is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
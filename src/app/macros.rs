@@ -0,0 +1,453 @@
+//! Effect macros: record a chain of effects and replay it in one click
+//!
+//! While recording, every applied effect is appended as a step with the
+//! parameters it ran with. Replaying runs the steps in order over a copy of
+//! the target ranges (the whole buffer when a step needs it) and writes the
+//! result back as one undoable edit. Macros are kept
+//! in `macros.json` next to the settings file so they work on any document.
+
+use std::path::PathBuf;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::formats::{ImageFormat, JpegParser};
+use crate::ui::PointerCursor;
+
+use super::effects::{editable_scan_ranges, effect_seed, flip_scan_ranges, AppliedEffect};
+use super::jobs::{JobOutput, BACKGROUND_JOB_THRESHOLD};
+use super::BendApp;
+
+/// Widget id of the macro name field, used to focus it on open
+const MACRO_NAME_ID: &str = "macro_name_input";
+
+/// One recorded effect
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MacroStep {
+    /// A selection effect, replayed over the selection (or whole buffer)
+    Effect(AppliedEffect),
+    /// The whole-file JPEG scan glitch
+    JpegScanGlitch { intensity: f32 },
+}
+
+impl MacroStep {
+    /// Name used in menus and log messages
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Effect(effect) => effect.label(),
+            Self::JpegScanGlitch { .. } => "JPEG scan glitch",
+        }
+    }
+}
+
+/// A named, saved chain of effects
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EffectMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+/// Saved macros and the recorder
+#[derive(Default)]
+pub struct MacroState {
+    /// Saved macros, in creation order
+    pub macros: Vec<EffectMacro>,
+    /// Steps recorded so far (None = not recording)
+    recording: Option<Vec<MacroStep>>,
+    /// A finished recording waiting for a name
+    pending: Option<(String, Vec<MacroStep>)>,
+}
+
+impl MacroState {
+    /// Whether the recorder is on
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Number of steps recorded so far
+    pub fn recorded_steps(&self) -> usize {
+        self.recording.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Append `step` if recording
+    pub(super) fn record(&mut self, step: MacroStep) {
+        if let Some(steps) = &mut self.recording {
+            steps.push(step);
+        }
+    }
+
+    /// Start recording, or stop and ask for a name (an empty recording is dropped)
+    fn toggle_recording(&mut self) -> bool {
+        match self.recording.take() {
+            None => {
+                self.recording = Some(Vec::new());
+                false
+            }
+            Some(steps) if steps.is_empty() => false,
+            Some(steps) => {
+                let name = format!("Macro {}", self.macros.len() + 1);
+                self.pending = Some((name, steps));
+                true
+            }
+        }
+    }
+
+    /// Path of the macros file
+    fn macros_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|mut path| {
+            path.push("bend-rs");
+            path.push("macros.json");
+            path
+        })
+    }
+
+    /// Load saved macros from disk (none if missing or unreadable)
+    pub fn load() -> Self {
+        let macros = Self::macros_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(macros) => Some(macros),
+                Err(e) => {
+                    log::warn!("Failed to parse macros file: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self {
+            macros,
+            ..Default::default()
+        }
+    }
+
+    /// Save macros to disk
+    fn save(&self) {
+        let Some(path) = Self::macros_path() else {
+            log::warn!("Could not determine config directory, macros not saved");
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                log::warn!("Failed to create config directory: {}", e);
+                return;
+            }
+        }
+        match serde_json::to_string_pretty(&self.macros) {
+            Ok(contents) => {
+                if let Err(e) = std::fs::write(&path, contents) {
+                    log::warn!("Failed to write macros file: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize macros: {}", e),
+        }
+    }
+}
+
+/// The differing stretch between `before` and `after` (same length), as a
+/// `(start, bytes)` span of `after`
+fn changed_span(before: &[u8], after: &[u8]) -> Option<(usize, Vec<u8>)> {
+    let first = before.iter().zip(after).position(|(a, b)| a != b)?;
+    let last = before.iter().zip(after).rposition(|(a, b)| a != b)?;
+    Some((first, after[first..=last].to_vec()))
+}
+
+/// Run `steps` over `buffer`: effects over each of `ranges`, whole-file steps
+/// through `scan_ranges` (called with the buffer as it stands before that step)
+fn run_steps(
+    steps: &[MacroStep],
+    buffer: &mut [u8],
+    ranges: &[std::ops::Range<usize>],
+    seed: u64,
+    mut scan_ranges: impl FnMut(&[u8]) -> Vec<std::ops::Range<usize>>,
+) {
+    for (i, &step) in steps.iter().enumerate() {
+        let seed = seed.wrapping_add(i as u64);
+        match step {
+            MacroStep::Effect(effect) => {
+                for (j, range) in ranges.iter().enumerate() {
+                    effect.apply(&mut buffer[range.clone()], seed.wrapping_add(j as u64));
+                }
+            }
+            MacroStep::JpegScanGlitch { intensity } => {
+                let ranges = scan_ranges(buffer);
+                flip_scan_ranges(buffer, 0, &ranges, intensity, seed);
+            }
+        }
+    }
+}
+
+impl BendApp {
+    /// Start or stop the macro recorder; stopping asks for the macro's name
+    pub(super) fn toggle_macro_recording(&mut self, ctx: &egui::Context) {
+        if self.ui.effects.macros.toggle_recording() {
+            ctx.memory_mut(|m| m.request_focus(egui::Id::new(MACRO_NAME_ID)));
+        }
+    }
+
+    /// Replay saved macro `index` over each selected range (or the whole
+    /// buffer), as one undoable edit. Only the target ranges are copied unless
+    /// a whole-file step needs the buffer; large targets run in the background.
+    pub(super) fn run_macro(&mut self, index: usize) {
+        let Some(effect_macro) = self.ui.effects.macros.macros.get(index) else {
+            return;
+        };
+        let label = format!("Macro \"{}\"", effect_macro.name);
+        let steps = effect_macro.steps.clone();
+        let ranges = self.target_ranges();
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        if ranges.is_empty() {
+            return;
+        }
+        if self.is_job_running() {
+            log::warn!("Macro skipped: another operation is still running");
+            return;
+        }
+        let has_range_steps = steps.iter().any(|s| matches!(s, MacroStep::Effect(_)));
+        if has_range_steps
+            && ranges
+                .iter()
+                .any(|range| self.doc.is_range_protected(range.start, range.len()))
+        {
+            log::warn!("Macro blocked: range overlaps a protected section");
+            return;
+        }
+
+        let seed = effect_seed();
+        let needs_buffer = steps
+            .iter()
+            .any(|s| matches!(s, MacroStep::JpegScanGlitch { .. }));
+        if needs_buffer {
+            let mut buffer = editor.working().to_vec();
+            let copied = buffer.len();
+            let protected = self.doc.protected_ranges().to_vec();
+            let work_steps = steps.clone();
+            self.finish_macro(label, copied, steps, move || {
+                let original = buffer.clone();
+                run_steps(&work_steps, &mut buffer, &ranges, seed, |data| {
+                    if JpegParser.can_parse(data) {
+                        editable_scan_ranges(data, &protected)
+                    } else {
+                        Vec::new()
+                    }
+                });
+                changed_span(&original, &buffer).into_iter().collect()
+            });
+        } else {
+            let mut chunks: Vec<_> = ranges
+                .iter()
+                .map(|range| (range.start, editor.working()[range.clone()].to_vec()))
+                .collect();
+            let copied = chunks.iter().map(|(_, bytes)| bytes.len()).sum();
+            let work_steps = steps.clone();
+            self.finish_macro(label, copied, steps, move || {
+                // Range j of step i gets seed + i + j, whichever loop runs outside
+                for (j, (_, bytes)) in chunks.iter_mut().enumerate() {
+                    let whole = 0..bytes.len();
+                    let seed = seed.wrapping_add(j as u64);
+                    run_steps(&work_steps, bytes, &[whole], seed, |_| Vec::new());
+                }
+                chunks
+            });
+        }
+    }
+
+    /// Run a macro's `work` now, or on a worker thread when it copied at least
+    /// `BACKGROUND_JOB_THRESHOLD` bytes, and commit the spans it returns
+    fn finish_macro(
+        &mut self,
+        label: String,
+        copied: usize,
+        steps: Vec<MacroStep>,
+        work: impl FnOnce() -> Vec<(usize, Vec<u8>)> + Send + 'static,
+    ) {
+        if copied >= BACKGROUND_JOB_THRESHOLD {
+            self.spawn_job(label, move || JobOutput::Macro {
+                spans: work(),
+                steps,
+            });
+        } else {
+            self.commit_macro(work(), steps);
+        }
+    }
+
+    /// Write a replayed macro's `(start, bytes)` spans back as one undoable
+    /// edit. Replaying while recording inlines the macro's steps.
+    pub(super) fn commit_macro(&mut self, spans: Vec<(usize, Vec<u8>)>, steps: Vec<MacroStep>) {
+        if let Some(editor) = &mut self.doc.editor {
+            match spans.as_slice() {
+                [] => {}
+                [(start, bytes)] => editor.replace_bytes(*start, bytes),
+                _ => {
+                    let spans: Vec<_> = spans
+                        .into_iter()
+                        .map(|(start, bytes)| (start, bytes.len(), bytes))
+                        .collect();
+                    editor.replace_spans(&spans);
+                }
+            }
+            self.doc.preview.mark_dirty();
+        }
+        for step in steps {
+            self.ui.effects.macros.record(step);
+        }
+    }
+
+    /// Delete saved macro `index`
+    pub(super) fn delete_macro(&mut self, index: usize) {
+        let macros = &mut self.ui.effects.macros;
+        if index < macros.macros.len() {
+            macros.macros.remove(index);
+            macros.save();
+        }
+    }
+
+    /// Show the name prompt for a just-recorded macro
+    pub(super) fn show_macro_name_dialog(&mut self, ctx: &egui::Context) {
+        let Some((name, steps)) = &mut self.ui.effects.macros.pending else {
+            return;
+        };
+
+        let mut should_save = false;
+        let mut should_cancel = false;
+        egui::Window::new("Save Macro")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let labels: Vec<_> = steps.iter().map(|s| s.label()).collect();
+                ui.label(format!("{} step(s): {}", steps.len(), labels.join(", ")));
+                ui.label("Name:");
+                let response =
+                    ui.add(egui::TextEdit::singleline(name).id(egui::Id::new(MACRO_NAME_ID)));
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    should_save = true;
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    should_cancel = true;
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").pointer_cursor().clicked() {
+                        should_save = true;
+                    }
+                    if ui.button("Discard").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        let macros = &mut self.ui.effects.macros;
+        if should_save {
+            if let Some((name, steps)) = macros.pending.take() {
+                let name = match name.trim() {
+                    "" => format!("Macro {}", macros.macros.len() + 1),
+                    trimmed => trimmed.to_string(),
+                };
+                macros.macros.push(EffectMacro { name, steps });
+                macros.save();
+            }
+        } else if should_cancel {
+            macros.pending = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::EditorState;
+
+    #[test]
+    fn test_recording_collects_applied_effects() {
+        let mut state = MacroState::default();
+        state.record(MacroStep::Effect(AppliedEffect::Shuffle));
+        assert!(!state.toggle_recording());
+        assert!(state.is_recording());
+        state.record(MacroStep::Effect(AppliedEffect::SwapEndian { word: 2 }));
+        state.record(MacroStep::JpegScanGlitch { intensity: 0.5 });
+        assert_eq!(state.recorded_steps(), 2);
+
+        // Stopping hands the steps over for naming
+        assert!(state.toggle_recording());
+        assert!(!state.is_recording());
+        let (name, steps) = state.pending.take().unwrap();
+        assert_eq!(name, "Macro 1");
+        assert_eq!(
+            steps[0],
+            MacroStep::Effect(AppliedEffect::SwapEndian { word: 2 })
+        );
+
+        // An empty recording is simply dropped
+        state.toggle_recording();
+        assert!(!state.toggle_recording());
+        assert!(state.pending.is_none());
+    }
+
+    #[test]
+    fn test_run_macro_is_one_undo() {
+        let mut app = BendApp::default();
+        let mut editor = EditorState::new(vec![1, 2, 3, 4, 5, 6]);
+        editor.set_selection(1, 5);
+        app.doc.editor = Some(editor);
+        app.ui.effects.macros.macros.push(EffectMacro {
+            name: "Swap twice".to_string(),
+            steps: vec![
+                MacroStep::Effect(AppliedEffect::SwapEndian { word: 2 }),
+                MacroStep::Effect(AppliedEffect::SwapEndian { word: 4 }),
+            ],
+        });
+
+        app.run_macro(0);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), &[1, 4, 5, 2, 3, 6]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[1, 2, 3, 4, 5, 6]);
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn test_run_macro_covers_every_selected_range() {
+        let mut app = BendApp::default();
+        let mut editor = EditorState::new(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        editor.set_selection(0, 2);
+        editor.add_selection_at(4);
+        editor.extend_selection_to(5);
+        // A Ctrl+click without a drag leaves no active range
+        editor.add_selection_at(7);
+        app.doc.editor = Some(editor);
+        app.ui.effects.macros.macros.push(EffectMacro {
+            name: "Swap".to_string(),
+            steps: vec![MacroStep::Effect(AppliedEffect::SwapEndian { word: 2 })],
+        });
+
+        app.run_macro(0);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), &[2, 1, 3, 4, 6, 5, 7, 8]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_large_macro_runs_in_the_background() {
+        let mut app = BendApp::default();
+        let mut data = vec![0; BACKGROUND_JOB_THRESHOLD];
+        data[0] = 1;
+        app.doc.editor = Some(EditorState::new(data));
+        app.ui.effects.macros.macros.push(EffectMacro {
+            name: "Swap".to_string(),
+            steps: vec![MacroStep::Effect(AppliedEffect::SwapEndian { word: 2 })],
+        });
+
+        app.run_macro(0);
+        assert!(app.is_job_running());
+        while app.is_job_running() {
+            app.poll_job();
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working()[..2], [0, 1]);
+        assert!(editor.undo());
+        assert_eq!(editor.working()[..2], [1, 0]);
+    }
+}
@@ -1,17 +1,15 @@
+use crate::editor::buffer::{EditMode, WriteMode};
+use crate::editor::effects::SWAP_WORD_SIZES;
+use crate::formats::FormatKind;
+use crate::keybindings::ShortcutAction;
 use crate::ui::theme::AppColors;
 use eframe::egui;
+use std::path::PathBuf;
 
+use super::effects::{MAX_EFFECT_STRIDE, MAX_SCANLINE_ROW_WIDTH};
+use super::trim::TrimSide;
 use super::BendApp;
 
-/// Returns the platform-appropriate modifier key text for shortcuts
-fn modifier_key() -> &'static str {
-    if cfg!(target_os = "macos") {
-        "⌘ " // space to give the character that follows more breathing room
-    } else {
-        "Ctrl+"
-    }
-}
-
 /// Menu item with shortcut hint that has better contrast than egui's default.
 /// Uses a horizontal layout with the shortcut text aligned right.
 /// Shortcut text is dimmer when not hovered, brighter when hovered.
@@ -64,13 +62,28 @@ fn menu_item_with_shortcut(
     response.clicked()
 }
 
+/// Label for a menu item that shows a checkmark when its state is active
+fn checked_label(label: &str, checked: bool) -> String {
+    if checked {
+        format!("\u{2714} {}", label)
+    } else {
+        label.to_string()
+    }
+}
+
 impl BendApp {
+    /// Menu hint for `action`'s current shortcut ("" if unbound)
+    fn shortcut_text(&self, action: ShortcutAction) -> String {
+        self.config.settings.keybindings.display(action)
+    }
+
     /// Render the top menu bar
     pub(super) fn render_menu_bar(&mut self, ctx: &egui::Context) {
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| self.render_file_menu(ui, ctx));
                 ui.menu_button("Edit", |ui| self.render_edit_menu(ui));
+                ui.menu_button("Effects", |ui| self.render_effects_menu(ui));
                 ui.menu_button("Help", |ui| self.render_help_menu(ui));
             });
         });
@@ -78,9 +91,9 @@ impl BendApp {
 
     /// Render the File menu contents
     fn render_file_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        let mod_str = modifier_key();
-        let open_shortcut = format!("{}O", mod_str);
-        let export_shortcut = format!("{}E", mod_str);
+        let open_shortcut = self.shortcut_text(ShortcutAction::Open);
+        let export_shortcut = self.shortcut_text(ShortcutAction::Export);
+        let quick_export_shortcut = self.shortcut_text(ShortcutAction::QuickExport);
         let colors = self.ui.colors;
 
         if menu_item_with_shortcut(ui, "Open...", &open_shortcut, true, colors) {
@@ -92,38 +105,156 @@ impl BendApp {
             self.export_file(ui.ctx());
             ui.close_menu();
         }
+        if menu_item_with_shortcut(ui, "Quick Export", &quick_export_shortcut, has_file, colors) {
+            self.quick_export(ui.ctx());
+            ui.close_menu();
+        }
+        let has_save_points = self
+            .doc
+            .editor
+            .as_ref()
+            .is_some_and(|e| !e.save_points().is_empty());
+        if ui
+            .add_enabled(
+                has_save_points,
+                egui::Button::new("Export Save Points as GIF..."),
+            )
+            .on_hover_text("Turn each save point into a frame of an animated GIF")
+            .clicked()
+        {
+            self.export_save_points_gif(ui.ctx());
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_file, egui::Button::new("Export as Raw PCM..."))
+            .on_hover_text("Write the selection (or whole file) as audio samples")
+            .clicked()
+        {
+            self.ui.audio_export.dialog_open = true;
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(
+                has_file,
+                egui::Button::new("Import Bytes into Selection..."),
+            )
+            .on_hover_text(
+                "Overwrite the selection (or from the cursor onward) with a file's bytes",
+            )
+            .clicked()
+        {
+            self.import_bytes_dialog(ui.ctx());
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui
+            .add_enabled(
+                self.doc.current_file.is_some(),
+                egui::Button::new("Open Containing Folder"),
+            )
+            .on_hover_text("Show the open file in the system file manager")
+            .clicked()
+        {
+            self.open_containing_folder();
+            ui.close_menu();
+        }
+        let last_export = self.io.last_export_path.as_ref();
+        let reveal_hint = last_export.map_or_else(String::new, |path| {
+            format!("Show {} in the system file manager", path.display())
+        });
+        if ui
+            .add_enabled(
+                last_export.is_some(),
+                egui::Button::new("Reveal Exported File"),
+            )
+            .on_hover_text(reveal_hint)
+            .on_disabled_hover_text("Nothing exported yet")
+            .clicked()
+        {
+            self.reveal_last_export();
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui
+            .add_enabled(has_file, egui::Button::new("Save Session..."))
+            .on_hover_text("Save the edits, save points and bookmarks to resume later")
+            .clicked()
+        {
+            self.save_session_dialog(ui.ctx());
+            ui.close_menu();
+        }
+        if ui.button("Load Session...").clicked() {
+            self.load_session_dialog(ui.ctx());
+            ui.close_menu();
+        }
+        ui.separator();
+        let close_tab_shortcut = self.shortcut_text(ShortcutAction::CloseTab);
+        let can_close_tab = has_file || self.tabs.len() > 1;
+        if menu_item_with_shortcut(ui, "Close Tab", &close_tab_shortcut, can_close_tab, colors) {
+            self.request_close_tab(self.tabs.active());
+            ui.close_menu();
+        }
         ui.separator();
 
-        // Recent files submenu
+        // Recent files submenu: pinned files first, then most recent
+        let pinned_files = self.config.settings.pinned_files().to_vec();
         let recent_files = self.config.settings.recent_files().to_vec();
-        let has_recent = !recent_files.is_empty();
+        let mut toggle_pin: Option<(PathBuf, bool)> = None;
         ui.menu_button("Recent Files", |ui| {
-            if has_recent {
-                for path in &recent_files {
-                    let display_name = path
-                        .file_name()
-                        .map(|n| n.to_string_lossy().into_owned())
-                        .unwrap_or_else(|| path.to_string_lossy().into_owned());
+            if pinned_files.is_empty() && recent_files.is_empty() {
+                ui.label("No recent files");
+                return;
+            }
+            let entries = pinned_files
+                .iter()
+                .map(|p| (p, true))
+                .chain(recent_files.iter().map(|p| (p, false)));
+            for (index, (path, pinned)) in entries.enumerate() {
+                if index == pinned_files.len() && index > 0 {
+                    ui.separator();
+                }
+                let display_name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.to_string_lossy().into_owned());
+                let label = if pinned {
+                    format!("\u{1F4CC} {}", display_name)
+                } else {
+                    display_name
+                };
 
-                    if ui
-                        .button(&display_name)
-                        .on_hover_text(path.to_string_lossy())
-                        .clicked()
-                    {
-                        self.io.pending_open_path = Some(path.clone());
+                let response = ui
+                    .button(label)
+                    .on_hover_text(format!("{}\nRight-click to pin or unpin", path.display()));
+                if response.clicked() {
+                    self.io.pending_open_path = Some(path.clone());
+                    ui.close_menu();
+                }
+                response.context_menu(|ui| {
+                    let text = if pinned { "Unpin" } else { "Pin to Top" };
+                    if ui.button(text).clicked() {
+                        toggle_pin = Some((path.clone(), !pinned));
                         ui.close_menu();
                     }
-                }
+                });
+            }
+            if !recent_files.is_empty() {
                 ui.separator();
                 if ui.button("Clear Recent Files").clicked() {
                     self.config.settings.clear_recent_files();
                     self.config.settings.save();
                     ui.close_menu();
                 }
-            } else {
-                ui.label("No recent files");
             }
         });
+        if let Some((path, pin)) = toggle_pin {
+            if pin {
+                self.config.settings.pin_recent_file(&path);
+            } else {
+                self.config.settings.unpin_recent_file(&path);
+            }
+            self.config.settings.save();
+        }
 
         ui.separator();
         if ui.button("Exit").clicked() {
@@ -139,15 +270,15 @@ impl BendApp {
 
     /// Render the Edit menu contents
     fn render_edit_menu(&mut self, ui: &mut egui::Ui) {
-        let mod_str = modifier_key();
         let has_file = self.doc.editor.is_some();
         let can_undo = self.doc.editor.as_ref().is_some_and(|e| e.can_undo());
         let can_redo = self.doc.editor.as_ref().is_some_and(|e| e.can_redo());
-        let undo_shortcut = format!("{}Z", mod_str);
-        let redo_shortcut = format!("{}Shift+Z", mod_str);
-        let find_shortcut = format!("{}F", mod_str);
-        let goto_shortcut = format!("{}G", mod_str);
-        let refresh_shortcut = format!("{}R", mod_str);
+        let undo_shortcut = self.shortcut_text(ShortcutAction::Undo);
+        let redo_shortcut = self.shortcut_text(ShortcutAction::Redo);
+        let find_shortcut = self.shortcut_text(ShortcutAction::FindReplace);
+        let goto_shortcut = self.shortcut_text(ShortcutAction::GoToOffset);
+        let marker_shortcut = self.shortcut_text(ShortcutAction::MatchingMarker);
+        let refresh_shortcut = self.shortcut_text(ShortcutAction::RefreshPreview);
         let colors = self.ui.colors;
 
         if menu_item_with_shortcut(ui, "Undo", &undo_shortcut, can_undo, colors) {
@@ -158,8 +289,124 @@ impl BendApp {
             self.do_redo();
             ui.close_menu();
         }
+        let can_revert = self
+            .doc
+            .editor
+            .as_ref()
+            .is_some_and(|e| e.working() != e.original());
+        if ui
+            .add_enabled(can_revert, egui::Button::new("Revert to Original..."))
+            .clicked()
+        {
+            self.ui.dialogs.show_revert_confirm = true;
+            ui.close_menu();
+        }
+        ui.separator();
+
+        // Explicit edit/write mode selection (the shortcuts only toggle)
+        let edit_mode_shortcut = self.shortcut_text(ShortcutAction::ToggleEditMode);
+        let write_mode_shortcut = self.shortcut_text(ShortcutAction::ToggleWriteMode);
+        let modes = self
+            .doc
+            .editor
+            .as_ref()
+            .map(|e| (e.edit_mode(), e.write_mode()));
+        for (label, mode) in [("Hex Mode", EditMode::Hex), ("ASCII Mode", EditMode::Ascii)] {
+            let checked = modes.is_some_and(|(m, _)| m == mode);
+            if menu_item_with_shortcut(
+                ui,
+                &checked_label(label, checked),
+                &edit_mode_shortcut,
+                has_file,
+                colors,
+            ) {
+                if let Some(editor) = &mut self.doc.editor {
+                    editor.set_edit_mode(mode);
+                }
+                ui.close_menu();
+            }
+        }
+        for (label, mode) in [
+            ("Insert Mode", WriteMode::Insert),
+            ("Overwrite Mode", WriteMode::Overwrite),
+        ] {
+            let checked = modes.is_some_and(|(_, m)| m == mode);
+            if menu_item_with_shortcut(
+                ui,
+                &checked_label(label, checked),
+                &write_mode_shortcut,
+                has_file,
+                colors,
+            ) {
+                if let Some(editor) = &mut self.doc.editor {
+                    editor.set_write_mode(mode);
+                }
+                ui.close_menu();
+            }
+        }
         ui.separator();
 
+        let has_selection = self
+            .doc
+            .editor
+            .as_ref()
+            .is_some_and(|e| e.single_selection().is_some());
+        if ui
+            .add_enabled(has_selection, egui::Button::new("Invert Selection"))
+            .on_hover_text("Select the larger part of the file outside the selection")
+            .clicked()
+        {
+            self.invert_selection();
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_file, egui::Button::new("Trim to Left..."))
+            .on_hover_text("Delete every byte before the cursor")
+            .clicked()
+        {
+            self.request_trim(TrimSide::Left);
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_file, egui::Button::new("Trim to Right..."))
+            .on_hover_text("Delete every byte after the cursor")
+            .clicked()
+        {
+            self.request_trim(TrimSide::Right);
+            ui.close_menu();
+        }
+        let locked = self
+            .doc
+            .editor
+            .as_ref()
+            .is_some_and(|e| e.selection_locked());
+        if ui
+            .add_enabled(
+                has_file,
+                egui::Button::new(checked_label("Lock Selection", locked)),
+            )
+            .on_hover_text("Keep the selection while clicking and navigating elsewhere")
+            .clicked()
+        {
+            if let Some(editor) = &mut self.doc.editor {
+                editor.set_selection_locked(!locked);
+            }
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(
+                has_file,
+                egui::Button::new(checked_label(
+                    "Measure Distance",
+                    self.ui.measure_state.active,
+                )),
+            )
+            .on_hover_text("Click two bytes to measure the distance between them")
+            .clicked()
+        {
+            self.ui.measure_state.toggle();
+            ui.close_menu();
+        }
         if menu_item_with_shortcut(ui, "Find & Replace...", &find_shortcut, has_file, colors) {
             self.ui.search_state.open_dialog();
             ui.close_menu();
@@ -168,10 +415,65 @@ impl BendApp {
             self.ui.go_to_offset_state.open_dialog();
             ui.close_menu();
         }
+        if menu_item_with_shortcut(
+            ui,
+            "Go to Matching Marker",
+            &marker_shortcut,
+            has_file,
+            colors,
+        ) {
+            self.go_to_matching_marker();
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_file, egui::Button::new("Scan for Image Headers..."))
+            .on_hover_text("List every offset where a BMP, JPEG, PNG, or GIF signature appears")
+            .clicked()
+        {
+            if let Some(editor) = &self.doc.editor {
+                self.ui.signature_scan_state.open(editor.working());
+            }
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_file, egui::Button::new("Find Byte Runs..."))
+            .on_hover_text("List long runs of one repeated byte, such as zero padding")
+            .clicked()
+        {
+            self.open_byte_runs();
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_file, egui::Button::new("Compare with File..."))
+            .on_hover_text("List and jump between the bytes that differ from another file")
+            .clicked()
+        {
+            self.compare_with_file_dialog(ui.ctx());
+            ui.close_menu();
+        }
+        ui.add_enabled_ui(has_file, |ui| {
+            ui.menu_button("Force Format", |ui| {
+                let forced = self.doc.forced_format;
+                let choices = std::iter::once((None, "Auto-detect"))
+                    .chain(FormatKind::ALL.iter().map(|&f| (Some(f), f.label())));
+                for (format, label) in choices {
+                    if ui.button(checked_label(label, forced == format)).clicked() {
+                        self.doc.forced_format = format;
+                        self.doc.reparse_sections();
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text(
+                "Parse the structure as a chosen format, even if its signature doesn't match",
+            );
+        });
         ui.separator();
 
-        let save_point_shortcut = format!("{}S", mod_str);
-        let bookmark_shortcut = format!("{}D", mod_str);
+        let save_point_shortcut = self.shortcut_text(ShortcutAction::CreateSavePoint);
+        let save_point_as_shortcut = self.shortcut_text(ShortcutAction::NameSavePoint);
+        let bookmark_shortcut = self.shortcut_text(ShortcutAction::AddBookmark);
 
         if menu_item_with_shortcut(
             ui,
@@ -181,23 +483,28 @@ impl BendApp {
             colors,
         ) {
             if let Some(editor) = &mut self.doc.editor {
-                let count = editor.save_points().len();
-                let name = format!("Save Point {}", count + 1);
+                let name = editor.next_save_point_name();
                 editor.create_save_point(name);
             }
             ui.close_menu();
         }
+        if menu_item_with_shortcut(
+            ui,
+            "Save Point As...",
+            &save_point_as_shortcut,
+            has_file,
+            colors,
+        ) {
+            self.open_save_point_name_dialog(ui.ctx());
+            ui.close_menu();
+        }
         if menu_item_with_shortcut(ui, "Add Bookmark", &bookmark_shortcut, has_file, colors) {
-            if let Some(editor) = &mut self.doc.editor {
-                let cursor_pos = editor.cursor();
-                let name = format!("Bookmark at 0x{:08X}", cursor_pos);
-                editor.add_bookmark(cursor_pos, name);
-            }
+            self.add_bookmark_at_cursor();
             ui.close_menu();
         }
         ui.separator();
         if menu_item_with_shortcut(ui, "Refresh Preview", &refresh_shortcut, has_file, colors) {
-            self.doc.preview.mark_dirty();
+            self.doc.preview.request_refresh();
             ui.close_menu();
         }
         ui.separator();
@@ -229,10 +536,229 @@ impl BendApp {
         }
     }
 
+    /// Render the Effects menu contents
+    fn render_effects_menu(&mut self, ui: &mut egui::Ui) {
+        // Effects apply to each selected range, or to the whole file without one
+        let target_len = self
+            .target_ranges()
+            .iter()
+            .map(|range| range.len())
+            .max()
+            .unwrap_or(0);
+        let has_target = target_len > 0;
+        let has_file = self.doc.editor.is_some();
+        let colors = self.ui.colors;
+
+        let last_label = self.ui.effects.last_applied.map(|e| e.label());
+        let repeat_label = match last_label {
+            Some(label) => format!("Repeat {}", label),
+            None => "Repeat Last Effect".to_string(),
+        };
+        let repeat_shortcut = self.shortcut_text(ShortcutAction::RepeatLastEffect);
+        if menu_item_with_shortcut(
+            ui,
+            &repeat_label,
+            &repeat_shortcut,
+            has_target && last_label.is_some(),
+            colors,
+        ) {
+            self.repeat_last_effect();
+            ui.close_menu();
+        }
+        let record_label = if self.ui.effects.macros.is_recording() {
+            "Stop Recording Macro..."
+        } else {
+            "Record Macro"
+        };
+        if ui
+            .button(record_label)
+            .on_hover_text("Record the effects you apply into a reusable macro")
+            .clicked()
+        {
+            self.toggle_macro_recording(ui.ctx());
+            ui.close_menu();
+        }
+        let macro_names: Vec<String> = self
+            .ui
+            .effects
+            .macros
+            .macros
+            .iter()
+            .map(|m| m.name.clone())
+            .collect();
+        ui.add_enabled_ui(!macro_names.is_empty(), |ui| {
+            ui.menu_button("Run Macro", |ui| {
+                for (index, name) in macro_names.iter().enumerate() {
+                    if ui.add_enabled(has_file, egui::Button::new(name)).clicked() {
+                        self.run_macro(index);
+                        ui.close_menu();
+                    }
+                }
+            });
+            ui.menu_button("Delete Macro", |ui| {
+                for (index, name) in macro_names.iter().enumerate() {
+                    if ui.button(name).clicked() {
+                        self.delete_macro(index);
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Intensity:");
+            ui.add(
+                egui::Slider::new(&mut self.ui.effects.intensity, 0.0..=1.0)
+                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                    .custom_parser(|s| {
+                        s.trim_end_matches('%')
+                            .trim()
+                            .parse::<f64>()
+                            .ok()
+                            .map(|v| v / 100.0)
+                    }),
+            )
+            .on_hover_text("How strongly effects below corrupt the selection (or the whole file)");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Every Nth byte:");
+            ui.add(egui::DragValue::new(&mut self.ui.effects.stride).range(1..=MAX_EFFECT_STRIDE))
+                .on_hover_text(
+                    "Bit flips, rotation and XOR only touch every Nth selected byte \
+                 (e.g. 3 = one channel of RGB data)",
+                );
+        });
+        ui.separator();
+
+        if ui
+            .add_enabled(has_target, egui::Button::new("Random Bit Flips"))
+            .on_hover_text("Flip one random bit in each byte with probability = intensity")
+            .clicked()
+        {
+            self.random_flip_selection();
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(has_target, egui::Button::new("Rotate Bits"))
+            .on_hover_text("Rotate each byte's bits left by 0-7 places, scaled by intensity")
+            .clicked()
+        {
+            self.rotate_bits_selection();
+            ui.close_menu();
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(has_target, egui::Button::new("XOR with"))
+                .on_hover_text("XOR each byte with the key; lower intensity keeps fewer key bits")
+                .clicked()
+            {
+                self.xor_selection();
+                ui.close_menu();
+            }
+            ui.add(
+                egui::DragValue::new(&mut self.ui.effects.xor_key)
+                    .hexadecimal(2, false, true)
+                    .prefix("0x"),
+            );
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(has_target, egui::Button::new("Scanline Shift"))
+                .on_hover_text(
+                    "Rotate each row of the selection right by a growing amount \
+                     (horizontal tearing); select the pixel data first",
+                )
+                .clicked()
+            {
+                self.scanline_shift_selection();
+                ui.close_menu();
+            }
+            ui.label("row");
+            ui.add(
+                egui::DragValue::new(&mut self.ui.effects.scanline_row_width)
+                    .range(1..=MAX_SCANLINE_ROW_WIDTH)
+                    .suffix(" B"),
+            )
+            .on_hover_text("Assumed row width in bytes");
+            let bmp_stride = self.bmp_row_stride();
+            if ui
+                .add_enabled(bmp_stride.is_some(), egui::Button::new("From BMP"))
+                .on_hover_text("Use the row width (with padding) from the BMP header")
+                .on_disabled_hover_text("Only available for BMP files")
+                .clicked()
+            {
+                if let Some(stride) = bmp_stride {
+                    self.ui.effects.scanline_row_width = stride;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Shift per row:");
+            ui.add(
+                egui::DragValue::new(&mut self.ui.effects.scanline_step)
+                    .range(0..=MAX_SCANLINE_ROW_WIDTH)
+                    .suffix(" B"),
+            );
+        });
+        if ui
+            .add_enabled(has_target, egui::Button::new("Gradient Fill..."))
+            .on_hover_text(
+                "Replace the bytes with a smooth ramp between two values \
+                 (banding when read as pixels)",
+            )
+            .clicked()
+        {
+            self.ui.effects.gradient_dialog_open = true;
+            ui.close_menu();
+        }
+        ui.separator();
+
+        if ui
+            .add_enabled(self.is_jpeg(), egui::Button::new("JPEG Scan Glitch"))
+            .on_hover_text(
+                "Flip random bits in the JPEG scan data only, skipping markers so the \
+                 image keeps decoding (whole file, no selection needed)",
+            )
+            .clicked()
+        {
+            self.jpeg_scan_glitch();
+            ui.close_menu();
+        }
+        ui.separator();
+
+        ui.add_enabled_ui(target_len >= 2, |ui| {
+            ui.menu_button("Swap Endianness", |ui| {
+                for &word in SWAP_WORD_SIZES {
+                    if ui
+                        .add_enabled(
+                            target_len >= word,
+                            egui::Button::new(format!("{}-byte words", word)),
+                        )
+                        .clicked()
+                    {
+                        self.swap_endian_selection(word);
+                        ui.close_menu();
+                    }
+                }
+            });
+        })
+        .response
+        .on_disabled_hover_text("The selection (or file) is shorter than one word");
+    }
+
     /// Render the Help menu contents
     fn render_help_menu(&mut self, ui: &mut egui::Ui) {
         let colors = self.ui.colors;
-        if menu_item_with_shortcut(ui, "Keyboard Shortcuts", "F1", true, colors) {
+        let palette_shortcut = self.shortcut_text(ShortcutAction::CommandPalette);
+        if menu_item_with_shortcut(ui, "Command Palette...", &palette_shortcut, true, colors) {
+            self.ui.command_palette.open();
+            ui.close_menu();
+        }
+        let help_shortcut = self.shortcut_text(ShortcutAction::ShortcutsHelp);
+        if menu_item_with_shortcut(ui, "Keyboard Shortcuts", &help_shortcut, true, colors) {
             self.ui.shortcuts_dialog_state.open();
             ui.close_menu();
         }
@@ -1,28 +1,50 @@
 //! Main application state and egui integration
 
+mod audio_export;
+mod byte_import;
 mod dialogs;
+mod effects;
+mod file_compare;
+mod gif_export;
 mod input;
+mod jobs;
+mod large_file;
+mod macros;
 mod menu_bar;
 mod preview;
+mod reveal;
 mod sections;
+mod session;
 mod state;
+mod tabs;
 mod toolbar;
+mod trim;
+mod work_copy;
 
-pub use dialogs::{DialogState, PendingEdit, PendingEditType};
-pub use preview::PreviewState;
+pub use audio_export::AudioExportState;
+pub use dialogs::{DialogState, PendingEdit, PendingEditType, PendingPaste};
+pub use effects::{EffectsState, SectionEffect};
+pub use preview::{
+    PreviewAdjustment, PreviewScale, PreviewState, BRIGHTNESS_RANGE, CONTRAST_RANGE, GAMMA_RANGE,
+};
 pub use state::{AppConfig, DocumentState, IoState, UiState};
+pub use tabs::{DocumentId, DocumentTabs};
 
 use crate::editor::buffer::{EditMode, WriteMode};
+use crate::editor::go_to_offset::percent_to_offset;
+use crate::editor::search::SearchScope;
 use crate::editor::EditorState;
-use crate::formats::parse_file;
+use crate::formats::{detect_format, parse_file};
 use crate::ui::theme::AppColors;
 use crate::ui::PointerCursor;
 use crate::ui::{
-    bookmarks, go_to_offset_dialog, hex_editor, image_preview, savepoints, search_dialog,
-    settings_dialog, shortcuts_dialog, structure_tree,
+    bookmarks, byte_runs_dialog, command_palette, data_inspector, file_diff_dialog, file_info,
+    go_to_offset_dialog, hex_editor, histogram, image_preview, savepoints, search_dialog,
+    settings_dialog, shortcuts_dialog, signature_scan_dialog, structure_tree,
 };
 use eframe::egui;
 use state::FileDialogResult;
+use std::io::Write;
 use std::path::PathBuf;
 use std::sync::mpsc;
 use std::time::{Duration, Instant};
@@ -41,10 +63,16 @@ where
     rx
 }
 
-/// Threshold for detecting window size changes (pixels)
+/// File extensions accepted by the open dialog and drag-and-drop (lowercase)
+const SUPPORTED_EXTENSIONS: &[&str] = &["bmp", "jpg", "jpeg", "gif", "pbm", "pgm", "ppm", "pnm"];
+
+/// Display name for a document read from standard input
+const STDIN_FILE_NAME: &str = "stdin";
+
+/// Threshold for detecting window size or position changes (pixels)
 const WINDOW_RESIZE_THRESHOLD: f32 = 1.0;
 
-/// Debounce delay for window resize saves (milliseconds)
+/// Debounce delay for window size/position saves (milliseconds)
 const WINDOW_RESIZE_DEBOUNCE_MS: u64 = 500;
 
 /// Main application state for bend-rs
@@ -67,6 +95,9 @@ pub struct BendApp {
     /// Document state: loaded editor, current file, preview, sections, header protection
     pub doc: DocumentState,
 
+    /// Documents open in the other (inactive) tabs
+    pub tabs: DocumentTabs,
+
     /// UI state: colors, dialogs, panel state, pending scroll
     pub ui: UiState,
 
@@ -83,19 +114,19 @@ impl BendApp {
         crate::ui::theme::apply_custom_visuals(&cc.egui_ctx);
 
         // Apply settings to initial state
-        let header_protection = settings.default_header_protection;
         let suppress_warnings = !settings.show_high_risk_warnings;
 
         Self {
-            doc: DocumentState {
-                header_protection,
-                ..Default::default()
-            },
+            doc: Self::new_document(&settings),
             ui: UiState {
                 dialogs: DialogState {
                     suppress_high_risk_warnings: suppress_warnings,
                     ..Default::default()
                 },
+                effects: EffectsState {
+                    macros: macros::MacroState::load(),
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             config: AppConfig { settings },
@@ -105,6 +136,10 @@ impl BendApp {
 
     /// Perform undo on the active editor (if any)
     pub(super) fn do_undo(&mut self) {
+        if self.is_job_running() {
+            self.note_edit_paused_for_job();
+            return;
+        }
         if let Some(editor) = &mut self.doc.editor {
             let _ = editor.undo();
         }
@@ -112,16 +147,98 @@ impl BendApp {
 
     /// Perform redo on the active editor (if any)
     pub(super) fn do_redo(&mut self) {
+        if self.is_job_running() {
+            self.note_edit_paused_for_job();
+            return;
+        }
         if let Some(editor) = &mut self.doc.editor {
             let _ = editor.redo();
         }
     }
 
+    /// Bookmark the cursor position, named after the section it lands in
+    pub(super) fn add_bookmark_at_cursor(&mut self) {
+        let Some(cursor_pos) = self.doc.editor.as_ref().map(|e| e.cursor()) else {
+            return;
+        };
+        let name = self.doc.default_bookmark_name(cursor_pos);
+        if let Some(editor) = &mut self.doc.editor {
+            editor.add_bookmark(cursor_pos, name);
+        }
+    }
+
+    /// Select the larger segment outside the current selection and scroll to it
+    pub fn invert_selection(&mut self) {
+        if let Some(editor) = &mut self.doc.editor {
+            if editor.invert_selection() {
+                self.ui.pending_hex_scroll = Some(editor.cursor());
+            }
+        }
+    }
+
+    /// Open the byte runs dialog with a fresh scan
+    pub fn open_byte_runs(&mut self) {
+        if let Some(editor) = &self.doc.editor {
+            self.ui
+                .byte_runs_state
+                .open(editor.working(), editor.edit_generation());
+        }
+    }
+
+    /// Jump between the first and last byte of the section under the cursor
+    pub fn go_to_matching_marker(&mut self) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        let Some(target) = self.doc.matching_marker_offset(editor.cursor()) else {
+            return;
+        };
+        if let Some(editor) = &mut self.doc.editor {
+            editor.clear_unlocked_selection();
+            editor.set_cursor(target);
+            self.ui.pending_hex_scroll = Some(target);
+        }
+    }
+
+    /// Create an automatic save point once `auto_save_point_interval` edits have
+    /// accumulated since the last automatic one (disabled when the interval is 0)
+    fn maybe_create_auto_save_point(&mut self) {
+        let interval = self.config.settings.auto_save_point_interval;
+        if interval == 0 {
+            return;
+        }
+        let Some(editor) = &mut self.doc.editor else {
+            return;
+        };
+        let generation = editor.edit_generation();
+        if generation >= self.doc.last_auto_save_generation + interval {
+            editor.create_save_point(format!("Auto @ gen {}", generation));
+            self.doc.last_auto_save_generation = generation;
+        }
+    }
+
     /// Request the hex editor to scroll to show the given byte offset
     pub fn scroll_hex_to_offset(&mut self, offset: usize) {
         self.ui.pending_hex_scroll = Some(offset);
     }
 
+    /// Slider beside the hex editor heading that scrolls to a percentage of the file
+    fn render_percent_jump(&mut self, ui: &mut egui::Ui) {
+        let len = self.doc.editor.as_ref().map_or(0, |e| e.len());
+        let response = ui
+            .add(
+                egui::Slider::new(&mut self.ui.go_to_offset_state.percent, 0.0..=100.0)
+                    .suffix("%")
+                    .fixed_decimals(1),
+            )
+            .on_hover_text("Jump to a position by percentage of the file");
+        if response.changed() {
+            let offset = percent_to_offset(self.ui.go_to_offset_state.percent, len);
+            self.scroll_hex_to_offset(offset);
+        }
+        ui.label("Jump to:");
+    }
+
     /// Navigate the editor cursor and hex view to the current search match
     pub fn navigate_to_search_match(&mut self) {
         if let Some(offset) = self.ui.search_state.current_match_offset() {
@@ -136,18 +253,68 @@ impl BendApp {
     pub fn refresh_search(&mut self) {
         if let Some(editor) = &self.doc.editor {
             let gen = editor.edit_generation();
-            crate::editor::search::execute_search(&mut self.ui.search_state, editor.working());
+            let ranges = self.search_ranges();
+            crate::editor::search::execute_search_in_ranges(
+                &mut self.ui.search_state,
+                editor.working(),
+                &ranges,
+            );
             self.ui.search_state.set_searched_generation(gen);
         }
     }
 
-    /// Check if there are unsaved changes
+    /// Byte ranges `(start, end_exclusive)` covered by the current search scope:
+    /// each selected range, or one range for the section/whole file. Falls
+    /// back to the whole buffer when there is no selection/section.
+    pub(crate) fn search_ranges(&self) -> Vec<(usize, usize)> {
+        let Some(editor) = &self.doc.editor else {
+            return vec![(0, 0)];
+        };
+        let whole = (0, editor.len());
+        match self.ui.search_state.scope {
+            SearchScope::WholeFile => vec![whole],
+            SearchScope::Selection => {
+                let ranges = editor.selections();
+                if ranges.is_empty() {
+                    vec![whole]
+                } else {
+                    ranges
+                }
+            }
+            SearchScope::Section => vec![self
+                .doc
+                .section_at_offset(editor.cursor())
+                .map(|s| (s.start, s.end.min(editor.len())))
+                .unwrap_or(whole)],
+        }
+    }
+
+    /// Check if any open tab has unsaved changes
     pub fn has_unsaved_changes(&self) -> bool {
-        self.doc.editor.as_ref().is_some_and(|e| e.is_modified())
+        self.any_tab_modified()
     }
 
-    /// Export the working buffer to a new file (non-blocking)
+    /// Export the working buffer, first asking for confirmation if it no longer
+    /// decodes as an image (unless that warning is turned off in settings)
     pub fn export_file(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() {
+            return;
+        }
+        if self.doc.editor.is_none() {
+            return;
+        }
+        // The preview already decodes the working buffer; trust its last result
+        if self.config.settings.warn_on_undecodable_export
+            && self.doc.preview.decode_error.is_some()
+        {
+            self.ui.dialogs.show_export_decode_warning = true;
+            return;
+        }
+        self.export_file_unchecked(ctx);
+    }
+
+    /// Export the working buffer to a new file (non-blocking)
+    pub(super) fn export_file_unchecked(&mut self, ctx: &egui::Context) {
         if self.io.is_dialog_pending() || self.doc.editor.is_none() {
             return;
         }
@@ -155,34 +322,23 @@ impl BendApp {
         let editor = self.doc.editor.as_ref().unwrap();
         let buffer = editor.working().to_vec();
 
-        // Pre-compute filename and extension on main thread
-        let default_name = self
-            .doc
-            .current_file
-            .as_ref()
-            .and_then(|p| p.file_stem())
-            .map(|s| format!("{}_glitched", s.to_string_lossy()))
-            .unwrap_or_else(|| "export".to_string());
-
-        let extension = self
-            .doc
-            .current_file
-            .as_ref()
-            .and_then(|p| p.extension())
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "bmp".to_string());
+        // Pre-compute the suggested filename on main thread
+        let file_name = self.export_file_name();
+        let last_dir = self.config.settings.last_export_dir.clone();
 
         let rx = spawn_file_dialog(ctx, move || {
             // Use AsyncFileDialog to avoid NSSavePanel::runModal on macOS,
             // which enters a nested event loop that can trigger a winit panic
             // when drag events fire during the modal dialog.
             let result = pollster::block_on(async {
-                rfd::AsyncFileDialog::new()
-                    .set_file_name(format!("{}.{}", default_name, extension))
-                    .add_filter("Images", &["bmp", "jpg", "jpeg", "gif"])
-                    .add_filter("All files", &["*"])
-                    .save_file()
-                    .await
+                let mut dialog = rfd::AsyncFileDialog::new()
+                    .set_file_name(file_name)
+                    .add_filter("Images", SUPPORTED_EXTENSIONS)
+                    .add_filter("All files", &["*"]);
+                if let Some(dir) = last_dir {
+                    dialog = dialog.set_directory(dir);
+                }
+                dialog.save_file().await
             });
             if let Some(handle) = result {
                 let path = handle.path().to_path_buf();
@@ -198,38 +354,144 @@ impl BendApp {
         self.io.export_dialog_rx = Some(rx);
     }
 
+    /// Export straight into the last export directory as "<stem>_glitched_NNN.<ext>",
+    /// without a dialog. Falls back to the full Export dialog until a directory is known.
+    pub(super) fn quick_export(&mut self, ctx: &egui::Context) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        let Some(dir) = self
+            .config
+            .settings
+            .last_export_dir
+            .clone()
+            .filter(|dir| dir.is_dir())
+        else {
+            self.export_file(ctx);
+            return;
+        };
+
+        let (stem, extension) = self.export_name_parts();
+        let result = create_quick_export_file(&dir, &stem, &extension).and_then(
+            |(path, mut file)| match file.write_all(editor.working()) {
+                Ok(()) => Ok(path),
+                Err(e) => {
+                    let _ = std::fs::remove_file(&path);
+                    Err(e)
+                }
+            },
+        );
+        match result {
+            Ok(path) => {
+                log::info!("Quick exported to: {}", path.display());
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.ui.quick_export_notice = Some((name, Instant::now()));
+                self.io.last_export_path = Some(path);
+            }
+            Err(e) => log::error!("Failed to export: {}", e),
+        }
+    }
+
+    /// Remember where an export went so the next dialog and Quick Export start there
+    fn remember_export_dir(&mut self, path: &std::path::Path) {
+        let Some(dir) = path.parent() else {
+            return;
+        };
+        if self.config.settings.last_export_dir.as_deref() != Some(dir) {
+            self.config.settings.last_export_dir = Some(dir.to_path_buf());
+            self.config.settings.save();
+        }
+    }
+
+    /// Suggested export filename: "<stem>_glitched.<ext>"
+    fn export_file_name(&self) -> String {
+        let (stem, extension) = self.export_name_parts();
+        format!("{}.{}", stem, extension)
+    }
+
+    /// Export name stem ("<stem>_glitched") and extension (see `export_extension`)
+    fn export_name_parts(&self) -> (String, String) {
+        let stem = self
+            .doc
+            .name_stem()
+            .map(|s| format!("{}_glitched", s))
+            .unwrap_or_else(|| "export".to_string());
+        let source = self
+            .doc
+            .current_file
+            .as_ref()
+            .and_then(|p| p.extension())
+            .map(|s| s.to_string_lossy().to_string());
+        let data = self.doc.editor.as_ref().map_or(&[][..], |e| e.working());
+        (stem, export_extension(source.as_deref(), data))
+    }
+
     /// Check if a file extension is a supported format
     fn is_supported_extension(path: &std::path::Path) -> bool {
         match path.extension().and_then(|e| e.to_str()) {
-            Some(ext) => matches!(
-                ext.to_ascii_lowercase().as_str(),
-                "bmp" | "jpg" | "jpeg" | "gif"
-            ),
+            Some(ext) => SUPPORTED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()),
             None => false,
         }
     }
 
-    /// Open a file from a path
+    /// Open a file from a path.
+    ///
+    /// Switches to its tab if it's already open; otherwise loads it into the
+    /// current tab when that tab is empty, or into a new tab.
     pub fn open_file(&mut self, path: PathBuf) {
+        if let Some(index) = self.tab_for_file(&path) {
+            self.switch_tab(index);
+            return;
+        }
+
         if !Self::is_supported_extension(&path) {
             self.doc.preview.decode_error = Some(
-                "Unsupported file format. Bend supports BMP (.bmp), JPEG (.jpg, .jpeg), and GIF (.gif) files."
+                "Unsupported file format. Bend supports BMP (.bmp), JPEG (.jpg, .jpeg), GIF (.gif), and Netpbm (.pbm, .pgm, .ppm, .pnm) files."
                     .to_string(),
             );
             return;
         }
 
-        match std::fs::read(&path) {
+        if self.needs_large_file_confirmation(&path) {
+            return;
+        }
+        self.open_file_unchecked(path);
+    }
+
+    /// Load `path` without the large-file size check
+    pub(super) fn open_file_unchecked(&mut self, path: PathBuf) {
+        // With "Work on Copy" on, edit a fresh copy and leave the source alone
+        let load_path = if self.config.settings.work_on_copy {
+            match self.make_work_copy(&path) {
+                Ok(copy) => {
+                    log::info!("Working on copy: {}", copy.display());
+                    copy
+                }
+                Err(e) => {
+                    log::error!("Failed to copy file: {}", e);
+                    self.doc.preview.decode_error = Some(format!("Failed to copy file: {}", e));
+                    return;
+                }
+            }
+        } else {
+            path.clone()
+        };
+
+        match std::fs::read(&load_path) {
             Ok(bytes) => {
-                log::info!("Loaded file: {} ({} bytes)", path.display(), bytes.len());
-                // Parse file structure for section highlighting
-                self.doc.cached_sections = parse_file(&bytes);
-                self.doc.editor = Some(EditorState::new(bytes));
-                self.doc.current_file = Some(path.clone());
-                self.doc.preview.mark_dirty();
-                self.doc.preview.decode_error = None;
-                // Clear existing textures and animation state
-                self.doc.preview.reset_for_new_file();
+                log::info!(
+                    "Loaded file: {} ({} bytes)",
+                    load_path.display(),
+                    bytes.len()
+                );
+                let is_copy = load_path != path;
+                self.load_document(Some(load_path), bytes);
+                if is_copy {
+                    self.doc.source_file = Some(path.clone());
+                }
                 // Add to recent files and save settings
                 self.config.settings.add_recent_file(path);
                 self.config.settings.save();
@@ -237,10 +499,72 @@ impl BendApp {
             Err(e) => {
                 log::error!("Failed to load file: {}", e);
                 self.doc.preview.decode_error = Some(format!("Failed to load file: {}", e));
+                // Don't leave behind a copy nothing is editing
+                if load_path != path {
+                    if let Err(e) = std::fs::remove_file(&load_path) {
+                        log::warn!("Failed to remove work copy: {}", e);
+                    }
+                }
             }
         }
     }
 
+    /// Open bytes read from standard input (`bend-rs -`). The document has no
+    /// file (it shows as `STDIN_FILE_NAME`) and is not added to recent files;
+    /// the format is detected from the bytes alone.
+    pub fn open_stdin(&mut self, bytes: Vec<u8>) {
+        log::info!("Loaded {} bytes from stdin", bytes.len());
+        self.load_document(None, bytes);
+        self.doc.from_stdin = true;
+    }
+
+    /// Make `bytes` the active document (in a new tab if one is already open)
+    fn load_document(&mut self, path: Option<PathBuf>, bytes: Vec<u8>) {
+        if self.doc.editor.is_some() {
+            self.open_new_tab();
+        }
+        // Parse file structure for section highlighting
+        self.doc.set_sections(parse_file(&bytes));
+        self.doc.forced_format = None;
+        self.doc.editor = Some(EditorState::new(bytes));
+        self.doc.current_file = path;
+        self.doc.source_file = None;
+        self.doc.from_stdin = false;
+        self.doc.last_auto_save_generation = 0;
+        self.doc.preview.request_refresh();
+        self.doc.preview.decode_error = None;
+        // Clear existing textures and animation state
+        self.doc.preview.reset_for_new_file();
+        self.ui.savepoints_state.clear_thumbnails();
+        self.doc.entropy_map.clear();
+        self.doc.diff_summary.clear();
+        self.doc.checksums.clear();
+    }
+
+    /// Open several files at once (e.g. a multi-file drop), each in its own tab.
+    ///
+    /// Unsupported files are skipped with a warning; the error is only shown
+    /// when none of the files could be opened. The first file ends up active.
+    pub(super) fn open_files(&mut self, paths: Vec<PathBuf>) {
+        let (supported, skipped) = split_supported(paths);
+        for path in &skipped {
+            log::warn!("Skipped unsupported dropped file: {}", path.display());
+        }
+        let Some(first) = supported.first().cloned() else {
+            if let Some(path) = skipped.into_iter().next() {
+                // Surfaces the usual "unsupported format" message
+                self.open_file(path);
+            }
+            return;
+        };
+        for path in supported {
+            self.open_file(path);
+        }
+        if let Some(index) = self.tab_for_file(&first) {
+            self.switch_tab(index);
+        }
+    }
+
     /// Open file dialog on a background thread (non-blocking)
     pub fn open_file_dialog(&mut self, ctx: &egui::Context) {
         if self.io.is_dialog_pending() {
@@ -253,7 +577,7 @@ impl BendApp {
             // when drag events fire during the modal dialog.
             let result = pollster::block_on(async {
                 rfd::AsyncFileDialog::new()
-                    .add_filter("Images", &["bmp", "jpg", "jpeg", "gif"])
+                    .add_filter("Images", SUPPORTED_EXTENSIONS)
                     .add_filter("All files", &["*"])
                     .pick_file()
                     .await
@@ -270,9 +594,20 @@ impl BendApp {
 
     /// Show all modal dialogs
     fn show_dialogs(&mut self, ctx: &egui::Context) {
+        let has_file = self.doc.editor.is_some();
+        if let Some(command) = command_palette::show(ctx, &mut self.ui.command_palette, has_file) {
+            self.run_palette_command(command, ctx);
+        }
         search_dialog::show(ctx, self);
         go_to_offset_dialog::show(ctx, &mut self.doc, &mut self.ui);
-        shortcuts_dialog::show(ctx, &mut self.ui.shortcuts_dialog_state);
+        file_diff_dialog::show(ctx, &mut self.doc, &mut self.ui);
+        shortcuts_dialog::show(
+            ctx,
+            &mut self.ui.shortcuts_dialog_state,
+            &self.config.settings.keybindings,
+        );
+        signature_scan_dialog::show(ctx, &mut self.doc, &mut self.ui);
+        byte_runs_dialog::show(ctx, &mut self.doc, &mut self.ui);
         // Settings dialog handles saving internally; sync runtime flag on change
         if settings_dialog::show(
             ctx,
@@ -281,28 +616,97 @@ impl BendApp {
         ) {
             self.ui.dialogs.suppress_high_risk_warnings =
                 !self.config.settings.show_high_risk_warnings;
+            self.sync_section_protection();
         }
         self.show_high_risk_warning_dialog(ctx);
     }
 
+    /// Watch the window's size, position and maximized state, saving them to
+    /// settings once they stop changing. While maximized only the flag is
+    /// updated, so un-maximizing next session restores the normal placement.
+    fn track_window_placement(&mut self, ctx: &egui::Context) {
+        let current_size = ctx.screen_rect().size();
+        let (current_pos, maximized) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.outer_rect.map(|r| r.min),
+                viewport.maximized.unwrap_or(false),
+            )
+        });
+
+        let moved = |last: Option<egui::Vec2>, current: egui::Vec2| {
+            last.is_some_and(|last| {
+                (current.x - last.x).abs() > WINDOW_RESIZE_THRESHOLD
+                    || (current.y - last.y).abs() > WINDOW_RESIZE_THRESHOLD
+            })
+        };
+        let resized = moved(self.io.last_window_size, current_size);
+        let repositioned = current_pos
+            .is_some_and(|pos| moved(self.io.last_window_pos.map(|p| p.to_vec2()), pos.to_vec2()));
+        let maximize_toggled = self.io.last_maximized.is_some_and(|m| m != maximized);
+        if resized || repositioned || maximize_toggled {
+            self.io.window_resize_timer = Some(Instant::now());
+        }
+        if resized || self.io.last_window_size.is_none() {
+            self.io.last_window_size = Some(current_size);
+        }
+        if repositioned || self.io.last_window_pos.is_none() {
+            self.io.last_window_pos = current_pos;
+        }
+        self.io.last_maximized = Some(maximized);
+
+        // Save placement after debounce period of no window activity
+        if let Some(timer) = self.io.window_resize_timer {
+            if timer.elapsed() > Duration::from_millis(WINDOW_RESIZE_DEBOUNCE_MS) {
+                let settings = &mut self.config.settings;
+                settings.maximized = maximized;
+                if !maximized {
+                    settings.window_width = current_size.x;
+                    settings.window_height = current_size.y;
+                    if let Some(pos) = current_pos {
+                        settings.window_x = Some(pos.x);
+                        settings.window_y = Some(pos.y);
+                    }
+                }
+                settings.save();
+                self.io.window_resize_timer = None;
+            }
+        }
+    }
+
     /// Render the status bar
-    fn render_status_bar(&self, ctx: &egui::Context) {
+    fn render_status_bar(&mut self, ctx: &egui::Context) {
         let colors = self.ui.colors;
+        if let Some(editor) = &self.doc.editor {
+            self.doc.diff_summary.update(editor);
+        }
         egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
-                // Unsaved changes indicator
-                if self.has_unsaved_changes() {
+                // Unsaved changes indicator (active tab only)
+                if self.doc.editor.as_ref().is_some_and(|e| e.is_modified()) {
                     ui.colored_label(colors.modified_indicator, "\u{25CF} Modified");
                     ui.separator();
                 }
                 if let Some(path) = &self.doc.current_file {
                     ui.label(format!("File: {}", path.display()));
+                } else if self.doc.from_stdin {
+                    ui.label(format!("File: {}", STDIN_FILE_NAME));
                 }
                 if let Some(editor) = &self.doc.editor {
                     ui.separator();
                     ui.label(format!("{} bytes", editor.working().len()));
                     ui.separator();
-                    ui.label(format!("Cursor: 0x{:08X}", editor.cursor()));
+                    ui.label(self.doc.diff_summary.text()).on_hover_text(
+                        "How much the working buffer differs from the original file",
+                    );
+                    ui.separator();
+                    ui.label(format!(
+                        "Cursor: {}",
+                        self.config
+                            .settings
+                            .offset_base
+                            .format_readout(editor.cursor())
+                    ));
                     ui.separator();
                     // Edit mode indicator
                     let mode_text = match editor.edit_mode() {
@@ -317,6 +721,61 @@ impl BendApp {
                         WriteMode::Overwrite => "OVR",
                     };
                     ui.label(write_mode_text);
+                    if let Some(status) = self
+                        .ui
+                        .motion_state
+                        .status()
+                        .filter(|_| self.config.settings.vim_motions)
+                    {
+                        ui.separator();
+                        ui.label(status).on_hover_text(
+                            "Vim command mode: type a count, then move. i or Escape to edit",
+                        );
+                    }
+                    if let Some(status) = self.ui.measure_state.status() {
+                        ui.separator();
+                        ui.label(status)
+                            .on_hover_text("Edit > Measure Distance. Escape to stop measuring");
+                    }
+                    if editor.selection_locked() {
+                        ui.separator();
+                        ui.label("Selection locked").on_hover_text(
+                            "Clicks and navigation keep the selection (Edit > Lock Selection)",
+                        );
+                    }
+                }
+                if self.ui.effects.macros.is_recording() {
+                    ui.separator();
+                    ui.colored_label(
+                        colors.error_text,
+                        format!(
+                            "\u{25CF} REC {} step(s)",
+                            self.ui.effects.macros.recorded_steps()
+                        ),
+                    )
+                    .on_hover_text("Recording a macro (Effects > Stop Recording Macro)");
+                }
+                if let Some(job) = &self.io.job {
+                    let (label, elapsed) = job.status();
+                    ui.separator();
+                    ui.spinner();
+                    ui.label(format!("{}... {}s", label, elapsed.as_secs()));
+                }
+                if let Some((text, remaining)) = self.ui.recent_job_notice() {
+                    ui.separator();
+                    ui.colored_label(colors.warning_text, text);
+                    ctx.request_repaint_after(remaining);
+                }
+                if let Some((name, remaining)) = self.ui.recent_quick_export() {
+                    ui.separator();
+                    ui.label(format!("Exported {}", name));
+                    ctx.request_repaint_after(remaining);
+                }
+                if let Some((_, remaining)) = self.ui.recent_blocked_edit() {
+                    ui.separator();
+                    ui.colored_label(colors.error_text, "Edit blocked: protected region");
+                    // Keep repainting until the message expires
+                    ctx.request_repaint_after(remaining);
                 }
                 if let Some(err) = &self.doc.preview.decode_error {
                     ui.separator();
@@ -326,12 +785,13 @@ impl BendApp {
         });
     }
 
-    /// Render the sidebar with structure tree, save points, and bookmarks
+    /// Render the sidebar with structure tree, data inspector, save points, bookmarks, byte histogram, and file info
     fn render_sidebar(&mut self, ctx: &egui::Context) {
         if self.doc.editor.is_none() {
             return;
         }
 
+        let mut section_glitch = None;
         egui::SidePanel::left("structure_panel")
             .resizable(true)
             .default_width(255.0)
@@ -342,7 +802,18 @@ impl BendApp {
                     egui::CollapsingHeader::new("File Structure")
                         .default_open(true)
                         .show(ui, |ui| {
-                            structure_tree::show(ui, &mut self.doc, &mut self.ui);
+                            section_glitch = structure_tree::show(ui, &mut self.doc, &mut self.ui);
+                        })
+                        .header_response
+                        .pointer_cursor();
+
+                    ui.add_space(10.0);
+
+                    // Cursor byte value and bit editor
+                    egui::CollapsingHeader::new("Data Inspector")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            data_inspector::show(ui, &mut self.doc, &self.ui.colors);
                         })
                         .header_response
                         .pointer_cursor();
@@ -372,8 +843,34 @@ impl BendApp {
                         })
                         .header_response
                         .pointer_cursor();
+
+                    ui.add_space(10.0);
+
+                    // Byte histogram section
+                    egui::CollapsingHeader::new("Byte Histogram")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            histogram::show(ui, &self.doc, &self.ui.colors);
+                        })
+                        .header_response
+                        .pointer_cursor();
+
+                    ui.add_space(10.0);
+
+                    // File size and checksum section (computed only while expanded)
+                    egui::CollapsingHeader::new("File Info")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            file_info::show(ui, &mut self.doc, &self.ui.colors);
+                        })
+                        .header_response
+                        .pointer_cursor();
                 });
             });
+
+        if let Some((start, end, effect)) = section_glitch {
+            self.glitch_section(start, end, effect);
+        }
     }
 
     /// Render the main content area with hex editor and image preview
@@ -386,7 +883,12 @@ impl BendApp {
                 .min_width(400.0)
                 .max_width(ctx.screen_rect().width() - 400.0) // Leave room for preview
                 .show(ctx, |ui| {
-                    ui.heading("Hex Editor");
+                    ui.horizontal(|ui| {
+                        ui.heading("Hex Editor");
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            self.render_percent_jump(ui);
+                        });
+                    });
                     hex_editor::show(ui, self);
                 });
         }
@@ -402,7 +904,7 @@ impl BendApp {
                     ui.vertical_centered(|ui| {
                         ui.heading("Welcome to bend-rs");
                         ui.add_space(20.0);
-                        ui.label("Open a BMP, JPEG, or GIF file to begin databending.");
+                        ui.label("Open a BMP, JPEG, GIF, or Netpbm file to begin databending.");
                         ui.add_space(10.0);
                         ui.label("Drag and drop a file here, or use File > Open");
                         ui.add_space(20.0);
@@ -426,36 +928,18 @@ impl eframe::App for BendApp {
         }
 
         // Refresh cached color palette for this frame
-        self.ui.colors = AppColors::new(ctx.style().visuals.dark_mode);
+        self.ui.colors = AppColors::new(ctx.style().visuals.dark_mode)
+            .with_risk_overrides(&self.config.settings.risk_colors);
 
-        // Track window size changes (debounced save)
-        let current_size = ctx.screen_rect().size();
-        if let Some(last_size) = self.io.last_window_size {
-            if (current_size.x - last_size.x).abs() > WINDOW_RESIZE_THRESHOLD
-                || (current_size.y - last_size.y).abs() > WINDOW_RESIZE_THRESHOLD
-            {
-                self.io.window_resize_timer = Some(Instant::now());
-                self.io.last_window_size = Some(current_size);
-            }
-        } else {
-            self.io.last_window_size = Some(current_size);
-        }
-
-        // Save window size after debounce period of no resize activity
-        if let Some(timer) = self.io.window_resize_timer {
-            if timer.elapsed() > Duration::from_millis(WINDOW_RESIZE_DEBOUNCE_MS) {
-                self.config.settings.window_width = current_size.x;
-                self.config.settings.window_height = current_size.y;
-                self.config.settings.save();
-                self.io.window_resize_timer = None;
-            }
-        }
+        self.track_window_placement(ctx);
 
         // Handle deferred file opening from recent files menu
         if let Some(path) = self.io.pending_open_path.take() {
             self.open_file(path);
         }
 
+        self.poll_job();
+
         // Poll background file dialogs
         if let Some(rx) = &self.io.open_dialog_rx {
             if let Ok(result) = rx.try_recv() {
@@ -470,6 +954,8 @@ impl eframe::App for BendApp {
                 match result {
                     FileDialogResult::ExportSuccess(path) => {
                         log::info!("Exported to: {}", path.display());
+                        self.remember_export_dir(&path);
+                        self.io.last_export_path = Some(path);
                     }
                     FileDialogResult::ExportError(e) => {
                         log::error!("Failed to export: {}", e);
@@ -479,11 +965,46 @@ impl eframe::App for BendApp {
                 self.io.export_dialog_rx = None;
             }
         }
+        if let Some(rx) = &self.io.import_dialog_rx {
+            if let Ok(result) = rx.try_recv() {
+                match result {
+                    FileDialogResult::ImportBytes(bytes) => self.import_bytes(&bytes),
+                    FileDialogResult::ImportError(e) => {
+                        log::error!("Failed to import bytes: {}", e);
+                    }
+                    _ => {}
+                }
+                self.io.import_dialog_rx = None;
+            }
+        }
+        if let Some(rx) = &self.io.compare_dialog_rx {
+            if let Ok(result) = rx.try_recv() {
+                match result {
+                    FileDialogResult::CompareBytes(name, bytes) => {
+                        self.load_comparison(name, bytes);
+                    }
+                    FileDialogResult::CompareError(e) => {
+                        log::error!("Failed to read comparison file: {}", e);
+                    }
+                    _ => {}
+                }
+                self.io.compare_dialog_rx = None;
+            }
+        }
+        if let Some(rx) = &self.io.session_dialog_rx {
+            if let Ok(result) = rx.try_recv() {
+                self.io.session_dialog_rx = None;
+                self.finish_session_dialog(result);
+            }
+        }
 
         // Handle input and process actions
         let input_actions = self.handle_input(ctx);
         self.process_input_actions(input_actions, ctx);
 
+        // Checkpoint long sessions (edits from the previous frame are counted here)
+        self.maybe_create_auto_save_point();
+
         // Advance animation frames (unconditional — runs independently of edits)
         self.advance_animation(ctx);
 
@@ -492,9 +1013,20 @@ impl eframe::App for BendApp {
 
         // Render UI components
         self.show_close_dialog(ctx);
+        self.show_revert_dialog(ctx);
+        self.show_paste_confirm_dialog(ctx);
+        self.show_trim_dialog(ctx);
+        self.show_large_file_dialog(ctx);
+        self.show_macro_name_dialog(ctx);
+        self.show_export_decode_dialog(ctx);
+        self.show_save_point_name_dialog(ctx);
+        self.show_audio_export_dialog(ctx);
+        self.show_gradient_fill_dialog(ctx);
         self.render_menu_bar(ctx);
         let toolbar_actions = self.render_toolbar(ctx);
         self.process_input_actions(toolbar_actions, ctx);
+        self.render_tab_strip(ctx);
+        self.show_close_tab_dialog(ctx);
         self.show_dialogs(ctx);
         self.render_status_bar(ctx);
         self.render_sidebar(ctx);
@@ -502,10 +1034,76 @@ impl eframe::App for BendApp {
     }
 }
 
+/// Create the first "<stem>_NNN.<ext>" in `dir` that doesn't exist yet,
+/// counting from 001. Each name is claimed with `create_new`, so a file that
+/// appears in the meantime is skipped rather than overwritten.
+fn create_quick_export_file(
+    dir: &std::path::Path,
+    stem: &str,
+    extension: &str,
+) -> std::io::Result<(PathBuf, std::fs::File)> {
+    for n in 1..=u32::MAX {
+        let path = dir.join(format!("{}_{:03}.{}", stem, n, extension));
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        "ran out of export numbers",
+    ))
+}
+
+/// Extension to export `data` with. The detected format decides (so carved
+/// data gets the right one), keeping a source extension that already names
+/// that format (".jpeg" stays ".jpeg"). Undetected data keeps the source
+/// extension, or gets ".bin" when there is none (e.g. stdin).
+fn export_extension(source: Option<&str>, data: &[u8]) -> String {
+    let source = source.map(str::to_ascii_lowercase);
+    let Some(detected) = detect_format(data).map(|format| format.extension(data)) else {
+        return source.unwrap_or_else(|| "bin".to_string());
+    };
+    let names_detected = source.as_deref().is_some_and(|ext| {
+        ext == detected
+            || (ext == "jpeg" && detected == "jpg")
+            || (ext == "pnm" && matches!(detected, "pbm" | "pgm" | "ppm"))
+    });
+    if names_detected {
+        source.unwrap_or_default()
+    } else {
+        detected.to_string()
+    }
+}
+
+/// Split paths into (supported formats, everything else), keeping their order
+fn split_supported(paths: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    paths
+        .into_iter()
+        .partition(|path| BendApp::is_supported_extension(path))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_supported_keeps_order() {
+        let paths = vec![
+            PathBuf::from("b.jpg"),
+            PathBuf::from("notes.txt"),
+            PathBuf::from("a.BMP"),
+        ];
+        let (supported, skipped) = split_supported(paths);
+        assert_eq!(supported, [PathBuf::from("b.jpg"), PathBuf::from("a.BMP")]);
+        assert_eq!(skipped, [PathBuf::from("notes.txt")]);
+    }
+
     #[test]
     fn test_is_supported_extension() {
         assert!(BendApp::is_supported_extension(std::path::Path::new(
@@ -529,6 +1127,12 @@ mod tests {
         assert!(BendApp::is_supported_extension(std::path::Path::new(
             "photo.GIF"
         )));
+        assert!(BendApp::is_supported_extension(std::path::Path::new(
+            "photo.ppm"
+        )));
+        assert!(BendApp::is_supported_extension(std::path::Path::new(
+            "photo.PGM"
+        )));
         assert!(!BendApp::is_supported_extension(std::path::Path::new(
             "document.txt"
         )));
@@ -557,6 +1161,115 @@ mod tests {
         assert!(app.doc.current_file.is_none());
     }
 
+    #[test]
+    fn test_quick_export_file_skips_existing_files() {
+        let dir = std::env::temp_dir().join(format!("bend-quick-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (first, _) = create_quick_export_file(&dir, "cat_glitched", "bmp").unwrap();
+        assert_eq!(first, dir.join("cat_glitched_001.bmp"));
+
+        std::fs::write(dir.join("cat_glitched_002.bmp"), b"x").unwrap();
+        let (third, _) = create_quick_export_file(&dir, "cat_glitched", "bmp").unwrap();
+        assert_eq!(third, dir.join("cat_glitched_003.bmp"));
+        // The existing file was left alone
+        assert_eq!(
+            std::fs::read(dir.join("cat_glitched_002.bmp")).unwrap(),
+            b"x"
+        );
+
+        assert!(create_quick_export_file(&dir.join("missing"), "cat_glitched", "bmp").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_stdin_detects_format_for_export_name() {
+        let mut app = BendApp::default();
+        app.open_stdin(vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10]);
+
+        assert!(app.doc.editor.is_some());
+        assert!(app.doc.cached_sections.is_some());
+        assert!(app.config.settings.recent_files.is_empty());
+        // Not a file: nothing to reveal, dedupe against or resume a session from
+        assert!(app.doc.current_file.is_none());
+        assert!(app.doc.from_stdin);
+        assert_eq!(
+            app.tab_for_file(std::path::Path::new(STDIN_FILE_NAME)),
+            None
+        );
+        let session = std::env::temp_dir().join("bend_stdin_session.json");
+        assert!(app.save_session(&session).is_err());
+        assert!(!session.exists());
+        // No extension on the synthetic name, so it comes from the bytes
+        assert_eq!(app.export_file_name(), "stdin_glitched.jpg");
+
+        // Unrecognized bytes fall back to a neutral extension
+        app.doc.editor = Some(EditorState::new(vec![0x00, 0x01]));
+        assert_eq!(app.export_file_name(), "stdin_glitched.bin");
+
+        // A real file keeps its own extension
+        app.doc.current_file = Some(PathBuf::from("/tmp/photo.gif"));
+        assert_eq!(app.export_file_name(), "photo_glitched.gif");
+    }
+
+    #[test]
+    fn test_export_extension_follows_detected_format() {
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        // Carved out of a container: the format wins over the source extension
+        assert_eq!(export_extension(Some("dat"), &jpeg), "jpg");
+        assert_eq!(export_extension(Some("bmp"), &jpeg), "jpg");
+        // Spellings of the same format are kept
+        assert_eq!(export_extension(Some("JPEG"), &jpeg), "jpeg");
+        assert_eq!(export_extension(Some("pnm"), b"P5\n1 1\n255\n\x00"), "pnm");
+        assert_eq!(export_extension(None, b"P6\n1 1\n255\n\x00\x00\x00"), "ppm");
+        // Nothing detected
+        assert_eq!(export_extension(Some("gif"), &[0, 1]), "gif");
+        assert_eq!(export_extension(None, &[0, 1]), "bin");
+    }
+
+    #[test]
+    fn test_export_warning_uses_preview_decode_result() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0x00, 0x01]));
+        app.doc.preview.decode_error = Some("Decode error: bad".to_string());
+        app.export_file(&egui::Context::default());
+        assert!(app.ui.dialogs.show_export_decode_warning);
+        assert!(!app.io.is_dialog_pending());
+    }
+
+    #[test]
+    fn test_auto_save_point_every_n_edits() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0u8; 16]));
+        app.config.settings.auto_save_point_interval = 3;
+
+        for i in 0..7u8 {
+            app.doc
+                .editor
+                .as_mut()
+                .unwrap()
+                .edit_byte(i as usize * 2, i + 1);
+            app.maybe_create_auto_save_point();
+        }
+
+        let save_points = app.doc.editor.as_ref().unwrap().save_points();
+        assert_eq!(save_points.len(), 2);
+        assert_eq!(save_points[0].name, "Auto @ gen 3");
+        assert_eq!(save_points[1].name, "Auto @ gen 6");
+    }
+
+    #[test]
+    fn test_auto_save_point_disabled_when_zero() {
+        let mut app = BendApp::default();
+        app.doc.editor = Some(EditorState::new(vec![0u8; 16]));
+
+        for i in 0..5 {
+            app.doc.editor.as_mut().unwrap().edit_byte(i * 2, 0xFF);
+            app.maybe_create_auto_save_point();
+        }
+
+        assert_eq!(app.doc.editor.as_ref().unwrap().save_point_count(), 0);
+    }
+
     #[test]
     fn test_settings_sync_suppress_warnings() {
         let mut app = BendApp::default();
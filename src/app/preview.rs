@@ -4,12 +4,118 @@ use std::time::{Duration, Instant};
 
 use super::BendApp;
 
-/// Debounce delay for preview updates (milliseconds)
-const PREVIEW_DEBOUNCE_MS: u64 = 150;
-
 /// Minimum frame delay to prevent busy-looping (browsers clamp to 10ms)
 const MIN_FRAME_DELAY_MS: u64 = 10;
 
+/// Default row width (in bytes/pixels) for the raw grayscale view
+pub const DEFAULT_RAW_VIEW_WIDTH: usize = 256;
+
+/// Maximum number of rows rendered in the raw view (keeps the texture within GPU limits)
+const MAX_RAW_VIEW_ROWS: usize = 4096;
+
+/// Slider range for the preview brightness offset
+pub const BRIGHTNESS_RANGE: std::ops::RangeInclusive<f32> = -1.0..=1.0;
+
+/// Slider range for the preview contrast factor (1.0 = unchanged)
+pub const CONTRAST_RANGE: std::ops::RangeInclusive<f32> = 0.0..=3.0;
+
+/// Slider range for the preview gamma (1.0 = unchanged, higher lifts shadows)
+pub const GAMMA_RANGE: std::ops::RangeInclusive<f32> = 0.2..=5.0;
+
+/// How the preview image is sized in its panel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreviewScale {
+    /// Shrink to the available area, preserving aspect ratio; small images
+    /// stay at their actual size
+    #[default]
+    Fit,
+    /// Like `Fit`, but small images are enlarged to fill the area too
+    Enlarge,
+    /// One image pixel per screen point, scrollable when larger than the panel
+    Actual,
+}
+
+impl PreviewScale {
+    /// Every mode, in toggle order
+    pub const ALL: [PreviewScale; 3] = [Self::Fit, Self::Enlarge, Self::Actual];
+
+    /// Toggle label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Fit => "Fit to Window",
+            Self::Enlarge => "Enlarge to Fit",
+            Self::Actual => "Actual Size",
+        }
+    }
+
+    /// Scale factor for drawing `texture_size` into `available`
+    pub fn scale_for(self, texture_size: egui::Vec2, available: egui::Vec2) -> f32 {
+        let fit = (available.x / texture_size.x).min(available.y / texture_size.y);
+        match self {
+            Self::Fit => fit.min(1.0),
+            Self::Enlarge => fit,
+            Self::Actual => 1.0,
+        }
+    }
+}
+
+/// Display-only brightness/contrast/gamma applied to decoded preview textures.
+/// The working buffer is never touched, so exports are unaffected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PreviewAdjustment {
+    /// Added to every channel, in 0.0-1.0 units
+    pub brightness: f32,
+    /// Scales channels around mid-gray
+    pub contrast: f32,
+    /// Channels are raised to `1 / gamma` (applied before contrast and brightness)
+    pub gamma: f32,
+}
+
+impl Default for PreviewAdjustment {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl PreviewAdjustment {
+    /// Whether this adjustment leaves pixels unchanged
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Lookup table mapping each 8-bit channel value to its adjusted value
+    fn lookup_table(&self) -> [u8; 256] {
+        let inverse_gamma = 1.0 / self.gamma.max(f32::EPSILON);
+        std::array::from_fn(|i| {
+            let v = (i as f32 / 255.0).powf(inverse_gamma);
+            let v = (v - 0.5) * self.contrast + 0.5 + self.brightness;
+            (v.clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+    }
+
+    /// Apply the adjustment to the color channels of `image` (alpha is kept)
+    pub fn apply(&self, mut image: egui::ColorImage) -> egui::ColorImage {
+        if self.is_identity() {
+            return image;
+        }
+        let lut = self.lookup_table();
+        for pixel in &mut image.pixels {
+            let [r, g, b, a] = pixel.to_srgba_unmultiplied();
+            *pixel = egui::Color32::from_rgba_unmultiplied(
+                lut[r as usize],
+                lut[g as usize],
+                lut[b as usize],
+                a,
+            );
+        }
+        image
+    }
+}
+
 /// State for animated GIF playback
 pub struct AnimationState {
     /// Pre-uploaded GPU texture handles (one per frame, Arc-backed — cheap to clone)
@@ -30,7 +136,6 @@ pub struct AnimationState {
 type AnimationDecodeResult = Result<(Vec<egui::ColorImage>, Vec<Duration>), image::ImageError>;
 
 /// State for image preview rendering and comparison
-#[derive(Default)]
 pub struct PreviewState {
     /// Texture handle for the rendered image preview
     pub texture: Option<egui::TextureHandle>,
@@ -38,6 +143,9 @@ pub struct PreviewState {
     pub original_texture: Option<egui::TextureHandle>,
     /// Whether the preview needs to be re-rendered
     pub dirty: bool,
+    /// Whether the next render was asked for explicitly (renders even with
+    /// Live Preview off)
+    pub refresh_requested: bool,
     /// Last decode error message (if any)
     pub decode_error: Option<String>,
     /// Whether comparison mode is enabled (side-by-side original and current)
@@ -52,6 +160,45 @@ pub struct PreviewState {
     pub pending_animation: Option<mpsc::Receiver<AnimationDecodeResult>>,
     /// Pending background decode for original animated GIF (comparison mode)
     pub pending_original_animation: Option<mpsc::Receiver<AnimationDecodeResult>>,
+    /// Whether to always show the raw grayscale view instead of the decoded image
+    pub raw_view: bool,
+    /// Row width (bytes per row) used to lay out the raw grayscale view
+    pub raw_view_width: usize,
+    /// Raw grayscale rendering of the working buffer (shown when enabled or decoding fails)
+    pub raw_texture: Option<egui::TextureHandle>,
+    /// Preview-only brightness/contrast/gamma (never written to the buffer)
+    pub adjustment: PreviewAdjustment,
+    /// Fit the image to the panel or show it at 1:1
+    pub scale_mode: PreviewScale,
+    /// Width and height of the last successful decode of the working buffer
+    pub decoded_size: Option<[usize; 2]>,
+    /// Width and height the original file's header declares
+    pub original_size: Option<[usize; 2]>,
+}
+
+impl Default for PreviewState {
+    fn default() -> Self {
+        Self {
+            texture: None,
+            original_texture: None,
+            dirty: false,
+            refresh_requested: false,
+            decode_error: None,
+            comparison_mode: false,
+            last_edit_time: None,
+            animation: None,
+            original_animation: None,
+            pending_animation: None,
+            pending_original_animation: None,
+            raw_view: false,
+            raw_view_width: DEFAULT_RAW_VIEW_WIDTH,
+            raw_texture: None,
+            adjustment: PreviewAdjustment::default(),
+            scale_mode: PreviewScale::default(),
+            decoded_size: None,
+            original_size: None,
+        }
+    }
 }
 
 impl PreviewState {
@@ -65,6 +212,30 @@ impl PreviewState {
         self.original_animation = None;
         self.pending_animation = None;
         self.pending_original_animation = None;
+        self.raw_texture = None;
+        self.decoded_size = None;
+        self.original_size = None;
+    }
+
+    /// Preview heading readout, e.g. "640 × 480 px", with the original's
+    /// size added when a glitch has changed it
+    pub fn dimensions_text(&self) -> Option<String> {
+        let [width, height] = self.decoded_size?;
+        let mut text = format!("{} \u{d7} {} px", width, height);
+        if let Some([orig_width, orig_height]) =
+            self.original_size.filter(|&size| size != [width, height])
+        {
+            text.push_str(&format!(
+                " (original {} \u{d7} {})",
+                orig_width, orig_height
+            ));
+        }
+        Some(text)
+    }
+
+    /// Whether the raw grayscale view should be displayed instead of the decoded image
+    pub fn showing_raw_view(&self) -> bool {
+        self.raw_view || (self.texture.is_none() && self.decode_error.is_some())
     }
 
     /// Mark the preview as needing update (with debounce timestamp).
@@ -73,6 +244,28 @@ impl PreviewState {
         self.last_edit_time = Some(Instant::now());
     }
 
+    /// Re-render on the next update even if Live Preview is off (explicit
+    /// refreshes and changes to how the preview is shown, not edits)
+    pub fn request_refresh(&mut self) {
+        self.mark_dirty();
+        self.refresh_requested = true;
+    }
+
+    /// Whether the next `update_preview` should render. With Live Preview
+    /// off, edits only leave the preview dirty until a refresh is requested.
+    pub fn should_render(&self, live: bool) -> bool {
+        self.dirty && (live || self.refresh_requested)
+    }
+
+    /// Re-render both previews after the adjustment changed. The original is
+    /// dropped so it is rebuilt with the new adjustment too.
+    pub fn adjustment_changed(&mut self) {
+        self.original_texture = None;
+        self.original_animation = None;
+        self.pending_original_animation = None;
+        self.request_refresh();
+    }
+
     /// Set the animation to a specific frame (used by UI controls).
     /// Uses pre-uploaded GPU texture handles — no per-frame upload needed.
     pub fn set_animation_frame(&mut self, frame_index: usize) {
@@ -137,11 +330,10 @@ fn decode_animated_gif(
     for frame in &frames {
         // Extract delay — numer_denom_ms returns (numerator, denominator)
         let (numer, denom) = frame.delay().numer_denom_ms();
-        let delay_ms = if denom == 0 {
-            MIN_FRAME_DELAY_MS
-        } else {
-            (numer / denom).max(MIN_FRAME_DELAY_MS as u32) as u64
-        };
+        let delay_ms = numer
+            .checked_div(denom)
+            .map(|ms| ms.max(MIN_FRAME_DELAY_MS as u32) as u64)
+            .unwrap_or(MIN_FRAME_DELAY_MS);
         delays.push(Duration::from_millis(delay_ms));
 
         // Convert frame buffer to egui::ColorImage
@@ -154,6 +346,21 @@ fn decode_animated_gif(
     Ok((images, delays))
 }
 
+/// Interpret bytes as an 8-bit grayscale image `width` pixels wide.
+/// The last row is padded with black; rows beyond `MAX_RAW_VIEW_ROWS` are dropped.
+fn raw_grayscale_image(data: &[u8], width: usize) -> egui::ColorImage {
+    let width = width.max(1);
+    let rows = data.len().div_ceil(width).clamp(1, MAX_RAW_VIEW_ROWS);
+    let mut pixels = vec![egui::Color32::BLACK; width * rows];
+    for (pixel, &byte) in pixels.iter_mut().zip(data) {
+        *pixel = egui::Color32::from_gray(byte);
+    }
+    egui::ColorImage {
+        size: [width, rows],
+        pixels,
+    }
+}
+
 /// Advance a single animation by one frame if playing and the delay has elapsed.
 /// Returns `true` if the frame index changed (caller should update the displayed texture).
 fn advance_single_animation(anim: &mut AnimationState) -> bool {
@@ -191,24 +398,84 @@ fn poll_animation_decode(
     }
 }
 
+/// Where an edit most likely broke decoding: the first byte the working buffer
+/// differs from the original at (the original decoded, so the break is at or
+/// after it). A truncated or extended buffer breaks where the shorter one ends.
+/// None when the buffers are identical.
+fn probable_error_offset(original: &[u8], working: &[u8]) -> Option<usize> {
+    original
+        .iter()
+        .zip(working)
+        .position(|(a, b)| a != b)
+        .or_else(|| (original.len() != working.len()).then(|| original.len().min(working.len())))
+}
+
+/// Width and height from an image's header, without decoding its pixels
+fn image_dimensions(data: &[u8]) -> Option<[usize; 2]> {
+    let (width, height) = image::ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    Some([width as usize, height as usize])
+}
+
 impl BendApp {
-    /// Decode image data into an egui texture handle.
+    /// Decode image data into an egui texture handle, with the preview adjustment applied.
     fn decode_to_texture(
         ctx: &egui::Context,
         data: &[u8],
         name: &str,
+        adjustment: PreviewAdjustment,
     ) -> Result<egui::TextureHandle, image::ImageError> {
         let img = image::load_from_memory(data)?;
         let rgba = img.to_rgba8();
         let size = [rgba.width() as usize, rgba.height() as usize];
         let pixels = rgba.into_raw();
-        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+        let color_image = adjustment.apply(egui::ColorImage::from_rgba_unmultiplied(size, &pixels));
         Ok(ctx.load_texture(name, color_image, egui::TextureOptions::LINEAR))
     }
 
+    /// Show a decode failure in the preview. When the preview was decoding
+    /// until now and "Jump to decode-error location" is on, the cursor moves
+    /// to the probable break (see `probable_error_offset`).
+    fn report_decode_error(&mut self, message: String) {
+        let newly_broken = self.doc.preview.decode_error.is_none();
+        self.doc.preview.decode_error = Some(message);
+        if newly_broken && self.config.settings.jump_to_decode_error {
+            self.jump_to_decode_error();
+        }
+    }
+
+    /// Put the cursor on the byte most likely to have broken decoding and
+    /// scroll the hex view to it
+    fn jump_to_decode_error(&mut self) {
+        let Some(editor) = &mut self.doc.editor else {
+            return;
+        };
+        let Some(offset) = probable_error_offset(editor.original(), editor.working()) else {
+            return;
+        };
+        editor.clear_unlocked_selection();
+        editor.set_cursor(offset);
+        self.ui.pending_hex_scroll = Some(editor.cursor());
+    }
+
+    /// Rebuild the raw grayscale texture from the working buffer
+    fn update_raw_texture(&mut self, ctx: &egui::Context) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        let image = raw_grayscale_image(editor.working(), self.doc.preview.raw_view_width);
+        // Nearest filtering keeps individual bytes crisp when scaled
+        self.doc.preview.raw_texture =
+            Some(ctx.load_texture("raw_view", image, egui::TextureOptions::NEAREST));
+    }
+
     /// Advance animation frame if playing and delay has elapsed.
     /// Must be called unconditionally from BendApp::update() — not guarded by dirty flag.
     pub fn advance_animation(&mut self, ctx: &egui::Context) {
+        let adjustment = self.doc.preview.adjustment;
         // Poll pending background animation decode (working buffer)
         if let Some(result) = poll_animation_decode(&mut self.doc.preview.pending_animation) {
             match result {
@@ -221,7 +488,7 @@ impl BendApp {
                             .map(|(i, frame)| {
                                 ctx.load_texture(
                                     format!("anim_frame_{i}"),
-                                    frame.clone(),
+                                    adjustment.apply(frame.clone()),
                                     egui::TextureOptions::LINEAR,
                                 )
                             })
@@ -236,6 +503,7 @@ impl BendApp {
                             .map(|a| (a.current_frame.min(frames.len() - 1), a.playing))
                             .unwrap_or((0, true));
 
+                        self.doc.preview.decoded_size = Some(textures[current_frame].size());
                         self.doc.preview.texture = Some(textures[current_frame].clone());
                         self.doc.preview.animation = Some(AnimationState {
                             textures,
@@ -249,9 +517,10 @@ impl BendApp {
                         // Single frame — treat as static (one-shot upload)
                         let texture = ctx.load_texture(
                             "preview",
-                            frames[0].clone(),
+                            adjustment.apply(frames[0].clone()),
                             egui::TextureOptions::LINEAR,
                         );
+                        self.doc.preview.decoded_size = Some(texture.size());
                         self.doc.preview.texture = Some(texture);
                         self.doc.preview.animation = None;
                     }
@@ -259,7 +528,10 @@ impl BendApp {
                 }
                 Err(e) => {
                     log::warn!("Background animated GIF decode failed: {}", e);
-                    self.doc.preview.decode_error = Some(format!("Decode error: {}", e));
+                    self.report_decode_error(format!("Decode error: {}", e));
+                    if self.doc.preview.showing_raw_view() {
+                        self.update_raw_texture(ctx);
+                    }
                 }
             }
         }
@@ -278,7 +550,7 @@ impl BendApp {
                             .map(|(i, frame)| {
                                 ctx.load_texture(
                                     format!("orig_anim_frame_{i}"),
-                                    frame.clone(),
+                                    adjustment.apply(frame.clone()),
                                     egui::TextureOptions::LINEAR,
                                 )
                             })
@@ -296,7 +568,7 @@ impl BendApp {
                     } else if frames.len() == 1 {
                         let texture = ctx.load_texture(
                             "original",
-                            frames[0].clone(),
+                            adjustment.apply(frames[0].clone()),
                             egui::TextureOptions::LINEAR,
                         );
                         self.doc.preview.original_texture = Some(texture);
@@ -346,7 +618,9 @@ impl BendApp {
                     self.doc.preview.pending_original_animation = Some(rx);
                 } else {
                     // Static original — decode directly
-                    if let Ok(texture) = Self::decode_to_texture(ctx, &original_data, "original") {
+                    if let Ok(texture) =
+                        Self::decode_to_texture(ctx, &original_data, "original", adjustment)
+                    {
                         self.doc.preview.original_texture = Some(texture);
                     }
                 }
@@ -354,17 +628,29 @@ impl BendApp {
         }
     }
 
+    /// Switch between live and manual-refresh preview and remember the choice
+    pub(super) fn toggle_live_preview(&mut self) {
+        let settings = &mut self.config.settings;
+        settings.live_preview = !settings.live_preview;
+        settings.save();
+    }
+
     /// Update the image preview texture from the working buffer
     /// Uses debouncing to prevent excessive re-renders during rapid editing
     pub fn update_preview(&mut self, ctx: &egui::Context) {
-        if !self.doc.preview.dirty {
+        if !self
+            .doc
+            .preview
+            .should_render(self.config.settings.live_preview)
+        {
             return;
         }
 
         // Debounce: wait for edits to settle before re-rendering
         if let Some(edit_time) = self.doc.preview.last_edit_time {
             let elapsed = edit_time.elapsed();
-            let debounce_duration = std::time::Duration::from_millis(PREVIEW_DEBOUNCE_MS);
+            let debounce_duration =
+                std::time::Duration::from_millis(self.config.settings.preview_debounce_ms());
             if elapsed < debounce_duration {
                 // Schedule a repaint after the remaining debounce time
                 let remaining = debounce_duration - elapsed;
@@ -378,6 +664,11 @@ impl BendApp {
         };
 
         let working = editor.working();
+        let adjustment = self.doc.preview.adjustment;
+        let mut decode_failure = None;
+        if self.doc.preview.original_size.is_none() {
+            self.doc.preview.original_size = image_dimensions(editor.original());
+        }
 
         // Check if this is a GIF
         if crate::formats::is_animated_format(working) {
@@ -411,14 +702,15 @@ impl BendApp {
             }
         } else {
             // Non-GIF: use existing static decode path
-            match Self::decode_to_texture(ctx, working, "preview") {
+            match Self::decode_to_texture(ctx, working, "preview", adjustment) {
                 Ok(texture) => {
+                    self.doc.preview.decoded_size = Some(texture.size());
                     self.doc.preview.texture = Some(texture);
                     self.doc.preview.decode_error = None;
                 }
                 Err(e) => {
                     log::warn!("Failed to decode image: {}", e);
-                    self.doc.preview.decode_error = Some(format!("Decode error: {}", e));
+                    decode_failure = Some(format!("Decode error: {}", e));
                     // Keep the old texture as "last valid state"
                 }
             }
@@ -427,15 +719,26 @@ impl BendApp {
             self.doc.preview.animation = None;
             self.doc.preview.original_animation = None;
 
-            // Also update original texture if not yet loaded
-            if self.doc.preview.original_texture.is_none() {
-                if let Ok(texture) = Self::decode_to_texture(ctx, editor.original(), "original") {
+            // Decode the original lazily, only once comparison is actually in use
+            if self.doc.preview.comparison_mode && self.doc.preview.original_texture.is_none() {
+                if let Ok(texture) =
+                    Self::decode_to_texture(ctx, editor.original(), "original", adjustment)
+                {
                     self.doc.preview.original_texture = Some(texture);
                 }
             }
         }
 
+        if let Some(message) = decode_failure {
+            self.report_decode_error(message);
+        }
+
+        if self.doc.preview.showing_raw_view() {
+            self.update_raw_texture(ctx);
+        }
+
         self.doc.preview.dirty = false;
+        self.doc.preview.refresh_requested = false;
     }
 }
 
@@ -443,13 +746,43 @@ impl BendApp {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_preview_scale_fits_both_ways_or_stays_one_to_one() {
+        let area = egui::vec2(400.0, 300.0);
+        // Large images shrink to the limiting side; tiny ones stay 1:1
+        assert_eq!(
+            PreviewScale::Fit.scale_for(egui::vec2(800.0, 200.0), area),
+            0.5
+        );
+        assert_eq!(
+            PreviewScale::Fit.scale_for(egui::vec2(16.0, 16.0), area),
+            1.0
+        );
+        // Enlarging is its own mode
+        assert_eq!(
+            PreviewScale::Enlarge.scale_for(egui::vec2(800.0, 200.0), area),
+            0.5
+        );
+        assert_eq!(
+            PreviewScale::Enlarge.scale_for(egui::vec2(16.0, 16.0), area),
+            18.75
+        );
+        assert_eq!(
+            PreviewScale::Actual.scale_for(egui::vec2(800.0, 200.0), area),
+            1.0
+        );
+        assert_eq!(PreviewState::default().scale_mode, PreviewScale::Fit);
+    }
+
     #[test]
     fn test_reset_for_new_file_clears_textures_preserves_mode() {
-        let mut state = PreviewState::default();
-        state.comparison_mode = true;
-        state.dirty = true;
-        state.last_edit_time = Some(Instant::now());
-        state.decode_error = Some("old error".to_string());
+        let mut state = PreviewState {
+            comparison_mode: true,
+            dirty: true,
+            last_edit_time: Some(Instant::now()),
+            decode_error: Some("old error".to_string()),
+            ..Default::default()
+        };
 
         state.reset_for_new_file();
 
@@ -460,6 +793,7 @@ mod tests {
         assert!(state.original_animation.is_none());
         assert!(state.pending_animation.is_none());
         assert!(state.pending_original_animation.is_none());
+        assert!(state.raw_texture.is_none());
 
         // Preserved fields
         assert!(state.comparison_mode);
@@ -468,6 +802,140 @@ mod tests {
         assert!(state.decode_error.is_some());
     }
 
+    #[test]
+    fn test_probable_error_offset_is_first_difference() {
+        assert_eq!(probable_error_offset(b"abcdef", b"abcdef"), None);
+        assert_eq!(probable_error_offset(b"abcdef", b"abXdeY"), Some(2));
+        // Truncated or extended with an unchanged prefix: where the shorter ends
+        assert_eq!(probable_error_offset(b"abcdef", b"abc"), Some(3));
+        assert_eq!(probable_error_offset(b"abc", b"abcdef"), Some(3));
+    }
+
+    #[test]
+    fn test_decode_error_jump_is_opt_in_and_fires_once() {
+        let mut app = BendApp::default();
+        let mut editor = crate::editor::EditorState::new(vec![0; 32]);
+        editor.replace_bytes(20, &[0xFF]);
+        app.doc.editor = Some(editor);
+
+        app.report_decode_error("Decode error: bad".to_string());
+        assert_eq!(app.doc.editor.as_ref().unwrap().cursor(), 0);
+
+        app.doc.preview.decode_error = None;
+        app.config.settings.jump_to_decode_error = true;
+        app.report_decode_error("Decode error: bad".to_string());
+        assert_eq!(app.doc.editor.as_ref().unwrap().cursor(), 20);
+        assert_eq!(app.ui.pending_hex_scroll, Some(20));
+
+        // A preview that was already broken doesn't pull the cursor again
+        app.doc.editor.as_mut().unwrap().set_cursor(3);
+        app.report_decode_error("Decode error: still bad".to_string());
+        assert_eq!(app.doc.editor.as_ref().unwrap().cursor(), 3);
+    }
+
+    #[test]
+    fn test_dimensions_text_notes_changed_size() {
+        let mut bmp = Vec::new();
+        image::RgbImage::new(3, 2)
+            .write_to(&mut std::io::Cursor::new(&mut bmp), image::ImageFormat::Bmp)
+            .unwrap();
+        assert_eq!(image_dimensions(&bmp), Some([3, 2]));
+        assert_eq!(image_dimensions(b"not an image"), None);
+
+        let mut state = PreviewState::default();
+        assert_eq!(state.dimensions_text(), None);
+        state.decoded_size = Some([3, 2]);
+        state.original_size = Some([3, 2]);
+        assert_eq!(state.dimensions_text().as_deref(), Some("3 \u{d7} 2 px"));
+        state.decoded_size = Some([6, 1]);
+        assert_eq!(
+            state.dimensions_text().as_deref(),
+            Some("6 \u{d7} 1 px (original 3 \u{d7} 2)")
+        );
+        state.reset_for_new_file();
+        assert_eq!(state.dimensions_text(), None);
+    }
+
+    #[test]
+    fn test_raw_grayscale_image_pads_last_row() {
+        let image = raw_grayscale_image(&[0x00, 0x80, 0xFF, 0x10, 0x20], 2);
+        assert_eq!(image.size, [2, 3]);
+        assert_eq!(image.pixels[1], egui::Color32::from_gray(0x80));
+        assert_eq!(image.pixels[4], egui::Color32::from_gray(0x20));
+        assert_eq!(image.pixels[5], egui::Color32::BLACK); // padding
+    }
+
+    #[test]
+    fn test_raw_grayscale_image_clamps_rows_and_width() {
+        let image = raw_grayscale_image(&[], 0);
+        assert_eq!(image.size, [1, 1]);
+
+        let data = vec![0u8; MAX_RAW_VIEW_ROWS * 2 + 5];
+        let image = raw_grayscale_image(&data, 1);
+        assert_eq!(image.size, [1, MAX_RAW_VIEW_ROWS]);
+    }
+
+    #[test]
+    fn test_preview_adjustment() {
+        let image = egui::ColorImage {
+            size: [3, 1],
+            pixels: vec![
+                egui::Color32::from_gray(0),
+                egui::Color32::from_gray(64),
+                egui::Color32::from_rgba_unmultiplied(200, 100, 50, 128),
+            ],
+        };
+        let identity = PreviewAdjustment::default();
+        assert!(identity.is_identity());
+        assert_eq!(identity.apply(image.clone()), image);
+
+        // Gamma above 1 lifts shadows but keeps black black
+        let gamma = PreviewAdjustment {
+            gamma: 2.2,
+            ..Default::default()
+        };
+        let lifted = gamma.apply(image.clone());
+        assert_eq!(lifted.pixels[0], egui::Color32::from_gray(0));
+        assert!(lifted.pixels[1].r() > 64);
+        // Alpha is left alone
+        assert_eq!(lifted.pixels[2].a(), 128);
+
+        let bright = PreviewAdjustment {
+            brightness: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(bright.apply(image).pixels[0], egui::Color32::from_gray(255));
+    }
+
+    #[test]
+    fn test_manual_preview_waits_for_refresh() {
+        let mut state = PreviewState::default();
+        assert!(!state.should_render(true));
+
+        // Edits render right away in live mode but not in manual mode
+        state.mark_dirty();
+        assert!(state.should_render(true));
+        assert!(!state.should_render(false));
+
+        state.request_refresh();
+        assert!(state.should_render(false));
+    }
+
+    #[test]
+    fn test_showing_raw_view() {
+        let mut state = PreviewState::default();
+        assert_eq!(state.raw_view_width, DEFAULT_RAW_VIEW_WIDTH);
+        assert!(!state.showing_raw_view());
+
+        // Falls back automatically when nothing could be decoded
+        state.decode_error = Some("bad data".to_string());
+        assert!(state.showing_raw_view());
+
+        state.decode_error = None;
+        state.raw_view = true;
+        assert!(state.showing_raw_view());
+    }
+
     #[test]
     fn test_decode_animated_gif_minimal() {
         // Build a minimal valid 2-frame animated GIF
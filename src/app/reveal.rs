@@ -0,0 +1,73 @@
+//! "Open Containing Folder" and "Reveal Exported File": show a file in the
+//! OS file manager using the platform's own opener command
+
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+
+use super::BendApp;
+
+/// Program and arguments that open the file manager at `path`'s folder,
+/// with `path` itself selected where the platform supports that
+fn reveal_command(path: &Path) -> (&'static str, Vec<OsString>) {
+    if cfg!(target_os = "macos") {
+        ("open", vec!["-R".into(), path.into()])
+    } else if cfg!(target_os = "windows") {
+        let mut select = OsString::from("/select,");
+        select.push(path);
+        ("explorer", vec![select])
+    } else {
+        // xdg-open can't select a file, so open its folder
+        let dir = path.parent().unwrap_or(path);
+        ("xdg-open", vec![dir.into()])
+    }
+}
+
+/// Open the file manager on `path` without blocking the UI
+fn reveal_in_file_manager(path: &Path) {
+    let (program, args) = reveal_command(path);
+    match Command::new(program).args(&args).spawn() {
+        // Reap the opener when it exits so it doesn't linger as a zombie
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(e) => log::error!("Failed to run {}: {}", program, e),
+    }
+}
+
+impl BendApp {
+    /// Show the open file in the OS file manager
+    pub(super) fn open_containing_folder(&self) {
+        if let Some(path) = &self.doc.current_file {
+            reveal_in_file_manager(path);
+        }
+    }
+
+    /// Show the most recently exported file in the OS file manager
+    pub(super) fn reveal_last_export(&self) {
+        if let Some(path) = &self.io.last_export_path {
+            reveal_in_file_manager(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reveal_command_targets_the_file() {
+        let path = Path::new("/tmp/exports/photo_glitched.bmp");
+        let (program, args) = reveal_command(path);
+        if cfg!(target_os = "macos") {
+            assert_eq!(program, "open");
+            assert_eq!(args, vec![OsString::from("-R"), path.into()]);
+        } else if cfg!(target_os = "windows") {
+            assert_eq!(program, "explorer");
+            assert_eq!(args.len(), 1);
+        } else {
+            assert_eq!(program, "xdg-open");
+            assert_eq!(args, vec![OsString::from("/tmp/exports")]);
+        }
+    }
+}
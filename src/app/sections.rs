@@ -5,14 +5,20 @@
 
 use eframe::egui;
 
+use crate::formats::RiskLevel;
+
 use super::BendApp;
 
 impl BendApp {
     /// Get the background color for a byte based on its section's risk level.
-    /// Combines document-structure lookup with UI color palette.
+    /// Combines document-structure lookup with UI color palette. Unknown
+    /// (gap-filled) sections are left untinted when the user turns that off.
     pub fn section_color_for_offset(&self, offset: usize) -> Option<egui::Color32> {
         self.doc
             .section_at_offset(offset)
+            .filter(|section| {
+                section.risk != RiskLevel::Unknown || self.config.settings.highlight_unknown_regions
+            })
             .map(|section| self.ui.colors.risk_bg_color(section.risk))
     }
 
@@ -29,18 +35,13 @@ impl BendApp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app::DocumentState;
-    use crate::formats::{FileSection, RiskLevel};
+    use crate::formats::FileSection;
 
     /// Helper to create a test app with cached sections
     fn create_test_app_with_sections(sections: Vec<FileSection>) -> BendApp {
-        BendApp {
-            doc: DocumentState {
-                cached_sections: Some(sections),
-                ..Default::default()
-            },
-            ..Default::default()
-        }
+        let mut app = BendApp::default();
+        app.doc.set_sections(Some(sections));
+        app
     }
 
     #[test]
@@ -121,6 +122,41 @@ mod tests {
         assert!(color.is_none());
     }
 
+    #[test]
+    fn test_unknown_regions_can_be_left_untinted() {
+        let sections = vec![
+            FileSection::new("Header", 0, 10, RiskLevel::Critical),
+            FileSection::new("Unknown", 10, 20, RiskLevel::Unknown),
+        ];
+        let mut app = create_test_app_with_sections(sections);
+        assert!(app.section_color_for_offset(15).is_some());
+
+        app.config.settings.highlight_unknown_regions = false;
+        assert!(app.section_color_for_offset(15).is_none());
+        assert!(app.section_color_for_offset(5).is_some());
+        // The section itself is still there for the structure tree
+        assert!(app.doc.section_at_offset(15).is_some());
+    }
+
+    #[test]
+    fn test_matching_marker_offset() {
+        let mut header = FileSection::new("Header", 0, 20, RiskLevel::Critical);
+        header.children = vec![FileSection::new("Marker", 4, 6, RiskLevel::High)];
+        let app = create_test_app_with_sections(vec![
+            header,
+            FileSection::new("Tiny", 20, 21, RiskLevel::Safe),
+        ]);
+
+        assert_eq!(app.doc.matching_marker_offset(0), Some(19));
+        assert_eq!(app.doc.matching_marker_offset(19), Some(0));
+        assert_eq!(app.doc.matching_marker_offset(10), Some(0));
+        // The innermost section is matched
+        assert_eq!(app.doc.matching_marker_offset(4), Some(5));
+        assert_eq!(app.doc.matching_marker_offset(5), Some(4));
+        assert_eq!(app.doc.matching_marker_offset(20), None);
+        assert_eq!(app.doc.matching_marker_offset(50), None);
+    }
+
     #[test]
     fn test_section_at_offset_no_sections() {
         let app = BendApp::default();
@@ -234,4 +270,55 @@ mod tests {
         assert!(!app.doc.is_range_protected(10, 5));
         assert!(!app.doc.is_range_protected(20, 5));
     }
+
+    #[test]
+    fn test_flattened_sections_depth_first() {
+        let parent = FileSection::new("Header", 0, 54, RiskLevel::Caution)
+            .with_child(FileSection::new("Magic", 0, 2, RiskLevel::Critical));
+        let sections = vec![parent, FileSection::new("Data", 54, 100, RiskLevel::Safe)];
+        let app = create_test_app_with_sections(sections);
+
+        let flat: Vec<(usize, &str)> = app
+            .doc
+            .flattened_sections()
+            .into_iter()
+            .map(|(depth, s)| (depth, s.name.as_ref()))
+            .collect();
+        assert_eq!(flat, [(0, "Header"), (1, "Magic"), (0, "Data")]);
+    }
+
+    #[test]
+    fn test_default_bookmark_name_uses_section() {
+        let sections = vec![FileSection::new("Pixel Data", 10, 100, RiskLevel::Safe)];
+        let app = create_test_app_with_sections(sections);
+
+        assert_eq!(app.doc.default_bookmark_name(16), "Pixel Data @ 0x00000010");
+        assert_eq!(app.doc.default_bookmark_name(5), "Bookmark at 0x00000005");
+    }
+
+    #[test]
+    fn test_section_name_protection_lists() {
+        let header = FileSection::new("Header", 0, 20, RiskLevel::Caution)
+            .with_child(FileSection::new("Magic", 0, 2, RiskLevel::Critical))
+            .with_child(FileSection::new("Palette", 2, 10, RiskLevel::High));
+        let sections = vec![header, FileSection::new("Data", 20, 40, RiskLevel::Safe)];
+        let mut app = create_test_app_with_sections(sections);
+        app.doc.header_protection = true;
+        app.doc.allowed_section_names = vec!["Palette".to_string()];
+        app.doc.protected_section_names = vec!["Header".to_string(), "Data".to_string()];
+        app.doc.refresh_protection();
+
+        // Allowlist beats the High risk rule
+        assert!(!app.doc.is_offset_protected(5));
+        // Critical child is still protected (via the rule and the parent name)
+        assert!(app.doc.is_offset_protected(0));
+        // Caution region protected because its parent is named
+        assert!(app.doc.is_offset_protected(15));
+        // Safe section protected by name
+        assert!(app.doc.is_offset_protected(30));
+
+        // Lists only apply while header protection is on
+        app.doc.header_protection = false;
+        assert!(!app.doc.is_offset_protected(30));
+    }
 }
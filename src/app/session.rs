@@ -0,0 +1,295 @@
+//! Save and resume a whole editing session as JSON
+//!
+//! A session file records the source file's path (its bytes stay the base for
+//! save point diffs), the working buffer, save points, bookmarks, header
+//! protection and the cursor/selection. Undo history is not kept. Loading
+//! re-reads the source file and refuses if it changed since the session was
+//! saved, since the save point diffs would no longer line up.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::bookmarks::Bookmark;
+use crate::editor::savepoints::SavePoint;
+use crate::editor::EditorState;
+
+use super::state::FileDialogResult;
+use super::{spawn_file_dialog, BendApp};
+
+/// Session format version written by this build
+const SESSION_VERSION: u32 = 1;
+
+/// Extension offered for session files
+const SESSION_EXTENSION: &str = "json";
+
+/// Everything needed to resume editing a document
+#[derive(Serialize, Deserialize)]
+struct Session {
+    version: u32,
+    /// File the document was opened from
+    source_path: PathBuf,
+    /// CRC32 of the source file's bytes, to detect it changing on disk
+    source_crc32: u32,
+    /// Working buffer, base64-encoded
+    working: String,
+    save_points: Vec<SavePoint>,
+    bookmarks: Vec<Bookmark>,
+    header_protection: bool,
+    cursor: usize,
+    selection: Option<(usize, usize)>,
+    /// Further ranges of a multi-range selection, after `selection`
+    #[serde(default)]
+    extra_selections: Vec<(usize, usize)>,
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard (padded) base64 encoding of `data`
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decode standard base64, or None if `text` isn't valid base64
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    for chunk in text.chunks(4) {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 {
+            return None;
+        }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+            n = (n << 6) | value;
+        }
+        n <<= 6 * padding as u32;
+        out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Some(out)
+}
+
+impl BendApp {
+    /// Serialize the active document's session to JSON
+    fn session_json(&self) -> Result<String, String> {
+        let Some(editor) = &self.doc.editor else {
+            return Err("No file is open".to_string());
+        };
+        let Some(source_path) = self.doc.current_file.clone() else {
+            return Err("Sessions need a document opened from a file".to_string());
+        };
+        let selections = editor.selections();
+        let session = Session {
+            version: SESSION_VERSION,
+            source_path,
+            source_crc32: crc32fast::hash(editor.original()),
+            working: base64_encode(editor.working()),
+            save_points: editor.save_points().to_vec(),
+            bookmarks: editor.bookmarks().all().to_vec(),
+            header_protection: self.doc.header_protection,
+            cursor: editor.cursor(),
+            selection: selections.first().copied(),
+            extra_selections: selections.get(1..).unwrap_or_default().to_vec(),
+        };
+        serde_json::to_string_pretty(&session).map_err(|e| e.to_string())
+    }
+
+    /// Write the active document's session to `path`
+    pub fn save_session(&self, path: &Path) -> Result<(), String> {
+        let json = self.session_json()?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    /// Resume the session saved at `path`, in a new tab if a file is already open
+    pub fn load_session(&mut self, path: &Path) -> Result<(), String> {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let session: Session =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid session file: {}", e))?;
+        if session.version > SESSION_VERSION {
+            return Err(format!(
+                "Session version {} is newer than this build supports",
+                session.version
+            ));
+        }
+        let original = std::fs::read(&session.source_path).map_err(|e| {
+            format!(
+                "Failed to read source file {}: {}",
+                session.source_path.display(),
+                e
+            )
+        })?;
+        if crc32fast::hash(&original) != session.source_crc32 {
+            return Err(format!(
+                "{} has changed since the session was saved",
+                session.source_path.display()
+            ));
+        }
+        let working = base64_decode(&session.working)
+            .ok_or_else(|| "Invalid session file: bad working buffer".to_string())?;
+
+        self.load_document(Some(session.source_path), original.clone());
+        let mut editor =
+            EditorState::from_session(original, working, session.save_points, session.bookmarks);
+        match session.selection {
+            Some(range) => {
+                let ranges: Vec<_> = std::iter::once(range)
+                    .chain(session.extra_selections)
+                    .collect();
+                editor.set_selections(&ranges);
+            }
+            None => editor.set_cursor(session.cursor),
+        }
+        self.ui.pending_hex_scroll = Some(editor.cursor());
+        self.doc.editor = Some(editor);
+        self.doc.header_protection = session.header_protection;
+        self.doc.reparse_sections();
+        Ok(())
+    }
+
+    /// Pick where to save the active document's session (non-blocking)
+    pub(super) fn save_session_dialog(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() || self.doc.editor.is_none() {
+            return;
+        }
+        let file_name = format!(
+            "{}_session.{}",
+            self.doc.name_stem().unwrap_or_else(|| "bend".to_string()),
+            SESSION_EXTENSION
+        );
+
+        let rx = spawn_file_dialog(ctx, move || {
+            let result = pollster::block_on(async {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name(file_name)
+                    .add_filter("Bend session", &[SESSION_EXTENSION])
+                    .save_file()
+                    .await
+            });
+            match result {
+                Some(handle) => FileDialogResult::SaveSession(handle.path().to_path_buf()),
+                None => FileDialogResult::Cancelled,
+            }
+        });
+        self.io.session_dialog_rx = Some(rx);
+    }
+
+    /// Pick a session file to resume (non-blocking)
+    pub(super) fn load_session_dialog(&mut self, ctx: &egui::Context) {
+        if self.io.is_dialog_pending() {
+            return;
+        }
+        let rx = spawn_file_dialog(ctx, || {
+            let result = pollster::block_on(async {
+                rfd::AsyncFileDialog::new()
+                    .add_filter("Bend session", &[SESSION_EXTENSION])
+                    .add_filter("All files", &["*"])
+                    .pick_file()
+                    .await
+            });
+            match result {
+                Some(handle) => FileDialogResult::OpenSession(handle.path().to_path_buf()),
+                None => FileDialogResult::Cancelled,
+            }
+        });
+        self.io.session_dialog_rx = Some(rx);
+    }
+
+    /// Handle a finished session dialog
+    pub(super) fn finish_session_dialog(&mut self, result: FileDialogResult) {
+        match result {
+            FileDialogResult::SaveSession(path) => match self.save_session(&path) {
+                Ok(()) => log::info!("Saved session to: {}", path.display()),
+                Err(e) => log::error!("Failed to save session: {}", e),
+            },
+            FileDialogResult::OpenSession(path) => {
+                if let Err(e) = self.load_session(&path) {
+                    log::error!("Failed to load session: {}", e);
+                    self.doc.preview.decode_error = Some(format!("Failed to load session: {}", e));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(base64_decode(&base64_encode(&data)).unwrap(), data);
+        assert_eq!(base64_decode("Zm8=").unwrap(), b"fo");
+        assert!(base64_decode("Zm8").is_none());
+        assert!(base64_decode("Z!==").is_none());
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let dir = std::env::temp_dir().join(format!("bend_session_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.bmp");
+        std::fs::write(&source, [0u8; 8]).unwrap();
+
+        let mut app = BendApp::default();
+        app.load_document(Some(source.clone()), vec![0; 8]);
+        let editor = app.doc.editor.as_mut().unwrap();
+        editor.edit_byte(1, 0xAA);
+        editor.create_save_point("First".to_string());
+        editor.edit_byte(2, 0xBB);
+        editor.add_bookmark(5, "Here".to_string());
+        editor.set_selection(2, 4);
+        editor.add_selection_at(6);
+        editor.extend_selection_to(6);
+        app.doc.header_protection = true;
+
+        let session = dir.join("test_session.json");
+        app.save_session(&session).unwrap();
+
+        let mut resumed = BendApp::default();
+        resumed.load_session(&session).unwrap();
+        let editor = resumed.doc.editor.as_ref().unwrap();
+        assert_eq!(editor.working(), &[0, 0xAA, 0xBB, 0, 0, 0, 0, 0]);
+        assert_eq!(editor.original(), &[0; 8]);
+        assert_eq!(editor.save_points()[0].name, "First");
+        assert_eq!(
+            editor.save_point_bytes(editor.save_points()[0].id).unwrap(),
+            [0, 0xAA, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(editor.bookmarks().all()[0].offset, 5);
+        assert_eq!(editor.selections(), vec![(2, 4), (6, 7)]);
+        assert!(resumed.doc.header_protection);
+
+        // A changed source file would make the save point diffs meaningless
+        std::fs::write(&source, [1u8; 8]).unwrap();
+        assert!(BendApp::default().load_session(&session).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -10,30 +10,53 @@
 //! 3. `UiState`         — UI panel/dialog/cache state
 //! 4. `DocumentState`   — loaded document, editor, preview
 
+use crate::editor::checksum::FileChecksums;
+use crate::editor::clipboard_history::ClipboardHistory;
+use crate::editor::diff_stats::DiffSummary;
+use crate::editor::entropy::EntropyMap;
+use crate::editor::file_diff::FileDiffState;
+use crate::editor::text_encoding::TextEncoding;
 use crate::editor::{EditorState, GoToOffsetState, SearchState};
-use crate::formats::{FileSection, RiskLevel};
+use crate::formats::{FileSection, FormatKind, RiskLevel};
 use crate::settings::AppSettings;
 use crate::ui::bookmarks::BookmarksPanelState;
-use crate::ui::hex_editor::ContextMenuState;
+use crate::ui::byte_runs_dialog::ByteRunsState;
+use crate::ui::command_palette::CommandPaletteState;
+use crate::ui::hex_editor::{ContextMenuState, MeasureState, MotionState};
 use crate::ui::savepoints::SavePointsPanelState;
 use crate::ui::settings_dialog::SettingsDialogState;
 use crate::ui::shortcuts_dialog::ShortcutsDialogState;
+use crate::ui::signature_scan_dialog::SignatureScanState;
 use crate::ui::theme::AppColors;
+use std::ops::Range;
 use std::path::PathBuf;
 use std::sync::mpsc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use super::{DialogState, PreviewState};
+use super::jobs::BackgroundJob;
+use super::{
+    AudioExportState, DialogState, DocumentId, EffectsState, PreviewState, STDIN_FILE_NAME,
+};
 
 /// Result of a background file dialog thread.
 pub(super) enum FileDialogResult {
     OpenFile(PathBuf),
     ExportSuccess(PathBuf),
     ExportError(String),
+    /// Contents of a file chosen for "Import Bytes into Selection"
+    ImportBytes(Vec<u8>),
+    ImportError(String),
+    /// Name and contents of a file chosen for "Compare with File"
+    CompareBytes(String, Vec<u8>),
+    CompareError(String),
+    /// Where to write the session chosen with "Save Session"
+    SaveSession(PathBuf),
+    /// A session file chosen for "Load Session"
+    OpenSession(PathBuf),
     Cancelled,
 }
 
-/// I/O plumbing: file-dialog receivers, deferred open paths, window-resize debounce.
+/// I/O plumbing: file-dialog receivers, deferred open paths, window-placement debounce.
 #[derive(Default)]
 pub struct IoState {
     /// Pending file path to open (for deferred actions from menus)
@@ -42,6 +65,12 @@ pub struct IoState {
     /// Last known window size (for change detection)
     pub last_window_size: Option<eframe::egui::Vec2>,
 
+    /// Last known outer window position (for change detection)
+    pub last_window_pos: Option<eframe::egui::Pos2>,
+
+    /// Last known maximized state (for change detection)
+    pub last_maximized: Option<bool>,
+
     /// Timer for debouncing window resize saves
     pub window_resize_timer: Option<Instant>,
 
@@ -50,12 +79,31 @@ pub struct IoState {
 
     /// Receiver for a pending export dialog running on a background thread
     pub(super) export_dialog_rx: Option<mpsc::Receiver<FileDialogResult>>,
+
+    /// Receiver for a pending byte-import dialog running on a background thread
+    pub(super) import_dialog_rx: Option<mpsc::Receiver<FileDialogResult>>,
+
+    /// Receiver for a pending compare-with-file dialog running on a background thread
+    pub(super) compare_dialog_rx: Option<mpsc::Receiver<FileDialogResult>>,
+
+    /// Receiver for a pending save/load session dialog running on a background thread
+    pub(super) session_dialog_rx: Option<mpsc::Receiver<FileDialogResult>>,
+
+    /// File written by the most recent successful export ("Reveal Exported File")
+    pub last_export_path: Option<PathBuf>,
+
+    /// Heavy edit running on a worker thread, committed when it finishes
+    pub(super) job: Option<BackgroundJob>,
 }
 
 impl IoState {
     /// Returns true if a file dialog is already open on a background thread.
     pub fn is_dialog_pending(&self) -> bool {
-        self.open_dialog_rx.is_some() || self.export_dialog_rx.is_some()
+        self.open_dialog_rx.is_some()
+            || self.export_dialog_rx.is_some()
+            || self.import_dialog_rx.is_some()
+            || self.compare_dialog_rx.is_some()
+            || self.session_dialog_rx.is_some()
     }
 }
 
@@ -83,6 +131,12 @@ pub struct UiState {
     /// Context menu state for hex editor
     pub context_menu_state: ContextMenuState,
 
+    /// Vim-style command mode and pending count for the hex editor
+    pub motion_state: MotionState,
+
+    /// Two-click "Measure Distance" mode for the hex editor
+    pub measure_state: MeasureState,
+
     /// Search and replace state
     pub search_state: SearchState,
 
@@ -101,8 +155,96 @@ pub struct UiState {
     /// Settings/preferences dialog state
     pub settings_dialog_state: SettingsDialogState,
 
+    /// Command palette (Ctrl+Shift+P) state
+    pub command_palette: CommandPaletteState,
+
+    /// Image header scan dialog state
+    pub signature_scan_state: SignatureScanState,
+
+    /// Byte run finder dialog state
+    pub byte_runs_state: ByteRunsState,
+
+    /// "Compare with File" results
+    pub file_diff: FileDiffState,
+
     /// Pending scroll offset for hex editor (Some(offset) = scroll to this byte offset)
     pub pending_hex_scroll: Option<usize>,
+
+    /// Whether the hex editor tints bytes by local entropy instead of section risk
+    pub show_entropy_map: bool,
+
+    /// Whether the hex editor highlights bytes that differ from the original
+    pub show_diff_highlight: bool,
+
+    /// Character set the ASCII pane shows bytes in
+    pub text_encoding: TextEncoding,
+
+    /// Shared effect parameters (corruption intensity, XOR key)
+    pub effects: EffectsState,
+
+    /// Options for exporting bytes as PCM audio
+    pub audio_export: AudioExportState,
+
+    /// Offset and time of the last edit blocked by protection, for brief feedback
+    pub blocked_edit: Option<(usize, Instant)>,
+
+    /// File name and time of the last Quick Export, for a brief status message
+    pub quick_export_notice: Option<(String, Instant)>,
+
+    /// Message and time of the last background job cancel/discard (or an edit
+    /// refused while one runs), for a status message
+    pub job_notice: Option<(String, Instant)>,
+
+    /// Recently copied/pasted bytes, shared by all tabs ("Paste Recent")
+    pub clipboard_history: ClipboardHistory,
+}
+
+/// How long the blocked-edit flash and status message stay visible
+pub const BLOCKED_EDIT_FEEDBACK: Duration = Duration::from_millis(1200);
+
+/// How long the "Exported ..." status message stays after a Quick Export
+pub const QUICK_EXPORT_FEEDBACK: Duration = Duration::from_secs(3);
+
+/// How long a background job notice stays in the status bar
+pub const JOB_NOTICE_FEEDBACK: Duration = Duration::from_secs(6);
+
+impl UiState {
+    /// Remember that an edit at `offset` was refused because the byte is protected
+    pub fn flag_blocked_edit(&mut self, offset: usize) {
+        self.blocked_edit = Some((offset, Instant::now()));
+    }
+
+    /// Offset of a recently blocked edit and how long its feedback has left
+    pub fn recent_blocked_edit(&self) -> Option<(usize, Duration)> {
+        let (offset, at) = self.blocked_edit?;
+        BLOCKED_EDIT_FEEDBACK
+            .checked_sub(at.elapsed())
+            .filter(|left| !left.is_zero())
+            .map(|left| (offset, left))
+    }
+
+    /// Show a background job notice in the status bar
+    pub fn flag_job_notice(&mut self, text: String) {
+        self.job_notice = Some((text, Instant::now()));
+    }
+
+    /// A recent background job notice and how long it has left
+    pub fn recent_job_notice(&self) -> Option<(&str, Duration)> {
+        let (text, at) = self.job_notice.as_ref()?;
+        JOB_NOTICE_FEEDBACK
+            .checked_sub(at.elapsed())
+            .filter(|left| !left.is_zero())
+            .map(|left| (text.as_str(), left))
+    }
+
+    /// Name of a recent Quick Export and how long its message has left
+    pub fn recent_quick_export(&self) -> Option<(&str, Duration)> {
+        let (name, at) = self.quick_export_notice.as_ref()?;
+        QUICK_EXPORT_FEEDBACK
+            .checked_sub(at.elapsed())
+            .filter(|left| !left.is_zero())
+            .map(|left| (name.as_str(), left))
+    }
 }
 
 /// Document state: the loaded buffer/editor, its preview, parsed structure,
@@ -112,12 +254,21 @@ pub struct UiState {
 /// Closing a file resets this substate; nothing else needs to change.
 #[derive(Default)]
 pub struct DocumentState {
+    /// Identifies this document while its tab index shifts
+    pub id: DocumentId,
+
     /// Editor state containing buffers, history, and file metadata
     pub editor: Option<EditorState>,
 
     /// Path to currently loaded file (for display purposes)
     pub current_file: Option<PathBuf>,
 
+    /// File the user opened when `current_file` is a Work on Copy duplicate of it
+    pub source_file: Option<PathBuf>,
+
+    /// Whether the document was read from standard input (it has no file)
+    pub from_stdin: bool,
+
     /// Image preview state (textures, dirty flag, comparison mode)
     pub preview: PreviewState,
 
@@ -125,11 +276,102 @@ pub struct DocumentState {
     /// Re-parsed when file is loaded or structure potentially changed
     pub cached_sections: Option<Vec<FileSection>>,
 
+    /// Format chosen with Edit > Force Format (None = auto-detect)
+    pub forced_format: Option<FormatKind>,
+
     /// Whether header protection is enabled (blocks edits to high-risk sections)
     pub header_protection: bool,
+
+    /// Section names that are always protected while header protection is on,
+    /// regardless of risk level (synced from `AppSettings::protected_sections`)
+    pub protected_section_names: Vec<String>,
+
+    /// Section names that are never protected, regardless of risk level
+    /// (synced from `AppSettings::allowed_sections`)
+    pub allowed_section_names: Vec<String>,
+
+    /// Sorted, non-overlapping byte ranges the name lists and section risks
+    /// protect (while header protection is on); see `refresh_protection`
+    pub(super) protected_ranges: Vec<Range<usize>>,
+
+    /// Edit generation at which the last automatic save point was created
+    pub last_auto_save_generation: u64,
+
+    /// Cached sliding-window entropy of the working buffer (for the entropy overlay)
+    pub entropy_map: EntropyMap,
+
+    /// Cached "bytes changed vs original" summary for the status bar
+    pub diff_summary: DiffSummary,
+
+    /// Cached sizes and CRC32s of the working and original buffers (for the file info panel)
+    pub checksums: FileChecksums,
+}
+
+/// Whether `start..start + len` overlaps any of the sorted, disjoint `ranges`
+pub fn overlaps_any(ranges: &[Range<usize>], start: usize, len: usize) -> bool {
+    if len == 0 {
+        return false;
+    }
+    let end = start.saturating_add(len);
+    let index = ranges.partition_point(|r| r.end <= start);
+    ranges.get(index).is_some_and(|range| range.start < end)
 }
 
 impl DocumentState {
+    /// Stem for names derived from the document (exports, sessions): the
+    /// file's stem, or `STDIN_FILE_NAME` for piped input
+    pub fn name_stem(&self) -> Option<String> {
+        match &self.current_file {
+            Some(path) => path.file_stem().map(|s| s.to_string_lossy().into_owned()),
+            None => self.from_stdin.then(|| STDIN_FILE_NAME.to_string()),
+        }
+    }
+
+    /// Re-parse the working buffer into `cached_sections`, using the forced
+    /// format if one is set
+    pub fn reparse_sections(&mut self) {
+        let Some(editor) = &self.editor else {
+            return;
+        };
+        let sections = match self.forced_format {
+            Some(format) => Some(crate::formats::parse_file_as(editor.working(), format)),
+            None => crate::formats::parse_file(editor.working()),
+        };
+        self.set_sections(sections);
+    }
+
+    /// Replace `cached_sections` and the protected ranges derived from them
+    pub fn set_sections(&mut self, sections: Option<Vec<FileSection>>) {
+        self.cached_sections = sections;
+        self.refresh_protection();
+    }
+
+    /// Recompute `protected_ranges` from the sections and name lists. Called
+    /// whenever either changes so protection checks are a binary search.
+    pub fn refresh_protection(&mut self) {
+        let mut bounds: Vec<usize> = self
+            .flattened_sections()
+            .iter()
+            .flat_map(|(_, section)| [section.start, section.end])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        // The section path is the same for every byte between two bounds
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for pair in bounds.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            if !self.path_is_protected(start) {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(last) if last.end == start => last.end = end,
+                _ => ranges.push(start..end),
+            }
+        }
+        self.protected_ranges = ranges;
+    }
+
     /// Find the section containing a byte offset
     pub fn section_at_offset(&self, offset: usize) -> Option<&FileSection> {
         fn find_in_sections(sections: &[FileSection], offset: usize) -> Option<&FileSection> {
@@ -150,23 +392,107 @@ impl DocumentState {
             .and_then(|sections| find_in_sections(sections, offset))
     }
 
-    /// Check if an offset is in a protected region (header protection enabled + High/Critical risk)
-    pub fn is_offset_protected(&self, offset: usize) -> bool {
-        if !self.header_protection {
-            return false;
+    /// Where "go to matching marker" lands from `offset`, like bracket matching:
+    /// the last byte of the enclosing section when on its first byte, otherwise
+    /// its first byte. None outside any section or in a one-byte section.
+    pub fn matching_marker_offset(&self, offset: usize) -> Option<usize> {
+        let section = self.section_at_offset(offset)?;
+        let last = section.end.checked_sub(1)?;
+        if last <= section.start {
+            return None;
         }
+        Some(if offset == section.start {
+            last
+        } else {
+            section.start
+        })
+    }
 
-        self.section_at_offset(offset)
+    /// All cached sections in depth-first order, paired with their nesting depth
+    pub fn flattened_sections(&self) -> Vec<(usize, &FileSection)> {
+        fn flatten<'a>(
+            sections: &'a [FileSection],
+            depth: usize,
+            out: &mut Vec<(usize, &'a FileSection)>,
+        ) {
+            for section in sections {
+                out.push((depth, section));
+                flatten(&section.children, depth + 1, out);
+            }
+        }
+
+        let mut out = Vec::new();
+        if let Some(sections) = &self.cached_sections {
+            flatten(sections, 0, &mut out);
+        }
+        out
+    }
+
+    /// Default name for a bookmark at `offset`, named after the section it lands in
+    pub fn default_bookmark_name(&self, offset: usize) -> String {
+        match self.section_at_offset(offset) {
+            Some(section) => format!("{} @ 0x{:08X}", section.name, offset),
+            None => format!("Bookmark at 0x{:08X}", offset),
+        }
+    }
+
+    /// Sections containing a byte offset, from the top-level section down to the innermost
+    fn section_path_at_offset(&self, offset: usize) -> Vec<&FileSection> {
+        let mut path = Vec::new();
+        let mut level = self.cached_sections.as_deref().unwrap_or_default();
+        while let Some(section) = level.iter().find(|s| offset >= s.start && offset < s.end) {
+            path.push(section);
+            level = &section.children;
+        }
+        path
+    }
+
+    /// Whether the sections containing `offset` make it protected, ignoring
+    /// whether header protection is on.
+    ///
+    /// Section name allow/protect lists are checked first, innermost section first,
+    /// so naming a parent section covers its children unless a child is listed itself.
+    fn path_is_protected(&self, offset: usize) -> bool {
+        let path = self.section_path_at_offset(offset);
+        for section in path.iter().rev() {
+            let name: &str = section.name.as_ref();
+            if self.allowed_section_names.iter().any(|n| n == name) {
+                return false;
+            }
+            if self.protected_section_names.iter().any(|n| n == name) {
+                return true;
+            }
+        }
+
+        path.last()
             .map(|section| matches!(section.risk, RiskLevel::High | RiskLevel::Critical))
             .unwrap_or(false)
     }
 
+    /// Check if an offset is in a protected region (header protection enabled + High/Critical risk)
+    pub fn is_offset_protected(&self, offset: usize) -> bool {
+        self.is_range_protected(offset, 1)
+    }
+
+    /// Copy the section name allow/protect lists from settings
+    pub fn sync_section_protection(&mut self, settings: &AppSettings) {
+        self.protected_section_names = settings.protected_sections.clone();
+        self.allowed_section_names = settings.allowed_sections.clone();
+        self.refresh_protection();
+    }
+
     /// Check if any byte in a range overlaps a protected region
     pub fn is_range_protected(&self, start: usize, len: usize) -> bool {
-        if !self.header_protection || len == 0 {
-            return false;
+        overlaps_any(self.protected_ranges(), start, len)
+    }
+
+    /// The protected byte ranges, sorted (empty while header protection is off)
+    pub fn protected_ranges(&self) -> &[Range<usize>] {
+        if self.header_protection {
+            &self.protected_ranges
+        } else {
+            &[]
         }
-        (start..start + len).any(|offset| self.is_offset_protected(offset))
     }
 
     /// Check if an offset is in a high-risk region that should show a warning.
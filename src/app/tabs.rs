@@ -0,0 +1,505 @@
+//! Multi-file tabs
+//!
+//! The active document always lives in `BendApp::doc`, so the rest of the app
+//! keeps working with a single `DocumentState`. Inactive tabs are parked here
+//! and swapped in when selected. Tab order is
+//! `parked[..active] ++ [doc] ++ parked[active..]`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use eframe::egui;
+
+use crate::settings::AppSettings;
+use crate::ui::PointerCursor;
+
+use super::{BendApp, DocumentState, STDIN_FILE_NAME};
+
+/// Stable identity of an open document. Unlike a tab index it doesn't shift
+/// as other tabs open and close.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DocumentId(u64);
+
+impl Default for DocumentId {
+    /// A fresh id, distinct from every other one handed out
+    fn default() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Documents open in inactive tabs
+#[derive(Default)]
+pub struct DocumentTabs {
+    /// Inactive documents, in tab order with the active one left out
+    parked: Vec<DocumentState>,
+    /// Tab index of the document currently in `BendApp::doc`
+    active: usize,
+}
+
+impl DocumentTabs {
+    /// Total number of tabs, including the active one
+    pub fn len(&self) -> usize {
+        self.parked.len() + 1
+    }
+
+    /// Tab index of the active document
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// The document shown by tab `index`
+    fn get<'a>(&'a self, active_doc: &'a DocumentState, index: usize) -> Option<&'a DocumentState> {
+        match index.cmp(&self.active) {
+            std::cmp::Ordering::Less => self.parked.get(index),
+            std::cmp::Ordering::Equal => Some(active_doc),
+            std::cmp::Ordering::Greater => self.parked.get(index - 1),
+        }
+    }
+
+    /// Iterate over every tab's document in tab order
+    fn iter<'a>(
+        &'a self,
+        active_doc: &'a DocumentState,
+    ) -> impl Iterator<Item = &'a DocumentState> {
+        (0..self.len()).filter_map(move |i| self.get(active_doc, i))
+    }
+
+    /// Every tab's document, the active one first
+    fn iter_mut<'a>(
+        &'a mut self,
+        active_doc: &'a mut DocumentState,
+    ) -> impl Iterator<Item = &'a mut DocumentState> {
+        std::iter::once(active_doc).chain(self.parked.iter_mut())
+    }
+
+    /// Rebuild the full tab list with `active_doc` taken out of its slot
+    fn take_all(&mut self, active_doc: &mut DocumentState) -> Vec<DocumentState> {
+        let mut all = std::mem::take(&mut self.parked);
+        all.insert(self.active, std::mem::take(active_doc));
+        all
+    }
+
+    /// Make `all[index]` the active document and park the rest
+    fn restore(
+        &mut self,
+        active_doc: &mut DocumentState,
+        mut all: Vec<DocumentState>,
+        index: usize,
+    ) {
+        *active_doc = all.remove(index);
+        self.parked = all;
+        self.active = index;
+    }
+
+    /// Swap tab `index` into `active_doc`. Returns false if nothing changed.
+    fn switch(&mut self, active_doc: &mut DocumentState, index: usize) -> bool {
+        if index == self.active || index >= self.len() {
+            return false;
+        }
+        let all = self.take_all(active_doc);
+        self.restore(active_doc, all, index);
+        true
+    }
+
+    /// Add `doc` as a new last tab and make it active
+    fn push(&mut self, active_doc: &mut DocumentState, doc: DocumentState) {
+        let mut all = self.take_all(active_doc);
+        all.push(doc);
+        let index = all.len() - 1;
+        self.restore(active_doc, all, index);
+    }
+
+    /// Close tab `index`. Closing the last remaining tab replaces it with `fresh`.
+    fn close(&mut self, active_doc: &mut DocumentState, index: usize, fresh: DocumentState) {
+        if index >= self.len() {
+            return;
+        }
+        let was_active = self.active;
+        let mut all = self.take_all(active_doc);
+        all.remove(index);
+        if all.is_empty() {
+            all.push(fresh);
+        }
+        // Keep the same document active unless it was the one closed
+        let next = if index < was_active {
+            was_active - 1
+        } else {
+            was_active.min(all.len() - 1)
+        };
+        self.restore(active_doc, all, next);
+    }
+}
+
+/// Short label for a tab: the file name, marked when it has unsaved edits
+fn tab_label(doc: &DocumentState) -> String {
+    let name = match &doc.current_file {
+        Some(path) => path.file_name().map(|n| n.to_string_lossy().into_owned()),
+        None => doc.from_stdin.then(|| STDIN_FILE_NAME.to_string()),
+    }
+    .unwrap_or_else(|| "Untitled".to_string());
+    if doc.editor.as_ref().is_some_and(|e| e.is_modified()) {
+        format!("\u{25CF} {}", name)
+    } else {
+        name
+    }
+}
+
+impl BendApp {
+    /// A blank document with settings-derived defaults (header protection, section lists)
+    pub(super) fn new_document(settings: &AppSettings) -> DocumentState {
+        let mut doc = DocumentState {
+            header_protection: settings.default_header_protection,
+            ..Default::default()
+        };
+        doc.sync_section_protection(settings);
+        doc
+    }
+
+    /// Copy the section allow/protect lists from settings into every tab
+    pub(super) fn sync_section_protection(&mut self) {
+        for doc in self.tabs.iter_mut(&mut self.doc) {
+            doc.sync_section_protection(&self.config.settings);
+        }
+    }
+
+    /// Whether any open tab has unsaved changes
+    pub(super) fn any_tab_modified(&self) -> bool {
+        self.tabs
+            .iter(&self.doc)
+            .any(|doc| doc.editor.as_ref().is_some_and(|e| e.is_modified()))
+    }
+
+    /// Tab index of an already-open file (or a Work on Copy of it), if any
+    pub(super) fn tab_for_file(&self, path: &std::path::Path) -> Option<usize> {
+        self.tabs.iter(&self.doc).position(|doc| {
+            doc.current_file.as_deref() == Some(path) || doc.source_file.as_deref() == Some(path)
+        })
+    }
+
+    /// Start a new empty tab and make it active
+    pub(super) fn open_new_tab(&mut self) {
+        let doc = Self::new_document(&self.config.settings);
+        self.tabs.push(&mut self.doc, doc);
+        self.reset_document_ui();
+    }
+
+    /// Make tab `index` the active document
+    pub(super) fn switch_tab(&mut self, index: usize) {
+        if self.tabs.switch(&mut self.doc, index) {
+            self.reset_document_ui();
+        }
+    }
+
+    /// Activate the next (or previous) tab, wrapping around
+    pub(super) fn cycle_tab(&mut self, forward: bool) {
+        let len = self.tabs.len();
+        let index = if forward {
+            (self.tabs.active() + 1) % len
+        } else {
+            (self.tabs.active() + len - 1) % len
+        };
+        self.switch_tab(index);
+    }
+
+    /// Close tab `index`, asking first if it has unsaved changes
+    pub(super) fn request_close_tab(&mut self, index: usize) {
+        let Some(doc) = self.tabs.get(&self.doc, index) else {
+            return;
+        };
+        if doc.editor.as_ref().is_some_and(|e| e.is_modified()) {
+            self.ui.dialogs.pending_close_tab = Some(doc.id);
+        } else {
+            self.close_tab(index);
+        }
+    }
+
+    /// Close tab `index` without confirmation
+    fn close_tab(&mut self, index: usize) {
+        let fresh = Self::new_document(&self.config.settings);
+        let active_before = self.tabs.active();
+        self.tabs.close(&mut self.doc, index, fresh);
+        if index == active_before {
+            self.reset_document_ui();
+        }
+    }
+
+    /// Drop UI state that refers to the previously active document
+    fn reset_document_ui(&mut self) {
+        // Save point ids and search offsets are per-document
+        self.ui.savepoints_state.clear_thumbnails();
+        self.ui.search_state.clear_results();
+        self.ui.file_diff.clear();
+        self.ui.signature_scan_state.dialog_open = false;
+        self.ui.byte_runs_state.clear();
+        self.ui.measure_state.exit();
+        self.ui.dialogs.pending_high_risk_edit = None;
+        self.ui.dialogs.pending_paste = None;
+        self.ui.dialogs.pending_trim = None;
+        self.ui.dialogs.show_revert_confirm = false;
+        self.ui.dialogs.show_export_decode_warning = false;
+        self.ui.dialogs.pending_save_point_name = None;
+        self.cancel_job();
+        self.ui.pending_hex_scroll = self.doc.editor.as_ref().map(|e| e.cursor());
+    }
+
+    /// Render the tab strip (hidden until a file is open)
+    pub(super) fn render_tab_strip(&mut self, ctx: &egui::Context) {
+        if self.tabs.len() == 1 && self.doc.editor.is_none() {
+            return;
+        }
+
+        let mut action_switch = None;
+        let mut action_close = None;
+        egui::TopBottomPanel::top("tab_strip").show(ctx, |ui| {
+            egui::ScrollArea::horizontal().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (index, doc) in self.tabs.iter(&self.doc).enumerate() {
+                        let selected = index == self.tabs.active();
+                        let mut response = ui
+                            .selectable_label(selected, tab_label(doc))
+                            .pointer_cursor();
+                        if let Some(path) = &doc.current_file {
+                            response = response.on_hover_text(path.to_string_lossy());
+                        }
+                        if response.clicked() {
+                            action_switch = Some(index);
+                        }
+                        if response.middle_clicked() {
+                            action_close = Some(index);
+                        }
+                        if ui
+                            .small_button("\u{00D7}")
+                            .pointer_cursor()
+                            .on_hover_text("Close tab")
+                            .clicked()
+                        {
+                            action_close = Some(index);
+                        }
+                        ui.separator();
+                    }
+                });
+            });
+        });
+
+        if let Some(index) = action_close {
+            self.request_close_tab(index);
+        } else if let Some(index) = action_switch {
+            self.switch_tab(index);
+        }
+    }
+
+    /// Show the "close tab with unsaved changes" confirmation dialog
+    pub(super) fn show_close_tab_dialog(&mut self, ctx: &egui::Context) {
+        let Some(id) = self.ui.dialogs.pending_close_tab else {
+            return;
+        };
+        // Looked up by id: tabs may have opened or closed since the prompt appeared
+        let Some((index, doc)) = self
+            .tabs
+            .iter(&self.doc)
+            .enumerate()
+            .find(|(_, doc)| doc.id == id)
+        else {
+            self.ui.dialogs.pending_close_tab = None;
+            return;
+        };
+        let label = tab_label(doc);
+
+        let mut should_close = false;
+        let mut should_cancel = false;
+        egui::Window::new("Close Tab")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "\"{}\" has unsaved changes. Close it anyway?",
+                    label
+                ));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Discard & Close").pointer_cursor().clicked() {
+                        should_close = true;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_close {
+            self.ui.dialogs.pending_close_tab = None;
+            self.close_tab(index);
+        } else if should_cancel {
+            self.ui.dialogs.pending_close_tab = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::{KeyBinding, ShortcutAction};
+    use std::path::PathBuf;
+
+    fn doc(name: &str) -> DocumentState {
+        DocumentState {
+            current_file: Some(PathBuf::from(name)),
+            ..Default::default()
+        }
+    }
+
+    fn names(tabs: &DocumentTabs, active: &DocumentState) -> Vec<String> {
+        tabs.iter(active).map(tab_label).collect()
+    }
+
+    #[test]
+    fn test_push_and_switch_keep_tab_order() {
+        let mut tabs = DocumentTabs::default();
+        let mut active = doc("a.bmp");
+        tabs.push(&mut active, doc("b.bmp"));
+        tabs.push(&mut active, doc("c.bmp"));
+        assert_eq!(tabs.active(), 2);
+        assert_eq!(tab_label(&active), "c.bmp");
+
+        assert!(tabs.switch(&mut active, 0));
+        assert_eq!(tab_label(&active), "a.bmp");
+        assert_eq!(names(&tabs, &active), ["a.bmp", "b.bmp", "c.bmp"]);
+
+        assert!(tabs.switch(&mut active, 1));
+        assert_eq!(tab_label(&active), "b.bmp");
+        assert_eq!(names(&tabs, &active), ["a.bmp", "b.bmp", "c.bmp"]);
+
+        assert!(!tabs.switch(&mut active, 1));
+        assert!(!tabs.switch(&mut active, 3));
+    }
+
+    #[test]
+    fn test_close_tabs() {
+        let mut tabs = DocumentTabs::default();
+        let mut active = doc("a.bmp");
+        tabs.push(&mut active, doc("b.bmp"));
+        tabs.push(&mut active, doc("c.bmp"));
+        assert!(tabs.switch(&mut active, 1));
+
+        // Closing an earlier tab keeps the same document active
+        tabs.close(&mut active, 0, doc("fresh"));
+        assert_eq!(tab_label(&active), "b.bmp");
+        assert_eq!(tabs.active(), 0);
+
+        // Closing the active tab activates its right neighbor (or the new last tab)
+        tabs.close(&mut active, 0, doc("fresh"));
+        assert_eq!(names(&tabs, &active), ["c.bmp"]);
+
+        // Closing the only tab leaves a fresh document behind
+        tabs.close(&mut active, 0, doc("fresh"));
+        assert_eq!(tabs.len(), 1);
+        assert_eq!(tab_label(&active), "fresh");
+    }
+
+    #[test]
+    fn test_settings_change_reaches_inactive_tabs() {
+        let mut app = BendApp::default();
+        app.open_new_tab();
+        app.config.settings.protected_sections = vec!["IHDR".to_string()];
+        app.sync_section_protection();
+
+        app.switch_tab(0);
+        assert_eq!(app.doc.protected_section_names, ["IHDR"]);
+    }
+
+    #[test]
+    fn test_pending_close_follows_the_document_across_tab_changes() {
+        let mut app = BendApp::default();
+        app.open_new_tab();
+        app.doc.editor = Some(crate::editor::EditorState::new(vec![0; 4]));
+        app.doc.editor.as_mut().unwrap().edit_byte(0, 1);
+        let modified = app.doc.id;
+        app.request_close_tab(1);
+        assert_eq!(app.ui.dialogs.pending_close_tab, Some(modified));
+
+        // Closing the tab before it shifts the modified document to index 0
+        app.close_tab(0);
+        app.open_new_tab();
+        let position = app.tabs.iter(&app.doc).position(|doc| doc.id == modified);
+        assert_eq!(position, Some(0));
+    }
+
+    #[test]
+    fn test_switching_tabs_closes_document_confirmations() {
+        let mut app = BendApp::default();
+        app.open_new_tab();
+        app.ui.dialogs.show_revert_confirm = true;
+        app.ui.dialogs.show_export_decode_warning = true;
+        app.ui.dialogs.pending_save_point_name = Some("Before".to_string());
+
+        // Confirming after the switch would act on the other document
+        app.switch_tab(0);
+        assert!(!app.ui.dialogs.show_revert_confirm);
+        assert!(!app.ui.dialogs.show_export_decode_warning);
+        assert!(app.ui.dialogs.pending_save_point_name.is_none());
+    }
+
+    #[test]
+    fn test_work_copy_tab_matches_its_source_file() {
+        let mut app = BendApp::default();
+        app.doc.current_file = Some(PathBuf::from("/tmp/photo_copy_20240101-120000.bmp"));
+        app.doc.source_file = Some(PathBuf::from("/tmp/photo.bmp"));
+        app.open_new_tab();
+
+        assert_eq!(
+            app.tab_for_file(std::path::Path::new("/tmp/photo.bmp")),
+            Some(0)
+        );
+        assert_eq!(
+            app.tab_for_file(std::path::Path::new("/tmp/photo_copy_20240101-120000.bmp")),
+            Some(0)
+        );
+        assert_eq!(
+            app.tab_for_file(std::path::Path::new("/tmp/other.bmp")),
+            None
+        );
+    }
+
+    /// Run one frame with a toolbar-like button after pressing `binding`,
+    /// returning whether egui gave some widget keyboard focus
+    fn focus_after(binding: KeyBinding) -> bool {
+        let ctx = egui::Context::default();
+        let modifiers = egui::Modifiers {
+            ctrl: binding.ctrl,
+            command: binding.ctrl,
+            shift: binding.shift,
+            alt: binding.alt,
+            ..Default::default()
+        };
+        let input = egui::RawInput {
+            events: vec![egui::Event::Key {
+                key: binding.key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers,
+            }],
+            modifiers,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let _ = ui.button("Open");
+            });
+        });
+        ctx.memory(|m| m.focused().is_some())
+    }
+
+    #[test]
+    fn test_tab_switch_keys_leave_keyboard_focus_alone() {
+        // A focused widget makes the hex editor ignore typing and navigation
+        for action in [ShortcutAction::NextTab, ShortcutAction::PreviousTab] {
+            for binding in action.default_bindings() {
+                assert!(!focus_after(binding), "{}", binding);
+            }
+        }
+        // Why Tab isn't used: egui moves focus even with Ctrl held
+        assert!(focus_after(KeyBinding::ctrl(egui::Key::Tab)));
+    }
+}
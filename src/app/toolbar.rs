@@ -1,23 +1,53 @@
 use eframe::egui;
 
 use crate::editor::buffer::EditMode;
-use crate::ui::PointerCursor;
+use crate::editor::text_encoding::TextEncoding;
+use crate::settings::ThemePreference;
+use crate::ui::command_palette::PaletteCommand;
+use crate::ui::{go_to_offset_dialog, PointerCursor};
 
+use super::trim::TrimSide;
 use super::BendApp;
 
 /// Actions triggered by keyboard/mouse input, processed after input handling
 #[derive(Default)]
 pub(super) struct InputActions {
     pub open: bool,
+    /// Files dropped onto the window this frame
+    pub dropped_files: Vec<std::path::PathBuf>,
     pub export: bool,
+    /// Export to the last export directory without a dialog
+    pub quick_export: bool,
     pub search: bool,
     pub go_to: bool,
     pub undo: bool,
     pub redo: bool,
     pub create_save_point: bool,
+    /// Prompt for a name before creating a save point
+    pub name_save_point: bool,
     pub add_bookmark: bool,
     pub refresh_preview: bool,
+    /// Reapply the last selection effect with the same parameters
+    pub repeat_last_effect: bool,
+    pub toggle_comparison: bool,
+    /// Repeat the last byte jump (Some(true) = next, Some(false) = previous)
+    pub repeat_byte_jump: Option<bool>,
+    /// Jump between the start and end of the section under the cursor
+    pub matching_marker: bool,
     pub set_edit_mode: Option<EditMode>,
+    /// Switch tabs (Some(true) = next, Some(false) = previous)
+    pub cycle_tab: Option<bool>,
+    pub close_tab: bool,
+    pub command_palette: bool,
+}
+
+/// Format an undo/redo button label with its history depth, e.g. "Undo (12)"
+fn history_label(label: &str, count: usize) -> String {
+    if count > 0 {
+        format!("{} ({})", label, count)
+    } else {
+        label.to_string()
+    }
 }
 
 impl BendApp {
@@ -28,8 +58,8 @@ impl BendApp {
         egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 let has_file = self.doc.editor.is_some();
-                let can_undo = self.doc.editor.as_ref().is_some_and(|e| e.can_undo());
-                let can_redo = self.doc.editor.as_ref().is_some_and(|e| e.can_redo());
+                let undo_count = self.doc.editor.as_ref().map_or(0, |e| e.undo_count());
+                let redo_count = self.doc.editor.as_ref().map_or(0, |e| e.redo_count());
 
                 // File operations
                 if ui.button("Open").pointer_cursor().clicked() {
@@ -45,17 +75,25 @@ impl BendApp {
 
                 ui.separator();
 
-                // Undo/Redo
+                // Undo/Redo (with available history depth)
                 if ui
-                    .add_enabled(can_undo, egui::Button::new("Undo"))
+                    .add_enabled(
+                        undo_count > 0,
+                        egui::Button::new(history_label("Undo", undo_count)),
+                    )
                     .pointer_cursor()
+                    .on_hover_text(format!("{} operation(s) available to undo", undo_count))
                     .clicked()
                 {
                     actions.undo = true;
                 }
                 if ui
-                    .add_enabled(can_redo, egui::Button::new("Redo"))
+                    .add_enabled(
+                        redo_count > 0,
+                        egui::Button::new(history_label("Redo", redo_count)),
+                    )
                     .pointer_cursor()
+                    .on_hover_text(format!("{} operation(s) available to redo", redo_count))
                     .clicked()
                 {
                     actions.redo = true;
@@ -88,9 +126,10 @@ impl BendApp {
                         egui::SelectableLabel::new(self.doc.preview.comparison_mode, "Compare"),
                     )
                     .pointer_cursor()
+                    .on_hover_text("Compare with original (Ctrl+Shift+C / Cmd+Shift+C)")
                     .clicked()
                 {
-                    self.doc.preview.comparison_mode = !self.doc.preview.comparison_mode;
+                    actions.toggle_comparison = true;
                 }
                 if ui
                     .add_enabled(
@@ -134,6 +173,26 @@ impl BendApp {
                     actions.set_edit_mode = Some(EditMode::Ascii);
                 }
 
+                // Character set of the ASCII pane (display only)
+                ui.add_enabled_ui(has_file, |ui| {
+                    egui::ComboBox::from_id_salt("text_encoding")
+                        .selected_text(self.ui.text_encoding.label())
+                        .width(80.0)
+                        .show_ui(ui, |ui| {
+                            for encoding in TextEncoding::ALL {
+                                ui.selectable_value(
+                                    &mut self.ui.text_encoding,
+                                    encoding,
+                                    encoding.label(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "How the ASCII pane shows bytes (typing still writes ASCII)",
+                        );
+                });
+
                 ui.separator();
 
                 // Refresh preview
@@ -145,6 +204,72 @@ impl BendApp {
                 {
                     actions.refresh_preview = true;
                 }
+
+                // Live/manual preview toggle
+                let live = self.config.settings.live_preview;
+                let stale = !live && self.doc.preview.dirty;
+                let live_label = if stale { "Live \u{25CF}" } else { "Live" };
+                if ui
+                    .add_enabled(has_file, egui::SelectableLabel::new(live, live_label))
+                    .pointer_cursor()
+                    .on_hover_text(if stale {
+                        "Live Preview is off and the preview is out of date; press Refresh"
+                    } else {
+                        "Re-render the preview after every edit (off: only on Refresh)"
+                    })
+                    .clicked()
+                {
+                    self.toggle_live_preview();
+                }
+
+                // Entropy overlay toggle
+                if ui
+                    .add_enabled(
+                        has_file,
+                        egui::SelectableLabel::new(self.ui.show_entropy_map, "Entropy"),
+                    )
+                    .pointer_cursor()
+                    .on_hover_text(
+                        "Tint the hex view by local byte entropy (blue = structural, pink = compressed/noisy)",
+                    )
+                    .clicked()
+                {
+                    self.ui.show_entropy_map = !self.ui.show_entropy_map;
+                }
+
+                // Diff highlight toggle
+                if ui
+                    .add_enabled(
+                        has_file,
+                        egui::SelectableLabel::new(self.ui.show_diff_highlight, "Diff"),
+                    )
+                    .pointer_cursor()
+                    .on_hover_text("Show Diff Highlight: tint bytes that differ from the original")
+                    .clicked()
+                {
+                    self.ui.show_diff_highlight = !self.ui.show_diff_highlight;
+                }
+
+                // Quick dark/light switch (persisted like the settings dialog choice)
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let dark_mode = ui.visuals().dark_mode;
+                    let (icon, hint) = if dark_mode {
+                        ("☀", "Switch to light theme")
+                    } else {
+                        ("🌙", "Switch to dark theme")
+                    };
+                    if ui
+                        .button(icon)
+                        .pointer_cursor()
+                        .on_hover_text(hint)
+                        .clicked()
+                    {
+                        let theme = ThemePreference::toggled(dark_mode);
+                        theme.apply(ui.ctx());
+                        self.config.settings.theme = theme;
+                        self.config.settings.save();
+                    }
+                });
             });
         });
 
@@ -156,9 +281,15 @@ impl BendApp {
         if actions.open {
             self.open_file_dialog(ctx);
         }
+        if !actions.dropped_files.is_empty() {
+            self.open_files(actions.dropped_files);
+        }
         if actions.export {
             self.export_file(ctx);
         }
+        if actions.quick_export {
+            self.quick_export(ctx);
+        }
         if actions.search {
             self.ui.search_state.open_dialog();
         }
@@ -173,25 +304,124 @@ impl BendApp {
         }
         if actions.create_save_point {
             if let Some(editor) = &mut self.doc.editor {
-                let count = editor.save_points().len();
-                let name = format!("Save Point {}", count + 1);
+                let name = editor.next_save_point_name();
                 editor.create_save_point(name);
             }
         }
+        if actions.name_save_point {
+            self.open_save_point_name_dialog(ctx);
+        }
         if actions.add_bookmark {
-            if let Some(editor) = &mut self.doc.editor {
-                let cursor_pos = editor.cursor();
-                let name = format!("Bookmark at 0x{:08X}", cursor_pos);
-                editor.add_bookmark(cursor_pos, name);
-            }
+            self.add_bookmark_at_cursor();
         }
         if actions.refresh_preview {
-            self.doc.preview.mark_dirty();
+            self.doc.preview.request_refresh();
+        }
+        if actions.repeat_last_effect {
+            self.repeat_last_effect();
+        }
+        if actions.toggle_comparison {
+            self.doc.preview.comparison_mode = !self.doc.preview.comparison_mode;
+        }
+        if let Some(forward) = actions.repeat_byte_jump {
+            if let Some(value) = self.ui.go_to_offset_state.last_byte {
+                go_to_offset_dialog::jump_to_byte(&mut self.doc, &mut self.ui, value, forward);
+            } else {
+                self.ui.go_to_offset_state.open_dialog();
+            }
+        }
+        if actions.matching_marker {
+            self.go_to_matching_marker();
+        }
+        if let Some(forward) = actions.cycle_tab {
+            self.cycle_tab(forward);
+        }
+        if actions.close_tab {
+            self.request_close_tab(self.tabs.active());
         }
         if let Some(mode) = actions.set_edit_mode {
             if let Some(editor) = &mut self.doc.editor {
                 editor.set_edit_mode(mode);
             }
         }
+        if actions.command_palette {
+            self.ui.command_palette.open();
+        }
+    }
+
+    /// Run a command picked in the command palette. Shortcut-backed commands go
+    /// through `InputActions`; the rest call the same methods as their menu items.
+    pub(super) fn run_palette_command(&mut self, command: PaletteCommand, ctx: &egui::Context) {
+        let mut actions = InputActions::default();
+        match command {
+            PaletteCommand::Open => actions.open = true,
+            PaletteCommand::Export => actions.export = true,
+            PaletteCommand::QuickExport => actions.quick_export = true,
+            PaletteCommand::ExportSavePointsGif => self.export_save_points_gif(ctx),
+            PaletteCommand::ExportPcm => self.ui.audio_export.dialog_open = true,
+            PaletteCommand::ImportBytes => self.import_bytes_dialog(ctx),
+            PaletteCommand::SaveSession => self.save_session_dialog(ctx),
+            PaletteCommand::ToggleMacroRecording => self.toggle_macro_recording(ctx),
+            PaletteCommand::LoadSession => self.load_session_dialog(ctx),
+            PaletteCommand::OpenContainingFolder => self.open_containing_folder(),
+            PaletteCommand::RevealExportedFile => self.reveal_last_export(),
+            PaletteCommand::CloseTab => actions.close_tab = true,
+            PaletteCommand::NextTab => actions.cycle_tab = Some(true),
+            PaletteCommand::PreviousTab => actions.cycle_tab = Some(false),
+            PaletteCommand::Undo => actions.undo = true,
+            PaletteCommand::Redo => actions.redo = true,
+            PaletteCommand::FindReplace => actions.search = true,
+            PaletteCommand::GoToOffset => actions.go_to = true,
+            PaletteCommand::MatchingMarker => actions.matching_marker = true,
+            PaletteCommand::InvertSelection => self.invert_selection(),
+            PaletteCommand::TrimLeft => self.request_trim(TrimSide::Left),
+            PaletteCommand::TrimRight => self.request_trim(TrimSide::Right),
+            PaletteCommand::ToggleSelectionLock => {
+                if let Some(editor) = &mut self.doc.editor {
+                    editor.set_selection_locked(!editor.selection_locked());
+                }
+            }
+            PaletteCommand::ToggleMeasureDistance => self.ui.measure_state.toggle(),
+            PaletteCommand::FindByteRuns => self.open_byte_runs(),
+            PaletteCommand::ScanImageHeaders => {
+                if let Some(editor) = &self.doc.editor {
+                    self.ui.signature_scan_state.open(editor.working());
+                }
+            }
+            PaletteCommand::CompareWithFile => self.compare_with_file_dialog(ctx),
+            PaletteCommand::CreateSavePoint => actions.create_save_point = true,
+            PaletteCommand::NameSavePoint => actions.name_save_point = true,
+            PaletteCommand::AddBookmark => actions.add_bookmark = true,
+            PaletteCommand::RefreshPreview => actions.refresh_preview = true,
+            PaletteCommand::ToggleLivePreview => self.toggle_live_preview(),
+            PaletteCommand::ToggleComparison => actions.toggle_comparison = true,
+            PaletteCommand::ToggleHeaderProtection => {
+                self.doc.header_protection = !self.doc.header_protection;
+            }
+            PaletteCommand::ToggleEntropyMap => {
+                self.ui.show_entropy_map = !self.ui.show_entropy_map;
+            }
+            PaletteCommand::ToggleDiffHighlight => {
+                self.ui.show_diff_highlight = !self.ui.show_diff_highlight;
+            }
+            PaletteCommand::HexMode => actions.set_edit_mode = Some(EditMode::Hex),
+            PaletteCommand::AsciiMode => actions.set_edit_mode = Some(EditMode::Ascii),
+            PaletteCommand::ToggleWriteMode => {
+                if let Some(editor) = &mut self.doc.editor {
+                    editor.toggle_write_mode();
+                }
+            }
+            PaletteCommand::RandomBitFlips => self.random_flip_selection(),
+            PaletteCommand::RotateBits => self.rotate_bits_selection(),
+            PaletteCommand::ScanlineShift => self.scanline_shift_selection(),
+            PaletteCommand::XorSelection => self.xor_selection(),
+            PaletteCommand::JpegScanGlitch => self.jpeg_scan_glitch(),
+            PaletteCommand::RepeatLastEffect => actions.repeat_last_effect = true,
+            PaletteCommand::Preferences => {
+                self.ui.settings_dialog_state.open(&self.config.settings);
+            }
+            PaletteCommand::KeyboardShortcuts => self.ui.shortcuts_dialog_state.open(),
+        }
+        self.process_input_actions(actions, ctx);
     }
 }
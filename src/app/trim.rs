@@ -0,0 +1,196 @@
+//! Trim everything before or after the cursor
+//!
+//! For carving an embedded file out of its container: put the cursor on the
+//! first (or last) byte found with the header scan, trim the rest away and
+//! export. Each trim is one undoable delete and asks for confirmation first.
+
+use std::ops::Range;
+
+use eframe::egui;
+
+use crate::ui::PointerCursor;
+
+use super::BendApp;
+
+/// Which side of the cursor a trim removes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrimSide {
+    /// Every byte before the cursor
+    Left,
+    /// Every byte after the cursor
+    Right,
+}
+
+/// A trim waiting for confirmation
+pub struct PendingTrim {
+    pub side: TrimSide,
+    /// Bytes that will be deleted, taken when the trim was requested
+    pub range: Range<usize>,
+    /// Edit generation `range` was taken at
+    pub generation: u64,
+}
+
+/// Bytes a trim removes from a `len`-byte buffer; the cursor byte is kept
+fn trim_range(side: TrimSide, cursor: usize, len: usize) -> Range<usize> {
+    match side {
+        TrimSide::Left => 0..cursor.min(len),
+        TrimSide::Right => (cursor + 1).min(len)..len,
+    }
+}
+
+impl BendApp {
+    /// Ask to trim one side of the cursor (nothing happens if there is nothing to trim)
+    pub(super) fn request_trim(&mut self, side: TrimSide) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        let range = trim_range(side, editor.cursor(), editor.len());
+        if range.is_empty() {
+            return;
+        }
+        if self.doc.is_range_protected(range.start, range.len()) {
+            log::warn!("Trim blocked: the trimmed range overlaps a protected section");
+            return;
+        }
+        let generation = editor.edit_generation();
+        self.ui.dialogs.pending_trim = Some(PendingTrim {
+            side,
+            range,
+            generation,
+        });
+    }
+
+    /// Apply a confirmed trim, or ask again with a fresh range if the buffer
+    /// changed while the confirmation was open
+    fn confirm_trim(&mut self, pending: PendingTrim) {
+        let Some(editor) = &self.doc.editor else {
+            return;
+        };
+        if editor.edit_generation() == pending.generation {
+            self.apply_trim(pending.range);
+        } else {
+            log::warn!("Trim range recomputed: the buffer changed since it was requested");
+            self.request_trim(pending.side);
+        }
+    }
+
+    /// Delete the trimmed range and re-parse what is left
+    fn apply_trim(&mut self, range: Range<usize>) {
+        let Some(editor) = &mut self.doc.editor else {
+            return;
+        };
+        if editor.delete_range(range.start, range.end) && editor.take_length_changed() {
+            self.ui.pending_hex_scroll = Some(editor.cursor());
+            self.doc.reparse_sections();
+            self.doc.preview.mark_dirty();
+        }
+    }
+
+    /// Show the trim confirmation dialog
+    pub(super) fn show_trim_dialog(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.ui.dialogs.pending_trim else {
+            return;
+        };
+        let direction = match pending.side {
+            TrimSide::Left => "before",
+            TrimSide::Right => "after",
+        };
+        let summary = format!(
+            "Delete all {} bytes {} the cursor (0x{:08X}-0x{:08X})?",
+            pending.range.len(),
+            direction,
+            pending.range.start,
+            pending.range.end - 1
+        );
+
+        let mut should_trim = false;
+        let mut should_cancel = false;
+        egui::Window::new("Confirm Trim")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(summary);
+                ui.label(
+                    "Bookmarks in the trimmed range move to the nearest kept byte and are \
+                     marked as shifted. The trim can be undone.",
+                );
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Trim").pointer_cursor().clicked() {
+                        should_trim = true;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_trim {
+            if let Some(pending) = self.ui.dialogs.pending_trim.take() {
+                self.confirm_trim(pending);
+            }
+        } else if should_cancel {
+            self.ui.dialogs.pending_trim = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::EditorState;
+
+    #[test]
+    fn test_trim_range_keeps_cursor_byte() {
+        assert_eq!(trim_range(TrimSide::Left, 3, 10), 0..3);
+        assert_eq!(trim_range(TrimSide::Right, 3, 10), 4..10);
+        assert!(trim_range(TrimSide::Left, 0, 10).is_empty());
+        assert!(trim_range(TrimSide::Right, 9, 10).is_empty());
+    }
+
+    #[test]
+    fn test_trim_both_sides_leaves_cursor_region() {
+        let mut app = BendApp::default();
+        let mut editor = EditorState::new((0..10).collect());
+        editor.set_cursor(7);
+        app.doc.editor = Some(editor);
+
+        app.request_trim(TrimSide::Right);
+        let pending = app.ui.dialogs.pending_trim.take().unwrap();
+        assert_eq!(pending.range, 8..10);
+        app.apply_trim(pending.range);
+
+        app.doc.editor.as_mut().unwrap().set_cursor(2);
+        app.request_trim(TrimSide::Left);
+        let pending = app.ui.dialogs.pending_trim.take().unwrap();
+        app.apply_trim(pending.range);
+
+        let editor = app.doc.editor.as_ref().unwrap();
+        assert_eq!(editor.working(), &[2, 3, 4, 5, 6, 7]);
+        assert_eq!(editor.cursor(), 0);
+        assert_eq!(editor.undo_count(), 2);
+    }
+
+    #[test]
+    fn test_trim_is_recomputed_after_newer_edits() {
+        let mut app = BendApp::default();
+        let mut editor = EditorState::new((0..10).collect());
+        editor.set_cursor(7);
+        app.doc.editor = Some(editor);
+        app.request_trim(TrimSide::Right);
+
+        // An insert before the cursor shifts what "after the cursor" means
+        let editor = app.doc.editor.as_mut().unwrap();
+        editor.insert_bytes(0, &[0xFF]);
+        editor.set_cursor(8);
+        let pending = app.ui.dialogs.pending_trim.take().unwrap();
+        app.confirm_trim(pending);
+        assert_eq!(app.doc.editor.as_ref().unwrap().len(), 11);
+        let pending = app.ui.dialogs.pending_trim.take().unwrap();
+        assert_eq!(pending.range, 9..11);
+
+        app.confirm_trim(pending);
+        assert_eq!(app.doc.editor.as_ref().unwrap().len(), 9);
+    }
+}
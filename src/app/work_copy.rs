@@ -0,0 +1,111 @@
+//! "Work on Copy": edit a timestamped copy instead of the file that was opened
+//!
+//! With the setting on, opening a file first copies it to
+//! `<stem>_copy_<YYYYMMDD-HHMMSS>.<ext>` in the chosen folder (or next to the
+//! source) and loads the copy, so nothing ever writes to the original.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::BendApp;
+
+/// `secs` since the Unix epoch as a UTC `YYYYMMDD-HHMMSS` stamp
+fn utc_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+/// Path in `dir` for a copy of `source` made at `stamp`, skipping names
+/// that already exist
+fn work_copy_path(dir: &Path, source: &Path, stamp: &str) -> PathBuf {
+    let stem = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "bend".to_string());
+    let ext = source
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut path = dir.join(format!("{}_copy_{}{}", stem, stamp, ext));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}_copy_{}_{}{}", stem, stamp, n, ext));
+        n += 1;
+    }
+    path
+}
+
+impl BendApp {
+    /// Copy `source` into the work copy folder and return the copy's path
+    pub(super) fn make_work_copy(&self, source: &Path) -> Result<PathBuf, String> {
+        let dir = match &self.config.settings.work_copy_dir {
+            Some(dir) => dir.clone(),
+            None => source
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from(".")),
+        };
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let copy = work_copy_path(&dir, source, &utc_timestamp(secs));
+        std::fs::copy(source, &copy).map_err(|e| e.to_string())?;
+        Ok(copy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(0), "19700101-000000");
+        assert_eq!(utc_timestamp(951_782_400), "20000229-000000");
+        assert_eq!(utc_timestamp(1_700_000_000), "20231114-221320");
+    }
+
+    #[test]
+    fn test_make_work_copy() {
+        let dir = std::env::temp_dir().join(format!("bend_work_copy_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("photo.bmp");
+        std::fs::write(&source, b"BM").unwrap();
+
+        let first = work_copy_path(&dir, &source, "20240101-120000");
+        assert_eq!(first, dir.join("photo_copy_20240101-120000.bmp"));
+        std::fs::write(&first, b"").unwrap();
+        assert_eq!(
+            work_copy_path(&dir, &source, "20240101-120000"),
+            dir.join("photo_copy_20240101-120000_2.bmp")
+        );
+
+        let mut app = BendApp::default();
+        app.config.settings.work_copy_dir = Some(dir.join("copies"));
+        let copy = app.make_work_copy(&source).unwrap();
+        assert_eq!(copy.parent(), Some(dir.join("copies").as_path()));
+        assert_eq!(std::fs::read(&copy).unwrap(), b"BM");
+        assert_eq!(std::fs::read(&source).unwrap(), b"BM");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,191 @@
+//! Whole-buffer byte statistics kept up to date incrementally
+//!
+//! The histogram is built once when a file is loaded and then adjusted from
+//! each applied `EditOperation` (subtracting the bytes it removed and adding
+//! the bytes it wrote), so edits on large files never trigger a full rescan.
+//!
+//! `find_byte_runs` is a one-off scan for long fills of a single byte value.
+
+use super::history::EditOperation;
+
+/// Occurrence count of every byte value in a buffer
+#[derive(Clone, Debug, PartialEq)]
+pub struct ByteHistogram {
+    /// Number of occurrences of each byte value
+    counts: [u64; 256],
+    /// Total number of bytes counted
+    total: u64,
+}
+
+impl Default for ByteHistogram {
+    fn default() -> Self {
+        Self {
+            counts: [0; 256],
+            total: 0,
+        }
+    }
+}
+
+impl ByteHistogram {
+    /// Count every byte in `data`
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let mut histogram = Self::default();
+        histogram.add(data);
+        histogram
+    }
+
+    /// Occurrence counts indexed by byte value
+    pub fn counts(&self) -> &[u64; 256] {
+        &self.counts
+    }
+
+    /// Shannon entropy of the counted bytes in bits per byte (0.0-8.0)
+    pub fn entropy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let total = self.total as f64;
+        self.counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    fn add(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.counts[b as usize] += 1;
+        }
+        self.total += bytes.len() as u64;
+    }
+
+    fn remove(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.counts[b as usize] -= 1;
+        }
+        self.total -= bytes.len() as u64;
+    }
+
+    /// Update counts for an operation applied in the redo direction (`undo == false`)
+    /// or reverted in the undo direction (`undo == true`)
+    pub(super) fn apply_operation(&mut self, op: &EditOperation, undo: bool) {
+        let (removed, added): (&[u8], &[u8]) = match op {
+            EditOperation::Single {
+                old_value,
+                new_value,
+                ..
+            } => (
+                std::slice::from_ref(old_value),
+                std::slice::from_ref(new_value),
+            ),
+            EditOperation::Range {
+                old_values,
+                new_values,
+                ..
+            } => (old_values, new_values),
+            EditOperation::InsertBytes { values, .. } => (&[], values),
+            EditOperation::DeleteBytes { values, .. } => (values, &[]),
+            EditOperation::AddBookmark(_) | EditOperation::RemoveBookmark(_) => return,
+            EditOperation::Group(ops) => {
+                if undo {
+                    ops.iter()
+                        .rev()
+                        .for_each(|sub| self.apply_operation(sub, true));
+                } else {
+                    ops.iter().for_each(|sub| self.apply_operation(sub, false));
+                }
+                return;
+            }
+        };
+        if undo {
+            self.remove(added);
+            self.add(removed);
+        } else {
+            self.remove(removed);
+            self.add(added);
+        }
+    }
+}
+
+/// Runs of one repeated byte value at least `min_len` long, as
+/// `(offset, len, value)` in buffer order. Long zero padding or 0xFF fills are
+/// usually dull to corrupt, so these mark what to skip.
+pub fn find_byte_runs(data: &[u8], min_len: usize) -> Vec<(usize, usize, u8)> {
+    let min_len = min_len.max(2);
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < data.len() {
+        let value = data[start];
+        let len = data[start..].iter().take_while(|&&b| b == value).count();
+        if len >= min_len {
+            runs.push((start, len, value));
+        }
+        start += len;
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_byte_runs() {
+        let mut data = vec![1, 2, 2, 3];
+        data.extend([0; 5]);
+        data.push(7);
+        data.extend([0xFF; 4]);
+        assert_eq!(find_byte_runs(&data, 4), vec![(4, 5, 0), (10, 4, 0xFF)]);
+        assert_eq!(find_byte_runs(&data, 5), vec![(4, 5, 0)]);
+        // A run reaching the end of the buffer is found, single bytes never are
+        assert_eq!(find_byte_runs(&[9, 9], 0), vec![(0, 2, 9)]);
+        assert!(find_byte_runs(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_from_bytes_counts() {
+        let histogram = ByteHistogram::from_bytes(&[0, 0, 7, 255]);
+        assert_eq!(histogram.counts()[0], 2);
+        assert_eq!(histogram.counts()[7], 1);
+        assert_eq!(histogram.counts()[255], 1);
+        assert_eq!(histogram.total, 4);
+    }
+
+    #[test]
+    fn test_entropy() {
+        assert_eq!(ByteHistogram::from_bytes(&[]).entropy(), 0.0);
+        assert_eq!(ByteHistogram::from_bytes(&[9; 100]).entropy(), 0.0);
+        let all: Vec<u8> = (0..=255).collect();
+        assert!((ByteHistogram::from_bytes(&all).entropy() - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_operation_round_trip() {
+        let mut histogram = ByteHistogram::from_bytes(&[1, 2, 3]);
+        let before = histogram.clone();
+        let op = EditOperation::Group(vec![
+            EditOperation::Single {
+                offset: 0,
+                old_value: 1,
+                new_value: 9,
+            },
+            EditOperation::InsertBytes {
+                offset: 3,
+                values: vec![9, 9],
+            },
+            EditOperation::DeleteBytes {
+                offset: 1,
+                values: vec![2],
+                clamped: Vec::new(),
+            },
+        ]);
+
+        histogram.apply_operation(&op, false);
+        assert_eq!(histogram, ByteHistogram::from_bytes(&[9, 3, 9, 9]));
+        histogram.apply_operation(&op, true);
+        assert_eq!(histogram, before);
+    }
+}
@@ -1,9 +1,10 @@
 //! Bookmarks and annotations for the hex editor
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// A bookmark marking a specific offset in the file with an optional annotation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bookmark {
     /// Unique identifier for this bookmark
     pub id: u64,
@@ -13,6 +14,10 @@ pub struct Bookmark {
     pub name: String,
     /// Optional annotation/note about this location
     pub annotation: String,
+    /// Set when the bytes it marked were deleted and it was moved to the
+    /// deletion point, so it may no longer mark what it was added for
+    #[serde(default)]
+    pub shifted: bool,
 }
 
 impl Bookmark {
@@ -23,10 +28,22 @@ impl Bookmark {
             offset,
             name,
             annotation: String::new(),
+            shifted: false,
         }
     }
 }
 
+/// A bookmark as it was before a deletion clamped it to the deletion point,
+/// kept with the delete so undo can put it back
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClampedBookmark {
+    pub id: u64,
+    /// Offset before the deletion
+    pub offset: usize,
+    /// "Shifted" flag before the deletion
+    pub shifted: bool,
+}
+
 /// Manager for bookmarks in the current file
 #[derive(Default)]
 pub struct BookmarkManager {
@@ -49,18 +66,35 @@ impl BookmarkManager {
         let id = self.next_id;
         self.next_id += 1;
 
-        let bookmark = Bookmark::new(id, offset, name);
+        self.insert_sorted(Bookmark::new(id, offset, name));
+        id
+    }
+
+    /// Put back a previously removed bookmark, keeping its ID (for undo/redo)
+    pub fn restore(&mut self, bookmark: Bookmark) {
+        self.next_id = self.next_id.max(bookmark.id + 1);
+        self.insert_sorted(bookmark);
+    }
 
+    /// Insert at the bookmark's sorted position and update the index
+    fn insert_sorted(&mut self, bookmark: Bookmark) {
         // Binary search for sorted insertion position — O(log n) instead of full sort
-        let pos = self.bookmarks.partition_point(|b| b.offset < offset);
+        let pos = self
+            .bookmarks
+            .partition_point(|b| b.offset < bookmark.offset);
         self.bookmarks.insert(pos, bookmark);
 
         // Rebuild indices for items at and after the insertion point
         for (index, bm) in self.bookmarks.iter().enumerate().skip(pos) {
             self.id_to_index.insert(bm.id, index);
         }
+    }
 
-        id
+    /// Get a bookmark by ID
+    pub fn get(&self, id: u64) -> Option<&Bookmark> {
+        self.id_to_index
+            .get(&id)
+            .map(|&index| &self.bookmarks[index])
     }
 
     /// Rebuild the ID -> index map after structural changes
@@ -120,6 +154,17 @@ impl BookmarkManager {
         }
     }
 
+    /// Clear a bookmark's "shifted" flag once the user has checked it
+    #[must_use = "returns whether the bookmark was found"]
+    pub fn clear_shifted(&mut self, id: u64) -> bool {
+        if let Some(bookmark) = self.get_mut(id) {
+            bookmark.shifted = false;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Check if there's a bookmark at the given offset (binary search on sorted vec)
     pub fn at_offset(&self, offset: usize) -> Option<&Bookmark> {
         let idx = self.bookmarks.partition_point(|b| b.offset < offset);
@@ -144,17 +189,73 @@ impl BookmarkManager {
 
     /// Adjust bookmark offsets after bytes were deleted starting at `offset`
     ///
-    /// Bookmarks within the deleted range `[offset, offset+count)` are removed.
+    /// Bookmarks within the deleted range `[offset, offset+count)` are clamped
+    /// to `offset` (the new last byte if the deletion reached the end of the
+    /// `new_len`-byte buffer) and flagged as shifted.
     /// Bookmarks after the deleted range are shifted left by `count`.
-    pub fn adjust_offsets_after_delete(&mut self, offset: usize, count: usize) {
-        let delete_end = offset + count;
-        self.bookmarks
-            .retain(|b| b.offset < offset || b.offset >= delete_end);
+    /// Returns the clamped bookmarks as they were before.
+    pub fn adjust_offsets_after_delete(
+        &mut self,
+        offset: usize,
+        count: usize,
+        new_len: usize,
+    ) -> Vec<ClampedBookmark> {
+        self.adjust_offsets_after_replace(offset, count, 0, new_len)
+    }
+
+    /// Adjust bookmark offsets after `removed` bytes at `offset` were replaced
+    /// by `inserted` new ones
+    ///
+    /// Bookmarks within the removed range are clamped to `offset` (the start of
+    /// the replacement) and flagged as shifted; bookmarks after it move by the
+    /// change in length. When nothing follows the removed range in the
+    /// `new_len`-byte buffer, they go to its last byte instead so they stay
+    /// inside the file. With nothing removed this is a plain insert.
+    /// Returns the clamped bookmarks as they were before.
+    pub fn adjust_offsets_after_replace(
+        &mut self,
+        offset: usize,
+        removed: usize,
+        inserted: usize,
+        new_len: usize,
+    ) -> Vec<ClampedBookmark> {
+        if removed == 0 {
+            self.adjust_offsets_after_insert(offset, inserted);
+            return Vec::new();
+        }
+        let removed_end = offset + removed;
+        let clamp_to = offset.min(new_len.saturating_sub(1));
+        let mut clamped = Vec::new();
         for bookmark in &mut self.bookmarks {
-            if bookmark.offset >= delete_end {
-                bookmark.offset -= count;
+            if bookmark.offset >= removed_end {
+                bookmark.offset = bookmark.offset - removed + inserted;
+            } else if bookmark.offset >= offset {
+                clamped.push(ClampedBookmark {
+                    id: bookmark.id,
+                    offset: bookmark.offset,
+                    shifted: bookmark.shifted,
+                });
+                bookmark.offset = clamp_to;
+                bookmark.shifted = true;
+            }
+        }
+        // Clamped and shifted offsets keep their order, so the list stays sorted
+        clamped
+    }
+
+    /// Put clamped bookmarks back where they were before the deletion (undo),
+    /// with their old "shifted" flag
+    pub fn restore_clamped(&mut self, clamped: &[ClampedBookmark]) {
+        if clamped.is_empty() {
+            return;
+        }
+        for before in clamped {
+            if let Some(bookmark) = self.get_mut(before.id) {
+                bookmark.offset = before.offset;
+                bookmark.shifted = before.shifted;
             }
         }
+        self.bookmarks.sort_by_key(|b| b.offset);
         self.rebuild_index();
     }
 }
@@ -248,28 +349,96 @@ mod tests {
     fn test_adjust_offsets_after_delete() {
         let mut manager = BookmarkManager::new();
         manager.add(100, "Before".to_string());
-        manager.add(150, "In range".to_string());
+        let in_range = manager.add(150, "In range".to_string());
         manager.add(300, "After".to_string());
 
         // Delete 100 bytes starting at offset 120 (range 120..220)
-        manager.adjust_offsets_after_delete(120, 100);
+        manager.adjust_offsets_after_delete(120, 100, 300);
 
         let bookmarks = manager.all();
-        assert_eq!(bookmarks.len(), 2); // Bookmark at 150 removed
+        assert_eq!(bookmarks.len(), 3);
         assert_eq!(bookmarks[0].offset, 100); // Before: unchanged
-        assert_eq!(bookmarks[1].offset, 200); // After: shifted -100
+        assert!(!bookmarks[0].shifted);
+        assert_eq!(bookmarks[1].offset, 120); // In range: clamped to the deletion point
+        assert!(bookmarks[1].shifted);
+        assert_eq!(bookmarks[2].offset, 200); // After: shifted -100
+        assert!(!bookmarks[2].shifted);
+
+        assert!(manager.clear_shifted(in_range));
+        assert!(!manager.get(in_range).unwrap().shifted);
     }
 
     #[test]
     fn test_adjust_offsets_delete_at_boundary() {
         let mut manager = BookmarkManager::new();
+        manager.add(9, "Just before".to_string());
         manager.add(10, "Start".to_string());
         manager.add(14, "End in range".to_string());
+        manager.add(15, "Just after".to_string());
 
         // Delete 5 bytes at offset 10 (range 10..15)
-        manager.adjust_offsets_after_delete(10, 5);
+        manager.adjust_offsets_after_delete(10, 5, 15);
+
+        let offsets: Vec<_> = manager
+            .all()
+            .iter()
+            .map(|b| (b.offset, b.shifted))
+            .collect();
+        // Both ends of [10, 15) are inside the range; 9 and 15 are not
+        assert_eq!(offsets, [(9, false), (10, true), (10, true), (10, false)]);
+    }
 
-        let bookmarks = manager.all();
-        assert_eq!(bookmarks.len(), 0); // Both removed (both in range [10, 15))
+    #[test]
+    fn test_adjust_offsets_delete_to_end_stays_in_file() {
+        let mut manager = BookmarkManager::new();
+        manager.add(3, "Kept".to_string());
+        manager.add(6, "Trimmed".to_string());
+        manager.add(9, "Last byte".to_string());
+
+        // Trim to Right from a cursor on 4 deletes 5..10 of a 10-byte buffer
+        let clamped = manager.adjust_offsets_after_delete(5, 5, 5);
+        let offsets: Vec<_> = manager
+            .all()
+            .iter()
+            .map(|b| (b.offset, b.shifted))
+            .collect();
+        assert_eq!(offsets, [(3, false), (4, true), (4, true)]);
+        assert_eq!(clamped.len(), 2);
+
+        // Deleting everything leaves them at 0
+        let mut manager = BookmarkManager::new();
+        manager.add(2, "Gone".to_string());
+        manager.adjust_offsets_after_delete(0, 4, 0);
+        assert_eq!(manager.all()[0].offset, 0);
+    }
+
+    #[test]
+    fn test_adjust_offsets_insert_at_boundary() {
+        let mut manager = BookmarkManager::new();
+        manager.add(4, "Before".to_string());
+        manager.add(5, "At".to_string());
+
+        // Insert at 5: a bookmark on the insert point moves with its byte
+        manager.adjust_offsets_after_insert(5, 3);
+        let offsets: Vec<_> = manager.all().iter().map(|b| b.offset).collect();
+        assert_eq!(offsets, [4, 8]);
+        assert!(manager.all().iter().all(|b| !b.shifted));
+    }
+
+    #[test]
+    fn test_adjust_offsets_after_replace() {
+        let mut manager = BookmarkManager::new();
+        manager.add(2, "Start of span".to_string());
+        manager.add(3, "Inside span".to_string());
+        manager.add(6, "After".to_string());
+
+        // Replace 2..5 (3 bytes) with 5 bytes: the span's bookmarks stay at its start
+        manager.adjust_offsets_after_replace(2, 3, 5, 12);
+        let offsets: Vec<_> = manager
+            .all()
+            .iter()
+            .map(|b| (b.offset, b.shifted))
+            .collect();
+        assert_eq!(offsets, [(2, true), (2, true), (8, false)]);
     }
 }
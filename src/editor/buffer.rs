@@ -17,7 +17,8 @@
 //! 3. Save points can efficiently diff against a known base
 //! 4. Export writes working buffer to a new location
 
-use super::bookmarks::BookmarkManager;
+use super::analysis::ByteHistogram;
+use super::bookmarks::{Bookmark, BookmarkManager, ClampedBookmark};
 use super::history::{EditOperation, History};
 use super::savepoints::{SavePoint, SavePointManager};
 
@@ -51,9 +52,16 @@ pub struct EditorState {
     /// Selection range (start, end) - None if no selection
     pub(super) selection: Option<(usize, usize)>,
 
+    /// Further ranges added with Ctrl+drag, alongside `selection` (the range
+    /// currently being dragged or extended)
+    pub(super) extra_selections: Vec<(usize, usize)>,
+
     /// Selection anchor point - where selection started (for Shift+click/arrow)
     pub(super) selection_anchor: Option<usize>,
 
+    /// Keep the selection when the cursor is moved by clicks or navigation keys
+    pub(super) selection_locked: bool,
+
     /// Whether the working buffer has unsaved changes
     pub(super) modified: bool,
 
@@ -63,17 +71,28 @@ pub struct EditorState {
     /// Current write mode (insert vs overwrite)
     pub(super) write_mode: WriteMode,
 
+    /// Whether typing past the last byte in overwrite mode appends new bytes
+    pub(super) grow_at_eof: bool,
+
+    /// Edit generation at which the last byte was typed over with growing on;
+    /// the next typed byte is appended while this is still current
+    pub(super) eof_append_generation: Option<u64>,
+
     /// Whether buffer length changed since last check (for UI cache invalidation)
     pub(super) length_changed: bool,
 
-    /// Monotonically increasing counter, bumped on every edit/undo/redo
+    /// Monotonically increasing counter, bumped on every edit/undo/redo/restore
     edit_generation: u64,
+
+    /// Byte histogram of the working buffer, updated from each applied operation
+    histogram: ByteHistogram,
 }
 
 impl EditorState {
     /// Create a new editor state from file bytes
     pub fn new(bytes: Vec<u8>) -> Self {
         let save_points = SavePointManager::new(&bytes);
+        let histogram = ByteHistogram::from_bytes(&bytes);
         Self {
             working: bytes.clone(),
             original: bytes,
@@ -83,15 +102,39 @@ impl EditorState {
             cursor: 0,
             nibble: NibblePosition::High,
             selection: None,
+            extra_selections: Vec::new(),
             selection_anchor: None,
+            selection_locked: false,
             modified: false,
             edit_mode: EditMode::default(),
             write_mode: WriteMode::default(),
+            grow_at_eof: false,
+            eof_append_generation: None,
             length_changed: false,
             edit_generation: 0,
+            histogram,
         }
     }
 
+    /// Rebuild an editor from a saved session: `working` over `original`, with
+    /// the session's save points and bookmarks. Undo history starts empty.
+    pub fn from_session(
+        original: Vec<u8>,
+        working: Vec<u8>,
+        save_points: Vec<SavePoint>,
+        bookmarks: Vec<Bookmark>,
+    ) -> Self {
+        let mut editor = Self::new(original);
+        editor.save_points = SavePointManager::from_save_points(save_points, &editor.original);
+        for bookmark in bookmarks {
+            editor.bookmarks.restore(bookmark);
+        }
+        editor.histogram = ByteHistogram::from_bytes(&working);
+        editor.modified = working != editor.original;
+        editor.working = working;
+        editor
+    }
+
     /// Get a reference to the original (unmodified) bytes
     pub fn original(&self) -> &[u8] {
         &self.original
@@ -102,18 +145,25 @@ impl EditorState {
         &self.working
     }
 
-    /// Get the current edit generation counter (incremented on every edit/undo/redo)
+    /// Get the current edit generation counter (incremented on every mutation of the
+    /// working buffer: edits, inserts/deletes, undo, redo, and save point restores)
     pub fn edit_generation(&self) -> u64 {
         self.edit_generation
     }
 
     /// Record an edit operation: push to history, mark modified, bump generation
-    fn record_operation(&mut self, op: EditOperation) {
+    pub(super) fn record_operation(&mut self, op: EditOperation) {
+        self.histogram.apply_operation(&op, false);
         self.history.push(op);
         self.modified = true;
         self.edit_generation += 1;
     }
 
+    /// Byte histogram of the working buffer
+    pub fn histogram(&self) -> &ByteHistogram {
+        &self.histogram
+    }
+
     /// Check and reset the length_changed flag (returns true if length changed since last call)
     pub fn take_length_changed(&mut self) -> bool {
         let changed = self.length_changed;
@@ -250,6 +300,149 @@ impl EditorState {
         self.record_operation(EditOperation::Group(sub_ops));
     }
 
+    /// Replace `old_len` bytes at `offset` with `new_values`, which may differ in
+    /// length. Recorded as a single atomic delete+insert; equal lengths fall back
+    /// to an in-place `replace_bytes`.
+    pub fn replace_range_with(&mut self, offset: usize, old_len: usize, new_values: &[u8]) {
+        self.replace_ranges_with(&[offset], old_len, new_values);
+    }
+
+    /// Replace `old_len` bytes at each of `offsets` with `new_values` as one atomic
+    /// undo/redo operation. Offsets refer to the buffer before any replacement.
+    pub fn replace_ranges_with(&mut self, offsets: &[usize], old_len: usize, new_values: &[u8]) {
+        if old_len == new_values.len() {
+            if let [offset] = offsets {
+                self.replace_bytes(*offset, new_values);
+            } else {
+                self.replace_all_bytes(offsets, new_values);
+            }
+            return;
+        }
+
+        let mut buffer = std::mem::take(&mut self.working);
+        let removed = splice_ranges(&mut buffer, offsets, old_len, new_values);
+        self.commit_spliced_ranges(buffer, removed, new_values);
+    }
+
+    /// Install `spliced`, the result of `splice_ranges` over the working buffer
+    /// (possibly computed on another thread), and record it as one atomic
+    /// undo/redo operation. `removed` is what `splice_ranges` returned.
+    pub fn commit_spliced_ranges(
+        &mut self,
+        spliced: Vec<u8>,
+        removed: Vec<(usize, Vec<u8>)>,
+        new_values: &[u8],
+    ) {
+        // `removed` runs back to front; track the length after each span
+        let removed_total: usize = removed.iter().map(|(_, r)| r.len()).sum();
+        let mut len = spliced.len() + removed_total - removed.len() * new_values.len();
+        self.working = spliced;
+        let mut sub_ops = Vec::new();
+        for (offset, removed) in removed {
+            len = len - removed.len() + new_values.len();
+            // Bookmarks in a replaced span stay at its start rather than being
+            // pushed past the new bytes
+            let clamped = self.bookmarks.adjust_offsets_after_replace(
+                offset,
+                removed.len(),
+                new_values.len(),
+                len,
+            );
+            if !removed.is_empty() {
+                sub_ops.push(EditOperation::DeleteBytes {
+                    offset,
+                    values: removed,
+                    clamped,
+                });
+            }
+            if !new_values.is_empty() {
+                sub_ops.push(EditOperation::InsertBytes {
+                    offset,
+                    values: new_values.to_vec(),
+                });
+            }
+        }
+        if sub_ops.is_empty() {
+            return;
+        }
+
+        self.save_points.clear_all(&self.original);
+        self.length_changed = true;
+        if !self.working.is_empty() {
+            self.cursor = self.cursor.min(self.working.len() - 1);
+        }
+        self.record_operation(EditOperation::Group(sub_ops));
+    }
+
+    /// Replace several disjoint spans, each `(offset, old_len, new_values)`
+    /// with its own replacement, as one atomic undo/redo operation. Offsets
+    /// refer to the buffer before any replacement; a span whose replacement
+    /// differs in length grows or shrinks the buffer.
+    pub fn replace_spans(&mut self, spans: &[(usize, usize, Vec<u8>)]) {
+        let mut sorted: Vec<_> = spans
+            .iter()
+            .filter(|(offset, _, _)| *offset < self.working.len())
+            .collect();
+        // Back to front, so earlier offsets stay valid as later spans resize
+        sorted.sort_unstable_by_key(|(offset, _, _)| std::cmp::Reverse(*offset));
+
+        let mut sub_ops = Vec::new();
+        let mut resized = false;
+        for (offset, old_len, new_values) in sorted {
+            let offset = *offset;
+            let end = (offset + old_len).min(self.working.len());
+            if end - offset == new_values.len() {
+                let old_values = self.working[offset..end].to_vec();
+                if old_values == *new_values {
+                    continue;
+                }
+                self.working[offset..end].copy_from_slice(new_values);
+                sub_ops.push(EditOperation::Range {
+                    offset,
+                    old_values,
+                    new_values: new_values.clone(),
+                });
+                continue;
+            }
+            let removed: Vec<u8> = self
+                .working
+                .splice(offset..end, new_values.iter().copied())
+                .collect();
+            let clamped = self.bookmarks.adjust_offsets_after_replace(
+                offset,
+                removed.len(),
+                new_values.len(),
+                self.working.len(),
+            );
+            resized = true;
+            if !removed.is_empty() {
+                sub_ops.push(EditOperation::DeleteBytes {
+                    offset,
+                    values: removed,
+                    clamped,
+                });
+            }
+            if !new_values.is_empty() {
+                sub_ops.push(EditOperation::InsertBytes {
+                    offset,
+                    values: new_values.clone(),
+                });
+            }
+        }
+        if sub_ops.is_empty() {
+            return;
+        }
+
+        if resized {
+            self.save_points.clear_all(&self.original);
+            self.length_changed = true;
+            if !self.working.is_empty() {
+                self.cursor = self.cursor.min(self.working.len() - 1);
+            }
+        }
+        self.record_operation(EditOperation::Group(sub_ops));
+    }
+
     /// Edit a single byte at the given offset
     pub fn edit_byte(&mut self, offset: usize, new_value: u8) {
         if offset >= self.working.len() {
@@ -273,16 +466,24 @@ impl EditorState {
 
     /// Called after any operation that changes buffer length.
     /// Clears save points, adjusts bookmarks, and sets the length_changed flag.
-    fn on_length_changed(&mut self, offset: usize, count: usize, is_insert: bool) {
+    /// Returns the bookmarks a deletion clamped (see `ClampedBookmark`).
+    fn on_length_changed(
+        &mut self,
+        offset: usize,
+        count: usize,
+        is_insert: bool,
+    ) -> Vec<ClampedBookmark> {
         // Save points use absolute offsets — invalidate them all
         self.save_points.clear_all(&self.original);
+        self.length_changed = true;
         // Adjust bookmark offsets
         if is_insert {
             self.bookmarks.adjust_offsets_after_insert(offset, count);
+            Vec::new()
         } else {
-            self.bookmarks.adjust_offsets_after_delete(offset, count);
+            self.bookmarks
+                .adjust_offsets_after_delete(offset, count, self.working.len())
         }
-        self.length_changed = true;
     }
 
     /// Insert a single byte at the given offset
@@ -310,31 +511,73 @@ impl EditorState {
         self.on_length_changed(offset, values.len(), true);
     }
 
+    /// Insert a copy of the selected bytes right after the selection.
+    /// Returns false without a selection or with several selected ranges.
+    #[must_use = "returns whether anything was duplicated"]
+    pub fn duplicate_selection(&mut self) -> bool {
+        let Some((start, end)) = self.single_selection() else {
+            return false;
+        };
+        let copied = self.working[start..end].to_vec();
+        self.insert_bytes(end, &copied);
+        true
+    }
+
     /// Delete the byte at the given offset, returning the deleted value
     pub fn delete_byte(&mut self, offset: usize) -> Option<u8> {
         if offset >= self.working.len() {
             return None;
         }
         let value = self.working.remove(offset);
+        let clamped = self.on_length_changed(offset, 1, false);
         self.record_operation(EditOperation::DeleteBytes {
             offset,
             values: vec![value],
+            clamped,
         });
         // Clamp cursor if it now points past the end
         if !self.working.is_empty() {
             self.cursor = self.cursor.min(self.working.len() - 1);
         }
-        self.on_length_changed(offset, 1, false);
         Some(value)
     }
 
+    /// Delete `start..end` as a single undoable operation. The cursor keeps
+    /// pointing at the same byte (or the first byte after the range if it was
+    /// inside it) and the selection is cleared. Returns false if nothing was
+    /// deleted.
+    pub fn delete_range(&mut self, start: usize, end: usize) -> bool {
+        let end = end.min(self.working.len());
+        if start >= end {
+            return false;
+        }
+        let count = end - start;
+        let values: Vec<u8> = self.working.drain(start..end).collect();
+        let clamped = self.on_length_changed(start, count, false);
+        self.record_operation(EditOperation::DeleteBytes {
+            offset: start,
+            values,
+            clamped,
+        });
+        if self.cursor >= end {
+            self.cursor -= count;
+        } else if self.cursor > start {
+            self.cursor = start;
+        }
+        self.cursor = self.cursor.min(self.working.len().saturating_sub(1));
+        self.clear_selection();
+        true
+    }
+
     // ========== Undo/Redo Shared Helpers ==========
 
-    /// Splice bytes into the working buffer and adjust bookmarks/save points
-    fn apply_insert(&mut self, offset: usize, values: &[u8]) {
+    /// Splice bytes into the working buffer and adjust bookmarks/save points.
+    /// `clamped` bookmarks (from undoing a delete) go back to their old offsets.
+    fn apply_insert(&mut self, offset: usize, values: &[u8], clamped: &[ClampedBookmark]) {
         let count = values.len();
         self.working.splice(offset..offset, values.iter().copied());
         self.bookmarks.adjust_offsets_after_insert(offset, count);
+        self.bookmarks.restore_clamped(clamped);
         self.save_points.clear_all(&self.original);
         self.length_changed = true;
     }
@@ -342,7 +585,8 @@ impl EditorState {
     /// Drain bytes from the working buffer and adjust bookmarks/save points/cursor
     fn apply_delete(&mut self, offset: usize, count: usize) {
         self.working.drain(offset..offset + count);
-        self.bookmarks.adjust_offsets_after_delete(offset, count);
+        self.bookmarks
+            .adjust_offsets_after_delete(offset, count, self.working.len());
         self.save_points.clear_all(&self.original);
         self.length_changed = true;
         if !self.working.is_empty() {
@@ -366,14 +610,24 @@ impl EditorState {
             EditOperation::InsertBytes { offset, values } => {
                 self.apply_delete(*offset, values.len());
             }
-            EditOperation::DeleteBytes { offset, values } => {
-                self.apply_insert(*offset, values);
+            EditOperation::DeleteBytes {
+                offset,
+                values,
+                clamped,
+            } => {
+                self.apply_insert(*offset, values, clamped);
             }
             EditOperation::Group(ops) => {
                 for sub_op in ops.iter().rev() {
                     self.apply_undo_op(sub_op);
                 }
             }
+            EditOperation::AddBookmark(bookmark) => {
+                let _ = self.bookmarks.remove(bookmark.id);
+            }
+            EditOperation::RemoveBookmark(bookmark) => {
+                self.bookmarks.restore(bookmark.clone());
+            }
         }
     }
 
@@ -391,9 +645,9 @@ impl EditorState {
                 self.working[*offset..*offset + new_values.len()].copy_from_slice(new_values);
             }
             EditOperation::InsertBytes { offset, values } => {
-                self.apply_insert(*offset, values);
+                self.apply_insert(*offset, values, &[]);
             }
-            EditOperation::DeleteBytes { offset, values } => {
+            EditOperation::DeleteBytes { offset, values, .. } => {
                 self.apply_delete(*offset, values.len());
             }
             EditOperation::Group(ops) => {
@@ -401,6 +655,12 @@ impl EditorState {
                     self.apply_redo_op(sub_op);
                 }
             }
+            EditOperation::AddBookmark(bookmark) => {
+                self.bookmarks.restore(bookmark.clone());
+            }
+            EditOperation::RemoveBookmark(bookmark) => {
+                let _ = self.bookmarks.remove(bookmark.id);
+            }
         }
     }
 
@@ -409,8 +669,11 @@ impl EditorState {
     pub fn undo(&mut self) -> bool {
         if let Some(op) = self.history.undo() {
             self.apply_undo_op(&op);
+            self.histogram.apply_operation(&op, true);
             self.modified = self.working != self.original;
-            self.edit_generation += 1;
+            if op.changes_bytes() {
+                self.edit_generation += 1;
+            }
             true
         } else {
             false
@@ -422,8 +685,11 @@ impl EditorState {
     pub fn redo(&mut self) -> bool {
         if let Some(op) = self.history.redo() {
             self.apply_redo_op(&op);
+            self.histogram.apply_operation(&op, false);
             self.modified = self.working != self.original;
-            self.edit_generation += 1;
+            if op.changes_bytes() {
+                self.edit_generation += 1;
+            }
             true
         } else {
             false
@@ -440,6 +706,24 @@ impl EditorState {
         self.history.can_redo()
     }
 
+    /// Number of operations available to undo
+    pub fn undo_count(&self) -> usize {
+        self.history.undo_count()
+    }
+
+    /// Number of operations available to redo
+    pub fn redo_count(&self) -> usize {
+        self.history.redo_count()
+    }
+
+    /// Check whether the working byte at `offset` differs from the original.
+    /// Always false once the buffer length has changed, since offsets no longer line up.
+    pub fn differs_at(&self, offset: usize) -> bool {
+        self.working.len() == self.original.len()
+            && offset < self.working.len()
+            && self.working[offset] != self.original[offset]
+    }
+
     /// Get a slice of bytes for display
     pub fn bytes_in_range(&self, start: usize, end: usize) -> &[u8] {
         let start = start.min(self.working.len());
@@ -447,6 +731,21 @@ impl EditorState {
         &self.working[start..end]
     }
 
+    /// Find the first occurrence of `value` at or after `from`
+    pub fn find_next_byte(&self, from: usize, value: u8) -> Option<usize> {
+        let from = from.min(self.working.len());
+        self.working[from..]
+            .iter()
+            .position(|&b| b == value)
+            .map(|pos| from + pos)
+    }
+
+    /// Find the last occurrence of `value` strictly before `from`
+    pub fn find_prev_byte(&self, from: usize, value: u8) -> Option<usize> {
+        let from = from.min(self.working.len());
+        self.working[..from].iter().rposition(|&b| b == value)
+    }
+
     /// Total number of bytes
     pub fn len(&self) -> usize {
         self.working.len()
@@ -459,11 +758,21 @@ impl EditorState {
         self.save_points.create(name, &self.working)
     }
 
+    /// Default name for the next save point, e.g. "Save Point 3"
+    pub fn next_save_point_name(&self) -> String {
+        format!("Save Point {}", self.save_point_count() + 1)
+    }
+
     /// Get all save points
     pub fn save_points(&self) -> &[SavePoint] {
         self.save_points.save_points()
     }
 
+    /// Resolve the full buffer contents of a save point without restoring it
+    pub fn save_point_bytes(&self, id: u64) -> Option<Vec<u8>> {
+        self.save_points.restore(id, &self.original)
+    }
+
     /// Restore the buffer to a specific save point
     ///
     /// This operation is undoable - the entire restoration is recorded as a
@@ -481,10 +790,11 @@ impl EditorState {
 
         // Only record if there's actually a change
         if old_values != self.working {
-            self.history.push(super::history::EditOperation::Range {
+            let new_values = self.working.clone();
+            self.record_operation(EditOperation::Range {
                 offset: 0,
                 old_values,
-                new_values: self.working.clone(),
+                new_values,
             });
         }
 
@@ -492,18 +802,80 @@ impl EditorState {
         true
     }
 
+    /// Revert the working buffer to the original file contents
+    ///
+    /// Recorded as a single undoable operation. If the buffer length changed,
+    /// the differing tail is recorded as an insert/delete alongside the
+    /// overlapping range so undo restores the exact previous buffer.
+    ///
+    /// Returns true if anything changed
+    #[must_use = "returns whether the buffer was reverted"]
+    pub fn revert_to_original(&mut self) -> bool {
+        if self.working == self.original {
+            return false;
+        }
+
+        let common = self.working.len().min(self.original.len());
+        let mut ops = Vec::new();
+        if self.working[..common] != self.original[..common] {
+            ops.push(EditOperation::Range {
+                offset: 0,
+                old_values: self.working[..common].to_vec(),
+                new_values: self.original[..common].to_vec(),
+            });
+        }
+        self.working[..common].copy_from_slice(&self.original[..common]);
+
+        if self.working.len() > common {
+            let values: Vec<u8> = self.working.drain(common..).collect();
+            let clamped = self.on_length_changed(common, values.len(), false);
+            ops.push(EditOperation::DeleteBytes {
+                offset: common,
+                values,
+                clamped,
+            });
+        } else if self.original.len() > common {
+            let values = self.original[common..].to_vec();
+            let count = values.len();
+            self.working.extend_from_slice(&values);
+            ops.push(EditOperation::InsertBytes {
+                offset: common,
+                values,
+            });
+            self.on_length_changed(common, count, true);
+        }
+
+        let op = if ops.len() == 1 {
+            ops.remove(0)
+        } else {
+            EditOperation::Group(ops)
+        };
+        self.record_operation(op);
+        self.modified = false;
+        if !self.working.is_empty() {
+            self.cursor = self.cursor.min(self.working.len() - 1);
+        }
+        true
+    }
+
     /// Rename a save point
     #[must_use = "returns whether the save point was found and renamed"]
     pub fn rename_save_point(&mut self, id: u64, new_name: String) -> bool {
         self.save_points.rename(id, new_name)
     }
 
+    /// Replace the note attached to a save point
+    #[must_use = "returns whether the save point was found and updated"]
+    pub fn set_save_point_note(&mut self, id: u64, note: String) -> bool {
+        self.save_points.set_note(id, note)
+    }
+
     /// Check if a save point can be deleted
     pub fn can_delete_save_point(&self, id: u64) -> bool {
         self.save_points.can_delete(id)
     }
 
-    /// Delete a save point (only leaf save points can be deleted)
+    /// Delete a save point, merging its diff into its successor if it has one
     #[must_use = "returns whether the save point was deleted"]
     pub fn delete_save_point(&mut self, id: u64) -> bool {
         self.save_points.delete(id)
@@ -516,15 +888,44 @@ impl EditorState {
 
     // ========== Bookmarks ==========
 
-    /// Add a bookmark at the given offset
+    /// Add a bookmark at the given offset (undoable)
     pub fn add_bookmark(&mut self, offset: usize, name: String) -> u64 {
-        self.bookmarks.add(offset, name)
+        let id = self.bookmarks.add(offset, name);
+        if let Some(bookmark) = self.bookmarks.get(id) {
+            self.history
+                .push(EditOperation::AddBookmark(bookmark.clone()));
+        }
+        id
+    }
+
+    /// Add several bookmarks as one undo step. Returns how many were added.
+    pub fn add_bookmarks(&mut self, bookmarks: impl IntoIterator<Item = (usize, String)>) -> usize {
+        let ops: Vec<EditOperation> = bookmarks
+            .into_iter()
+            .filter_map(|(offset, name)| {
+                let id = self.bookmarks.add(offset, name);
+                self.bookmarks
+                    .get(id)
+                    .cloned()
+                    .map(EditOperation::AddBookmark)
+            })
+            .collect();
+        let added = ops.len();
+        if added > 0 {
+            self.history.push(EditOperation::Group(ops));
+        }
+        added
     }
 
-    /// Remove a bookmark by ID
+    /// Remove a bookmark by ID (undoable)
     #[must_use = "returns whether the bookmark was found and removed"]
     pub fn remove_bookmark(&mut self, id: u64) -> bool {
-        self.bookmarks.remove(id)
+        let Some(bookmark) = self.bookmarks.get(id).cloned() else {
+            return false;
+        };
+        let removed = self.bookmarks.remove(id);
+        self.history.push(EditOperation::RemoveBookmark(bookmark));
+        removed
     }
 
     /// Get all bookmarks
@@ -543,6 +944,42 @@ impl EditorState {
     }
 }
 
+/// Replace `old_len` bytes at each in-range offset of `buffer` with `new_values`,
+/// working back-to-front so earlier offsets stay valid as the buffer resizes.
+/// The ranges must not overlap (see `search::non_overlapping_matches`).
+/// Returns each offset with the bytes removed there, last offset first, for
+/// `EditorState::commit_spliced_ranges`.
+pub fn splice_ranges(
+    buffer: &mut Vec<u8>,
+    offsets: &[usize],
+    old_len: usize,
+    new_values: &[u8],
+) -> Vec<(usize, Vec<u8>)> {
+    let mut sorted: Vec<usize> = offsets
+        .iter()
+        .copied()
+        .filter(|&o| o < buffer.len())
+        .collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert!(
+        sorted.windows(2).all(|w| w[1] >= w[0] + old_len),
+        "splice_ranges: replaced ranges overlap"
+    );
+
+    sorted
+        .into_iter()
+        .rev()
+        .map(|offset| {
+            let end = (offset + old_len).min(buffer.len());
+            let removed = buffer
+                .splice(offset..end, new_values.iter().copied())
+                .collect();
+            (offset, removed)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -569,6 +1006,33 @@ mod tests {
         assert!(editor.is_modified());
     }
 
+    #[test]
+    fn test_bookmark_add_and_remove_are_undoable() {
+        let mut editor = EditorState::new(vec![0; 16]);
+        let id = editor.add_bookmark(4, "Four".to_string());
+        let other = editor.add_bookmark(8, "Eight".to_string());
+        assert!(editor.remove_bookmark(id));
+        let generation = editor.edit_generation();
+
+        // Undo the removal: same ID and offset come back, bytes untouched
+        assert!(editor.undo());
+        let restored = editor.bookmarks().at_offset(4).unwrap();
+        assert_eq!((restored.id, restored.name.as_str()), (id, "Four"));
+        assert_eq!(editor.edit_generation(), generation);
+        assert!(!editor.is_modified());
+
+        // Undo the second add, then redo it
+        assert!(editor.undo());
+        assert!(!editor.has_bookmark_at(8));
+        assert!(editor.redo());
+        assert_eq!(editor.bookmarks().at_offset(8).unwrap().id, other);
+
+        // New bookmarks never reuse a restored ID
+        assert!(editor.redo());
+        assert!(!editor.has_bookmark_at(4));
+        assert!(editor.add_bookmark(0, "Zero".to_string()) > other);
+    }
+
     #[test]
     fn test_undo_redo() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -586,6 +1050,61 @@ mod tests {
         assert!(editor.is_modified());
     }
 
+    #[test]
+    fn test_edit_generation_bumps_on_each_mutation() {
+        let mut editor = EditorState::new(vec![0x00, 0x01, 0x02, 0x03]);
+        let mut last = editor.edit_generation();
+        assert_eq!(last, 0);
+
+        let mut assert_bumped = |editor: &EditorState| {
+            assert!(editor.edit_generation() > last);
+            last = editor.edit_generation();
+        };
+
+        editor.edit_byte(0, 0xFF);
+        assert_bumped(&editor);
+        let _ = editor.edit_nibble(0xA);
+        assert_bumped(&editor);
+        let _ = editor.edit_ascii('Z');
+        assert_bumped(&editor);
+        editor.replace_bytes(1, &[0xAA, 0xBB]);
+        assert_bumped(&editor);
+        editor.replace_all_bytes(&[0, 2], &[0x11]);
+        assert_bumped(&editor);
+        editor.insert_byte(0, 0x42);
+        assert_bumped(&editor);
+        editor.insert_bytes(0, &[0x01, 0x02]);
+        assert_bumped(&editor);
+        let _ = editor.delete_byte(0);
+        assert_bumped(&editor);
+        assert!(editor.undo());
+        assert_bumped(&editor);
+        assert!(editor.redo());
+        assert_bumped(&editor);
+    }
+
+    #[test]
+    fn test_edit_generation_unchanged_on_no_op() {
+        let mut editor = EditorState::new(vec![0x00, 0x01]);
+        editor.edit_byte(1, 0x01); // same value
+        editor.edit_byte(10, 0xFF); // out of bounds
+        assert!(!editor.undo());
+        assert!(!editor.redo());
+        assert_eq!(editor.edit_generation(), 0);
+    }
+
+    #[test]
+    fn test_edit_generation_bumps_on_restore_save_point() {
+        let mut editor = EditorState::new(vec![0x00, 0x01, 0x02]);
+        let id = editor.create_save_point("SP".to_string());
+        editor.edit_byte(0, 0xFF);
+        let before = editor.edit_generation();
+
+        assert!(editor.restore_save_point(id));
+        assert!(editor.edit_generation() > before);
+        assert_eq!(editor.working(), &[0x00, 0x01, 0x02]);
+    }
+
     #[test]
     fn test_nibble_editing() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -673,6 +1192,23 @@ mod tests {
         assert_eq!(sps[0].name, "New name");
     }
 
+    #[test]
+    fn test_next_save_point_name() {
+        let mut editor = EditorState::new(vec![0x00]);
+        assert_eq!(editor.next_save_point_name(), "Save Point 1");
+        editor.create_save_point("Custom".to_string());
+        assert_eq!(editor.next_save_point_name(), "Save Point 2");
+    }
+
+    #[test]
+    fn test_save_point_note() {
+        let mut editor = EditorState::new(vec![0x00, 0x01]);
+        let sp_id = editor.create_save_point("SP".to_string());
+        assert!(editor.set_save_point_note(sp_id, "before the shift".to_string()));
+        assert_eq!(editor.save_points()[0].note, "before the shift");
+        assert!(!editor.set_save_point_note(sp_id + 1, String::new()));
+    }
+
     #[test]
     fn test_save_point_delete() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -681,15 +1217,16 @@ mod tests {
         let sp1 = editor.create_save_point("SP1".to_string());
         let sp2 = editor.create_save_point("SP2".to_string());
 
-        // Can only delete leaf (sp2)
-        assert!(!editor.can_delete_save_point(sp1));
+        assert!(editor.can_delete_save_point(sp1));
         assert!(editor.can_delete_save_point(sp2));
 
-        assert!(editor.delete_save_point(sp2));
+        // Deleting the non-leaf keeps sp2 restorable to the same bytes
+        let sp2_bytes = editor.save_point_bytes(sp2).unwrap();
+        assert!(editor.delete_save_point(sp1));
         assert_eq!(editor.save_point_count(), 1);
+        assert_eq!(editor.save_point_bytes(sp2).unwrap(), sp2_bytes);
 
-        // Now sp1 is the leaf
-        assert!(editor.delete_save_point(sp1));
+        assert!(editor.delete_save_point(sp2));
         assert_eq!(editor.save_point_count(), 0);
     }
 
@@ -762,6 +1299,63 @@ mod tests {
 
     // ========== Insert/Delete Tests ==========
 
+    #[test]
+    fn test_histogram_tracks_edits_undo_redo() {
+        let mut editor = EditorState::new(vec![0u8; 32]);
+        let check = |editor: &EditorState| {
+            assert_eq!(
+                editor.histogram(),
+                &ByteHistogram::from_bytes(editor.working())
+            );
+        };
+
+        editor.edit_byte(3, 0xAA);
+        editor.edit_byte(4, 0xBB); // coalesces with the previous edit
+        editor.insert_bytes(10, &[1, 2, 3]);
+        editor.delete_byte(0);
+        editor.replace_bytes(5, &[7, 7, 7, 7]);
+        check(&editor);
+
+        while editor.undo() {
+            check(&editor);
+        }
+        while editor.redo() {
+            check(&editor);
+        }
+        assert!(editor.revert_to_original());
+        check(&editor);
+    }
+
+    #[test]
+    fn test_find_next_and_prev_byte() {
+        let state = EditorState::new(vec![0xFF, 0x00, 0xFF, 0x10, 0xFF]);
+        assert_eq!(state.find_next_byte(0, 0xFF), Some(0));
+        assert_eq!(state.find_next_byte(1, 0xFF), Some(2));
+        assert_eq!(state.find_next_byte(5, 0xFF), None);
+        assert_eq!(state.find_next_byte(99, 0xFF), None);
+        assert_eq!(state.find_next_byte(0, 0x42), None);
+
+        assert_eq!(state.find_prev_byte(4, 0xFF), Some(2));
+        assert_eq!(state.find_prev_byte(2, 0xFF), Some(0));
+        assert_eq!(state.find_prev_byte(0, 0xFF), None);
+        assert_eq!(state.find_prev_byte(99, 0x10), Some(3));
+    }
+
+    #[test]
+    fn test_duplicate_selection() {
+        let mut state = EditorState::new(vec![1, 2, 3, 4]);
+        assert!(!state.duplicate_selection());
+
+        state.set_selection(1, 3);
+        assert!(state.duplicate_selection());
+        assert_eq!(state.working(), &[1, 2, 3, 2, 3, 4]);
+        assert!(state.take_length_changed());
+
+        // One undo step removes the whole copy
+        assert!(state.undo());
+        assert_eq!(state.working(), &[1, 2, 3, 4]);
+    }
+
     #[test]
     fn test_insert_byte() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -813,6 +1407,24 @@ mod tests {
         assert!(editor.is_modified());
     }
 
+    #[test]
+    fn test_delete_range_is_one_undo() {
+        let mut editor = EditorState::new(vec![0, 1, 2, 3, 4, 5]);
+        editor.set_selection(1, 3);
+        editor.set_cursor(4);
+        assert!(editor.delete_range(0, 3));
+        assert_eq!(editor.working(), &[3, 4, 5]);
+        // Still on the byte that was at offset 4
+        assert_eq!(editor.cursor(), 1);
+        assert_eq!(editor.selection(), None);
+        assert!(editor.take_length_changed());
+
+        assert_eq!(editor.undo_count(), 1);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0, 1, 2, 3, 4, 5]);
+        assert!(!editor.delete_range(6, 8));
+    }
+
     #[test]
     fn test_delete_byte_out_of_bounds() {
         let data = vec![0x00, 0x01, 0x02];
@@ -920,13 +1532,58 @@ mod tests {
         editor.add_bookmark(2, "Deleted".to_string());
         editor.add_bookmark(4, "After".to_string());
 
-        // Delete byte at position 2 — bookmark at 2 removed, bookmark at 4 shifts to 3
+        // Delete byte at position 2 — bookmark at 2 stays there flagged, bookmark at 4 shifts to 3
         editor.delete_byte(2);
 
         let bookmarks = editor.bookmarks().all();
-        assert_eq!(bookmarks.len(), 2); // One removed
+        assert_eq!(bookmarks.len(), 3);
         assert_eq!(bookmarks[0].offset, 1); // Before: unchanged
-        assert_eq!(bookmarks[1].offset, 3); // After: shifted -1
+        assert_eq!(bookmarks[1].offset, 2); // Deleted: clamped to the deletion point
+        assert!(bookmarks[1].shifted);
+        assert_eq!(bookmarks[2].offset, 3); // After: shifted -1
+    }
+
+    #[test]
+    fn test_undo_delete_restores_clamped_bookmarks() {
+        let mut editor = EditorState::new(vec![0x00, 0x01, 0x02, 0x03, 0x04, 0x05]);
+        for offset in 0..6 {
+            editor.add_bookmark(offset, format!("At {}", offset));
+        }
+        let offsets = |editor: &EditorState| -> Vec<(usize, bool)> {
+            editor
+                .bookmarks()
+                .all()
+                .iter()
+                .map(|b| (b.offset, b.shifted))
+                .collect()
+        };
+
+        // The deleted range's first byte, interior and last byte all clamp to 1
+        assert!(editor.delete_range(1, 4));
+        assert_eq!(
+            offsets(&editor),
+            [
+                (0, false),
+                (1, true),
+                (1, true),
+                (1, true),
+                (1, false),
+                (2, false)
+            ]
+        );
+
+        assert!(editor.undo());
+        let restored: Vec<_> = (0..6).map(|offset| (offset, false)).collect();
+        assert_eq!(offsets(&editor), restored);
+
+        assert!(editor.redo());
+        assert_eq!(offsets(&editor)[1..4], [(1, true), (1, true), (1, true)]);
+
+        // Same through a resizing replace
+        assert!(editor.undo());
+        editor.replace_range_with(2, 2, &[0xFF]);
+        assert!(editor.undo());
+        assert_eq!(offsets(&editor), restored);
     }
 
     #[test]
@@ -953,6 +1610,74 @@ mod tests {
         assert_eq!(editor.len(), 0);
     }
 
+    #[test]
+    fn test_differs_at() {
+        let mut editor = EditorState::new(vec![0x00, 0x01, 0x02]);
+        assert!(!editor.differs_at(1));
+
+        editor.edit_byte(1, 0xFF);
+        assert!(editor.differs_at(1));
+        assert!(!editor.differs_at(0));
+        assert!(!editor.differs_at(99));
+
+        // Offsets no longer correspond once the length changes
+        editor.insert_byte(0, 0xAA);
+        assert!(!editor.differs_at(0));
+    }
+
+    // ========== Revert to Original Tests ==========
+
+    #[test]
+    fn test_revert_to_original_is_undoable() {
+        let data = vec![0x00, 0x01, 0x02, 0x03];
+        let mut editor = EditorState::new(data.clone());
+
+        editor.edit_byte(1, 0xAA);
+        editor.edit_byte(3, 0xBB);
+        assert!(editor.revert_to_original());
+        assert_eq!(editor.working(), &data);
+        assert!(!editor.is_modified());
+
+        // Single undo brings back all edits
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x00, 0xAA, 0x02, 0xBB]);
+        assert!(editor.is_modified());
+
+        assert!(editor.redo());
+        assert_eq!(editor.working(), &data);
+        assert!(!editor.is_modified());
+    }
+
+    #[test]
+    fn test_revert_to_original_no_change() {
+        let mut editor = EditorState::new(vec![0x00, 0x01]);
+        assert!(!editor.revert_to_original());
+        assert!(!editor.can_undo());
+    }
+
+    #[test]
+    fn test_revert_to_original_after_length_change() {
+        let data = vec![0x00, 0x01, 0x02];
+        let mut editor = EditorState::new(data.clone());
+
+        editor.insert_bytes(1, &[0xAA, 0xBB]);
+        editor.set_cursor(4);
+        assert!(editor.revert_to_original());
+        assert_eq!(editor.working(), &data);
+        assert!(editor.cursor() < data.len());
+
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x00, 0xAA, 0xBB, 0x01, 0x02]);
+
+        // Shrunk buffer grows back on revert
+        let mut editor = EditorState::new(data.clone());
+        let _ = editor.delete_byte(0);
+        assert!(editor.revert_to_original());
+        assert_eq!(editor.working(), &data);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x01, 0x02]);
+    }
+
     // ========== Replace Bytes Tests ==========
 
     #[test]
@@ -1010,6 +1735,80 @@ mod tests {
         assert_eq!(editor.save_point_count(), 0);
     }
 
+    // ========== Variable-Length Replace Tests ==========
+
+    #[test]
+    fn test_replace_range_with_longer_and_shorter() {
+        let data = vec![0x00, 0x01, 0x02, 0x03];
+        let mut editor = EditorState::new(data.clone());
+
+        editor.replace_range_with(1, 2, &[0xAA, 0xBB, 0xCC]);
+        assert_eq!(editor.working(), &[0x00, 0xAA, 0xBB, 0xCC, 0x03]);
+        assert!(editor.take_length_changed());
+
+        editor.replace_range_with(1, 3, &[0xDD]);
+        assert_eq!(editor.working(), &[0x00, 0xDD, 0x03]);
+
+        // Each replacement is a single undo step
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x00, 0xAA, 0xBB, 0xCC, 0x03]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &data);
+        assert!(!editor.can_undo());
+    }
+
+    #[test]
+    fn test_replace_ranges_with_is_atomic() {
+        let data = b"ab-ab-ab".to_vec();
+        let mut editor = EditorState::new(data.clone());
+        editor.add_bookmark(7, "End".to_string());
+
+        editor.replace_ranges_with(&[0, 3, 6], 2, b"xyz");
+        assert_eq!(editor.working(), b"xyz-xyz-xyz");
+        // Bookmark inside the last replaced span moves to the start of its replacement
+        let bookmark = &editor.bookmarks().all()[0];
+        assert_eq!(bookmark.offset, 8);
+        assert!(bookmark.shifted);
+
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &data);
+        // Only the bookmark addition is left to undo
+        assert_eq!(editor.undo_count(), 1);
+
+        assert!(editor.redo());
+        assert_eq!(editor.working(), b"xyz-xyz-xyz");
+    }
+
+    #[test]
+    fn test_replace_ranges_with_equal_length_stays_in_place() {
+        let mut editor = EditorState::new(vec![0xAA, 0x00, 0xAA]);
+        editor.replace_ranges_with(&[0, 2], 1, &[0xBB]);
+        assert_eq!(editor.working(), &[0xBB, 0x00, 0xBB]);
+        assert!(!editor.take_length_changed());
+    }
+
+    #[test]
+    fn test_replace_spans_mixed_lengths_is_atomic() {
+        let data = b"aa-bb-cc".to_vec();
+        let mut editor = EditorState::new(data.clone());
+
+        // Offsets refer to the original buffer, whatever order they come in
+        editor.replace_spans(&[
+            (6, 2, b"Z".to_vec()),
+            (0, 2, b"XX".to_vec()),
+            (3, 2, b"YYY".to_vec()),
+        ]);
+        assert_eq!(editor.working(), b"XX-YYY-Z");
+        assert!(editor.take_length_changed());
+
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &data);
+        assert!(!editor.can_undo());
+
+        assert!(editor.redo());
+        assert_eq!(editor.working(), b"XX-YYY-Z");
+    }
+
     // ========== Replace All Bytes (Atomic) Tests ==========
 
     #[test]
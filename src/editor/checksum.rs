@@ -0,0 +1,99 @@
+//! Sizes and CRC32 checksums of the working and original buffers
+//!
+//! CRC32 runs at several GB/s, so recomputing on the UI thread once per edit
+//! generation is cheap even for large images. The original buffer never
+//! changes for an open file, so its checksum is computed only once.
+
+use super::EditorState;
+
+/// Length and CRC32 of a byte buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferChecksum {
+    pub len: usize,
+    pub crc32: u32,
+}
+
+impl BufferChecksum {
+    /// Measure a buffer
+    pub fn of(data: &[u8]) -> Self {
+        Self {
+            len: data.len(),
+            crc32: crc32fast::hash(data),
+        }
+    }
+}
+
+/// Cached checksums, recomputed only when the edit generation changes
+#[derive(Default)]
+pub struct FileChecksums {
+    /// Edit generation the working checksum was computed for (None = never computed)
+    generation: Option<u64>,
+    working: Option<BufferChecksum>,
+    original: Option<BufferChecksum>,
+}
+
+impl FileChecksums {
+    /// Recompute the working checksum if the buffer changed since the last call
+    pub fn update(&mut self, editor: &EditorState) {
+        if self.original.is_none() {
+            self.original = Some(BufferChecksum::of(editor.original()));
+        }
+        let generation = editor.edit_generation();
+        if self.generation == Some(generation) {
+            return;
+        }
+        self.working = Some(BufferChecksum::of(editor.working()));
+        self.generation = Some(generation);
+    }
+
+    /// Drop cached checksums (e.g. when a new file is opened)
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Checksum of the working buffer as of the last update
+    pub fn working(&self) -> Option<BufferChecksum> {
+        self.working
+    }
+
+    /// Checksum of the original file bytes
+    pub fn original(&self) -> Option<BufferChecksum> {
+        self.original
+    }
+
+    /// Whether the working buffer's length and CRC32 match the original
+    pub fn matches_original(&self) -> bool {
+        self.working.is_some() && self.working == self.original
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_checksum_known_value() {
+        // Standard CRC32 check value
+        let checksum = BufferChecksum::of(b"123456789");
+        assert_eq!(checksum.len, 9);
+        assert_eq!(checksum.crc32, 0xCBF4_3926);
+        assert_eq!(BufferChecksum::of(&[]).crc32, 0);
+    }
+
+    #[test]
+    fn test_checksums_track_edits() {
+        let mut editor = EditorState::new(vec![0u8; 16]);
+        let mut checksums = FileChecksums::default();
+        checksums.update(&editor);
+        assert!(checksums.matches_original());
+
+        editor.edit_byte(3, 0xFF);
+        checksums.update(&editor);
+        assert!(!checksums.matches_original());
+        assert_eq!(checksums.original(), Some(BufferChecksum::of(&[0u8; 16])));
+
+        editor.edit_byte(3, 0x00);
+        checksums.update(&editor);
+        assert!(checksums.matches_original());
+    }
+}
@@ -0,0 +1,116 @@
+//! Recently copied and pasted byte sequences, for "Paste Recent"
+
+use std::collections::VecDeque;
+
+/// Most sequences remembered; the oldest is dropped first
+pub const MAX_CLIPBOARD_HISTORY: usize = 10;
+
+/// Largest sequence remembered; bigger copies and pastes are skipped so the
+/// history can't pin large buffers in memory
+pub const MAX_CLIPBOARD_ENTRY_BYTES: usize = 64 * 1024;
+
+/// Bytes shown in a history entry's label before it's cut off
+const PREVIEW_BYTES: usize = 8;
+
+/// Ring buffer of byte sequences, most recent first
+#[derive(Default)]
+pub struct ClipboardHistory {
+    entries: VecDeque<Vec<u8>>,
+}
+
+impl ClipboardHistory {
+    /// Remember `bytes` as the most recent entry. A sequence already in the
+    /// history moves to the front instead of being stored twice; empty or
+    /// oversized sequences aren't remembered.
+    pub fn push(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() || bytes.len() > MAX_CLIPBOARD_ENTRY_BYTES {
+            return;
+        }
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|e| e.len() == bytes.len() && e.as_slice() == bytes)
+        {
+            if let Some(entry) = self.entries.remove(index) {
+                self.entries.push_front(entry);
+            }
+            return;
+        }
+        self.entries.push_front(bytes.to_vec());
+        self.entries.truncate(MAX_CLIPBOARD_HISTORY);
+    }
+
+    /// Entries, most recent first
+    pub fn entries(&self) -> impl Iterator<Item = &[u8]> {
+        self.entries.iter().map(Vec::as_slice)
+    }
+
+    /// Entry `index` (0 = most recent)
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        self.entries.get(index).map(Vec::as_slice)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget every entry
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Menu label for an entry: its first bytes in hex and its length
+pub fn entry_label(bytes: &[u8]) -> String {
+    let preview: Vec<String> = bytes
+        .iter()
+        .take(PREVIEW_BYTES)
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    let ellipsis = if bytes.len() > PREVIEW_BYTES {
+        " \u{2026}"
+    } else {
+        ""
+    };
+    format!("{}{} ({} bytes)", preview.join(" "), ellipsis, bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_dedupes_and_caps() {
+        let mut history = ClipboardHistory::default();
+        history.push(&[]);
+        history.push(&vec![0; MAX_CLIPBOARD_ENTRY_BYTES + 1]);
+        assert!(history.is_empty());
+
+        history.push(&[1]);
+        history.push(&[2, 2]);
+        history.push(&[1]);
+        let entries: Vec<_> = history.entries().collect();
+        assert_eq!(entries, [&[1][..], &[2, 2][..]]);
+
+        for i in 0..MAX_CLIPBOARD_HISTORY as u8 + 5 {
+            history.push(&[0xF0, i]);
+        }
+        assert_eq!(history.entries().count(), MAX_CLIPBOARD_HISTORY);
+        assert_eq!(
+            history.get(0),
+            Some(&[0xF0, MAX_CLIPBOARD_HISTORY as u8 + 4][..])
+        );
+
+        history.clear();
+        assert!(history.get(0).is_none());
+    }
+
+    #[test]
+    fn test_entry_label() {
+        assert_eq!(entry_label(&[0xDE, 0xAD]), "DE AD (2 bytes)");
+        assert_eq!(
+            entry_label(&[0; 9]),
+            "00 00 00 00 00 00 00 00 \u{2026} (9 bytes)"
+        );
+    }
+}
@@ -22,6 +22,7 @@ impl EditorState {
     pub fn set_cursor(&mut self, pos: usize) {
         self.cursor = pos.min(self.working.len().saturating_sub(1));
         self.nibble = NibblePosition::High;
+        self.eof_append_generation = None;
     }
 
     /// Move cursor by offset, clamping to valid range
@@ -0,0 +1,111 @@
+//! Summary of how far the working buffer has drifted from the original
+
+use super::EditorState;
+
+/// Cached status-bar text describing the difference from the original file,
+/// recomputed only when the edit generation changes
+#[derive(Default)]
+pub struct DiffSummary {
+    /// Edit generation the text was computed for (None = never computed)
+    generation: Option<u64>,
+    /// Formatted summary, e.g. "Changed: 1,204 (0.8%)"
+    text: String,
+}
+
+impl DiffSummary {
+    /// Recompute the summary if the buffer changed since the last call
+    pub fn update(&mut self, editor: &EditorState) {
+        let generation = editor.edit_generation();
+        if self.generation == Some(generation) {
+            return;
+        }
+        self.text = format_diff_summary(editor.original(), editor.working());
+        self.generation = Some(generation);
+    }
+
+    /// Drop the cached summary (e.g. when a new file is opened)
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The most recently computed summary text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Count differing bytes when lengths match; otherwise report the length delta
+fn format_diff_summary(original: &[u8], working: &[u8]) -> String {
+    if original.len() != working.len() {
+        let delta = working.len() as i64 - original.len() as i64;
+        return format!("Length: {:+} bytes", delta);
+    }
+
+    let changed = original.iter().zip(working).filter(|(a, b)| a != b).count();
+    let percent = if working.is_empty() {
+        0.0
+    } else {
+        changed as f64 * 100.0 / working.len() as f64
+    };
+    format!("Changed: {} ({:.1}%)", format_thousands(changed), percent)
+}
+
+/// Format a count with comma thousands separators (e.g. 1204 -> "1,204")
+fn format_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_thousands() {
+        assert_eq!(format_thousands(0), "0");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_thousands(1204), "1,204");
+        assert_eq!(format_thousands(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_diff_summary() {
+        let original = vec![0u8; 1000];
+        let mut working = original.clone();
+        working[1] = 1;
+        working[500] = 2;
+        assert_eq!(
+            format_diff_summary(&original, &working),
+            "Changed: 2 (0.2%)"
+        );
+        assert_eq!(
+            format_diff_summary(&original, &original[..990]),
+            "Length: -10 bytes"
+        );
+        assert_eq!(format_diff_summary(&[], &[1, 2]), "Length: +2 bytes");
+        assert_eq!(format_diff_summary(&[], &[]), "Changed: 0 (0.0%)");
+    }
+
+    #[test]
+    fn test_summary_cached_per_generation() {
+        let mut editor = EditorState::new(vec![0u8; 10]);
+        let mut summary = DiffSummary::default();
+        summary.update(&editor);
+        assert_eq!(summary.text(), "Changed: 0 (0.0%)");
+
+        editor.edit_byte(0, 0xFF);
+        summary.update(&editor);
+        assert_eq!(summary.text(), "Changed: 1 (10.0%)");
+
+        editor.insert_byte(0, 0xAA);
+        summary.update(&editor);
+        assert_eq!(summary.text(), "Length: +1 bytes");
+    }
+}
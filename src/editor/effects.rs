@@ -0,0 +1,356 @@
+//! Byte-level glitch effects applied over a range of the buffer
+//!
+//! Effects that can be dialed from subtle to extreme take an `intensity`
+//! in 0.0-1.0 (the global corruption intensity slider). Per-byte effects also
+//! take a `stride`: only every `stride`-th byte of the range is touched, so a
+//! stride of 3 hits a single channel of packed RGB data.
+
+use std::ops::Range;
+
+/// Word sizes offered by the swap-endianness effect
+pub const SWAP_WORD_SIZES: &[usize] = &[2, 4, 8];
+
+/// Minimal xorshift64* generator so effects don't need an RNG dependency
+pub struct XorShift64(u64);
+
+impl XorShift64 {
+    /// Create a generator; a zero seed is remapped since xorshift would stay at zero
+    pub fn new(seed: u64) -> Self {
+        Self(if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        })
+    }
+
+    /// Next pseudo-random 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Next pseudo-random value in 0.0..1.0
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Clamp `range` to the buffer so effects can't index out of bounds
+fn clamp_range(len: usize, range: Range<usize>) -> Range<usize> {
+    let end = range.end.min(len);
+    range.start.min(end)..end
+}
+
+/// Every `stride`-th byte of `bytes`, starting with the first (0 is treated as 1)
+fn strided(bytes: &mut [u8], stride: usize) -> impl Iterator<Item = &mut u8> {
+    bytes.iter_mut().step_by(stride.max(1))
+}
+
+/// Number of bits (0-8) an intensity maps to
+fn intensity_bits(intensity: f32) -> u32 {
+    (intensity.clamp(0.0, 1.0) * 8.0).round() as u32
+}
+
+/// Reverse byte order within every `word`-byte word of `range`.
+///
+/// Trailing bytes that don't fill a whole word are left untouched, as is
+/// any part of the range past the end of the buffer.
+pub fn swap_endian(buffer: &mut [u8], range: Range<usize>, word: usize) {
+    if word < 2 {
+        return;
+    }
+    let range = clamp_range(buffer.len(), range);
+    for chunk in buffer[range].chunks_exact_mut(word) {
+        chunk.reverse();
+    }
+}
+
+/// Flip one random bit in every `stride`-th byte of `range` with probability `intensity`
+pub fn random_flip(
+    buffer: &mut [u8],
+    range: Range<usize>,
+    stride: usize,
+    intensity: f32,
+    seed: u64,
+) {
+    let range = clamp_range(buffer.len(), range);
+    let mut rng = XorShift64::new(seed);
+    for byte in strided(&mut buffer[range], stride) {
+        if rng.next_f32() < intensity {
+            *byte ^= 1 << (rng.next_u64() % 8);
+        }
+    }
+}
+
+/// Flip one random bit in bytes of JPEG entropy-coded data with the given
+/// per-byte `probability`, without creating or damaging markers.
+///
+/// 0xFF bytes and the byte after each one (stuffing zero or RST index) are
+/// left alone, and flips that would produce a new 0xFF are skipped, so the
+/// scan's marker structure survives and the image keeps decoding more often.
+pub fn jpeg_scan_flip(buffer: &mut [u8], range: Range<usize>, probability: f32, seed: u64) {
+    let range = clamp_range(buffer.len(), range);
+    let mut rng = XorShift64::new(seed);
+    let mut after_ff = range.start > 0 && buffer[range.start - 1] == 0xFF;
+    for byte in &mut buffer[range] {
+        let is_ff = *byte == 0xFF;
+        if !is_ff && !after_ff && rng.next_f32() < probability {
+            let flipped = *byte ^ (1 << (rng.next_u64() % 8));
+            if flipped != 0xFF {
+                *byte = flipped;
+            }
+        }
+        after_ff = is_ff;
+    }
+}
+
+/// Rotate the bits of every `stride`-th byte in `range` left by 0-7 places,
+/// scaled by `intensity`
+pub fn rotate_bits(buffer: &mut [u8], range: Range<usize>, stride: usize, intensity: f32) {
+    let amount = (intensity.clamp(0.0, 1.0) * 7.0).round() as u32;
+    let range = clamp_range(buffer.len(), range);
+    for byte in strided(&mut buffer[range], stride) {
+        *byte = byte.rotate_left(amount);
+    }
+}
+
+/// XOR every `stride`-th byte in `range` with `key`, keeping only the low bits
+/// of the key that `intensity` allows (0% = no bits, 100% = the full key)
+pub fn xor_mask(buffer: &mut [u8], range: Range<usize>, stride: usize, key: u8, intensity: f32) {
+    let mask = ((1u16 << intensity_bits(intensity)) - 1) as u8;
+    let key = key & mask;
+    let range = clamp_range(buffer.len(), range);
+    for byte in strided(&mut buffer[range], stride) {
+        *byte ^= key;
+    }
+}
+
+/// Split `range` into rows of `row_width` bytes and rotate row `i` right by
+/// `i * step` bytes, so each row is pushed a little further sideways than the
+/// one before: the classic horizontal-tear glitch. A trailing partial row is
+/// rotated within its own length.
+pub fn scanline_shift(buffer: &mut [u8], range: Range<usize>, row_width: usize, step: usize) {
+    if row_width == 0 {
+        return;
+    }
+    let range = clamp_range(buffer.len(), range);
+    for (i, row) in buffer[range].chunks_mut(row_width).enumerate() {
+        let len = row.len();
+        row.rotate_right((i % len) * (step % len) % len);
+    }
+}
+
+/// Shuffle the bytes of `range` into a random order (Fisher-Yates).
+/// The byte values are kept; only their positions change.
+pub fn shuffle(buffer: &mut [u8], range: Range<usize>, seed: u64) {
+    let range = clamp_range(buffer.len(), range);
+    let bytes = &mut buffer[range];
+    let mut rng = XorShift64::new(seed);
+    for i in (1..bytes.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        bytes.swap(i, j);
+    }
+}
+
+/// Fill `range` with a linear ramp of byte values from `start` at its first
+/// byte to `end` at its last, so pixel data turns into smooth bands
+pub fn gradient_fill(buffer: &mut [u8], range: Range<usize>, start: u8, end: u8) {
+    let range = clamp_range(buffer.len(), range);
+    let steps = range.len().saturating_sub(1).max(1) as f64;
+    let (start, end) = (f64::from(start), f64::from(end));
+    for (i, byte) in buffer[range].iter_mut().enumerate() {
+        *byte = (start + (end - start) * i as f64 / steps).round() as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_endian_words() {
+        let mut data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+        swap_endian(&mut data, 0..9, 4);
+        // Trailing byte 9 doesn't fill a word and is untouched
+        assert_eq!(data, vec![4, 3, 2, 1, 8, 7, 6, 5, 9]);
+
+        let mut data = vec![0xAA, 1, 2, 3, 4];
+        swap_endian(&mut data, 1..5, 2);
+        assert_eq!(data, vec![0xAA, 2, 1, 4, 3]);
+    }
+
+    #[test]
+    fn test_swap_endian_twice_restores_original() {
+        let original: Vec<u8> = (0..37).collect();
+        for &word in SWAP_WORD_SIZES {
+            let mut data = original.clone();
+            swap_endian(&mut data, 3..37, word);
+            assert_ne!(data, original);
+            swap_endian(&mut data, 3..37, word);
+            assert_eq!(data, original);
+        }
+    }
+
+    #[test]
+    fn test_random_flip_intensity() {
+        let original = vec![0u8; 1000];
+
+        let mut data = original.clone();
+        random_flip(&mut data, 0..1000, 1, 0.0, 42);
+        assert_eq!(data, original);
+
+        let mut data = original.clone();
+        random_flip(&mut data, 0..1000, 1, 1.0, 42);
+        // Every byte has exactly one bit flipped
+        assert!(data.iter().all(|b| b.count_ones() == 1));
+
+        let mut data = original.clone();
+        random_flip(&mut data, 0..1000, 1, 0.1, 42);
+        let flipped = data.iter().filter(|&&b| b != 0).count();
+        assert!((50..150).contains(&flipped), "flipped {}", flipped);
+    }
+
+    #[test]
+    fn test_jpeg_scan_flip_preserves_markers() {
+        // Scan data with a stuffed FF 00, an RST marker, and bytes one flip away from FF
+        let mut data = vec![0x12, 0xFF, 0x00, 0x7F, 0xFF, 0xD0, 0xFE, 0xEF];
+        data.extend(std::iter::repeat_n(0x00, 100));
+        let original = data.clone();
+
+        jpeg_scan_flip(&mut data, 0..original.len(), 1.0, 7);
+        assert_eq!(&data[1..3], &original[1..3]);
+        assert_eq!(&data[4..6], &original[4..6]);
+        // No new 0xFF bytes appeared
+        let ff_count = |d: &[u8]| d.iter().filter(|&&b| b == 0xFF).count();
+        assert_eq!(ff_count(&data), ff_count(&original));
+        // Ordinary bytes were flipped
+        assert!(data[8..].iter().all(|b| b.count_ones() == 1));
+
+        let mut data = original.clone();
+        jpeg_scan_flip(&mut data, 0..original.len(), 0.0, 7);
+        assert_eq!(data, original);
+    }
+
+    #[test]
+    fn test_jpeg_scan_flip_respects_preceding_ff() {
+        // A range starting right after 0xFF must not touch the marker's second byte
+        let mut data = vec![0xFF, 0x00, 0x00];
+        jpeg_scan_flip(&mut data, 1..3, 1.0, 3);
+        assert_eq!(data[1], 0x00);
+        assert_ne!(data[2], 0x00);
+    }
+
+    #[test]
+    fn test_rotate_bits_scales_with_intensity() {
+        let mut data = vec![0b0000_0001];
+        rotate_bits(&mut data, 0..1, 1, 0.0);
+        assert_eq!(data, vec![0b0000_0001]);
+        rotate_bits(&mut data, 0..1, 1, 1.0);
+        assert_eq!(data, vec![0b1000_0000]);
+        rotate_bits(&mut data, 0..1, 1, 0.15);
+        assert_eq!(data, vec![0b0000_0001]);
+    }
+
+    #[test]
+    fn test_xor_mask_masks_fewer_bits_at_low_intensity() {
+        let mut data = vec![0x00, 0x00];
+        xor_mask(&mut data, 0..2, 1, 0xFF, 1.0);
+        assert_eq!(data, vec![0xFF, 0xFF]);
+
+        let mut data = vec![0x00];
+        xor_mask(&mut data, 0..1, 1, 0xFF, 0.5);
+        assert_eq!(data, vec![0x0F]);
+
+        let mut data = vec![0x5A];
+        xor_mask(&mut data, 0..1, 1, 0xFF, 0.0);
+        assert_eq!(data, vec![0x5A]);
+    }
+
+    #[test]
+    fn test_stride_touches_every_nth_byte() {
+        let mut data = vec![0x00; 7];
+        xor_mask(&mut data, 1..7, 3, 0xFF, 1.0);
+        assert_eq!(data, vec![0x00, 0xFF, 0x00, 0x00, 0xFF, 0x00, 0x00]);
+
+        let mut data = vec![0u8; 30];
+        random_flip(&mut data, 0..30, 3, 1.0, 9);
+        for (i, b) in data.iter().enumerate() {
+            assert_eq!(b.count_ones(), u32::from(i % 3 == 0), "byte {}", i);
+        }
+
+        // A zero stride behaves like stride 1
+        let mut data = vec![0b0000_0001; 2];
+        rotate_bits(&mut data, 0..2, 0, 1.0);
+        assert_eq!(data, vec![0b1000_0000; 2]);
+    }
+
+    #[test]
+    fn test_scanline_shift_rotates_rows_progressively() {
+        let mut data: Vec<u8> = (0..14).collect();
+        scanline_shift(&mut data, 1..14, 4, 1);
+        assert_eq!(
+            data,
+            vec![
+                0, // outside the range
+                1, 2, 3, 4, // row 0: unshifted
+                8, 5, 6, 7, // row 1: shifted by 1
+                11, 12, 9, 10, // row 2: shifted by 2
+                13, // partial row 3: shift wraps within its single byte
+            ]
+        );
+
+        // Zero width is a no-op
+        let before = data.clone();
+        scanline_shift(&mut data, 0..14, 0, 3);
+        assert_eq!(data, before);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_bytes_and_stays_in_range() {
+        let original: Vec<u8> = (0..64).collect();
+        let mut data = original.clone();
+        shuffle(&mut data, 8..56, 42);
+        assert_eq!(&data[..8], &original[..8]);
+        assert_eq!(&data[56..], &original[56..]);
+        assert_ne!(data, original);
+
+        let mut sorted = data[8..56].to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, &original[8..56]);
+
+        // Same seed, same order
+        let mut again = original.clone();
+        shuffle(&mut again, 8..56, 42);
+        assert_eq!(again, data);
+    }
+
+    #[test]
+    fn test_swap_endian_clamps_range() {
+        let mut data = vec![1, 2, 3];
+        swap_endian(&mut data, 0..100, 2);
+        assert_eq!(data, vec![2, 1, 3]);
+        swap_endian(&mut data, 50..100, 2);
+        assert_eq!(data, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_gradient_fill_hits_both_endpoints() {
+        let mut data = vec![0xAA; 12];
+        gradient_fill(&mut data, 1..11, 0x10, 0xF0);
+        assert_eq!(data[1], 0x10);
+        assert_eq!(data[10], 0xF0);
+        assert!(data[1..11].windows(2).all(|w| w[0] <= w[1]));
+        // Bytes outside the range are untouched
+        assert_eq!((data[0], data[11]), (0xAA, 0xAA));
+
+        // Descending ramps work too, and a single byte takes the start value
+        let mut data = vec![0; 5];
+        gradient_fill(&mut data, 0..5, 200, 0);
+        assert_eq!(data, vec![200, 150, 100, 50, 0]);
+        let mut data = vec![0; 1];
+        gradient_fill(&mut data, 0..1, 7, 9);
+        assert_eq!(data, vec![7]);
+    }
+}
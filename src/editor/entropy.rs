@@ -0,0 +1,151 @@
+//! Sliding-window Shannon entropy map of the working buffer
+//!
+//! Low-entropy regions (headers, padding, flat colors) tend to be structural,
+//! while high-entropy regions (compressed streams, noisy pixel data) are where
+//! glitches get interesting. The map stores one normalized value per block of
+//! `ENTROPY_BLOCK_SIZE` bytes and is recomputed only when the edit generation
+//! changes.
+
+/// Number of bytes covered by each entry in the map
+pub const ENTROPY_BLOCK_SIZE: usize = 16;
+
+/// Width of the sliding window used to measure entropy around each block
+pub const ENTROPY_WINDOW: usize = 256;
+
+/// Cached entropy values for a buffer at a given edit generation
+#[derive(Default)]
+pub struct EntropyMap {
+    /// Edit generation the values were computed for (None = never computed)
+    generation: Option<u64>,
+    /// Buffer length the values were computed for
+    len: usize,
+    /// Normalized entropy (0.0-1.0) per block
+    values: Vec<f32>,
+}
+
+impl EntropyMap {
+    /// Recompute the map if the buffer changed since the last computation
+    pub fn update(&mut self, data: &[u8], generation: u64) {
+        if self.generation == Some(generation) && self.len == data.len() {
+            return;
+        }
+        self.values = compute_entropy_blocks(data);
+        self.generation = Some(generation);
+        self.len = data.len();
+    }
+
+    /// Drop cached values (e.g. when a new file is opened)
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Normalized entropy (0.0-1.0) of the window around a byte offset
+    pub fn value_at(&self, offset: usize) -> Option<f32> {
+        self.values.get(offset / ENTROPY_BLOCK_SIZE).copied()
+    }
+}
+
+/// `count * log2(count)`, the per-symbol term of the entropy sum
+fn count_term(count: u32) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        let c = count as f64;
+        c * c.log2()
+    }
+}
+
+/// Compute normalized entropy for each block using a window centered on it.
+///
+/// Uses H = log2(N) - (1/N) * sum(c * log2(c)), keeping the sum up to date as
+/// bytes enter and leave the window so the whole pass is O(n).
+fn compute_entropy_blocks(data: &[u8]) -> Vec<f32> {
+    let block_count = data.len().div_ceil(ENTROPY_BLOCK_SIZE);
+    let mut values = Vec::with_capacity(block_count);
+    let mut counts = [0u32; 256];
+    let mut term_sum = 0.0_f64;
+    let (mut win_start, mut win_end) = (0usize, 0usize);
+
+    for block in 0..block_count {
+        let center = block * ENTROPY_BLOCK_SIZE + ENTROPY_BLOCK_SIZE / 2;
+        let start = center.saturating_sub(ENTROPY_WINDOW / 2);
+        let end = (start + ENTROPY_WINDOW).min(data.len());
+
+        while win_end < end {
+            let c = &mut counts[data[win_end] as usize];
+            term_sum += count_term(*c + 1) - count_term(*c);
+            *c += 1;
+            win_end += 1;
+        }
+        while win_start < start {
+            let c = &mut counts[data[win_start] as usize];
+            term_sum += count_term(*c - 1) - count_term(*c);
+            *c -= 1;
+            win_start += 1;
+        }
+
+        let n = (win_end - win_start) as f64;
+        let entropy = if n > 0.0 {
+            n.log2() - term_sum / n
+        } else {
+            0.0
+        };
+        values.push((entropy / 8.0).clamp(0.0, 1.0) as f32);
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_data_has_zero_entropy() {
+        let values = compute_entropy_blocks(&[0xAA; 1024]);
+        assert_eq!(values.len(), 1024 / ENTROPY_BLOCK_SIZE);
+        assert!(values.iter().all(|&v| v < 1e-6));
+    }
+
+    #[test]
+    fn test_all_byte_values_have_max_entropy() {
+        let data: Vec<u8> = (0..1024).map(|i| (i % 256) as u8).collect();
+        let values = compute_entropy_blocks(&data);
+        // A full 256-byte window containing every value once is 8 bits/byte
+        let mid = values[values.len() / 2];
+        assert!((mid - 1.0).abs() < 1e-4, "got {}", mid);
+    }
+
+    #[test]
+    fn test_mixed_regions_are_distinguished() {
+        let mut data = vec![0u8; 1024];
+        data.extend((0..1024).map(|i| (i * 7 % 256) as u8));
+        let mut map = EntropyMap::default();
+        map.update(&data, 1);
+        assert!(map.value_at(100).unwrap() < 0.1);
+        assert!(map.value_at(1800).unwrap() > 0.9);
+        assert!(map.value_at(data.len()).is_none());
+    }
+
+    #[test]
+    fn test_update_is_cached_per_generation() {
+        let mut map = EntropyMap::default();
+        map.update(&[0u8; 64], 3);
+        assert!(map.value_at(0).unwrap() < 1e-6);
+
+        // Same generation and length: values are not recomputed
+        let noisy: Vec<u8> = (0..64).collect();
+        map.update(&noisy, 3);
+        assert!(map.value_at(0).unwrap() < 1e-6);
+
+        map.update(&noisy, 4);
+        assert!(map.value_at(0).unwrap() > 0.5);
+    }
+
+    #[test]
+    fn test_empty_buffer() {
+        let mut map = EntropyMap::default();
+        map.update(&[], 0);
+        assert!(map.value_at(0).is_none());
+    }
+}
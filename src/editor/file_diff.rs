@@ -0,0 +1,168 @@
+//! Byte-level comparison of the working buffer against another file
+//!
+//! Produces the list of contiguous differing ranges, navigable like search
+//! matches. Handy for seeing what a round trip through another tool changed.
+
+use std::ops::Range;
+
+/// Contiguous runs of offsets where `a` and `b` differ. Lengths must match.
+pub fn diff_ranges(a: &[u8], b: &[u8]) -> Result<Vec<Range<usize>>, String> {
+    if a.len() != b.len() {
+        return Err(format!(
+            "File sizes differ ({} vs {} bytes); only same-size files can be compared",
+            a.len(),
+            b.len()
+        ));
+    }
+
+    let mut ranges = Vec::new();
+    let mut run_start = None;
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        match (x != y, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                ranges.push(start..i);
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = run_start {
+        ranges.push(start..a.len());
+    }
+    Ok(ranges)
+}
+
+/// State for the "Compare with File" tool
+#[derive(Default)]
+pub struct FileDiffState {
+    /// Whether the results window is visible
+    pub dialog_open: bool,
+    /// Name of the file being compared against (for display)
+    pub other_name: String,
+    /// Bytes of the file being compared against, kept for re-comparing
+    other: Vec<u8>,
+    /// Differing ranges, sorted and non-overlapping
+    ranges: Vec<Range<usize>>,
+    /// Index of the range last jumped to
+    current: Option<usize>,
+    /// Edit generation the ranges were computed at
+    compared_at_generation: u64,
+    /// Why the last comparison failed, if it did
+    pub error: Option<String>,
+}
+
+impl FileDiffState {
+    /// Start comparing against a newly loaded file
+    pub fn load(&mut self, name: String, other: Vec<u8>, working: &[u8], generation: u64) {
+        self.other_name = name;
+        self.other = other;
+        self.dialog_open = true;
+        self.recompare(working, generation);
+    }
+
+    /// Recompute the differences against the stored file
+    pub fn recompare(&mut self, working: &[u8], generation: u64) {
+        self.current = None;
+        self.compared_at_generation = generation;
+        match diff_ranges(working, &self.other) {
+            Ok(ranges) => {
+                self.ranges = ranges;
+                self.error = None;
+            }
+            Err(e) => {
+                self.ranges.clear();
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// Forget the comparison (e.g. when switching documents)
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Differing ranges from the last comparison
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Total number of differing bytes
+    pub fn differing_bytes(&self) -> usize {
+        self.ranges.iter().map(|r| r.len()).sum()
+    }
+
+    /// Whether the working buffer changed since the comparison ran
+    pub fn is_stale(&self, current_generation: u64) -> bool {
+        !self.other.is_empty() && self.compared_at_generation != current_generation
+    }
+
+    /// Whether `offset` lies in a differing range
+    pub fn contains(&self, offset: usize) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= offset);
+        self.ranges.get(index).is_some_and(|r| r.contains(&offset))
+    }
+
+    /// Index of the range last jumped to
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Make range `index` current and return it
+    pub fn select(&mut self, index: usize) -> Option<Range<usize>> {
+        let range = self.ranges.get(index)?.clone();
+        self.current = Some(index);
+        Some(range)
+    }
+
+    /// Step to the next (or previous) range, wrapping around
+    pub fn step(&mut self, forward: bool) -> Option<Range<usize>> {
+        let len = self.ranges.len();
+        if len == 0 {
+            return None;
+        }
+        let index = match (self.current, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+        };
+        self.select(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_ranges() {
+        let a = [0, 1, 2, 3, 4, 5, 6, 7];
+        let b = [0, 9, 9, 3, 4, 5, 6, 9];
+        assert_eq!(diff_ranges(&a, &b), Ok(vec![1..3, 7..8]));
+        assert_eq!(diff_ranges(&a, &a), Ok(vec![]));
+        assert!(diff_ranges(&a, &a[..4]).is_err());
+    }
+
+    #[test]
+    fn test_navigation_and_lookup() {
+        let mut state = FileDiffState::default();
+        state.load("other.bmp".into(), vec![9, 1, 9, 9, 4], &[0, 1, 2, 3, 4], 7);
+        assert_eq!(state.ranges(), &[0..1, 2..4]);
+        assert_eq!(state.differing_bytes(), 3);
+        assert!(state.contains(3));
+        assert!(!state.contains(1));
+        assert!(!state.contains(4));
+
+        assert_eq!(state.step(false), Some(2..4));
+        assert_eq!(state.step(true), Some(0..1));
+        assert_eq!(state.step(true), Some(2..4));
+        assert_eq!(state.current(), Some(1));
+
+        assert!(!state.is_stale(7));
+        assert!(state.is_stale(8));
+        state.recompare(&[9, 1, 9, 9, 4], 8);
+        assert!(state.ranges().is_empty());
+        assert_eq!(state.step(true), None);
+    }
+}
@@ -7,6 +7,16 @@ pub struct GoToOffsetState {
     pub dialog_open: bool,
     /// The user's input text (can be decimal or hex with 0x prefix)
     pub input_text: String,
+    /// Whether the dialog shows a second input for selecting a range
+    pub select_range: bool,
+    /// Inclusive end offset of the range to select (only used in range mode)
+    pub end_text: String,
+    /// Byte value to jump to, as typed (hex)
+    pub byte_text: String,
+    /// Last byte value jumped to, repeated by the next/previous byte shortcuts
+    pub last_byte: Option<u8>,
+    /// Position of the heading's "jump to percentage" slider (0-100)
+    pub percent: f32,
     /// Error message for invalid input
     pub error: Option<String>,
 }
@@ -16,6 +26,7 @@ impl GoToOffsetState {
     pub fn open_dialog(&mut self) {
         self.dialog_open = true;
         self.input_text.clear();
+        self.end_text.clear();
         self.error = None;
     }
 
@@ -60,6 +71,43 @@ pub fn parse_offset(input: &str) -> Result<usize, String> {
     })
 }
 
+/// Parse a single byte value written in hex ("FF" or "0xFF")
+pub fn parse_byte_value(input: &str) -> Result<u8, String> {
+    let trimmed = input.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    if digits.is_empty() {
+        return Err("Please enter a byte value".to_string());
+    }
+    u8::from_str_radix(digits, 16)
+        .map_err(|_| format!("Invalid byte value '{}' (use hex 00-FF)", trimmed))
+}
+
+/// Offset at `percent` (0-100) of a buffer of length `len`, clamped to the last byte
+pub fn percent_to_offset(percent: f32, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let fraction = (percent.clamp(0.0, 100.0) / 100.0) as f64;
+    ((fraction * len as f64) as usize).min(len - 1)
+}
+
+/// Parse a start/end pair into an end-exclusive selection range.
+/// The end offset is inclusive, matching how byte ranges are usually written in format specs.
+pub fn parse_range(start_input: &str, end_input: &str) -> Result<(usize, usize), String> {
+    let start = parse_offset(start_input)?;
+    let end = parse_offset(end_input)?;
+    if end < start {
+        return Err(format!(
+            "End offset 0x{:X} is before start offset 0x{:X}",
+            end, start
+        ));
+    }
+    Ok((start, end + 1))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +140,34 @@ mod tests {
         assert!(parse_offset("-1").is_err());
     }
 
+    #[test]
+    fn test_percent_to_offset() {
+        assert_eq!(percent_to_offset(0.0, 1000), 0);
+        assert_eq!(percent_to_offset(50.0, 1000), 500);
+        assert_eq!(percent_to_offset(100.0, 1000), 999); // clamped to last byte
+        assert_eq!(percent_to_offset(150.0, 1000), 999);
+        assert_eq!(percent_to_offset(-5.0, 1000), 0);
+        assert_eq!(percent_to_offset(50.0, 0), 0);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("0x10", "0x1F"), Ok((0x10, 0x20)));
+        assert_eq!(parse_range("5", "5"), Ok((5, 6))); // single byte
+        assert!(parse_range("0x20", "0x10").is_err()); // reversed
+        assert!(parse_range("0x10", "").is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_value() {
+        assert_eq!(parse_byte_value("FF"), Ok(0xFF));
+        assert_eq!(parse_byte_value(" 0x0a "), Ok(0x0A));
+        assert_eq!(parse_byte_value("7"), Ok(0x07));
+        assert!(parse_byte_value("").is_err());
+        assert!(parse_byte_value("100").is_err());
+        assert!(parse_byte_value("GG").is_err());
+    }
+
     #[test]
     fn test_dialog_state() {
         let mut state = GoToOffsetState::default();
@@ -103,11 +179,13 @@ mod tests {
 
         // Open dialog
         state.input_text = "old value".to_string();
+        state.end_text = "old end".to_string();
         state.error = Some("old error".to_string());
         state.open_dialog();
 
         assert!(state.dialog_open);
         assert!(state.input_text.is_empty()); // cleared
+        assert!(state.end_text.is_empty()); // cleared
         assert!(state.error.is_none()); // cleared
 
         // Close dialog
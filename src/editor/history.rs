@@ -3,6 +3,8 @@
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+use super::bookmarks::{Bookmark, ClampedBookmark};
+
 /// Maximum number of operations to keep in history
 const MAX_HISTORY_SIZE: usize = 1000;
 
@@ -26,10 +28,31 @@ pub enum EditOperation {
     },
     /// Insert bytes at an offset (buffer grows)
     InsertBytes { offset: usize, values: Vec<u8> },
-    /// Delete bytes at an offset (buffer shrinks)
-    DeleteBytes { offset: usize, values: Vec<u8> },
+    /// Delete bytes at an offset (buffer shrinks). `clamped` holds the
+    /// bookmarks the deletion moved to `offset`, restored on undo.
+    DeleteBytes {
+        offset: usize,
+        values: Vec<u8>,
+        clamped: Vec<ClampedBookmark>,
+    },
     /// A group of operations treated as a single atomic undo/redo unit
     Group(Vec<EditOperation>),
+    /// A bookmark was added (the bytes are untouched)
+    AddBookmark(Bookmark),
+    /// A bookmark was removed (the bytes are untouched)
+    RemoveBookmark(Bookmark),
+}
+
+impl EditOperation {
+    /// Whether undoing/redoing this changes the working buffer (bookmark-only
+    /// operations don't)
+    pub fn changes_bytes(&self) -> bool {
+        match self {
+            Self::AddBookmark(_) | Self::RemoveBookmark(_) => false,
+            Self::Group(ops) => ops.iter().any(Self::changes_bytes),
+            _ => true,
+        }
+    }
 }
 
 /// Try to coalesce a new operation with an existing one
@@ -83,10 +106,12 @@ fn try_coalesce(existing: &mut EditOperation, new: &EditOperation) -> bool {
             false
         }
 
-        // InsertBytes, DeleteBytes, and Group never coalesce
+        // InsertBytes, DeleteBytes, Group, and bookmark changes never coalesce
         EditOperation::InsertBytes { .. }
         | EditOperation::DeleteBytes { .. }
-        | EditOperation::Group(_) => false,
+        | EditOperation::Group(_)
+        | EditOperation::AddBookmark(_)
+        | EditOperation::RemoveBookmark(_) => false,
 
         // Extend a range with an adjacent single-byte edit
         EditOperation::Range {
@@ -198,6 +223,16 @@ impl History {
     pub fn can_redo(&self) -> bool {
         !self.redo_stack.is_empty()
     }
+
+    /// Number of operations that can be undone
+    pub fn undo_count(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Number of operations that can be redone
+    pub fn redo_count(&self) -> usize {
+        self.redo_stack.len()
+    }
 }
 
 impl Default for History {
@@ -220,6 +255,32 @@ fn count_undos(history: &mut History) -> usize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_undo_redo_counts() {
+        let mut history = History::new();
+        assert_eq!(history.undo_count(), 0);
+        assert_eq!(history.redo_count(), 0);
+
+        // Non-adjacent offsets so the edits are not coalesced
+        for offset in [0, 10, 20] {
+            history.push(EditOperation::Single {
+                offset,
+                old_value: 0,
+                new_value: 1,
+            });
+        }
+        assert_eq!(history.undo_count(), 3);
+
+        history.undo();
+        history.undo();
+        assert_eq!(history.undo_count(), 1);
+        assert_eq!(history.redo_count(), 2);
+
+        history.redo();
+        assert_eq!(history.undo_count(), 2);
+        assert_eq!(history.redo_count(), 1);
+    }
+
     #[test]
     fn test_push_and_undo() {
         let mut history = History::new();
@@ -483,6 +544,7 @@ mod tests {
         history.push(EditOperation::DeleteBytes {
             offset: 0,
             values: vec![0xAA],
+            clamped: Vec::new(),
         });
 
         // Push an adjacent Single edit — should NOT coalesce with DeleteBytes
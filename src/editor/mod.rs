@@ -1,14 +1,22 @@
 //! Editor module: buffer management, history, and edit operations
 
+pub mod analysis;
 pub mod bookmarks;
 pub mod buffer;
+pub mod checksum;
+pub mod clipboard_history;
 mod cursor;
+pub mod diff_stats;
+pub mod effects;
+pub mod entropy;
+pub mod file_diff;
 pub mod go_to_offset;
 mod history;
 mod modes;
 pub mod savepoints;
 pub mod search;
 mod selection;
+pub mod text_encoding;
 
 pub use buffer::EditorState;
 pub use go_to_offset::GoToOffsetState;
@@ -41,6 +41,37 @@ impl EditorState {
         self.write_mode
     }
 
+    /// Set the write mode
+    pub fn set_write_mode(&mut self, mode: WriteMode) {
+        self.write_mode = mode;
+    }
+
+    /// Let typing past the last byte in Overwrite mode append new bytes
+    pub fn set_grow_at_eof(&mut self, grow: bool) {
+        self.grow_at_eof = grow;
+    }
+
+    /// Whether the next typed byte is appended: the last byte was just typed
+    /// over with growing on, and nothing was edited or moved since
+    fn eof_append_pending(&self) -> bool {
+        self.grow_at_eof
+            && self.eof_append_generation == Some(self.edit_generation())
+            && self.cursor + 1 == self.working.len()
+    }
+
+    /// After typing in Overwrite mode, arm the append if the cursor is stuck on the last byte
+    fn arm_eof_append(&mut self) {
+        self.eof_append_generation = (self.grow_at_eof && self.cursor + 1 == self.working.len())
+            .then(|| self.edit_generation());
+    }
+
+    /// Append a typed byte to the buffer and move the cursor onto it
+    fn append_typed_byte(&mut self, value: u8) {
+        let end = self.working.len();
+        self.insert_byte(end, value);
+        self.cursor = end;
+    }
+
     /// Toggle between Insert and Overwrite write modes
     pub fn toggle_write_mode(&mut self) {
         self.write_mode = match self.write_mode {
@@ -51,7 +82,8 @@ impl EditorState {
 
     /// Edit a nibble respecting the current write mode
     ///
-    /// In Overwrite mode, delegates to `edit_nibble()`.
+    /// In Overwrite mode, delegates to `edit_nibble()`; with growing on, a
+    /// high nibble typed after the last byte was completed appends a new byte.
     /// In Insert mode:
     /// - High nibble: inserts a new byte with `nibble << 4`, sets nibble to Low
     /// - Low nibble: overwrites the low nibble of the just-inserted byte, advances cursor
@@ -61,7 +93,18 @@ impl EditorState {
             return false;
         }
         match self.write_mode {
-            WriteMode::Overwrite => self.edit_nibble(nibble_value),
+            WriteMode::Overwrite => {
+                if self.nibble == NibblePosition::High && self.eof_append_pending() {
+                    self.append_typed_byte(nibble_value << 4);
+                    self.nibble = NibblePosition::Low;
+                    return false;
+                }
+                let advanced = self.edit_nibble(nibble_value);
+                if advanced {
+                    self.arm_eof_append();
+                }
+                advanced
+            }
             WriteMode::Insert => match self.nibble {
                 NibblePosition::High => {
                     let value = nibble_value << 4;
@@ -74,13 +117,12 @@ impl EditorState {
                         let current = self.working[self.cursor];
                         let new_value = (current & 0xF0) | nibble_value;
                         if current != new_value {
-                            self.history.push(EditOperation::Single {
+                            self.working[self.cursor] = new_value;
+                            self.record_operation(EditOperation::Single {
                                 offset: self.cursor,
                                 old_value: current,
                                 new_value,
                             });
-                            self.working[self.cursor] = new_value;
-                            self.modified = true;
                         }
                     }
                     self.nibble = NibblePosition::High;
@@ -95,7 +137,8 @@ impl EditorState {
 
     /// Edit an ASCII character respecting the current write mode
     ///
-    /// In Overwrite mode, delegates to `edit_ascii()`.
+    /// In Overwrite mode, delegates to `edit_ascii()`, or appends once the
+    /// last byte was typed over with growing on.
     /// In Insert mode, inserts a new byte and advances cursor.
     #[must_use = "returns whether the character was accepted"]
     pub fn edit_ascii_with_mode(&mut self, ch: char) -> bool {
@@ -103,7 +146,15 @@ impl EditorState {
             return false;
         }
         match self.write_mode {
-            WriteMode::Overwrite => self.edit_ascii(ch),
+            WriteMode::Overwrite => {
+                if self.eof_append_pending() {
+                    self.append_typed_byte(ch as u8);
+                } else if !self.edit_ascii(ch) {
+                    return false;
+                }
+                self.arm_eof_append();
+                true
+            }
             WriteMode::Insert => {
                 let value = ch as u8;
                 self.insert_byte(self.cursor, value);
@@ -218,6 +269,17 @@ mod tests {
         assert_eq!(editor.write_mode(), WriteMode::Overwrite);
     }
 
+    #[test]
+    fn test_set_write_mode() {
+        let mut editor = EditorState::new(vec![0x00]);
+        editor.set_write_mode(WriteMode::Insert);
+        assert_eq!(editor.write_mode(), WriteMode::Insert);
+        editor.set_write_mode(WriteMode::Insert);
+        assert_eq!(editor.write_mode(), WriteMode::Insert);
+        editor.set_write_mode(WriteMode::Overwrite);
+        assert_eq!(editor.write_mode(), WriteMode::Overwrite);
+    }
+
     #[test]
     fn test_edit_nibble_with_mode_overwrite() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -272,6 +334,20 @@ mod tests {
         assert_eq!(editor.working(), &data);
     }
 
+    #[test]
+    fn test_edit_nibble_insert_bumps_generation() {
+        let mut editor = EditorState::new(vec![0x00, 0x01]);
+        editor.toggle_write_mode();
+
+        let _ = editor.edit_nibble_with_mode(0xA);
+        let after_insert = editor.edit_generation();
+        assert!(after_insert > 0);
+
+        // Low nibble completes the inserted byte in place
+        let _ = editor.edit_nibble_with_mode(0xB);
+        assert!(editor.edit_generation() > after_insert);
+    }
+
     #[test]
     fn test_edit_ascii_with_mode_overwrite() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -295,6 +371,52 @@ mod tests {
         assert_eq!(editor.working(), &[b'H', 0x00, 0x01, 0x02, 0x03]);
     }
 
+    #[test]
+    fn test_overwrite_grows_at_eof_and_undoes_per_byte() {
+        let mut editor = EditorState::new(vec![0x00, 0x01, 0x02]);
+        editor.set_edit_mode(EditMode::Ascii);
+        editor.set_cursor(2);
+
+        // Without growing, typing at the last byte keeps overwriting it
+        assert!(editor.edit_ascii_with_mode('a'));
+        assert!(editor.edit_ascii_with_mode('b'));
+        assert_eq!(editor.working(), &[0x00, 0x01, b'b']);
+
+        editor.set_grow_at_eof(true);
+        editor.set_cursor(2);
+        assert!(editor.edit_ascii_with_mode('x'));
+        assert!(editor.edit_ascii_with_mode('y'));
+        assert!(editor.edit_ascii_with_mode('z'));
+        assert_eq!(editor.working(), &[0x00, 0x01, b'x', b'y', b'z']);
+        assert_eq!(editor.cursor(), 4);
+
+        // Each appended byte is its own undo step, not merged into the overwrite
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x00, 0x01, b'x', b'y']);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x00, 0x01, b'x']);
+        assert_eq!(editor.cursor(), 2);
+
+        // Hex typing appends a byte per pair of nibbles
+        editor.set_edit_mode(EditMode::Hex);
+        editor.set_cursor(2);
+        for nibble in [0xA, 0xB, 0xC, 0xD] {
+            let _ = editor.edit_nibble_with_mode(nibble);
+        }
+        assert_eq!(editor.working(), &[0x00, 0x01, 0xAB, 0xCD]);
+        assert!(editor.undo());
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0x00, 0x01, 0xAB]);
+
+        // Moving the cursor disarms the append: typing overwrites again
+        editor.set_cursor(2);
+        editor.set_edit_mode(EditMode::Ascii);
+        assert!(editor.edit_ascii_with_mode('q'));
+        editor.set_cursor(2);
+        assert!(editor.edit_ascii_with_mode('r'));
+        assert_eq!(editor.working(), &[0x00, 0x01, b'r']);
+    }
+
     #[test]
     fn test_handle_backspace_overwrite() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -19,12 +19,15 @@
 //! This approach:
 //! - Uses minimal memory (only stores changes, not full copies)
 //! - Allows restoring to any point in the chain
-//! - Requires careful handling when deleting non-leaf save points
+//! - Requires careful handling when deleting non-leaf save points: the deleted
+//!   diff is merged into its successor so later save points still resolve to
+//!   the same bytes
 
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// A single byte change in a diff
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ByteChange {
     pub offset: usize,
     pub old_value: u8,
@@ -32,7 +35,7 @@ pub struct ByteChange {
 }
 
 /// A named snapshot of the editing state
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SavePoint {
     /// Unique identifier for this save point
     pub id: u64,
@@ -40,6 +43,9 @@ pub struct SavePoint {
     /// User-provided name for this save point
     pub name: String,
 
+    /// Free-form notes about this save point (empty if none)
+    pub note: String,
+
     /// Incremental diff from the previous save point (or original file if first)
     /// This represents changes that were made AFTER the previous save point
     pub diff: Vec<ByteChange>,
@@ -48,7 +54,12 @@ pub struct SavePoint {
 impl SavePoint {
     /// Create a new save point with the given name and diff
     pub fn new(id: u64, name: String, diff: Vec<ByteChange>) -> Self {
-        Self { id, name, diff }
+        Self {
+            id,
+            name,
+            note: String::new(),
+            diff,
+        }
     }
 }
 
@@ -80,6 +91,25 @@ impl SavePointManager {
         }
     }
 
+    /// Rebuild a manager from save points taken (in order) over `original_bytes`,
+    /// e.g. when loading a saved session
+    pub fn from_save_points(save_points: Vec<SavePoint>, original_bytes: &[u8]) -> Self {
+        let mut manager = Self::new(original_bytes);
+        manager.next_id = save_points.iter().map(|sp| sp.id + 1).max().unwrap_or(1);
+        manager.id_to_index = save_points
+            .iter()
+            .enumerate()
+            .map(|(i, sp)| (sp.id, i))
+            .collect();
+        manager.save_points = save_points;
+        if let Some(last) = manager.save_points.last() {
+            if let Some(state) = manager.restore(last.id, original_bytes) {
+                manager.last_save_point_state = state;
+            }
+        }
+        manager
+    }
+
     /// Get all save points
     pub fn save_points(&self) -> &[SavePoint] {
         &self.save_points
@@ -148,41 +178,58 @@ impl SavePointManager {
         }
     }
 
+    /// Replace the note attached to a save point
+    #[must_use = "returns whether the save point was found and updated"]
+    pub fn set_note(&mut self, id: u64, note: String) -> bool {
+        if let Some(sp) = self.get_mut(id) {
+            sp.note = note;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Check if a save point can be deleted
     ///
-    /// Currently, only the last (leaf) save point can be deleted to avoid
-    /// having to recompute diffs for successor save points.
+    /// Any existing save point can be deleted; deleting a non-leaf save point
+    /// merges its diff into its successor.
     pub fn can_delete(&self, id: u64) -> bool {
-        // Only allow deleting the last save point (leaf)
-        self.save_points
-            .last()
-            .map(|sp| sp.id == id)
-            .unwrap_or(false)
+        self.id_to_index.contains_key(&id)
     }
 
-    /// Delete a save point (only leaf save points can be deleted)
+    /// Delete a save point
+    ///
+    /// For a non-leaf save point K, the successor's diff is recomputed relative
+    /// to K-1's resolved state (or the original file), so every remaining save
+    /// point restores to exactly the same bytes as before.
     ///
     /// Returns true if the save point was deleted, false otherwise
     #[must_use = "returns whether the save point was deleted"]
     pub fn delete(&mut self, id: u64) -> bool {
-        if !self.can_delete(id) {
+        let Some(&index) = self.id_to_index.get(&id) else {
             return false;
-        }
+        };
 
-        if let Some(deleted) = self.save_points.pop() {
-            // Remove from index
-            self.id_to_index.remove(&deleted.id);
-
-            // Revert last_save_point_state by undoing the deleted save point's diff
+        let deleted = self.save_points.remove(index);
+        if let Some(successor) = self.save_points.get_mut(index) {
+            successor.diff = merge_diffs(&deleted.diff, &successor.diff);
+        } else {
+            // Deleted the leaf: revert last_save_point_state by undoing its diff
             for change in deleted.diff.iter().rev() {
                 if change.offset < self.last_save_point_state.len() {
                     self.last_save_point_state[change.offset] = change.old_value;
                 }
             }
-            true
-        } else {
-            false
         }
+
+        // Later save points shifted down by one
+        self.id_to_index = self
+            .save_points
+            .iter()
+            .enumerate()
+            .map(|(i, sp)| (sp.id, i))
+            .collect();
+        true
     }
 
     /// Get the number of save points
@@ -201,6 +248,28 @@ impl SavePointManager {
     }
 }
 
+/// Combine two consecutive diffs into one that goes from the state before
+/// `first` straight to the state after `second`.
+///
+/// Keeps the earliest old value and the latest new value per offset, and drops
+/// offsets whose value ends up unchanged.
+fn merge_diffs(first: &[ByteChange], second: &[ByteChange]) -> Vec<ByteChange> {
+    let mut merged: BTreeMap<usize, ByteChange> = first
+        .iter()
+        .map(|change| (change.offset, change.clone()))
+        .collect();
+    for change in second {
+        merged
+            .entry(change.offset)
+            .and_modify(|existing| existing.new_value = change.new_value)
+            .or_insert_with(|| change.clone());
+    }
+    merged
+        .into_values()
+        .filter(|change| change.old_value != change.new_value)
+        .collect()
+}
+
 /// Compute the diff between two byte slices
 fn compute_diff(old: &[u8], new: &[u8]) -> Vec<ByteChange> {
     let mut changes = Vec::new();
@@ -275,6 +344,18 @@ mod tests {
         assert_eq!(sps[0].name, "New name");
     }
 
+    #[test]
+    fn test_save_point_note() {
+        let original = vec![0x00, 0x01, 0x02, 0x03];
+        let mut manager = SavePointManager::new(&original);
+
+        let id = manager.create("SP".to_string(), &original);
+        assert!(manager.save_points()[0].note.is_empty());
+        assert!(manager.set_note(id, "shifted\nrows".to_string()));
+        assert_eq!(manager.save_points()[0].note, "shifted\nrows");
+        assert!(!manager.set_note(id + 1, "missing".to_string()));
+    }
+
     #[test]
     fn test_delete_leaf_save_point() {
         let original = vec![0x00, 0x01, 0x02, 0x03];
@@ -283,8 +364,7 @@ mod tests {
         let id1 = manager.create("SP1".to_string(), &[0xAA, 0x01, 0x02, 0x03]);
         let id2 = manager.create("SP2".to_string(), &[0xAA, 0xBB, 0x02, 0x03]);
 
-        // Can only delete the last one
-        assert!(!manager.can_delete(id1));
+        assert!(manager.can_delete(id1));
         assert!(manager.can_delete(id2));
 
         // Delete the leaf
@@ -292,9 +372,63 @@ mod tests {
         assert_eq!(manager.len(), 1);
 
         // Now id1 is the leaf
-        assert!(manager.can_delete(id1));
         assert!(manager.delete(id1));
         assert_eq!(manager.len(), 0);
+        assert!(!manager.can_delete(id1));
+        assert!(!manager.delete(id1));
+
+        // A new save point diffs against the original again
+        let id3 = manager.create("SP3".to_string(), &[0x00, 0x01, 0x02, 0xCC]);
+        assert_eq!(manager.save_points()[0].diff.len(), 1);
+        assert_eq!(
+            manager.restore(id3, &original).unwrap(),
+            vec![0x00, 0x01, 0x02, 0xCC]
+        );
+    }
+
+    #[test]
+    fn test_delete_middle_save_point_keeps_successor_bytes() {
+        let original = vec![0x00, 0x01, 0x02, 0x03];
+        let mut manager = SavePointManager::new(&original);
+
+        let id1 = manager.create("SP1".to_string(), &[0xAA, 0x01, 0x02, 0x03]);
+        // SP2 changes offset 1, and flips offset 0 back to the original value
+        let id2 = manager.create("SP2".to_string(), &[0x00, 0xBB, 0x02, 0x03]);
+        let id3 = manager.create("SP3".to_string(), &[0x00, 0xBB, 0xCC, 0x03]);
+
+        let before2 = manager.restore(id2, &original).unwrap();
+        let before3 = manager.restore(id3, &original).unwrap();
+
+        assert!(manager.delete(id1));
+        assert_eq!(manager.len(), 2);
+        assert!(manager.restore(id1, &original).is_none());
+        assert_eq!(manager.restore(id2, &original).unwrap(), before2);
+        assert_eq!(manager.restore(id3, &original).unwrap(), before3);
+
+        // Offset 0 netted out to no change, so only offset 1 remains in SP2's diff
+        assert_eq!(
+            manager.save_points()[0].diff,
+            vec![ByteChange {
+                offset: 1,
+                old_value: 0x01,
+                new_value: 0xBB,
+            }]
+        );
+
+        // Deleting another middle save point still preserves the leaf
+        let id4 = manager.create("SP4".to_string(), &[0xDD, 0xBB, 0xCC, 0x03]);
+        let before4 = manager.restore(id4, &original).unwrap();
+        assert!(manager.delete(id3));
+        assert_eq!(manager.restore(id2, &original).unwrap(), before2);
+        assert_eq!(manager.restore(id4, &original).unwrap(), before4);
+
+        // New save points still diff correctly against the (unchanged) leaf state
+        let id5 = manager.create("SP5".to_string(), &[0xDD, 0xBB, 0xCC, 0xEE]);
+        assert_eq!(manager.save_points().last().unwrap().diff.len(), 1);
+        assert_eq!(
+            manager.restore(id5, &original).unwrap(),
+            vec![0xDD, 0xBB, 0xCC, 0xEE]
+        );
     }
 
     #[test]
@@ -1,6 +1,10 @@
 //! Search and replace functionality for the hex editor
 
 use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// How long the query must sit unchanged before "Search as you type" re-runs it
+pub const LIVE_SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
 
 /// Search mode - either hex pattern or ASCII string
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -12,6 +16,18 @@ pub enum SearchMode {
     Ascii,
 }
 
+/// Which part of the buffer a search scans
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SearchScope {
+    /// Scan the whole buffer
+    #[default]
+    WholeFile,
+    /// Scan only the current selection (whole buffer if nothing is selected)
+    Selection,
+    /// Scan only the file section under the cursor
+    Section,
+}
+
 /// Parsed search pattern element
 #[derive(Debug, Clone, PartialEq)]
 pub enum PatternElement {
@@ -39,6 +55,8 @@ pub struct SearchState {
     pub mode: SearchMode,
     /// Case-sensitive search (only applies to ASCII mode)
     pub case_sensitive: bool,
+    /// Region of the buffer to search
+    pub scope: SearchScope,
     /// All match positions (byte offsets)
     pub matches: Vec<usize>,
     /// Pre-computed set of all offsets within any match (for O(1) highlight lookup)
@@ -61,8 +79,14 @@ pub struct SearchState {
     last_searched_mode: SearchMode,
     /// Case sensitivity that produced the current matches
     last_searched_case_sensitive: bool,
+    /// Scope that produced the current matches
+    last_searched_scope: SearchScope,
     /// Editor generation when search was last executed
     searched_at_generation: u64,
+    /// Whether "Bookmark All Matches" is awaiting confirmation (large match counts)
+    pub confirm_bookmark_all: bool,
+    /// When the query or an option last changed (search-as-you-type debounce)
+    query_edited_at: Option<Instant>,
 }
 
 impl SearchState {
@@ -99,11 +123,12 @@ impl SearchState {
         self.cached_pattern_len
     }
 
-    /// Check if the query/mode/case settings have changed since the last search
+    /// Check if the query/mode/case/scope settings have changed since the last search
     pub fn query_changed_since_search(&self) -> bool {
         self.query != self.last_searched_query
             || self.mode != self.last_searched_mode
             || self.case_sensitive != self.last_searched_case_sensitive
+            || self.scope != self.last_searched_scope
     }
 
     /// Check if match results may be stale due to buffer edits since the search
@@ -116,6 +141,23 @@ impl SearchState {
         self.searched_at_generation = generation;
     }
 
+    /// Restart the search-as-you-type delay (the query was just typed into)
+    pub fn note_query_edited(&mut self, now: Instant) {
+        self.query_edited_at = Some(now);
+    }
+
+    /// Time left before search-as-you-type should re-run the search: None when
+    /// the matches are still current (same query and options, no edits since),
+    /// zero once the query has settled for `LIVE_SEARCH_DEBOUNCE`
+    pub fn live_search_delay(&mut self, generation: u64, now: Instant) -> Option<Duration> {
+        if !self.query_changed_since_search() && !self.matches_may_be_stale(generation) {
+            self.query_edited_at = None;
+            return None;
+        }
+        let edited = *self.query_edited_at.get_or_insert(now);
+        Some(LIVE_SEARCH_DEBOUNCE.saturating_sub(now.duration_since(edited)))
+    }
+
     /// Move to the next match
     pub fn next_match(&mut self) {
         if self.matches.is_empty() {
@@ -158,6 +200,7 @@ impl SearchState {
         self.current_match = None;
         self.message = None;
         self.cached_pattern_len = 0;
+        self.confirm_bookmark_all = false;
     }
 }
 
@@ -299,8 +342,23 @@ pub fn search_ascii(data: &[u8], query: &str, case_sensitive: bool) -> Vec<usize
     matches
 }
 
+/// Drop every match that starts before the previous kept match ends, so the
+/// rest can be replaced independently. `matches` must be sorted.
+pub fn non_overlapping_matches(matches: &[usize], pattern_len: usize) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::with_capacity(matches.len());
+    for &offset in matches {
+        if kept.last().is_none_or(|&last| offset >= last + pattern_len) {
+            kept.push(offset);
+        }
+    }
+    kept
+}
+
 /// Execute search based on current state
-pub fn execute_search(state: &mut SearchState, data: &[u8]) {
+///
+/// `data` may be a slice of the buffer starting at `base_offset`; reported
+/// match positions are absolute buffer offsets.
+pub fn execute_search(state: &mut SearchState, data: &[u8], base_offset: usize) {
     state.clear_results();
 
     if state.query.is_empty() {
@@ -326,6 +384,12 @@ pub fn execute_search(state: &mut SearchState, data: &[u8]) {
         }
     };
 
+    if base_offset > 0 {
+        for offset in &mut state.matches {
+            *offset += base_offset;
+        }
+    }
+
     // Cache the pattern length and build highlighted offsets
     state.cached_pattern_len = pattern_len;
     state.rebuild_highlighted_offsets(pattern_len);
@@ -334,6 +398,7 @@ pub fn execute_search(state: &mut SearchState, data: &[u8]) {
     state.last_searched_query = state.query.clone();
     state.last_searched_mode = state.mode.clone();
     state.last_searched_case_sensitive = state.case_sensitive;
+    state.last_searched_scope = state.scope;
 
     // Set current match to first one if any found
     if !state.matches.is_empty() {
@@ -341,6 +406,27 @@ pub fn execute_search(state: &mut SearchState, data: &[u8]) {
     }
 }
 
+/// Run `execute_search` over the span of `ranges` (sorted and disjoint, as
+/// offsets into `data`), keeping only the matches that fit inside one range
+pub fn execute_search_in_ranges(state: &mut SearchState, data: &[u8], ranges: &[(usize, usize)]) {
+    let (Some(&(start, _)), Some(&(_, end))) = (ranges.first(), ranges.last()) else {
+        execute_search(state, &[], 0);
+        return;
+    };
+    execute_search(state, &data[start..end], start);
+    if ranges.len() < 2 {
+        return;
+    }
+    let pattern_len = state.cached_pattern_len;
+    state.matches.retain(|&offset| {
+        ranges
+            .iter()
+            .any(|&(start, end)| offset >= start && offset + pattern_len <= end)
+    });
+    state.rebuild_highlighted_offsets(pattern_len);
+    state.current_match = (!state.matches.is_empty()).then_some(0);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -426,8 +512,10 @@ mod tests {
 
     #[test]
     fn test_search_state_navigation() {
-        let mut state = SearchState::default();
-        state.matches = vec![10, 20, 30];
+        let mut state = SearchState {
+            matches: vec![10, 20, 30],
+            ..Default::default()
+        };
 
         assert_eq!(state.current_match, None);
 
@@ -463,15 +551,17 @@ mod tests {
     #[test]
     fn test_query_changed_since_search() {
         let data = b"hello world hello";
-        let mut state = SearchState::default();
-        state.mode = SearchMode::Ascii;
-        state.query = "hello".to_string();
+        let mut state = SearchState {
+            mode: SearchMode::Ascii,
+            query: "hello".to_string(),
+            ..Default::default()
+        };
 
         // Before any search, query_changed should be true (query differs from empty default)
         assert!(state.query_changed_since_search());
 
         // After search, query_changed should be false
-        execute_search(&mut state, data);
+        execute_search(&mut state, data, 0);
         assert!(!state.query_changed_since_search());
         assert_eq!(state.matches.len(), 2);
 
@@ -492,14 +582,35 @@ mod tests {
         assert!(state.query_changed_since_search());
     }
 
+    #[test]
+    fn test_execute_search_in_range_offsets_matches() {
+        let data = b"\xFF\xD8..\xFF\xD8..\xFF\xD8";
+        let mut state = SearchState {
+            query: "FF D8".to_string(),
+            scope: SearchScope::Selection,
+            ..Default::default()
+        };
+
+        execute_search(&mut state, &data[3..9], 3);
+        assert_eq!(state.matches, vec![4]);
+        assert!(state.is_within_match(5));
+        assert!(!state.is_within_match(0));
+        assert!(!state.query_changed_since_search());
+
+        state.scope = SearchScope::WholeFile;
+        assert!(state.query_changed_since_search());
+    }
+
     #[test]
     fn test_invalid_hex_query_sets_error_message() {
         let data = b"hello";
-        let mut state = SearchState::default();
-        state.mode = SearchMode::Hex;
-        state.query = "GG".to_string();
+        let mut state = SearchState {
+            mode: SearchMode::Hex,
+            query: "GG".to_string(),
+            ..Default::default()
+        };
 
-        execute_search(&mut state, data);
+        execute_search(&mut state, data, 0);
 
         match &state.message {
             Some(SearchMessage::Error(text)) => {
@@ -511,10 +622,69 @@ mod tests {
 
     #[test]
     fn test_clear_results_clears_message() {
-        let mut state = SearchState::default();
-        state.message = Some(SearchMessage::Info("test".to_string()));
+        let mut state = SearchState {
+            message: Some(SearchMessage::Info("test".to_string())),
+            ..Default::default()
+        };
 
         state.clear_results();
         assert!(state.message.is_none());
     }
+
+    #[test]
+    fn test_live_search_delay_debounces_and_skips_current_matches() {
+        let mut state = SearchState::default();
+        let start = Instant::now();
+        assert_eq!(state.live_search_delay(0, start), None);
+
+        state.query = "FF".to_string();
+        state.note_query_edited(start);
+        assert_eq!(
+            state.live_search_delay(0, start),
+            Some(LIVE_SEARCH_DEBOUNCE)
+        );
+        // Another keystroke restarts the delay
+        let later = start + Duration::from_millis(100);
+        state.query = "FF D8".to_string();
+        state.note_query_edited(later);
+        assert_eq!(
+            state.live_search_delay(0, later + Duration::from_millis(50)),
+            Some(Duration::from_millis(100))
+        );
+        let settled = later + LIVE_SEARCH_DEBOUNCE;
+        assert_eq!(state.live_search_delay(0, settled), Some(Duration::ZERO));
+
+        execute_search(&mut state, &[0xFF, 0xD8, 0xFF], 0);
+        state.set_searched_generation(0);
+        assert_eq!(state.live_search_delay(0, settled), None);
+        // An edit to the buffer makes the matches stale again
+        assert!(state.live_search_delay(1, settled).is_some());
+    }
+
+    #[test]
+    fn test_non_overlapping_matches_keeps_earliest() {
+        let matches = search_ascii(b"aaaaaaa", "aaa", true);
+        assert_eq!(matches, vec![0, 1, 2, 3, 4]);
+        assert_eq!(non_overlapping_matches(&matches, 3), vec![0, 3]);
+        assert_eq!(non_overlapping_matches(&[2, 5, 9], 3), vec![2, 5, 9]);
+    }
+
+    #[test]
+    fn test_search_in_ranges_skips_matches_outside_or_straddling() {
+        let mut state = SearchState {
+            query: "AB".to_string(),
+            mode: SearchMode::Ascii,
+            case_sensitive: true,
+            ..Default::default()
+        };
+        //           0 1 2 3 4 5 6 7 8 9
+        let data = b"ABxABxABAB";
+        execute_search_in_ranges(&mut state, data, &[(0, 2), (6, 9)]);
+        assert_eq!(state.matches, vec![0, 6]);
+        assert!(!state.is_within_match(3));
+        assert!(!state.is_within_match(8));
+
+        execute_search_in_ranges(&mut state, data, &[(3, 10)]);
+        assert_eq!(state.matches, vec![3, 6, 8]);
+    }
 }
@@ -9,12 +9,193 @@ impl EditorState {
         self.selection
     }
 
+    /// Every selected range, sorted by start with overlapping or touching
+    /// ranges merged and clamped to the buffer. Empty without a selection.
+    pub fn selections(&self) -> Vec<(usize, usize)> {
+        let len = self.working.len();
+        let mut ranges: Vec<(usize, usize)> = self
+            .extra_selections
+            .iter()
+            .chain(self.selection.iter())
+            .map(|&(start, end)| (start, end.min(len)))
+            .filter(|&(start, end)| start < end)
+            .collect();
+        ranges.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+    }
+
+    /// The selected range when there is exactly one (after merging). None
+    /// without a selection or with several disjoint ranges.
+    pub fn single_selection(&self) -> Option<(usize, usize)> {
+        match self.selections().as_slice() {
+            [range] => Some(*range),
+            _ => None,
+        }
+    }
+
+    /// The bytes of every selected range, concatenated in buffer order.
+    /// None without a selection.
+    pub fn selected_bytes(&self) -> Option<Vec<u8>> {
+        let ranges = self.selections();
+        if ranges.is_empty() {
+            return None;
+        }
+        Some(
+            ranges
+                .into_iter()
+                .flat_map(|(start, end)| self.working[start..end].iter().copied())
+                .collect(),
+        )
+    }
+
+    /// Start another range at `pos` while keeping the current selection
+    /// (Ctrl+click/drag). The new range grows from `pos` via `extend_selection_to`.
+    pub fn add_selection_at(&mut self, pos: usize) {
+        if let Some(range) = self.selection.take() {
+            self.extra_selections.push(range);
+        }
+        self.set_cursor(pos);
+        self.selection_anchor = Some(self.cursor);
+    }
+
     /// Clear selection
     pub fn clear_selection(&mut self) {
         self.selection = None;
+        self.extra_selections.clear();
         self.selection_anchor = None;
     }
 
+    /// Whether plain cursor moves leave the selection in place
+    pub fn selection_locked(&self) -> bool {
+        self.selection_locked
+    }
+
+    /// Lock or unlock the selection against plain cursor moves
+    pub fn set_selection_locked(&mut self, locked: bool) {
+        self.selection_locked = locked;
+    }
+
+    /// Clear the selection for a plain cursor move (click, arrow, jump) unless it is locked.
+    /// Explicit clears still go through `clear_selection`.
+    pub fn clear_unlocked_selection(&mut self) {
+        if !self.selection_locked {
+            self.clear_selection();
+        }
+    }
+
+    /// Select the byte range `start..end` (end exclusive) and move the cursor to `start`.
+    /// The range is clamped to the buffer; an empty range clears the selection.
+    pub fn set_selection(&mut self, start: usize, end: usize) {
+        let end = end.min(self.working.len());
+        if start >= end {
+            self.clear_selection();
+            return;
+        }
+        self.selection = Some((start, end));
+        self.extra_selections.clear();
+        self.selection_anchor = Some(start);
+        self.cursor = start;
+        self.nibble = NibblePosition::High;
+    }
+
+    /// Select every range in `ranges` (e.g. a saved multi-range selection).
+    /// The last one becomes the active range, with the cursor at its start.
+    pub fn set_selections(&mut self, ranges: &[(usize, usize)]) {
+        let Some((&(start, end), rest)) = ranges.split_last() else {
+            self.clear_selection();
+            return;
+        };
+        self.set_selection(start, end);
+        self.extra_selections = rest.to_vec();
+    }
+
+    /// Replace the selection with the larger of the segments before and after it
+    /// (the one before wins a tie). A single range can't hold both halves, so
+    /// this picks the bigger complement. Returns false if there was no
+    /// single selected range or it already covered the whole buffer.
+    pub fn invert_selection(&mut self) -> bool {
+        let Some((start, end)) = self.single_selection() else {
+            return false;
+        };
+        let len = self.working.len();
+        let (new_start, new_end) = if start >= len - end {
+            (0, start)
+        } else {
+            (end, len)
+        };
+        if new_start >= new_end {
+            return false;
+        }
+        self.set_selection(new_start, new_end);
+        true
+    }
+
+    /// Move the selection's end by `delta` bytes (negative shrinks), keeping the
+    /// anchor at its start. Without a selection the cursor byte is the starting
+    /// point. The selection never shrinks below one byte or runs past the
+    /// buffer. Returns whether it changed.
+    pub fn grow_selection(&mut self, delta: isize) -> bool {
+        let (start, end) = self.selection_or_cursor_byte();
+        let new_end = end
+            .saturating_add_signed(delta)
+            .clamp(start + 1, self.working.len().max(start + 1));
+        self.resize_selection(start, new_end)
+    }
+
+    /// Move the selection's start outward by `delta` bytes (negative shrinks),
+    /// the mirror of `grow_selection` for the other edge
+    pub fn grow_selection_start(&mut self, delta: isize) -> bool {
+        let (start, end) = self.selection_or_cursor_byte();
+        let new_start = start
+            .saturating_add_signed(delta.saturating_neg())
+            .min(end - 1);
+        self.resize_selection(new_start, end)
+    }
+
+    /// The selection, or the one-byte range under the cursor
+    fn selection_or_cursor_byte(&self) -> (usize, usize) {
+        self.selection.unwrap_or((self.cursor, self.cursor + 1))
+    }
+
+    /// Apply a resized selection; false if it is unchanged (or the buffer is empty)
+    fn resize_selection(&mut self, start: usize, end: usize) -> bool {
+        if self.working.is_empty() || Some((start, end)) == self.selection {
+            return false;
+        }
+        self.set_selection(start, end);
+        self.selection.is_some()
+    }
+
+    /// Select a whole `bytes_per_row`-wide row, like clicking a spreadsheet row header.
+    /// With `extend`, the selection grows from the anchor's row to cover every row
+    /// up to and including this one.
+    pub fn select_row(&mut self, row: usize, bytes_per_row: usize, extend: bool) {
+        let len = self.working.len();
+        let row_start = row * bytes_per_row;
+        if bytes_per_row == 0 || row_start >= len {
+            return;
+        }
+        let anchor_row = match (extend, self.selection_anchor) {
+            (true, Some(anchor)) => anchor / bytes_per_row,
+            (true, None) => self.cursor / bytes_per_row,
+            (false, _) => row,
+        };
+        let start = anchor_row.min(row) * bytes_per_row;
+        let end = ((anchor_row.max(row) + 1) * bytes_per_row).min(len);
+        self.selection = Some((start, end));
+        self.extra_selections.clear();
+        self.selection_anchor = Some(anchor_row * bytes_per_row);
+        self.cursor = row_start;
+        self.nibble = NibblePosition::High;
+    }
+
     /// Extend selection from anchor to the given position
     /// If no anchor exists, sets anchor at current cursor before extending
     pub fn extend_selection_to(&mut self, pos: usize) {
@@ -58,7 +239,7 @@ impl EditorState {
         if extend {
             self.extend_selection_to(pos);
         } else {
-            self.clear_selection();
+            self.clear_unlocked_selection();
             self.set_cursor(pos);
         }
     }
@@ -68,6 +249,26 @@ impl EditorState {
 mod tests {
     use super::super::buffer::EditorState;
 
+    #[test]
+    fn test_locked_selection_survives_cursor_moves() {
+        let mut editor = EditorState::new(vec![0; 16]);
+        editor.set_selection(2, 6);
+        editor.set_selection_locked(true);
+
+        editor.set_cursor_with_selection(10, false);
+        editor.clear_unlocked_selection();
+        assert_eq!(editor.cursor(), 10);
+        assert_eq!(editor.selection(), Some((2, 6)));
+
+        // Explicit clears and unlocking still work
+        editor.clear_selection();
+        assert!(editor.selection().is_none());
+        editor.set_selection(2, 6);
+        editor.set_selection_locked(false);
+        editor.set_cursor_with_selection(0, false);
+        assert!(editor.selection().is_none());
+    }
+
     #[test]
     fn test_selection_with_shift_arrow() {
         let data = vec![0x00, 0x01, 0x02, 0x03, 0x04, 0x05];
@@ -122,6 +323,61 @@ mod tests {
         assert!(editor.selection().is_none());
     }
 
+    #[test]
+    fn test_grow_and_shrink_selection_edges() {
+        let mut editor = EditorState::new(vec![0u8; 10]);
+        editor.set_cursor(4);
+
+        // Without a selection the cursor byte grows
+        assert!(editor.grow_selection(3));
+        assert_eq!(editor.selection(), Some((4, 8)));
+        assert!(editor.grow_selection(-2));
+        assert_eq!(editor.selection(), Some((4, 6)));
+
+        // Never below one byte or past either end of the buffer
+        assert!(editor.grow_selection(-100));
+        assert_eq!(editor.selection(), Some((4, 5)));
+        assert!(!editor.grow_selection(-1));
+        assert!(editor.grow_selection(100));
+        assert_eq!(editor.selection(), Some((4, 10)));
+
+        // The start edge moves the other way
+        assert!(editor.grow_selection_start(2));
+        assert_eq!(editor.selection(), Some((2, 10)));
+        assert!(editor.grow_selection_start(-100));
+        assert_eq!(editor.selection(), Some((9, 10)));
+        assert!(editor.grow_selection_start(100));
+        assert_eq!(editor.selection(), Some((0, 10)));
+
+        // Shift-extending keeps the anchor at the start
+        editor.set_selection(2, 4);
+        editor.grow_selection(2);
+        editor.extend_selection_to(8);
+        assert_eq!(editor.selection(), Some((2, 9)));
+
+        let mut empty = EditorState::new(Vec::new());
+        assert!(!empty.grow_selection(1));
+    }
+
+    #[test]
+    fn test_select_row() {
+        let mut editor = EditorState::new(vec![0u8; 40]);
+
+        editor.select_row(1, 16, false);
+        assert_eq!(editor.selection(), Some((16, 32)));
+        assert_eq!(editor.cursor(), 16);
+
+        // Shift-click extends by whole rows, clamping the last partial row
+        editor.select_row(2, 16, true);
+        assert_eq!(editor.selection(), Some((16, 40)));
+        editor.select_row(0, 16, true);
+        assert_eq!(editor.selection(), Some((0, 32)));
+
+        // Rows past the end are ignored
+        editor.select_row(3, 16, false);
+        assert_eq!(editor.selection(), Some((0, 32)));
+    }
+
     #[test]
     fn test_selection_with_extend_to() {
         let data = vec![0x00, 0x01, 0x02, 0x03, 0x04, 0x05];
@@ -138,6 +394,53 @@ mod tests {
         assert_eq!(editor.selection(), Some((0, 3)));
     }
 
+    #[test]
+    fn test_set_selection() {
+        let data = vec![0x00, 0x01, 0x02, 0x03, 0x04, 0x05];
+        let mut editor = EditorState::new(data);
+
+        editor.set_selection(1, 4);
+        assert_eq!(editor.cursor(), 1);
+        assert_eq!(editor.selection(), Some((1, 4)));
+
+        // Shift-extending continues from the selection start
+        editor.extend_selection_to(5);
+        assert_eq!(editor.selection(), Some((1, 6)));
+
+        // End is clamped to the buffer length
+        editor.set_selection(2, 100);
+        assert_eq!(editor.selection(), Some((2, 6)));
+
+        // Empty range clears the selection
+        editor.set_selection(3, 3);
+        assert!(editor.selection().is_none());
+    }
+
+    #[test]
+    fn test_invert_selection_picks_larger_complement() {
+        let mut editor = EditorState::new(vec![0u8; 10]);
+        assert!(!editor.invert_selection());
+
+        // Selected header: everything after it
+        editor.set_selection(0, 3);
+        assert!(editor.invert_selection());
+        assert_eq!(editor.selection(), Some((3, 10)));
+        assert_eq!(editor.cursor(), 3);
+
+        // Middle range: the bigger side, preferring the start on a tie
+        editor.set_selection(6, 8);
+        assert!(editor.invert_selection());
+        assert_eq!(editor.selection(), Some((0, 6)));
+        editor.set_selection(4, 6);
+        assert!(editor.invert_selection());
+        assert_eq!(editor.selection(), Some((0, 4)));
+
+        // Nothing left to select
+        editor.set_selection(0, 10);
+        assert!(!editor.invert_selection());
+        assert_eq!(editor.selection(), Some((0, 10)));
+    }
+
     #[test]
     fn test_clear_selection_clears_anchor() {
         let data = vec![0x00, 0x01, 0x02, 0x03];
@@ -153,4 +456,43 @@ mod tests {
         editor.move_cursor_with_selection(1);
         assert_eq!(editor.selection(), Some((2, 4)));
     }
+
+    #[test]
+    fn test_added_ranges_accumulate() {
+        let mut editor = EditorState::new((0..16).collect());
+        editor.set_selection(1, 3);
+
+        // Ctrl+drag from 8 to 9, then from 2 to 5 (overlapping the first range)
+        editor.add_selection_at(8);
+        editor.extend_selection_to(9);
+        editor.add_selection_at(2);
+        editor.extend_selection_to(4);
+        assert_eq!(editor.selections(), vec![(1, 5), (8, 10)]);
+        assert_eq!(editor.selected_bytes(), Some(vec![1, 2, 3, 4, 8, 9]));
+        // The active range is the one being dragged
+        assert_eq!(editor.selection(), Some((2, 5)));
+        assert_eq!(editor.single_selection(), None);
+        assert!(!editor.duplicate_selection());
+
+        // A plain selection replaces all of them
+        editor.set_selection(0, 1);
+        assert_eq!(editor.selections(), vec![(0, 1)]);
+        assert_eq!(editor.single_selection(), Some((0, 1)));
+        editor.add_selection_at(4);
+        editor.clear_selection();
+        assert!(editor.selections().is_empty());
+        assert_eq!(editor.selected_bytes(), None);
+    }
+
+    #[test]
+    fn test_set_selections_restores_every_range() {
+        let mut editor = EditorState::new((0..16).collect());
+        editor.set_selections(&[(1, 3), (8, 10)]);
+        assert_eq!(editor.selections(), vec![(1, 3), (8, 10)]);
+        assert_eq!(editor.selection(), Some((8, 10)));
+        assert_eq!(editor.cursor(), 8);
+
+        editor.set_selections(&[]);
+        assert!(editor.selections().is_empty());
+    }
 }
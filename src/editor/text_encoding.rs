@@ -0,0 +1,96 @@
+//! Character sets the ASCII pane can show bytes in
+//!
+//! Display only: typing in ASCII mode always writes ASCII bytes.
+
+/// How the ASCII pane maps bytes to glyphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextEncoding {
+    /// Printable 7-bit ASCII (0x20-0x7E)
+    #[default]
+    Ascii,
+    /// ISO 8859-1: ASCII plus the accented letters and symbols in 0xA0-0xFF
+    Latin1,
+    /// IBM EBCDIC code page 037, found in mainframe data
+    Ebcdic,
+}
+
+/// Code page 037 glyphs for bytes 0x40-0xFF; `'\0'` marks bytes with no
+/// printable form. Everything below 0x40 is a control code.
+const EBCDIC_037: [char; 192] = [
+    ' ', '\u{A0}', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|', '&', 'é',
+    'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬', '-', '/', 'Â', 'Ä', 'À',
+    'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?', 'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï',
+    'Ì', '`', ':', '#', '@', '\'', '=', '"', 'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«',
+    '»', 'ð', 'ý', 'þ', '±', '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸',
+    'Æ', '¤', 'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®', '^',
+    '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×', '{', 'A', 'B', 'C',
+    'D', 'E', 'F', 'G', 'H', 'I', '\0', 'ô', 'ö', 'ò', 'ó', 'õ', '}', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ', '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü',
+    'Ù', 'Ú', '\0',
+];
+
+impl TextEncoding {
+    /// Every encoding, in the order offered in the toolbar
+    pub const ALL: [TextEncoding; 3] = [
+        TextEncoding::Ascii,
+        TextEncoding::Latin1,
+        TextEncoding::Ebcdic,
+    ];
+
+    /// Short name for menus and tooltips
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Ascii => "ASCII",
+            TextEncoding::Latin1 => "Latin-1",
+            TextEncoding::Ebcdic => "EBCDIC",
+        }
+    }
+
+    /// The glyph `byte` stands for, or None if it has no printable form
+    pub fn decode(self, byte: u8) -> Option<char> {
+        match self {
+            TextEncoding::Ascii => super::is_printable_ascii(byte).then_some(byte as char),
+            TextEncoding::Latin1 => {
+                // 0xAD is a soft hyphen, which renders as nothing
+                let printable = super::is_printable_ascii(byte) || (byte >= 0xA0 && byte != 0xAD);
+                printable.then_some(byte as char)
+            }
+            TextEncoding::Ebcdic => {
+                let ch = EBCDIC_037[usize::from(byte.checked_sub(0x40)?)];
+                (ch != '\0').then_some(ch)
+            }
+        }
+    }
+
+    /// The glyph shown in the ASCII pane: `decode`, with '.' for non-printables
+    pub fn display_char(self, byte: u8) -> char {
+        self.decode(byte).unwrap_or('.')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_per_encoding() {
+        assert_eq!(TextEncoding::Ascii.decode(b'A'), Some('A'));
+        assert_eq!(TextEncoding::Ascii.decode(0xE9), None);
+        assert_eq!(TextEncoding::Latin1.decode(0xE9), Some('\u{E9}'));
+        assert_eq!(TextEncoding::Latin1.decode(0x85), None);
+        assert_eq!(TextEncoding::Latin1.decode(0xAD), None);
+
+        // "HELLO" and digits in code page 037
+        let hello: String = [0xC8, 0xC5, 0xD3, 0xD3, 0xD6]
+            .iter()
+            .map(|&b| TextEncoding::Ebcdic.display_char(b))
+            .collect();
+        assert_eq!(hello, "HELLO");
+        assert_eq!(TextEncoding::Ebcdic.decode(0xF0), Some('0'));
+        assert_eq!(TextEncoding::Ebcdic.decode(0x40), Some(' '));
+        // Control codes and ASCII letters have no EBCDIC glyph
+        assert_eq!(TextEncoding::Ebcdic.decode(0x15), None);
+        assert_eq!(TextEncoding::Ebcdic.display_char(0xFF), '.');
+    }
+}
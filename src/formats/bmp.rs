@@ -4,25 +4,101 @@
 //! - File Header (14 bytes): signature, file size, reserved, pixel data offset
 //! - DIB Header (variable): image dimensions, color depth, compression, etc.
 //! - Optional Color Table: palette for indexed color images
-//! - Pixel Data: the actual image pixels
+//! - Pixel Data: the actual image pixels, in rows padded to 4-byte boundaries
 
 use super::bytes;
 use super::traits::{FileSection, ImageFormat, ParseError, RiskLevel};
 
+/// Most per-row padding sections emitted; rows past this aren't marked, so a
+/// tall image doesn't flood the structure tree and per-byte section lookups
+const MAX_PADDING_SECTIONS: usize = 2048;
+
 /// BMP format parser
 pub struct BmpParser;
 
+impl BmpParser {
+    /// Bytes per pixel row, including the padding to a 4-byte boundary.
+    /// Needs a BITMAPINFOHEADER (or later) header; None otherwise.
+    pub fn row_stride(data: &[u8]) -> Option<usize> {
+        if !BmpParser.can_parse(data) {
+            return None;
+        }
+        Self::row_layout(data).map(|(stride, _)| stride)
+    }
+
+    /// Row stride and the padding bytes at the end of each row, from a
+    /// BITMAPINFOHEADER (or later) header
+    fn row_layout(data: &[u8]) -> Option<(usize, usize)> {
+        if bytes::read_u32_le(data, 14)? < 40 {
+            return None;
+        }
+        let width = (bytes::read_u32_le(data, 18)? as i32).unsigned_abs() as usize;
+        let bits_per_pixel = bytes::read_u16_le(data, 28)? as usize;
+        let row_bytes = (width * bits_per_pixel).div_ceil(8);
+        let stride = row_bytes.div_ceil(4) * 4;
+        (stride > 0).then_some((stride, stride - row_bytes))
+    }
+
+    /// "Rows" subsection of the pixel data, with each row's padding marked.
+    /// None for compressed images (RLE rows have no fixed stride) or when
+    /// the header doesn't describe the row layout.
+    fn rows_section(data: &[u8], pixel_offset: usize) -> Option<FileSection> {
+        // BI_RGB or BI_BITFIELDS: plain uncompressed rows
+        if !matches!(bytes::read_u32_le(data, 30)?, 0 | 3) {
+            return None;
+        }
+        let (stride, padding) = Self::row_layout(data)?;
+        let height = (bytes::read_u32_le(data, 22)? as i32).unsigned_abs() as usize;
+        let available = data.len().saturating_sub(pixel_offset) / stride;
+        let rows = height.min(available);
+        if rows == 0 {
+            return None;
+        }
+
+        let description = if padding > 0 {
+            format!(
+                "{} rows of {} bytes, the last {} of each is padding",
+                rows, stride, padding
+            )
+        } else {
+            format!("{} rows of {} bytes, no padding", rows, stride)
+        };
+        let mut section = FileSection::new(
+            "Rows",
+            pixel_offset,
+            pixel_offset + rows * stride,
+            RiskLevel::Safe,
+        )
+        .with_description(description);
+        if padding > 0 {
+            for row in 0..rows.min(MAX_PADDING_SECTIONS) {
+                let end = pixel_offset + (row + 1) * stride;
+                section = section.with_child(
+                    FileSection::new(
+                        format!("Row {} Padding", row),
+                        end - padding,
+                        end,
+                        RiskLevel::Caution,
+                    )
+                    .with_description("Fills the row to a 4-byte boundary - never displayed"),
+                );
+            }
+        }
+        Some(section)
+    }
+}
+
 impl ImageFormat for BmpParser {
+    fn extension(&self, _data: &[u8]) -> &'static str {
+        "bmp"
+    }
+
     fn can_parse(&self, data: &[u8]) -> bool {
         // BMP files start with "BM"
         data.len() >= 2 && data[0] == b'B' && data[1] == b'M'
     }
 
-    fn parse(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
-        if !self.can_parse(data) {
-            return Err(ParseError::InvalidSignature);
-        }
-
+    fn parse_unchecked(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
         let mut sections = Vec::new();
 
         // Need at least 14 bytes for the file header
@@ -152,9 +228,12 @@ impl ImageFormat for BmpParser {
 
         // Pixel Data
         if pixel_offset < data.len() {
-            let pixel_data =
+            let mut pixel_data =
                 FileSection::new("Pixel Data", pixel_offset, data.len(), RiskLevel::Safe)
                     .with_description("Image pixel data - the fun part to glitch!");
+            if let Some(rows) = Self::rows_section(data, pixel_offset) {
+                pixel_data = pixel_data.with_child(rows);
+            }
             sections.push(pixel_data);
         }
 
@@ -179,6 +258,32 @@ mod tests {
         assert!(!parser.can_parse(b"PNG"));
     }
 
+    #[test]
+    fn test_row_stride_pads_to_four_bytes() {
+        let mut bmp = vec![0u8; 54];
+        bmp[0] = b'B';
+        bmp[1] = b'M';
+        bmp[14] = 40;
+        // 5 pixels wide at 24 bpp = 15 bytes, padded to 16
+        bmp[18] = 5;
+        bmp[28] = 24;
+        assert_eq!(BmpParser::row_stride(&bmp), Some(16));
+
+        // Negative width (never valid, but don't panic) uses its magnitude
+        bmp[18..22].copy_from_slice(&(-5i32).to_le_bytes());
+        assert_eq!(BmpParser::row_stride(&bmp), Some(16));
+
+        // 1 bpp, 9 pixels = 2 bytes, padded to 4
+        bmp[18..22].copy_from_slice(&9u32.to_le_bytes());
+        bmp[28] = 1;
+        assert_eq!(BmpParser::row_stride(&bmp), Some(4));
+
+        // Old BITMAPCOREHEADER layout isn't supported
+        bmp[14] = 12;
+        assert_eq!(BmpParser::row_stride(&bmp), None);
+        assert_eq!(BmpParser::row_stride(b"BM"), None);
+    }
+
     #[test]
     fn test_parse_minimal_bmp() {
         let parser = BmpParser;
@@ -213,4 +318,45 @@ mod tests {
         assert!(sections[1].name.contains("BITMAPINFOHEADER"));
         assert_eq!(sections[2].name, "Pixel Data");
     }
+
+    #[test]
+    fn test_rows_section_marks_padding() {
+        // 5x3 at 24 bpp: 15 pixel bytes + 1 padding byte per row
+        let mut bmp = vec![0u8; 54 + 3 * 16 + 2];
+        bmp[0] = b'B';
+        bmp[1] = b'M';
+        bmp[10] = 54;
+        bmp[14] = 40;
+        bmp[18] = 5;
+        bmp[22..26].copy_from_slice(&(-3i32).to_le_bytes());
+        bmp[28] = 24;
+
+        let sections = BmpParser.parse(&bmp).unwrap();
+        let pixel_data = sections.last().unwrap();
+        assert_eq!(pixel_data.name, "Pixel Data");
+        let rows = &pixel_data.children[0];
+        assert_eq!(rows.name, "Rows");
+        assert_eq!((rows.start, rows.end), (54, 54 + 48));
+        assert_eq!(rows.children.len(), 3);
+        assert_eq!(rows.children[1].name, "Row 1 Padding");
+        assert_eq!((rows.children[1].start, rows.children[1].end), (85, 86));
+        assert_eq!(rows.children[1].risk, RiskLevel::Caution);
+
+        // Rows cut off by the end of the file aren't listed
+        bmp.truncate(54 + 40);
+        let sections = BmpParser.parse(&bmp).unwrap();
+        assert_eq!(sections.last().unwrap().children[0].children.len(), 2);
+
+        // A 4-byte-aligned width has rows but no padding sections
+        bmp[18] = 4;
+        let sections = BmpParser.parse(&bmp).unwrap();
+        let rows = &sections.last().unwrap().children[0];
+        assert_eq!(rows.end, 54 + 36);
+        assert!(rows.children.is_empty());
+
+        // RLE-compressed rows have no fixed stride
+        bmp[30] = 1;
+        let sections = BmpParser.parse(&bmp).unwrap();
+        assert!(sections.last().unwrap().children.is_empty());
+    }
 }
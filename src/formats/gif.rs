@@ -301,15 +301,15 @@ fn flush_frame(
 }
 
 impl ImageFormat for GifParser {
+    fn extension(&self, _data: &[u8]) -> &'static str {
+        "gif"
+    }
+
     fn can_parse(&self, data: &[u8]) -> bool {
         data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")
     }
 
-    fn parse(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
-        if !self.can_parse(data) {
-            return Err(ParseError::InvalidSignature);
-        }
-
+    fn parse_unchecked(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
         let mut sections = Vec::new();
 
         // --- Header (6 bytes) ---
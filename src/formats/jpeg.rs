@@ -7,16 +7,36 @@
 //! - DHT: Huffman tables
 //! - SOF: Start of Frame (image dimensions)
 //! - SOS: Start of Scan (compressed data follows)
-//! - Entropy-coded data
+//! - Entropy-coded data (may contain RST0-RST7 restart markers)
 //! - EOI (End of Image): FF D9
 
+use std::ops::Range;
+
 use super::bytes;
 use super::traits::{FileSection, ImageFormat, ParseError, RiskLevel};
 
+/// Name of the section covering each scan's entropy-coded data
+const ENTROPY_SECTION_NAME: &str = "Entropy-Coded Data";
+
 /// JPEG format parser
 pub struct JpegParser;
 
 impl JpegParser {
+    /// Byte ranges of every scan's entropy-coded data (SOS payload up to the
+    /// next non-RST marker). Empty if `data` isn't a parseable JPEG.
+    pub fn scan_data_ranges(data: &[u8]) -> Vec<Range<usize>> {
+        JpegParser
+            .parse(data)
+            .map(|sections| {
+                sections
+                    .iter()
+                    .filter(|s| s.name == ENTROPY_SECTION_NAME)
+                    .map(|s| s.start..s.end)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Get human-readable name for a marker
     fn marker_name(marker: u8) -> &'static str {
         match marker {
@@ -53,19 +73,111 @@ impl JpegParser {
             _ => RiskLevel::Caution,
         }
     }
+
+    /// Split a DQT segment payload into one section per quantization table.
+    /// Each table is a precision/id byte followed by 64 (8-bit) or 128 (16-bit) values.
+    fn parse_dqt_tables(data: &[u8], start: usize, end: usize) -> Vec<FileSection> {
+        let mut tables = Vec::new();
+        let mut pos = start;
+        while pos < end {
+            let precision = data[pos] >> 4;
+            let table_id = data[pos] & 0x0F;
+            let table_end = pos + 1 + if precision == 0 { 64 } else { 128 };
+            if table_end > end {
+                break;
+            }
+            tables.push(
+                FileSection::new(
+                    format!("Quantization Table {}", table_id),
+                    pos,
+                    table_end,
+                    RiskLevel::High,
+                )
+                .with_description(if precision == 0 {
+                    "8-bit quantization values (zigzag order)"
+                } else {
+                    "16-bit quantization values (zigzag order)"
+                }),
+            );
+            pos = table_end;
+        }
+        tables
+    }
+
+    /// Split a DHT segment payload into one section per Huffman table.
+    /// Each table is a class/id byte, 16 code-length counts, then the symbol values.
+    fn parse_dht_tables(data: &[u8], start: usize, end: usize) -> Vec<FileSection> {
+        let mut tables = Vec::new();
+        let mut pos = start;
+        while pos + 17 <= end {
+            let class = if data[pos] >> 4 == 0 { "DC" } else { "AC" };
+            let table_id = data[pos] & 0x0F;
+            let symbol_count: usize = data[pos + 1..pos + 17].iter().map(|&c| c as usize).sum();
+            let table_end = pos + 17 + symbol_count;
+            if table_end > end {
+                break;
+            }
+            tables.push(
+                FileSection::new(
+                    format!("Huffman Table ({} {})", class, table_id),
+                    pos,
+                    table_end,
+                    RiskLevel::High,
+                )
+                .with_description("Code-length counts and symbol values"),
+            );
+            pos = table_end;
+        }
+        tables
+    }
+
+    /// Find the end of entropy-coded scan data starting at `start`, collecting
+    /// restart markers (FF D0-D7) found along the way.
+    /// The scan ends at the first marker that isn't a stuffed byte (FF 00),
+    /// fill byte, or restart marker; returns `data.len()` if none is found.
+    fn scan_entropy_data(data: &[u8], start: usize) -> (usize, Vec<FileSection>) {
+        let mut restart_markers = Vec::new();
+        let mut i = start;
+        while i + 1 < data.len() {
+            if data[i] != 0xFF {
+                i += 1;
+                continue;
+            }
+            match data[i + 1] {
+                // Stuffed zero byte or fill byte — still part of the scan
+                0x00 | 0xFF => i += 1,
+                0xD0..=0xD7 => {
+                    restart_markers.push(
+                        FileSection::new(
+                            format!("RST{}", data[i + 1] - 0xD0),
+                            i,
+                            i + 2,
+                            RiskLevel::High,
+                        )
+                        .with_description(
+                            "Restart marker - decoder resynchronizes here after corrupted data",
+                        ),
+                    );
+                    i += 2;
+                }
+                _ => return (i, restart_markers),
+            }
+        }
+        (data.len(), restart_markers)
+    }
 }
 
 impl ImageFormat for JpegParser {
+    fn extension(&self, _data: &[u8]) -> &'static str {
+        "jpg"
+    }
+
     fn can_parse(&self, data: &[u8]) -> bool {
         // JPEG files start with FF D8 FF
         data.len() >= 3 && data[0] == 0xFF && data[1] == 0xD8 && data[2] == 0xFF
     }
 
-    fn parse(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
-        if !self.can_parse(data) {
-            return Err(ParseError::InvalidSignature);
-        }
-
+    fn parse_unchecked(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
         let mut sections = Vec::new();
         // SOI marker
         sections.push(
@@ -142,30 +254,23 @@ impl ImageFormat for JpegParser {
 
                     pos = segment_end;
 
-                    // Everything after SOS until EOI is entropy-coded data
+                    // Scan data runs until the next non-RST marker (EOI, or DHT/SOS
+                    // for progressive files with multiple scans)
                     let entropy_start = pos;
-
-                    // Find EOI
-                    let mut entropy_end = data.len();
-                    for i in pos..data.len().saturating_sub(1) {
-                        if data[i] == 0xFF && data[i + 1] == 0xD9 {
-                            entropy_end = i;
-                            break;
-                        }
-                    }
+                    let (entropy_end, restart_markers) = Self::scan_entropy_data(data, pos);
 
                     if entropy_end > entropy_start {
-                        sections.push(
-                            FileSection::new(
-                                "Entropy-Coded Data",
-                                entropy_start,
-                                entropy_end,
-                                RiskLevel::Caution,
-                            )
-                            .with_description(
-                                "Compressed image data - editing here creates glitch effects but often corrupts the image",
-                            ),
+                        let mut section = FileSection::new(
+                            ENTROPY_SECTION_NAME,
+                            entropy_start,
+                            entropy_end,
+                            RiskLevel::Safe,
+                        )
+                        .with_description(
+                            "Compressed image data - the prime databending target; edits here create glitch effects",
                         );
+                        section.children = restart_markers;
+                        sections.push(section);
                     }
 
                     pos = entropy_end;
@@ -203,6 +308,14 @@ impl ImageFormat for JpegParser {
                         section = section.with_description(desc);
                     }
 
+                    // Split table segments into their individual tables
+                    let payload_start = segment_start + 4;
+                    section.children = match marker {
+                        0xDB => Self::parse_dqt_tables(data, payload_start, segment_end),
+                        0xC4 => Self::parse_dht_tables(data, payload_start, segment_end),
+                        _ => Vec::new(),
+                    };
+
                     sections.push(section);
                     pos = segment_end;
                 }
@@ -250,4 +363,83 @@ mod tests {
         assert_eq!(sections[0].name, "SOI (Start of Image)");
         assert!(sections[1].name.contains("APP0"));
     }
+
+    /// Build a JPEG with one 8-bit DQT, one DHT (DC table, 2 symbols), SOF0,
+    /// SOS, scan data containing a stuffed byte and RST0, then EOI.
+    fn build_test_jpeg() -> Vec<u8> {
+        let mut jpeg = vec![0xFF, 0xD8];
+        // DQT: length 67 = 2 + 1 + 64
+        jpeg.extend_from_slice(&[0xFF, 0xDB, 0x00, 0x43, 0x00]);
+        jpeg.extend_from_slice(&[0x01; 64]);
+        // DHT: length 2 + 1 + 16 + 2 = 21
+        jpeg.extend_from_slice(&[0xFF, 0xC4, 0x00, 0x15, 0x00]);
+        let mut counts = [0u8; 16];
+        counts[1] = 2;
+        jpeg.extend_from_slice(&counts);
+        jpeg.extend_from_slice(&[0x00, 0x01]);
+        // SOF0: length 11, 8-bit, 1x1, 1 component
+        jpeg.extend_from_slice(&[
+            0xFF, 0xC0, 0x00, 0x0B, 0x08, 0x00, 0x01, 0x00, 0x01, 0x01, 0x01, 0x11, 0x00,
+        ]);
+        // SOS: length 8, 1 component
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x08, 0x01, 0x01, 0x00, 0x00, 0x3F, 0x00]);
+        // Scan data with stuffed FF 00 and a restart marker
+        jpeg.extend_from_slice(&[0x12, 0xFF, 0x00, 0x34, 0xFF, 0xD0, 0x56]);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_parse_table_children() {
+        let sections = JpegParser.parse(&build_test_jpeg()).unwrap();
+
+        let dqt = sections.iter().find(|s| s.name.starts_with("DQT")).unwrap();
+        assert_eq!(dqt.children.len(), 1);
+        assert_eq!(dqt.children[0].name, "Quantization Table 0");
+        assert_eq!(dqt.children[0].end - dqt.children[0].start, 65);
+        assert_eq!(dqt.children[0].risk, RiskLevel::High);
+
+        let dht = sections.iter().find(|s| s.name.starts_with("DHT")).unwrap();
+        assert_eq!(dht.children.len(), 1);
+        assert_eq!(dht.children[0].name, "Huffman Table (DC 0)");
+        assert_eq!(dht.children[0].end, dht.end);
+    }
+
+    #[test]
+    fn test_parse_scan_data_and_restart_markers() {
+        let jpeg = build_test_jpeg();
+        let sections = JpegParser.parse(&jpeg).unwrap();
+
+        let scan = sections
+            .iter()
+            .find(|s| s.name == "Entropy-Coded Data")
+            .unwrap();
+        assert_eq!(scan.risk, RiskLevel::Safe);
+        assert_eq!(scan.end, jpeg.len() - 2); // stops at EOI
+
+        assert_eq!(scan.children.len(), 1);
+        let rst = &scan.children[0];
+        assert_eq!(rst.name, "RST0");
+        assert_eq!(&jpeg[rst.start..rst.end], &[0xFF, 0xD0]);
+        assert_eq!(rst.risk, RiskLevel::High);
+
+        assert_eq!(sections.last().unwrap().name, "EOI (End of Image)");
+    }
+
+    #[test]
+    fn test_scan_data_ranges() {
+        let jpeg = build_test_jpeg();
+        let ranges = JpegParser::scan_data_ranges(&jpeg);
+        assert_eq!(ranges, vec![jpeg.len() - 9..jpeg.len() - 2]);
+        assert!(JpegParser::scan_data_ranges(b"BM not a jpeg").is_empty());
+    }
+
+    #[test]
+    fn test_scan_data_stops_at_next_marker() {
+        // Progressive files have DHT/SOS markers between scans
+        let data = [0x11, 0x22, 0xFF, 0xC4, 0x00];
+        let (end, markers) = JpegParser::scan_entropy_data(&data, 0);
+        assert_eq!(end, 2);
+        assert!(markers.is_empty());
+    }
 }
@@ -7,11 +7,14 @@ mod bmp;
 mod bytes;
 mod gif;
 mod jpeg;
+mod netpbm;
+pub mod signatures;
 pub mod traits;
 
 pub use bmp::BmpParser;
 pub use gif::GifParser;
 pub use jpeg::JpegParser;
+pub use netpbm::NetpbmParser;
 pub use traits::{FileSection, ImageFormat, RiskLevel};
 
 /// Returns `true` if the data is in an animated-capable format (currently GIF).
@@ -37,9 +40,61 @@ pub fn detect_format(data: &[u8]) -> Option<Box<dyn ImageFormat>> {
         return Some(Box::new(gif));
     }
 
+    let netpbm = NetpbmParser;
+    if netpbm.can_parse(data) {
+        return Some(Box::new(netpbm));
+    }
+
     None
 }
 
+/// A format the user can force a file to be parsed as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatKind {
+    Bmp,
+    Jpeg,
+    Gif,
+    Netpbm,
+}
+
+impl FormatKind {
+    /// Every format, in the order offered in the menu
+    pub const ALL: [FormatKind; 4] = [
+        FormatKind::Bmp,
+        FormatKind::Jpeg,
+        FormatKind::Gif,
+        FormatKind::Netpbm,
+    ];
+
+    /// Display name
+    pub fn label(self) -> &'static str {
+        match self {
+            FormatKind::Bmp => "BMP",
+            FormatKind::Jpeg => "JPEG",
+            FormatKind::Gif => "GIF",
+            FormatKind::Netpbm => "Netpbm (PBM/PGM/PPM)",
+        }
+    }
+
+    fn parser(self) -> Box<dyn ImageFormat> {
+        match self {
+            FormatKind::Bmp => Box::new(BmpParser),
+            FormatKind::Jpeg => Box::new(JpegParser),
+            FormatKind::Gif => Box::new(GifParser),
+            FormatKind::Netpbm => Box::new(NetpbmParser),
+        }
+    }
+}
+
+/// Trim sections (and their children) to `data_len`, dropping any left empty
+fn clamp_sections(sections: &mut Vec<FileSection>, data_len: usize) {
+    sections.retain_mut(|section| {
+        section.end = section.end.min(data_len);
+        clamp_sections(&mut section.children, data_len);
+        section.start < section.end
+    });
+}
+
 /// Fill gaps between parsed sections with "Unknown" sections.
 ///
 /// Sorts sections by start offset, then inserts `RiskLevel::Unknown` sections
@@ -100,10 +155,47 @@ pub fn parse_file(data: &[u8]) -> Option<Vec<FileSection>> {
     }
 }
 
+/// Parse `data` as `format` even if its signature doesn't match (e.g. a
+/// headerless or deliberately broken file). Whatever the parser can't place
+/// is left Unknown.
+pub fn parse_file_as(data: &[u8], format: FormatKind) -> Vec<FileSection> {
+    let mut sections = format.parser().parse_unchecked(data).unwrap_or_default();
+    clamp_sections(&mut sections, data.len());
+    fill_gaps(&mut sections, data.len());
+    sections
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_file_as_ignores_signature() {
+        // A GIF header with its signature wiped is no longer detected...
+        let mut data = b"GIF89a".to_vec();
+        data.extend_from_slice(&[1, 0, 1, 0, 0, 0, 0]);
+        data.push(0x3B);
+        data[..3].copy_from_slice(b"XXX");
+        assert_eq!(
+            parse_file(&data)
+                .unwrap_or_default()
+                .iter()
+                .filter(|s| s.risk != RiskLevel::Unknown)
+                .count(),
+            0
+        );
+        // ...but forcing GIF still finds its structure
+        let forced = parse_file_as(&data, FormatKind::Gif);
+        assert_eq!(forced[0].name, "Header");
+        assert_eq!(forced.last().unwrap().end, data.len());
+
+        // Sections past the end of short data are trimmed away
+        let short = parse_file_as(&[0xFF], FormatKind::Jpeg);
+        assert_eq!(short[0].end, 1);
+        assert!(short.iter().all(|s| s.end <= 1));
+        assert!(parse_file_as(&[], FormatKind::Netpbm).is_empty());
+    }
+
     #[test]
     fn test_is_animated_format_gif() {
         let mut data = b"GIF89a".to_vec();
@@ -202,6 +294,27 @@ mod tests {
         assert!(parse_file(&data).is_none());
     }
 
+    #[test]
+    fn test_detect_format_extension() {
+        let extension = |data: &[u8]| detect_format(data).map(|f| f.extension(data));
+        assert_eq!(extension(b"BM\0\0"), Some("bmp"));
+        assert_eq!(extension(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("jpg"));
+        assert_eq!(extension(b"GIF89a"), Some("gif"));
+        assert_eq!(extension(b"P5\n2 2\n255\n"), Some("pgm"));
+        assert_eq!(extension(b"P6\n"), Some("ppm"));
+        assert_eq!(extension(&[0x00, 0x01]), None);
+    }
+
+    #[test]
+    fn test_parse_file_detects_netpbm() {
+        let mut data = b"P5\n2 2\n255\n".to_vec();
+        data.extend_from_slice(&[0x10, 0x20, 0x30, 0x40]);
+        let sections = parse_file(&data).unwrap();
+        assert_eq!(sections[0].name, "Header");
+        assert_eq!(sections[1].name, "Pixel Data");
+        assert_eq!(sections[1].end, data.len());
+    }
+
     #[test]
     fn test_parse_file_bmp_truncated_returns_partial_with_unknown() {
         // Valid BMP signature but truncated (only 10 bytes — not enough for full header)
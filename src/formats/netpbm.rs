@@ -0,0 +1,256 @@
+//! Netpbm (PBM/PGM/PPM) file format parser
+//!
+//! Netpbm structure:
+//! - Magic number: "P1"-"P6" (ASCII)
+//! - Whitespace-separated header fields: width, height, and (except PBM) maxval.
+//!   `#` starts a comment that runs to the end of the line.
+//! - A single whitespace byte, then the pixel payload:
+//!   - P1/P2/P3: ASCII decimal numbers separated by whitespace
+//!   - P4: packed 1-bit rows (each row padded to a whole byte)
+//!   - P5/P6: raw 8-bit (or 16-bit big-endian when maxval > 255) samples
+
+use super::traits::{FileSection, ImageFormat, ParseError, RiskLevel};
+
+/// Netpbm format parser
+pub struct NetpbmParser;
+
+/// A header token (or comment) located in the file
+struct Token {
+    start: usize,
+    end: usize,
+}
+
+impl NetpbmParser {
+    /// Skip whitespace and comments starting at `pos`, collecting comment ranges.
+    /// Returns the next token, or None if the data ends first.
+    fn next_token(data: &[u8], mut pos: usize, comments: &mut Vec<Token>) -> Option<Token> {
+        loop {
+            match data.get(pos)? {
+                b if b.is_ascii_whitespace() => pos += 1,
+                b'#' => {
+                    let start = pos;
+                    while pos < data.len() && data[pos] != b'\n' && data[pos] != b'\r' {
+                        pos += 1;
+                    }
+                    comments.push(Token { start, end: pos });
+                }
+                _ => break,
+            }
+        }
+        let start = pos;
+        while pos < data.len() && !data[pos].is_ascii_whitespace() && data[pos] != b'#' {
+            pos += 1;
+        }
+        Some(Token { start, end: pos })
+    }
+
+    /// Parse a token as an unsigned decimal number
+    fn token_value(data: &[u8], token: &Token) -> Option<usize> {
+        std::str::from_utf8(&data[token.start..token.end])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// Expected payload size in bytes for the binary variants (P4/P5/P6), or an
+    /// error when the header's dimensions overflow
+    fn binary_payload_len(
+        kind: u8,
+        width: usize,
+        height: usize,
+        maxval: usize,
+    ) -> Result<usize, ParseError> {
+        let bytes_per_sample = if maxval > 255 { 2 } else { 1 };
+        let len = match kind {
+            b'4' => width.div_ceil(8).checked_mul(height),
+            b'5' => width
+                .checked_mul(height)
+                .and_then(|n| n.checked_mul(bytes_per_sample)),
+            _ => width
+                .checked_mul(height)
+                .and_then(|n| n.checked_mul(3 * bytes_per_sample)),
+        };
+        len.ok_or(ParseError::InvalidHeader("image dimensions are too large"))
+    }
+}
+
+impl ImageFormat for NetpbmParser {
+    fn can_parse(&self, data: &[u8]) -> bool {
+        // "P1"-"P6" followed by whitespace
+        data.len() >= 3
+            && data[0] == b'P'
+            && (b'1'..=b'6').contains(&data[1])
+            && data[2].is_ascii_whitespace()
+    }
+
+    fn extension(&self, data: &[u8]) -> &'static str {
+        // Bitmap (P1/P4), graymap (P2/P5), pixmap (P3/P6)
+        match data.get(1) {
+            Some(b'1' | b'4') => "pbm",
+            Some(b'2' | b'5') => "pgm",
+            Some(b'3' | b'6') => "ppm",
+            _ => "pnm",
+        }
+    }
+
+    fn parse_unchecked(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
+        let Some(&kind) = data.get(1) else {
+            return Ok(Vec::new());
+        };
+        let has_maxval = !matches!(kind, b'1' | b'4');
+        let mut comments = Vec::new();
+        let mut header = FileSection::new("Header", 0, 2, RiskLevel::Critical).with_child(
+            FileSection::new("Magic Number", 0, 2, RiskLevel::Critical).with_description(
+                match kind {
+                    b'1' => "P1 - ASCII bitmap (PBM)",
+                    b'2' => "P2 - ASCII graymap (PGM)",
+                    b'3' => "P3 - ASCII pixmap (PPM)",
+                    b'4' => "P4 - binary bitmap (PBM)",
+                    b'5' => "P5 - binary graymap (PGM)",
+                    _ => "P6 - binary pixmap (PPM)",
+                },
+            ),
+        );
+
+        // Header fields: width, height, and maxval (except for bitmaps)
+        let field_names: &[&'static str] = if has_maxval {
+            &["Width", "Height", "Max Value"]
+        } else {
+            &["Width", "Height"]
+        };
+        let mut values = Vec::with_capacity(field_names.len());
+        let mut pos = 2;
+        for &name in field_names {
+            let Some(token) = Self::next_token(data, pos, &mut comments) else {
+                // Truncated header — return what we have
+                header.end = data.len();
+                return Ok(vec![header]);
+            };
+            let risk = if name == "Max Value" {
+                RiskLevel::High
+            } else {
+                RiskLevel::Critical
+            };
+            values.push(Self::token_value(data, &token));
+            header = header.with_child(FileSection::new(name, token.start, token.end, risk));
+            pos = token.end;
+        }
+
+        // Exactly one whitespace byte separates the header from the payload
+        let payload_start = (pos + 1).min(data.len());
+        header.end = payload_start;
+        for comment in comments {
+            header = header.with_child(
+                FileSection::new("Comment", comment.start, comment.end, RiskLevel::Safe)
+                    .with_description("Header comment - safe to edit"),
+            );
+        }
+        header.children.sort_by_key(|s| s.start);
+
+        let mut sections = vec![header];
+        if payload_start >= data.len() {
+            return Ok(sections);
+        }
+
+        let pixel_data = if kind >= b'4' {
+            let width = values[0].unwrap_or(0);
+            let height = values[1].unwrap_or(0);
+            let maxval = values.get(2).copied().flatten().unwrap_or(255);
+            let expected = Self::binary_payload_len(kind, width, height, maxval)?;
+            let payload_end = payload_start
+                .saturating_add(expected)
+                .min(data.len())
+                .max(payload_start + 1);
+            FileSection::new("Pixel Data", payload_start, payload_end, RiskLevel::Safe)
+                .with_description("Raw pixel samples - the fun part to glitch!")
+        } else {
+            FileSection::new("Pixel Data", payload_start, data.len(), RiskLevel::Caution)
+                .with_description(
+                    "ASCII pixel values - changing digits shifts values, other characters may break parsing",
+                )
+        };
+        sections.push(pixel_data);
+
+        Ok(sections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_parse_netpbm() {
+        let parser = NetpbmParser;
+
+        assert!(parser.can_parse(b"P6\n1 1\n255\n"));
+        assert!(parser.can_parse(b"P1 1 1 0"));
+
+        assert!(!parser.can_parse(b""));
+        assert!(!parser.can_parse(b"P6"));
+        assert!(!parser.can_parse(b"P7\n"));
+        assert!(!parser.can_parse(b"P0\n"));
+        assert!(!parser.can_parse(b"BM\x00"));
+    }
+
+    #[test]
+    fn test_parse_binary_ppm() {
+        let mut ppm = b"P6\n# made by hand\n2 1\n255\n".to_vec();
+        let payload_start = ppm.len();
+        ppm.extend_from_slice(&[0xFF, 0x00, 0x00, 0x00, 0xFF, 0x00]);
+
+        let sections = NetpbmParser.parse(&ppm).unwrap();
+        assert_eq!(sections.len(), 2);
+
+        let header = &sections[0];
+        assert_eq!(header.risk, RiskLevel::Critical);
+        assert_eq!(header.end, payload_start);
+        let names: Vec<&str> = header.children.iter().map(|c| c.name.as_ref()).collect();
+        assert_eq!(
+            names,
+            ["Magic Number", "Comment", "Width", "Height", "Max Value"]
+        );
+        let maxval = &header.children[4];
+        assert_eq!(&ppm[maxval.start..maxval.end], b"255");
+        assert_eq!(maxval.risk, RiskLevel::High);
+
+        let pixels = &sections[1];
+        assert_eq!(pixels.name, "Pixel Data");
+        assert_eq!(pixels.start, payload_start);
+        assert_eq!(pixels.end, ppm.len());
+        assert_eq!(pixels.risk, RiskLevel::Safe);
+    }
+
+    #[test]
+    fn test_parse_pbm_has_no_maxval() {
+        // 10x2 bitmap: 2 bytes per row, plus trailing junk left for fill_gaps
+        let mut pbm = b"P4 10 2\n".to_vec();
+        pbm.extend_from_slice(&[0xAA, 0x80, 0x55, 0x40, 0x00]);
+
+        let sections = NetpbmParser.parse(&pbm).unwrap();
+        assert_eq!(sections[0].children.len(), 3); // magic, width, height
+        assert_eq!(sections[1].end - sections[1].start, 4);
+    }
+
+    #[test]
+    fn test_parse_ascii_pgm_payload_is_caution() {
+        let pgm = b"P2\n2 2\n15\n0 5\n10 15\n";
+        let sections = NetpbmParser.parse(pgm).unwrap();
+        assert_eq!(sections[1].risk, RiskLevel::Caution);
+        assert_eq!(sections[1].end, pgm.len());
+    }
+
+    #[test]
+    fn test_parse_truncated_header() {
+        let sections = NetpbmParser.parse(b"P5\n640").unwrap();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].end, 6);
+    }
+
+    #[test]
+    fn test_parse_overflowing_dimensions_is_an_error() {
+        let data = format!("P6\n{} {}\n255\n\x00\x00\x00", usize::MAX / 2, 3);
+        let result = NetpbmParser.parse_unchecked(data.as_bytes());
+        assert!(matches!(result, Err(ParseError::InvalidHeader(_))));
+    }
+}
@@ -0,0 +1,92 @@
+//! Scan arbitrary data for embedded image file signatures
+//!
+//! Used to carve images out of data dumps: every offset where a known format's
+//! magic bytes appear is reported, reusing each parser's own `can_parse` check.
+
+use super::traits::ImageFormat;
+use super::{BmpParser, GifParser, JpegParser};
+
+/// PNG file signature (there is no PNG structure parser, only its magic)
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Stop scanning after this many hits so noisy data can't flood the results list
+pub const MAX_SIGNATURE_HITS: usize = 10_000;
+
+/// An offset where a known image signature was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureHit {
+    /// Byte offset of the signature's first byte
+    pub offset: usize,
+    /// Short format name (e.g. "JPEG")
+    pub format: &'static str,
+}
+
+/// Identify the image format whose signature starts at the beginning of `data`
+fn signature_at(data: &[u8]) -> Option<&'static str> {
+    match data.first()? {
+        b'B' if BmpParser.can_parse(data) => {
+            // "BM" alone is common in arbitrary data; real BMPs have zeroed
+            // reserved fields at bytes 6-9
+            (data.len() >= 10 && data[6..10] == [0, 0, 0, 0]).then_some("BMP")
+        }
+        0xFF if JpegParser.can_parse(data) => Some("JPEG"),
+        b'G' if GifParser.can_parse(data) => Some("GIF"),
+        0x89 if data.starts_with(PNG_SIGNATURE) => Some("PNG"),
+        _ => None,
+    }
+}
+
+/// Find every offset in `data` where a BMP, JPEG, PNG, or GIF signature begins.
+/// Returns the hits (capped at `MAX_SIGNATURE_HITS`) and whether the cap was reached.
+pub fn scan_for_signatures(data: &[u8]) -> (Vec<SignatureHit>, bool) {
+    let mut hits = Vec::new();
+    for offset in 0..data.len() {
+        if let Some(format) = signature_at(&data[offset..]) {
+            if hits.len() == MAX_SIGNATURE_HITS {
+                return (hits, true);
+            }
+            hits.push(SignatureHit { offset, format });
+        }
+    }
+    (hits, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_finds_embedded_signatures() {
+        let mut data = vec![0u8; 16];
+        data.extend_from_slice(&[0xFF, 0xD8, 0xFF, 0xE0]);
+        data.extend_from_slice(&[0u8; 8]);
+        data.extend_from_slice(PNG_SIGNATURE);
+        data.extend_from_slice(b"GIF89a");
+        data.extend_from_slice(b"BM\x10\x00\x00\x00\x00\x00\x00\x00");
+
+        let (hits, truncated) = scan_for_signatures(&data);
+        assert!(!truncated);
+        let found: Vec<(usize, &str)> = hits.iter().map(|h| (h.offset, h.format)).collect();
+        assert_eq!(
+            found,
+            vec![(16, "JPEG"), (28, "PNG"), (36, "GIF"), (42, "BMP")]
+        );
+    }
+
+    #[test]
+    fn test_scan_skips_implausible_bmp() {
+        // "BM" followed by nonzero reserved bytes is just text
+        let (hits, _) = scan_for_signatures(b"BMW is not a bitmap header");
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_scan_caps_hits() {
+        let data: Vec<u8> = std::iter::repeat_n([0xFF, 0xD8, 0xFF], MAX_SIGNATURE_HITS + 5)
+            .flatten()
+            .collect();
+        let (hits, truncated) = scan_for_signatures(&data);
+        assert_eq!(hits.len(), MAX_SIGNATURE_HITS);
+        assert!(truncated);
+    }
+}
@@ -8,12 +8,15 @@ use std::fmt;
 pub enum ParseError {
     /// The file signature does not match the expected format.
     InvalidSignature,
+    /// A header field holds a value the parser can't work with.
+    InvalidHeader(&'static str),
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ParseError::InvalidSignature => write!(f, "Invalid file signature"),
+            ParseError::InvalidHeader(reason) => write!(f, "Invalid header: {}", reason),
         }
     }
 }
@@ -44,6 +47,26 @@ impl RiskLevel {
             RiskLevel::Unknown => "Unknown",
         }
     }
+
+    /// One-line explanation of what editing a section at this level risks
+    pub fn description(self) -> &'static str {
+        match self {
+            RiskLevel::Safe => "Safe to edit; won't break the file structure",
+            RiskLevel::Caution => "May change how the image looks but won't corrupt the file",
+            RiskLevel::High => "Likely to corrupt the file or make it unreadable",
+            RiskLevel::Critical => "Editing will almost certainly break the file",
+            RiskLevel::Unknown => "Unrecognized data, no special protection",
+        }
+    }
+
+    /// Every risk level, from safest to most dangerous, then Unknown
+    pub const ALL: [RiskLevel; 5] = [
+        RiskLevel::Safe,
+        RiskLevel::Caution,
+        RiskLevel::High,
+        RiskLevel::Critical,
+        RiskLevel::Unknown,
+    ];
 }
 
 /// A section of the file with metadata
@@ -97,8 +120,20 @@ impl FileSection {
 /// Trait for image format parsers
 pub trait ImageFormat {
     /// Parse the file structure and return sections
-    fn parse(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError>;
+    fn parse(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError> {
+        if !self.can_parse(data) {
+            return Err(ParseError::InvalidSignature);
+        }
+        self.parse_unchecked(data)
+    }
+
+    /// Parse as if `data` were in this format, whatever its signature says
+    /// (for a user-forced format). Sections may run past the end of short data.
+    fn parse_unchecked(&self, data: &[u8]) -> Result<Vec<FileSection>, ParseError>;
 
     /// Check if this parser can handle the given data
     fn can_parse(&self, data: &[u8]) -> bool;
+
+    /// File extension (without the dot) to suggest when saving this data
+    fn extension(&self, data: &[u8]) -> &'static str;
 }
@@ -0,0 +1,541 @@
+//! Customizable keyboard shortcuts for the global app actions
+//!
+//! Each `ShortcutAction` has built-in default bindings; `KeyBindings` stores
+//! only the actions the user rebound, so actions added in later versions pick
+//! up their defaults. Bindings are saved as text like `"Ctrl+Shift+Z"`, where
+//! Ctrl also means Cmd on macOS. Bindings need Ctrl or Alt (except function
+//! keys) so they don't swallow typed hex/text, can't use Tab, and can't take
+//! the editor's fixed `RESERVED` shortcuts.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// A global action that can be bound to keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    CommandPalette,
+    Open,
+    Export,
+    QuickExport,
+    CloseTab,
+    NextTab,
+    PreviousTab,
+    Undo,
+    Redo,
+    FindReplace,
+    GoToOffset,
+    MatchingMarker,
+    CreateSavePoint,
+    NameSavePoint,
+    AddBookmark,
+    RefreshPreview,
+    RepeatLastEffect,
+    ToggleComparison,
+    RepeatByteJumpNext,
+    RepeatByteJumpPrevious,
+    ToggleEditMode,
+    ToggleWriteMode,
+    ShortcutsHelp,
+}
+
+impl ShortcutAction {
+    /// Every action, in the order the Preferences page lists them
+    pub const ALL: [Self; 23] = [
+        Self::CommandPalette,
+        Self::Open,
+        Self::Export,
+        Self::QuickExport,
+        Self::CloseTab,
+        Self::NextTab,
+        Self::PreviousTab,
+        Self::Undo,
+        Self::Redo,
+        Self::FindReplace,
+        Self::GoToOffset,
+        Self::MatchingMarker,
+        Self::CreateSavePoint,
+        Self::NameSavePoint,
+        Self::AddBookmark,
+        Self::RefreshPreview,
+        Self::RepeatLastEffect,
+        Self::ToggleComparison,
+        Self::RepeatByteJumpNext,
+        Self::RepeatByteJumpPrevious,
+        Self::ToggleEditMode,
+        Self::ToggleWriteMode,
+        Self::ShortcutsHelp,
+    ];
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CommandPalette => "Command palette",
+            Self::Open => "Open file",
+            Self::Export => "Export file",
+            Self::QuickExport => "Quick export",
+            Self::CloseTab => "Close tab",
+            Self::NextTab => "Next tab",
+            Self::PreviousTab => "Previous tab",
+            Self::Undo => "Undo",
+            Self::Redo => "Redo",
+            Self::FindReplace => "Find & Replace",
+            Self::GoToOffset => "Go to offset",
+            Self::MatchingMarker => "Go to matching marker",
+            Self::CreateSavePoint => "Create save point",
+            Self::NameSavePoint => "Create named save point",
+            Self::AddBookmark => "Add bookmark",
+            Self::RefreshPreview => "Refresh preview",
+            Self::RepeatLastEffect => "Repeat last effect",
+            Self::ToggleComparison => "Toggle comparison mode",
+            Self::RepeatByteJumpNext => "Next occurrence of last byte",
+            Self::RepeatByteJumpPrevious => "Previous occurrence of last byte",
+            Self::ToggleEditMode => "Toggle Hex/ASCII mode",
+            Self::ToggleWriteMode => "Toggle Insert/Overwrite",
+            Self::ShortcutsHelp => "Keyboard shortcuts help",
+        }
+    }
+
+    /// Built-in bindings
+    pub fn default_bindings(self) -> Vec<KeyBinding> {
+        use egui::Key;
+        let ctrl = KeyBinding::ctrl;
+        let ctrl_shift = KeyBinding::ctrl_shift;
+        match self {
+            Self::CommandPalette => vec![ctrl_shift(Key::P), ctrl(Key::P)],
+            Self::Open => vec![ctrl(Key::O)],
+            Self::Export => vec![ctrl(Key::E)],
+            Self::QuickExport => vec![ctrl_shift(Key::E)],
+            Self::CloseTab => vec![ctrl(Key::W)],
+            Self::NextTab => vec![ctrl(Key::PageDown)],
+            Self::PreviousTab => vec![ctrl(Key::PageUp)],
+            Self::Undo => vec![ctrl(Key::Z)],
+            Self::Redo => vec![ctrl_shift(Key::Z), ctrl(Key::Y)],
+            Self::FindReplace => vec![ctrl(Key::F)],
+            Self::GoToOffset => vec![ctrl(Key::G)],
+            Self::MatchingMarker => vec![ctrl(Key::CloseBracket)],
+            Self::CreateSavePoint => vec![ctrl(Key::S)],
+            Self::NameSavePoint => vec![ctrl_shift(Key::S)],
+            Self::AddBookmark => vec![ctrl(Key::D)],
+            Self::RefreshPreview => vec![ctrl(Key::R)],
+            Self::RepeatLastEffect => vec![ctrl_shift(Key::R)],
+            Self::ToggleComparison => vec![ctrl_shift(Key::C)],
+            Self::RepeatByteJumpNext => vec![ctrl(Key::Period)],
+            Self::RepeatByteJumpPrevious => vec![ctrl(Key::Comma)],
+            Self::ToggleEditMode => vec![ctrl(Key::M)],
+            Self::ToggleWriteMode => vec![ctrl(Key::I)],
+            Self::ShortcutsHelp => vec![KeyBinding::new(Key::F1)],
+        }
+    }
+}
+
+/// Fixed shortcuts handled outside `KeyBindings`, which actions can't take
+pub const RESERVED: [(KeyBinding, &str); 4] = [
+    (KeyBinding::ctrl(egui::Key::C), "Copy"),
+    (KeyBinding::ctrl(egui::Key::V), "Paste"),
+    (KeyBinding::ctrl_shift(egui::Key::V), "Paste tiled"),
+    (KeyBinding::ctrl(egui::Key::A), "Select all"),
+];
+
+/// A key plus the modifiers that must be held (exactly) with it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyBinding {
+    pub key: egui::Key,
+    /// Ctrl, or Cmd on macOS
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    /// `key` with no modifiers
+    pub const fn new(key: egui::Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Ctrl/Cmd + `key`
+    pub const fn ctrl(key: egui::Key) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::new(key)
+        }
+    }
+
+    /// Ctrl/Cmd + Shift + `key`
+    pub const fn ctrl_shift(key: egui::Key) -> Self {
+        Self {
+            shift: true,
+            ..Self::ctrl(key)
+        }
+    }
+
+    /// `key` with the modifiers in `modifiers`
+    pub fn from_modifiers(key: egui::Key, modifiers: egui::Modifiers) -> Self {
+        Self {
+            key,
+            ctrl: modifiers.ctrl || modifiers.mac_cmd,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+        }
+    }
+
+    /// Whether the key is F1-F35, which may be bound without modifiers
+    fn is_function_key(&self) -> bool {
+        self.key
+            .name()
+            .strip_prefix('F')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    /// The fixed shortcut this binding would shadow, if any
+    pub fn reserved_for(&self) -> Option<&'static str> {
+        RESERVED
+            .iter()
+            .find(|(binding, _)| binding == self)
+            .map(|(_, label)| *label)
+    }
+
+    /// Why this binding can't be given to an action, if it can't
+    pub fn rejection(&self) -> Option<String> {
+        if self.key == egui::Key::Tab {
+            // egui moves keyboard focus on every Tab press whatever the
+            // modifiers, which leaves the hex editor ignoring keys
+            Some(format!(
+                "{} can't be used; Tab moves keyboard focus",
+                self.display()
+            ))
+        } else if !self.ctrl && !self.alt && !self.is_function_key() {
+            Some(format!(
+                "{} would block typing; shortcuts need Ctrl or Alt (except F-keys)",
+                self.display()
+            ))
+        } else {
+            self.reserved_for()
+                .map(|label| format!("{} is reserved for {}", self.display(), label))
+        }
+    }
+
+    /// Whether this binding was pressed this frame
+    pub fn pressed(&self, i: &egui::InputState) -> bool {
+        i.key_pressed(self.key) && Self::from_modifiers(self.key, i.modifiers) == *self
+    }
+
+    /// Text for menus and Preferences, using the platform's modifier names
+    pub fn display(&self) -> String {
+        let mut text = String::new();
+        if self.ctrl {
+            // Space gives the character after ⌘ more breathing room
+            text.push_str(if cfg!(target_os = "macos") {
+                "⌘ "
+            } else {
+                "Ctrl+"
+            });
+        }
+        if self.alt {
+            text.push_str("Alt+");
+        }
+        if self.shift {
+            text.push_str("Shift+");
+        }
+        text.push_str(self.key.symbol_or_name());
+        text
+    }
+}
+
+/// Platform-neutral form used in the settings file, e.g. "Ctrl+Shift+Z"
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let key_name = parts.pop().unwrap_or_default();
+        let key = egui::Key::from_name(key_name)
+            .ok_or_else(|| format!("Unknown key in shortcut: {}", text))?;
+        let mut binding = Self::new(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" => binding.ctrl = true,
+                "shift" => binding.shift = true,
+                "alt" => binding.alt = true,
+                _ => return Err(format!("Unknown modifier in shortcut: {}", text)),
+            }
+        }
+        Ok(binding)
+    }
+}
+
+impl From<KeyBinding> for String {
+    fn from(binding: KeyBinding) -> Self {
+        binding.to_string()
+    }
+}
+
+/// The user's rebound actions; everything else uses its defaults
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct KeyBindings {
+    overrides: BTreeMap<ShortcutAction, Vec<KeyBinding>>,
+}
+
+/// Lenient, so a hand-edited or newer settings file loads: unknown actions,
+/// keys and modifiers are skipped with a warning instead of failing every
+/// other setting along with them
+impl<'de> Deserialize<'de> for KeyBindings {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serde_json::Value::Object(raw) = serde_json::Value::deserialize(deserializer)? else {
+            log::warn!("Ignoring keyboard shortcuts: expected a map of actions");
+            return Ok(Self::default());
+        };
+        let mut overrides = BTreeMap::new();
+        for (name, value) in raw {
+            let action = serde_json::Value::String(name.clone());
+            let Ok(action) = serde_json::from_value::<ShortcutAction>(action) else {
+                log::warn!("Ignoring shortcuts for unknown action: {}", name);
+                continue;
+            };
+            let serde_json::Value::Array(entries) = value else {
+                log::warn!("Ignoring shortcuts for {}: expected a list", name);
+                continue;
+            };
+            let listed = entries.len();
+            let bindings: Vec<KeyBinding> = entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let serde_json::Value::String(text) = entry else {
+                        log::warn!("Ignoring shortcut for {}: {} is not text", name, entry);
+                        return None;
+                    };
+                    KeyBinding::try_from(text)
+                        .map_err(|e| log::warn!("Ignoring shortcut for {}: {}", name, e))
+                        .ok()
+                })
+                .collect();
+            // Nothing usable left: keep the defaults rather than unbinding
+            if bindings.is_empty() && listed > 0 {
+                continue;
+            }
+            overrides.insert(action, bindings);
+        }
+        Ok(Self { overrides })
+    }
+}
+
+impl KeyBindings {
+    /// Current bindings for `action` (empty = unbound). Hand-edited overrides
+    /// that `KeyBinding::rejection` refuses are ignored.
+    pub fn bindings(&self, action: ShortcutAction) -> Vec<KeyBinding> {
+        match self.overrides.get(&action) {
+            Some(bindings) => bindings
+                .iter()
+                .copied()
+                .filter(|b| b.rejection().is_none())
+                .collect(),
+            None => action.default_bindings(),
+        }
+    }
+
+    /// Text of all of `action`'s bindings, e.g. for the help dialog
+    pub fn display_all(&self, action: ShortcutAction) -> String {
+        let texts: Vec<_> = self
+            .bindings(action)
+            .iter()
+            .map(KeyBinding::display)
+            .collect();
+        texts.join(" / ")
+    }
+
+    /// Whether any of `action`'s bindings was pressed this frame
+    pub fn pressed(&self, action: ShortcutAction, i: &egui::InputState) -> bool {
+        self.bindings(action).iter().any(|b| b.pressed(i))
+    }
+
+    /// Text of `action`'s first binding for menus ("" if unbound)
+    pub fn display(&self, action: ShortcutAction) -> String {
+        self.bindings(action)
+            .first()
+            .map(KeyBinding::display)
+            .unwrap_or_default()
+    }
+
+    /// Bind `action` to `bindings` only
+    pub fn set(&mut self, action: ShortcutAction, bindings: Vec<KeyBinding>) {
+        if bindings == action.default_bindings() {
+            self.overrides.remove(&action);
+        } else {
+            self.overrides.insert(action, bindings);
+        }
+    }
+
+    /// Restore `action`'s default bindings
+    pub fn reset(&mut self, action: ShortcutAction) {
+        self.overrides.remove(&action);
+    }
+
+    /// Whether `action` uses its defaults
+    pub fn is_default(&self, action: ShortcutAction) -> bool {
+        !self.overrides.contains_key(&action)
+    }
+
+    /// Labels of what else `binding` triggers besides `action`: a fixed
+    /// shortcut it's reserved for, then other actions bound to it
+    pub fn conflicts_with(&self, action: ShortcutAction, binding: KeyBinding) -> Vec<&'static str> {
+        binding
+            .reserved_for()
+            .into_iter()
+            .chain(
+                ShortcutAction::ALL
+                    .into_iter()
+                    .filter(|&other| other != action && self.bindings(other).contains(&binding))
+                    .map(ShortcutAction::label),
+            )
+            .collect()
+    }
+
+    /// Every binding shared by more than one action or fixed shortcut, with
+    /// the labels of everything it triggers
+    pub fn conflicts(&self) -> Vec<(KeyBinding, Vec<&'static str>)> {
+        let mut conflicts: Vec<(KeyBinding, Vec<&'static str>)> = RESERVED
+            .iter()
+            .map(|(binding, label)| (*binding, vec![*label]))
+            .collect();
+        for action in ShortcutAction::ALL {
+            for binding in self.bindings(action) {
+                match conflicts.iter_mut().find(|(b, _)| *b == binding) {
+                    Some((_, labels)) => labels.push(action.label()),
+                    None => conflicts.push((binding, vec![action.label()])),
+                }
+            }
+        }
+        conflicts.retain(|(_, labels)| labels.len() > 1);
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binding_text_round_trip() {
+        let binding = KeyBinding::ctrl_shift(egui::Key::Z);
+        assert_eq!(binding.to_string(), "Ctrl+Shift+Z");
+        assert_eq!(KeyBinding::try_from(binding.to_string()), Ok(binding));
+        assert_eq!(
+            KeyBinding::try_from("cmd + CloseBracket".to_string()),
+            Ok(KeyBinding::ctrl(egui::Key::CloseBracket))
+        );
+        assert_eq!(
+            KeyBinding::try_from("F1".to_string()),
+            Ok(KeyBinding::new(egui::Key::F1))
+        );
+        assert!(KeyBinding::try_from("Ctrl+Nope".to_string()).is_err());
+        assert!(KeyBinding::try_from("Hyper+Z".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        assert!(KeyBindings::default().conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_defaults_are_all_valid() {
+        // A Tab binding would also move egui's keyboard focus off the hex editor
+        for action in ShortcutAction::ALL {
+            for binding in action.default_bindings() {
+                assert_eq!(binding.rejection(), None, "{:?}", action);
+            }
+        }
+        assert!(KeyBinding::ctrl(egui::Key::Tab).rejection().is_some());
+    }
+
+    #[test]
+    fn test_rebinding_and_conflicts() {
+        let mut bindings = KeyBindings::default();
+        let ctrl_d = KeyBinding::ctrl(egui::Key::D);
+        assert_eq!(
+            bindings.conflicts_with(ShortcutAction::CreateSavePoint, ctrl_d),
+            ["Add bookmark"]
+        );
+
+        bindings.set(ShortcutAction::CreateSavePoint, vec![ctrl_d]);
+        assert_eq!(bindings.bindings(ShortcutAction::CreateSavePoint), [ctrl_d]);
+        assert_eq!(
+            bindings.conflicts(),
+            [(ctrl_d, vec!["Create save point", "Add bookmark"])]
+        );
+
+        // Only overrides are saved, keyed by action name
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert_eq!(json, r#"{"CreateSavePoint":["Ctrl+D"]}"#);
+        let loaded: KeyBindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, bindings);
+
+        // Setting the defaults back drops the override
+        bindings.set(
+            ShortcutAction::CreateSavePoint,
+            ShortcutAction::CreateSavePoint.default_bindings(),
+        );
+        assert!(bindings.is_default(ShortcutAction::CreateSavePoint));
+    }
+
+    #[test]
+    fn test_bindings_need_a_modifier_and_avoid_reserved_keys() {
+        assert!(KeyBinding::new(egui::Key::A).rejection().is_some());
+        assert!(KeyBinding::new(egui::Key::Num1).rejection().is_some());
+        assert!(KeyBinding::ctrl(egui::Key::C).rejection().is_some());
+        assert!(KeyBinding::new(egui::Key::F1).rejection().is_none());
+        assert!(KeyBinding::new(egui::Key::F12).rejection().is_none());
+        assert!(KeyBinding::ctrl(egui::Key::K).rejection().is_none());
+        let alt_k = KeyBinding::from_modifiers(egui::Key::K, egui::Modifiers::ALT);
+        assert!(alt_k.rejection().is_none());
+
+        let bindings = KeyBindings::default();
+        let ctrl_v = KeyBinding::ctrl(egui::Key::V);
+        assert_eq!(
+            bindings.conflicts_with(ShortcutAction::Open, ctrl_v),
+            ["Paste"]
+        );
+
+        // Hand-edited overrides that would be rejected are ignored
+        let loaded: KeyBindings =
+            serde_json::from_str(r#"{"Undo":["Z","Ctrl+A","Alt+Z"]}"#).unwrap();
+        assert_eq!(
+            loaded.bindings(ShortcutAction::Undo),
+            [KeyBinding::from_modifiers(
+                egui::Key::Z,
+                egui::Modifiers::ALT
+            )]
+        );
+    }
+
+    #[test]
+    fn test_display_all_lists_every_binding() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(bindings.display_all(ShortcutAction::ShortcutsHelp), "F1");
+        bindings.set(ShortcutAction::ShortcutsHelp, Vec::new());
+        assert_eq!(bindings.display_all(ShortcutAction::ShortcutsHelp), "");
+    }
+}
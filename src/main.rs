@@ -6,14 +6,36 @@
 mod app;
 mod editor;
 mod formats;
+mod keybindings;
 mod settings;
 mod ui;
 
 use app::BendApp;
 use eframe::NativeOptions;
 use settings::AppSettings;
+use std::io::Read;
 use std::sync::Arc;
 
+/// Bytes piped in on standard input when started as `bend-rs -`. Read up
+/// front, since the GUI event loop can't block on stdin later.
+fn read_stdin_input() -> Option<Vec<u8>> {
+    if std::env::args_os().nth(1)? != "-" {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    match std::io::stdin().lock().read_to_end(&mut bytes) {
+        Ok(_) if !bytes.is_empty() => Some(bytes),
+        Ok(_) => {
+            log::warn!("No data on stdin; starting without a file");
+            None
+        }
+        Err(e) => {
+            log::error!("Failed to read stdin: {}", e);
+            None
+        }
+    }
+}
+
 fn load_icon() -> Option<egui::IconData> {
     #[cfg(target_os = "macos")]
     let icon_bytes = include_bytes!("../assets/icon_256x256_macos.png");
@@ -32,14 +54,20 @@ fn load_icon() -> Option<egui::IconData> {
 fn main() -> eframe::Result<()> {
     env_logger::init();
 
-    // Load settings for window size
+    let stdin_input = read_stdin_input();
+
+    // Load settings for window size and placement
     let settings = AppSettings::load();
 
     let mut viewport = egui::ViewportBuilder::default()
         .with_inner_size([settings.window_width, settings.window_height])
         .with_min_inner_size([800.0, 600.0])
+        .with_maximized(settings.maximized)
         .with_drag_and_drop(true)
         .with_app_id("com.bend.databending");
+    if let (Some(x), Some(y)) = (settings.window_x, settings.window_y) {
+        viewport = viewport.with_position([x, y]);
+    }
 
     if let Some(icon) = load_icon() {
         viewport = viewport.with_icon(Arc::new(icon));
@@ -53,6 +81,12 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "bend-rs - Databending Studio",
         options,
-        Box::new(|cc| Ok(Box::new(BendApp::new(cc, settings)))),
+        Box::new(|cc| {
+            let mut app = BendApp::new(cc, settings);
+            if let Some(bytes) = stdin_input {
+                app.open_stdin(bytes);
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
@@ -5,10 +5,12 @@
 //! - Windows: %APPDATA%/bend-rs/settings.json
 //! - Linux: ~/.config/bend-rs/settings.json
 
+use crate::formats::RiskLevel;
+use crate::keybindings::KeyBindings;
 use eframe::egui;
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// User preference for application theme
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -54,10 +56,110 @@ impl ThemePreference {
     pub fn apply(self, ctx: &egui::Context) {
         ctx.set_theme(egui::ThemePreference::from(self));
     }
+
+    /// The explicit theme opposite to the one currently displayed
+    /// (System resolves to whatever `dark_mode` says is showing)
+    pub fn toggled(dark_mode: bool) -> Self {
+        if dark_mode {
+            ThemePreference::Light
+        } else {
+            ThemePreference::Dark
+        }
+    }
+}
+
+/// Numeric base used to display offsets (offset column, status bar, copied dumps)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum OffsetBase {
+    #[default]
+    Hex,
+    Decimal,
+    /// Hex followed by decimal
+    Both,
+}
+
+impl fmt::Display for OffsetBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OffsetBase::Hex => write!(f, "Hex"),
+            OffsetBase::Decimal => write!(f, "Decimal"),
+            OffsetBase::Both => write!(f, "Both"),
+        }
+    }
 }
 
-/// Maximum number of recent files to track
-const MAX_RECENT_FILES: usize = 10;
+impl OffsetBase {
+    /// Every base, in the order offered in Preferences
+    pub const ALL: [OffsetBase; 3] = [OffsetBase::Hex, OffsetBase::Decimal, OffsetBase::Both];
+
+    /// Fixed-width form for the offset column and dumps, e.g. "000001F0" or "0000000496"
+    pub fn format_column(self, offset: usize) -> String {
+        match self {
+            OffsetBase::Hex => format!("{:08X}", offset),
+            OffsetBase::Decimal => format!("{:010}", offset),
+            OffsetBase::Both => format!("{:08X} {:010}", offset, offset),
+        }
+    }
+
+    /// Prefixed form for readouts, e.g. "0x000001F0" or "496"
+    pub fn format_readout(self, offset: usize) -> String {
+        match self {
+            OffsetBase::Hex => format!("0x{:08X}", offset),
+            OffsetBase::Decimal => offset.to_string(),
+            OffsetBase::Both => format!("0x{:08X} ({})", offset, offset),
+        }
+    }
+}
+
+/// User-chosen section colors (sRGB) per risk level; unset levels use the theme color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RiskColorOverrides {
+    pub safe: Option<[u8; 3]>,
+    pub caution: Option<[u8; 3]>,
+    pub high: Option<[u8; 3]>,
+    pub critical: Option<[u8; 3]>,
+    pub unknown: Option<[u8; 3]>,
+}
+
+impl RiskColorOverrides {
+    /// The override slot for `level`
+    pub fn get_mut(&mut self, level: RiskLevel) -> &mut Option<[u8; 3]> {
+        match level {
+            RiskLevel::Safe => &mut self.safe,
+            RiskLevel::Caution => &mut self.caution,
+            RiskLevel::High => &mut self.high,
+            RiskLevel::Critical => &mut self.critical,
+            RiskLevel::Unknown => &mut self.unknown,
+        }
+    }
+
+    /// The override for `level`, if one is set
+    pub fn get(&self, level: RiskLevel) -> Option<[u8; 3]> {
+        match level {
+            RiskLevel::Safe => self.safe,
+            RiskLevel::Caution => self.caution,
+            RiskLevel::High => self.high,
+            RiskLevel::Critical => self.critical,
+            RiskLevel::Unknown => self.unknown,
+        }
+    }
+}
+
+/// Upper bound for the configurable number of recent files
+pub const MAX_RECENT_FILES_LIMIT: usize = 50;
+
+/// Upper bound for the preview debounce delay (milliseconds)
+pub const MAX_PREVIEW_DEBOUNCE_MS: u64 = 1000;
+
+/// Upper bound for the selection grow/shrink step (bytes)
+pub const MAX_SELECTION_STEP: usize = 1 << 20;
+
+/// Range of monospace font sizes offered for the hex editor (points)
+pub const HEX_FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 8.0..=32.0;
+
+/// Row widths offered for the hex editor
+pub const BYTES_PER_ROW_OPTIONS: &[usize] = &[8, 16, 32];
 
 /// Application settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -68,6 +170,18 @@ pub struct AppSettings {
     /// Window height in logical pixels
     pub window_height: f32,
 
+    /// Outer window left edge in logical pixels (None = let the OS place it)
+    #[serde(default)]
+    pub window_x: Option<f32>,
+
+    /// Outer window top edge in logical pixels (None = let the OS place it)
+    #[serde(default)]
+    pub window_y: Option<f32>,
+
+    /// Whether the window was maximized when last closed
+    #[serde(default)]
+    pub maximized: bool,
+
     /// List of recently opened files (most recent first)
     pub recent_files: Vec<PathBuf>,
 
@@ -80,6 +194,191 @@ pub struct AppSettings {
     /// Theme preference (Dark, Light, or System)
     #[serde(default)]
     pub theme: ThemePreference,
+
+    /// Create an automatic save point every N edits (by edit generation); 0 disables
+    #[serde(default)]
+    pub auto_save_point_interval: u64,
+
+    /// Delay between frames when exporting save points as an animated GIF (milliseconds)
+    #[serde(default = "default_gif_frame_delay_ms")]
+    pub gif_frame_delay_ms: u32,
+
+    /// Monospace font size used by the hex editor (points)
+    #[serde(default = "default_hex_font_size")]
+    pub hex_font_size: f32,
+
+    /// Number of bytes shown per hex editor row (one of `BYTES_PER_ROW_OPTIONS`)
+    #[serde(default = "default_bytes_per_row")]
+    pub bytes_per_row: usize,
+
+    /// Section names that header protection always blocks, regardless of risk level
+    #[serde(default)]
+    pub protected_sections: Vec<String>,
+
+    /// Section names that header protection never blocks, regardless of risk level
+    #[serde(default)]
+    pub allowed_sections: Vec<String>,
+
+    /// Pastes larger than this many bytes ask for confirmation first; 0 disables
+    #[serde(default = "default_paste_confirm_threshold")]
+    pub paste_confirm_threshold: usize,
+
+    /// Files larger than this many megabytes ask for confirmation before
+    /// opening (each is held in memory twice); 0 disables
+    #[serde(default = "default_large_file_warn_mb")]
+    pub large_file_warn_mb: u64,
+
+    /// Ask before exporting a working buffer that no longer decodes as an image
+    #[serde(default = "default_warn_on_undecodable_export")]
+    pub warn_on_undecodable_export: bool,
+
+    /// How long edits must settle before the preview re-decodes (milliseconds)
+    #[serde(default = "default_preview_debounce_ms")]
+    pub preview_debounce_ms: u64,
+
+    /// Number of (unpinned) recent files to remember
+    #[serde(default = "default_max_recent_files")]
+    pub max_recent_files: usize,
+
+    /// Pinned favorite files, listed above recent files and never evicted
+    #[serde(default)]
+    pub pinned_files: Vec<PathBuf>,
+
+    /// Whether the ASCII column leaves a gap after every 8 characters, like the hex column
+    #[serde(default = "default_ascii_group_separator")]
+    pub ascii_group_separator: bool,
+
+    /// Whether the mouse back/forward side buttons undo and redo
+    #[serde(default = "default_mouse_history_buttons")]
+    pub mouse_history_buttons: bool,
+
+    /// Numeric base offsets are displayed in
+    #[serde(default)]
+    pub offset_base: OffsetBase,
+
+    /// Custom structure/section colors per risk level
+    #[serde(default)]
+    pub risk_colors: RiskColorOverrides,
+
+    /// Directory of the last successful export; Quick Export writes here
+    #[serde(default)]
+    pub last_export_dir: Option<PathBuf>,
+
+    /// Whether unparsed ("Unknown") regions get a background tint in the hex view
+    #[serde(default = "default_highlight_unknown_regions")]
+    pub highlight_unknown_regions: bool,
+
+    /// Whether the preview re-renders after every edit; when off it only
+    /// updates on an explicit refresh
+    #[serde(default = "default_live_preview")]
+    pub live_preview: bool,
+
+    /// Whether the cursor jumps to the probable break when the preview stops decoding
+    #[serde(default)]
+    pub jump_to_decode_error: bool,
+
+    /// Whether the Find field re-runs the search while typing instead of on Enter
+    #[serde(default)]
+    pub search_as_you_type: bool,
+
+    /// Whether typing past the last byte in Overwrite mode appends new bytes
+    #[serde(default)]
+    pub grow_on_overwrite_at_eof: bool,
+
+    /// Whether the hex view scrolls to keep a moving cursor visible
+    #[serde(default = "default_follow_cursor")]
+    pub follow_cursor: bool,
+
+    /// Whether Escape enters a vim-style command mode in the hex editor, where
+    /// a typed count repeats the next navigation key
+    #[serde(default)]
+    pub vim_motions: bool,
+
+    /// Bytes Alt+Left/Right grow or shrink the selection by
+    #[serde(default = "default_selection_step")]
+    pub selection_step: usize,
+
+    /// Whether opening a file first copies it and edits the copy instead
+    #[serde(default)]
+    pub work_on_copy: bool,
+
+    /// Folder the working copies go in (None = next to the source file)
+    #[serde(default)]
+    pub work_copy_dir: Option<PathBuf>,
+
+    /// Rebound keyboard shortcuts (unlisted actions use their defaults)
+    #[serde(default)]
+    pub keybindings: KeyBindings,
+}
+
+/// ASCII groups are separated to match the hex column unless turned off
+fn default_ascii_group_separator() -> bool {
+    true
+}
+
+/// Unknown regions are tinted like every other section unless turned off
+fn default_highlight_unknown_regions() -> bool {
+    true
+}
+
+/// The preview follows edits unless switched to manual refresh
+fn default_live_preview() -> bool {
+    true
+}
+
+/// The hex view follows the cursor unless the user prefers a static view
+fn default_follow_cursor() -> bool {
+    true
+}
+
+/// Selection arithmetic moves an edge one byte at a time by default
+fn default_selection_step() -> usize {
+    1
+}
+
+/// Mouse side buttons step through history unless the user opts out
+fn default_mouse_history_buttons() -> bool {
+    true
+}
+
+/// Default number of recent files to remember
+fn default_max_recent_files() -> usize {
+    10
+}
+
+/// Default hex editor font size (egui's default monospace size)
+fn default_hex_font_size() -> f32 {
+    12.0
+}
+
+/// Default hex editor row width
+fn default_bytes_per_row() -> usize {
+    16
+}
+
+/// Default preview debounce delay
+fn default_preview_debounce_ms() -> u64 {
+    150
+}
+
+/// Undecodable-export warning is on unless the user opts out
+fn default_warn_on_undecodable_export() -> bool {
+    true
+}
+
+/// Default paste size above which confirmation is requested
+fn default_paste_confirm_threshold() -> usize {
+    4096
+}
+
+/// Default file size (MB) above which opening asks for confirmation
+fn default_large_file_warn_mb() -> u64 {
+    256
+}
+
+/// Default frame delay for save point GIF export
+fn default_gif_frame_delay_ms() -> u32 {
+    200
 }
 
 impl Default for AppSettings {
@@ -87,15 +386,75 @@ impl Default for AppSettings {
         Self {
             window_width: 1200.0,
             window_height: 800.0,
+            window_x: None,
+            window_y: None,
+            maximized: false,
             recent_files: Vec::new(),
             default_header_protection: false,
             show_high_risk_warnings: true,
             theme: ThemePreference::default(),
+            auto_save_point_interval: 0,
+            gif_frame_delay_ms: default_gif_frame_delay_ms(),
+            hex_font_size: default_hex_font_size(),
+            bytes_per_row: default_bytes_per_row(),
+            protected_sections: Vec::new(),
+            allowed_sections: Vec::new(),
+            paste_confirm_threshold: default_paste_confirm_threshold(),
+            large_file_warn_mb: default_large_file_warn_mb(),
+            warn_on_undecodable_export: default_warn_on_undecodable_export(),
+            preview_debounce_ms: default_preview_debounce_ms(),
+            max_recent_files: default_max_recent_files(),
+            pinned_files: Vec::new(),
+            ascii_group_separator: default_ascii_group_separator(),
+            mouse_history_buttons: default_mouse_history_buttons(),
+            offset_base: OffsetBase::default(),
+            risk_colors: RiskColorOverrides::default(),
+            last_export_dir: None,
+            highlight_unknown_regions: default_highlight_unknown_regions(),
+            live_preview: default_live_preview(),
+            jump_to_decode_error: false,
+            search_as_you_type: false,
+            grow_on_overwrite_at_eof: false,
+            follow_cursor: default_follow_cursor(),
+            vim_motions: false,
+            selection_step: default_selection_step(),
+            work_on_copy: false,
+            work_copy_dir: None,
+            keybindings: KeyBindings::default(),
         }
     }
 }
 
 impl AppSettings {
+    /// Hex editor row width, falling back to the default if the stored value is unsupported
+    pub fn bytes_per_row(&self) -> usize {
+        if BYTES_PER_ROW_OPTIONS.contains(&self.bytes_per_row) {
+            self.bytes_per_row
+        } else {
+            default_bytes_per_row()
+        }
+    }
+
+    /// Hex editor font size, clamped to `HEX_FONT_SIZE_RANGE` (default if not a number)
+    pub fn hex_font_size(&self) -> f32 {
+        if self.hex_font_size.is_nan() {
+            default_hex_font_size()
+        } else {
+            self.hex_font_size
+                .clamp(*HEX_FONT_SIZE_RANGE.start(), *HEX_FONT_SIZE_RANGE.end())
+        }
+    }
+
+    /// Preview debounce delay, clamped to `0..=MAX_PREVIEW_DEBOUNCE_MS`
+    pub fn preview_debounce_ms(&self) -> u64 {
+        self.preview_debounce_ms.min(MAX_PREVIEW_DEBOUNCE_MS)
+    }
+
+    /// Number of recent files to remember, clamped to `1..=MAX_RECENT_FILES_LIMIT`
+    pub fn max_recent_files(&self) -> usize {
+        self.max_recent_files.clamp(1, MAX_RECENT_FILES_LIMIT)
+    }
+
     /// Get the path to the settings file
     fn settings_path() -> Option<PathBuf> {
         dirs::config_dir().map(|mut path| {
@@ -161,8 +520,12 @@ impl AppSettings {
         }
     }
 
-    /// Add a file to the recent files list
+    /// Add a file to the recent files list (pinned files stay where they are)
     pub fn add_recent_file(&mut self, path: PathBuf) {
+        if self.pinned_files.contains(&path) {
+            return;
+        }
+
         // Remove if already in list (to move to front)
         self.recent_files.retain(|p| p != &path);
 
@@ -170,15 +533,37 @@ impl AppSettings {
         self.recent_files.insert(0, path);
 
         // Trim to max size
-        self.recent_files.truncate(MAX_RECENT_FILES);
+        self.recent_files.truncate(self.max_recent_files());
     }
 
-    /// Get the recent files list
+    /// Get the recent files list (most recent first, excluding pinned files)
     pub fn recent_files(&self) -> &[PathBuf] {
-        &self.recent_files
+        &self.recent_files[..self.recent_files.len().min(self.max_recent_files())]
+    }
+
+    /// Get the pinned files, in the order they were pinned
+    pub fn pinned_files(&self) -> &[PathBuf] {
+        &self.pinned_files
     }
 
-    /// Clear the recent files list
+    /// Pin a file so it's listed first and never evicted
+    pub fn pin_recent_file(&mut self, path: &Path) {
+        self.recent_files.retain(|p| p != path);
+        if !self.pinned_files.iter().any(|p| p == path) {
+            self.pinned_files.push(path.to_path_buf());
+        }
+    }
+
+    /// Unpin a file, returning it to the front of the recent files list
+    pub fn unpin_recent_file(&mut self, path: &Path) {
+        let before = self.pinned_files.len();
+        self.pinned_files.retain(|p| p != path);
+        if self.pinned_files.len() != before {
+            self.add_recent_file(path.to_path_buf());
+        }
+    }
+
+    /// Clear the recent files list (pinned files are kept)
     pub fn clear_recent_files(&mut self) {
         self.recent_files.clear();
     }
@@ -187,16 +572,98 @@ impl AppSettings {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::keybindings::{KeyBinding, ShortcutAction};
 
     #[test]
     fn test_default_settings() {
         let settings = AppSettings::default();
         assert_eq!(settings.window_width, 1200.0);
         assert_eq!(settings.window_height, 800.0);
+        assert_eq!(settings.window_x, None);
+        assert!(!settings.maximized);
         assert!(settings.recent_files.is_empty());
         assert!(!settings.default_header_protection);
         assert!(settings.show_high_risk_warnings);
         assert_eq!(settings.theme, ThemePreference::System);
+        assert_eq!(settings.auto_save_point_interval, 0);
+        assert_eq!(settings.gif_frame_delay_ms, 200);
+        assert_eq!(settings.bytes_per_row(), 16);
+        assert_eq!(settings.paste_confirm_threshold, 4096);
+        assert_eq!(settings.large_file_warn_mb, 256);
+        assert!(settings.warn_on_undecodable_export);
+        assert_eq!(settings.preview_debounce_ms(), 150);
+        assert!(settings.mouse_history_buttons);
+        assert!(settings.ascii_group_separator);
+        assert_eq!(settings.hex_font_size(), 12.0);
+        assert_eq!(settings.offset_base, OffsetBase::Hex);
+        assert_eq!(settings.risk_colors, RiskColorOverrides::default());
+        assert_eq!(settings.last_export_dir, None);
+        assert!(settings.highlight_unknown_regions);
+        assert!(settings.live_preview);
+        assert!(!settings.jump_to_decode_error);
+        assert!(!settings.search_as_you_type);
+        assert!(!settings.grow_on_overwrite_at_eof);
+        assert!(settings.follow_cursor);
+        assert!(!settings.vim_motions);
+        assert_eq!(settings.selection_step, 1);
+        assert!(!settings.work_on_copy);
+        assert_eq!(settings.work_copy_dir, None);
+        assert_eq!(settings.keybindings, KeyBindings::default());
+    }
+
+    #[test]
+    fn test_risk_color_overrides_round_trip() {
+        let mut settings = AppSettings::default();
+        *settings.risk_colors.get_mut(RiskLevel::High) = Some([1, 2, 3]);
+        assert_eq!(settings.risk_colors.get(RiskLevel::High), Some([1, 2, 3]));
+        assert_eq!(settings.risk_colors.get(RiskLevel::Safe), None);
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.risk_colors, settings.risk_colors);
+    }
+
+    #[test]
+    fn test_offset_base_formats() {
+        assert_eq!(OffsetBase::Hex.format_column(496), "000001F0");
+        assert_eq!(OffsetBase::Decimal.format_column(496), "0000000496");
+        assert_eq!(OffsetBase::Both.format_column(496), "000001F0 0000000496");
+        assert_eq!(OffsetBase::Hex.format_readout(496), "0x000001F0");
+        assert_eq!(OffsetBase::Decimal.format_readout(496), "496");
+        assert_eq!(OffsetBase::Both.format_readout(496), "0x000001F0 (496)");
+    }
+
+    #[test]
+    fn test_hex_font_size_is_clamped() {
+        let mut settings = AppSettings {
+            hex_font_size: 200.0,
+            ..Default::default()
+        };
+        assert_eq!(settings.hex_font_size(), 32.0);
+        settings.hex_font_size = 1.0;
+        assert_eq!(settings.hex_font_size(), 8.0);
+        settings.hex_font_size = f32::NAN;
+        assert_eq!(settings.hex_font_size(), 12.0);
+    }
+
+    #[test]
+    fn test_preview_debounce_is_clamped() {
+        let settings = AppSettings {
+            preview_debounce_ms: 60_000,
+            ..Default::default()
+        };
+        assert_eq!(settings.preview_debounce_ms(), MAX_PREVIEW_DEBOUNCE_MS);
+    }
+
+    #[test]
+    fn test_bytes_per_row_falls_back_when_unsupported() {
+        let mut settings = AppSettings {
+            bytes_per_row: 32,
+            ..Default::default()
+        };
+        assert_eq!(settings.bytes_per_row(), 32);
+        settings.bytes_per_row = 0;
+        assert_eq!(settings.bytes_per_row(), 16);
     }
 
     #[test]
@@ -244,7 +711,7 @@ mod tests {
             settings.add_recent_file(PathBuf::from(format!("/path/to/file{}.bmp", i)));
         }
 
-        assert_eq!(settings.recent_files.len(), MAX_RECENT_FILES);
+        assert_eq!(settings.recent_files.len(), default_max_recent_files());
         // Most recent should be at front
         assert_eq!(
             settings.recent_files[0],
@@ -262,8 +729,10 @@ mod tests {
 
     #[test]
     fn test_serialize_deserialize() {
-        let mut settings = AppSettings::default();
-        settings.window_width = 1000.0;
+        let mut settings = AppSettings {
+            window_width: 1000.0,
+            ..Default::default()
+        };
         settings.add_recent_file(PathBuf::from("/path/to/file.bmp"));
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -311,8 +780,10 @@ mod tests {
         let b = AppSettings::default();
         assert_eq!(a, b);
 
-        let mut c = AppSettings::default();
-        c.window_width = 999.0;
+        let c = AppSettings {
+            window_width: 999.0,
+            ..Default::default()
+        };
         assert_ne!(a, c);
     }
 
@@ -323,14 +794,22 @@ mod tests {
             ThemePreference::Light,
             ThemePreference::System,
         ] {
-            let mut settings = AppSettings::default();
-            settings.theme = theme;
+            let settings = AppSettings {
+                theme,
+                ..Default::default()
+            };
             let json = serde_json::to_string(&settings).unwrap();
             let loaded: AppSettings = serde_json::from_str(&json).unwrap();
             assert_eq!(loaded.theme, theme);
         }
     }
 
+    #[test]
+    fn test_theme_toggled() {
+        assert_eq!(ThemePreference::toggled(true), ThemePreference::Light);
+        assert_eq!(ThemePreference::toggled(false), ThemePreference::Dark);
+    }
+
     #[test]
     fn test_theme_backward_compat_missing_key() {
         // Simulate a settings.json from before theme was added
@@ -343,5 +822,112 @@ mod tests {
         }"#;
         let loaded: AppSettings = serde_json::from_str(json).unwrap();
         assert_eq!(loaded.theme, ThemePreference::System);
+        assert_eq!(loaded.auto_save_point_interval, 0);
+        assert_eq!(loaded.gif_frame_delay_ms, 200);
+        assert_eq!(loaded.bytes_per_row, 16);
+        assert!(loaded.protected_sections.is_empty());
+        assert!(loaded.allowed_sections.is_empty());
+        assert_eq!(loaded.paste_confirm_threshold, 4096);
+        assert_eq!(loaded.large_file_warn_mb, 256);
+        assert!(loaded.warn_on_undecodable_export);
+        assert_eq!(loaded.preview_debounce_ms, 150);
+        assert_eq!(loaded.max_recent_files, 10);
+        assert!(loaded.pinned_files.is_empty());
+        assert!(loaded.mouse_history_buttons);
+        assert!(loaded.ascii_group_separator);
+        assert_eq!(loaded.hex_font_size, 12.0);
+        assert_eq!(loaded.window_x, None);
+        assert_eq!(loaded.window_y, None);
+        assert!(!loaded.maximized);
+        assert_eq!(loaded.offset_base, OffsetBase::Hex);
+        assert_eq!(loaded.risk_colors, RiskColorOverrides::default());
+        assert_eq!(loaded.last_export_dir, None);
+        assert!(loaded.highlight_unknown_regions);
+        assert!(loaded.live_preview);
+        assert!(!loaded.jump_to_decode_error);
+        assert!(!loaded.search_as_you_type);
+        assert!(!loaded.grow_on_overwrite_at_eof);
+        assert!(loaded.follow_cursor);
+        assert!(!loaded.vim_motions);
+        assert_eq!(loaded.selection_step, 1);
+        assert!(!loaded.work_on_copy);
+        assert_eq!(loaded.work_copy_dir, None);
+        assert_eq!(loaded.keybindings, KeyBindings::default());
+    }
+
+    #[test]
+    fn test_max_recent_files_is_configurable() {
+        let mut settings = AppSettings {
+            max_recent_files: 3,
+            ..Default::default()
+        };
+        for i in 0..5 {
+            settings.add_recent_file(PathBuf::from(format!("/f{}.bmp", i)));
+        }
+        assert_eq!(settings.recent_files().len(), 3);
+
+        // Lowering the limit hides older entries immediately; 0 is clamped to 1
+        settings.max_recent_files = 0;
+        assert_eq!(settings.recent_files(), [PathBuf::from("/f4.bmp")]);
+    }
+
+    #[test]
+    fn test_pin_and_unpin_recent_file() {
+        let mut settings = AppSettings {
+            max_recent_files: 2,
+            ..Default::default()
+        };
+        let pinned = PathBuf::from("/pinned.bmp");
+        settings.add_recent_file(pinned.clone());
+        settings.pin_recent_file(&pinned);
+        settings.pin_recent_file(&pinned);
+        assert_eq!(settings.pinned_files(), std::slice::from_ref(&pinned));
+        assert!(settings.recent_files().is_empty());
+
+        // Pinned files survive eviction and clearing, and reopening doesn't duplicate them
+        for i in 0..4 {
+            settings.add_recent_file(PathBuf::from(format!("/f{}.bmp", i)));
+        }
+        settings.add_recent_file(pinned.clone());
+        assert_eq!(settings.recent_files().len(), 2);
+        settings.clear_recent_files();
+        assert_eq!(settings.pinned_files(), std::slice::from_ref(&pinned));
+
+        settings.unpin_recent_file(&pinned);
+        assert!(settings.pinned_files().is_empty());
+        assert_eq!(settings.recent_files(), [pinned]);
+    }
+
+    #[test]
+    fn test_bad_shortcut_keeps_other_settings() {
+        let mut settings = AppSettings {
+            window_width: 1234.0,
+            ..Default::default()
+        };
+        settings.add_recent_file(PathBuf::from("/tmp/a.bmp"));
+        let mut json = serde_json::to_value(&settings).unwrap();
+        json["keybindings"] = serde_json::json!({
+            "Undo": ["Ctrl+Nope"],
+            "Redo": ["Hyper+Y", "Alt+Y"],
+            "Teleport": ["Ctrl+T"],
+            "Open": "Ctrl+O",
+            "GoToOffset": ["Ctrl+J"]
+        });
+        let json = json.to_string();
+        let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.window_width, 1234.0);
+        assert_eq!(loaded.recent_files(), [PathBuf::from("/tmp/a.bmp")]);
+
+        let bindings = &loaded.keybindings;
+        assert!(bindings.is_default(ShortcutAction::Undo));
+        assert!(bindings.is_default(ShortcutAction::Open));
+        assert_eq!(
+            bindings.bindings(ShortcutAction::Redo),
+            [KeyBinding::try_from("Alt+Y".to_string()).unwrap()]
+        );
+        assert_eq!(
+            bindings.bindings(ShortcutAction::GoToOffset),
+            [KeyBinding::ctrl(egui::Key::J)]
+        );
     }
 }
@@ -38,7 +38,7 @@ pub fn show(
     // Add bookmark button
     ui.horizontal(|ui| {
         if ui.button("+ Add Bookmark").pointer_cursor().clicked() {
-            let name = format!("Bookmark at 0x{:08X}", cursor_pos);
+            let name = doc.default_bookmark_name(cursor_pos);
             if let Some(editor) = &mut doc.editor {
                 editor.add_bookmark(cursor_pos, name);
             }
@@ -108,8 +108,31 @@ pub fn show(
                     });
                 }
 
-                // Offset display
-                ui.label(egui::RichText::new(format!("Offset: 0x{:08X}", bookmark.offset)).small());
+                // Offset display, flagged if a deletion moved the bookmark
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format!("Offset: 0x{:08X}", bookmark.offset)).small(),
+                    );
+                    if bookmark.shifted {
+                        ui.label(
+                            egui::RichText::new("\u{26A0} Shifted")
+                                .small()
+                                .color(ui_state.colors.warning_text),
+                        )
+                        .on_hover_text(
+                            "The bytes this bookmark marked were deleted, so it was moved \
+                             to where they started",
+                        );
+                        if ui
+                            .small_button("OK")
+                            .pointer_cursor()
+                            .on_hover_text("Clear the shifted flag")
+                            .clicked()
+                        {
+                            action = Some(BookmarkAction::ClearShifted(bookmark.id));
+                        }
+                    }
+                });
 
                 // Annotation (editable if editing)
                 if state.editing_annotation == Some(bookmark.id) {
@@ -160,10 +183,10 @@ pub fn show(
                                 bookmark.annotation.clone(),
                             ));
                         }
-                        if !bookmark.annotation.is_empty() {
-                            if ui.small_button("Delete Note").pointer_cursor().clicked() {
-                                action = Some(BookmarkAction::DeleteAnnotation(bookmark.id));
-                            }
+                        if !bookmark.annotation.is_empty()
+                            && ui.small_button("Delete Note").pointer_cursor().clicked()
+                        {
+                            action = Some(BookmarkAction::DeleteAnnotation(bookmark.id));
                         }
                         if ui.small_button("Delete").pointer_cursor().clicked() {
                             action = Some(BookmarkAction::Delete(bookmark.id));
@@ -221,6 +244,11 @@ pub fn show(
                     let _ = editor.bookmarks_mut().set_annotation(id, String::new());
                 }
             }
+            BookmarkAction::ClearShifted(id) => {
+                if let Some(editor) = &mut doc.editor {
+                    let _ = editor.bookmarks_mut().clear_shifted(id);
+                }
+            }
             BookmarkAction::Delete(id) => {
                 if let Some(editor) = &mut doc.editor {
                     let _ = editor.remove_bookmark(id); // #[must_use] result intentionally ignored — bookmark existence already verified by UI
@@ -240,5 +268,6 @@ enum BookmarkAction {
     FinishAnnotation(u64, String),
     CancelAnnotation,
     DeleteAnnotation(u64),
+    ClearShifted(u64),
     Delete(u64),
 }
@@ -0,0 +1,221 @@
+//! "Find Byte Runs" dialog UI component
+
+use crate::app::{DocumentState, UiState};
+use crate::editor::analysis::find_byte_runs;
+use crate::ui::PointerCursor;
+use eframe::egui;
+
+/// Shortest run listed unless the user changes it
+const DEFAULT_MIN_RUN: usize = 64;
+
+/// Most runs listed at once (navigation still reaches all of them)
+const MAX_LISTED_RUNS: usize = 1000;
+
+/// State for the byte runs dialog
+pub struct ByteRunsState {
+    /// Whether the dialog is visible
+    pub dialog_open: bool,
+    /// Shortest run to report
+    pub min_len: usize,
+    /// `(offset, len, value)` of each run found by the last scan
+    runs: Vec<(usize, usize, u8)>,
+    /// Index of the run last jumped to
+    current: Option<usize>,
+    /// Edit generation the runs were found at
+    scanned_at_generation: u64,
+}
+
+impl Default for ByteRunsState {
+    fn default() -> Self {
+        Self {
+            dialog_open: false,
+            min_len: DEFAULT_MIN_RUN,
+            runs: Vec::new(),
+            current: None,
+            scanned_at_generation: 0,
+        }
+    }
+}
+
+impl ByteRunsState {
+    /// Open the dialog and scan `data` immediately
+    pub fn open(&mut self, data: &[u8], generation: u64) {
+        self.dialog_open = true;
+        self.rescan(data, generation);
+    }
+
+    /// Re-run the scan against `data`
+    fn rescan(&mut self, data: &[u8], generation: u64) {
+        self.runs = find_byte_runs(data, self.min_len);
+        self.current = None;
+        self.scanned_at_generation = generation;
+    }
+
+    /// Forget the results (e.g. when switching documents)
+    pub fn clear(&mut self) {
+        self.dialog_open = false;
+        self.runs.clear();
+        self.current = None;
+    }
+
+    /// Total number of bytes covered by runs
+    fn run_bytes(&self) -> usize {
+        self.runs.iter().map(|&(_, len, _)| len).sum()
+    }
+
+    /// Make run `index` current and return it
+    fn select(&mut self, index: usize) -> Option<(usize, usize, u8)> {
+        let run = *self.runs.get(index)?;
+        self.current = Some(index);
+        Some(run)
+    }
+
+    /// Step to the next (or previous) run, wrapping around
+    fn step(&mut self, forward: bool) -> Option<(usize, usize, u8)> {
+        let len = self.runs.len();
+        if len == 0 {
+            return None;
+        }
+        let index = match (self.current, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+        };
+        self.select(index)
+    }
+}
+
+/// Show the byte runs dialog. Picking a run selects it in the hex view.
+pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState) {
+    if !ui_state.byte_runs_state.dialog_open {
+        return;
+    }
+    let Some(editor) = &mut doc.editor else {
+        ui_state.byte_runs_state.dialog_open = false;
+        return;
+    };
+    let generation = editor.edit_generation();
+
+    let mut open = true;
+    let mut jump = None;
+    let mut step = None;
+    let mut rescan = false;
+    let colors = ui_state.colors;
+    let state = &mut ui_state.byte_runs_state;
+
+    egui::Window::new("Find Byte Runs")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("At least");
+                let response = ui.add(
+                    egui::DragValue::new(&mut state.min_len)
+                        .range(2..=usize::MAX)
+                        .suffix(" bytes"),
+                );
+                if response.changed() {
+                    rescan = true;
+                }
+                if ui.button("Rescan").pointer_cursor().clicked() {
+                    rescan = true;
+                }
+            });
+            ui.label(format!(
+                "{} run(s), {} byte(s)",
+                state.runs.len(),
+                state.run_bytes()
+            ));
+            if state.scanned_at_generation != generation {
+                ui.colored_label(colors.warning_text, "Buffer edited since scanning");
+            }
+
+            ui.horizontal(|ui| {
+                let has_runs = !state.runs.is_empty();
+                if ui
+                    .add_enabled(has_runs, egui::Button::new("\u{25C0} Previous"))
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    step = Some(false);
+                }
+                if ui
+                    .add_enabled(has_runs, egui::Button::new("Next \u{25B6}"))
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    step = Some(true);
+                }
+            });
+            ui.separator();
+
+            if state.runs.is_empty() {
+                ui.label("No runs that long in the buffer");
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (index, &(offset, len, value)) in
+                        state.runs.iter().take(MAX_LISTED_RUNS).enumerate()
+                    {
+                        let text = format!("0x{:08X}  {} \u{00D7} 0x{:02X}", offset, len, value);
+                        let selected = state.current == Some(index);
+                        if ui
+                            .selectable_label(selected, egui::RichText::new(text).monospace())
+                            .pointer_cursor()
+                            .clicked()
+                        {
+                            jump = Some(index);
+                        }
+                    }
+                    if state.runs.len() > MAX_LISTED_RUNS {
+                        ui.weak(format!(
+                            "... {} more (use Next/Previous)",
+                            state.runs.len() - MAX_LISTED_RUNS
+                        ));
+                    }
+                });
+        });
+
+    if rescan {
+        state.rescan(editor.working(), generation);
+    }
+    let target = match (jump, step) {
+        (Some(index), _) => state.select(index),
+        (None, Some(forward)) => state.step(forward),
+        _ => None,
+    };
+    if let Some((offset, len, _)) = target {
+        editor.set_selection(offset, offset + len);
+        ui_state.pending_hex_scroll = Some(offset);
+    }
+    if !open {
+        state.dialog_open = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_and_step_through_runs() {
+        let mut state = ByteRunsState {
+            min_len: 3,
+            ..Default::default()
+        };
+        state.open(&[0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFF], 4);
+        assert_eq!(state.run_bytes(), 7);
+        assert_eq!(state.step(false), Some((4, 4, 0xFF)));
+        assert_eq!(state.step(true), Some((0, 3, 0)));
+        assert_eq!(state.current, Some(0));
+
+        state.clear();
+        assert!(!state.dialog_open);
+        assert_eq!(state.step(true), None);
+    }
+}
@@ -0,0 +1,445 @@
+//! Command palette: a searchable list of app actions (Ctrl+Shift+P)
+//!
+//! The palette only picks a `PaletteCommand`; `BendApp::run_palette_command`
+//! routes it through the same `InputActions`/effect dispatch as the menus.
+
+use crate::ui::PointerCursor;
+use eframe::egui;
+
+/// An action that can be run from the palette
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteCommand {
+    Open,
+    Export,
+    QuickExport,
+    ExportSavePointsGif,
+    ExportPcm,
+    ImportBytes,
+    SaveSession,
+    LoadSession,
+    OpenContainingFolder,
+    RevealExportedFile,
+    CloseTab,
+    NextTab,
+    PreviousTab,
+    Undo,
+    Redo,
+    FindReplace,
+    GoToOffset,
+    MatchingMarker,
+    InvertSelection,
+    TrimLeft,
+    TrimRight,
+    ToggleSelectionLock,
+    ToggleMeasureDistance,
+    ScanImageHeaders,
+    FindByteRuns,
+    CompareWithFile,
+    CreateSavePoint,
+    NameSavePoint,
+    AddBookmark,
+    RefreshPreview,
+    ToggleLivePreview,
+    ToggleComparison,
+    ToggleHeaderProtection,
+    ToggleEntropyMap,
+    ToggleDiffHighlight,
+    HexMode,
+    AsciiMode,
+    ToggleWriteMode,
+    RandomBitFlips,
+    RotateBits,
+    XorSelection,
+    ScanlineShift,
+    JpegScanGlitch,
+    RepeatLastEffect,
+    ToggleMacroRecording,
+    Preferences,
+    KeyboardShortcuts,
+}
+
+/// One palette row: what it runs and how it's listed
+pub struct PaletteEntry {
+    pub command: PaletteCommand,
+    /// Menu-style grouping shown before the label, e.g. "File"
+    pub category: &'static str,
+    pub label: &'static str,
+    /// Hidden while no file is open
+    pub needs_file: bool,
+}
+
+/// Build an entry (keeps the registry table compact)
+const fn entry(
+    command: PaletteCommand,
+    category: &'static str,
+    label: &'static str,
+    needs_file: bool,
+) -> PaletteEntry {
+    PaletteEntry {
+        command,
+        category,
+        label,
+        needs_file,
+    }
+}
+
+/// Every command the palette offers, in display order
+pub const COMMANDS: &[PaletteEntry] = &[
+    entry(PaletteCommand::Open, "File", "Open...", false),
+    entry(PaletteCommand::Export, "File", "Export...", true),
+    entry(PaletteCommand::QuickExport, "File", "Quick Export", true),
+    entry(
+        PaletteCommand::ExportSavePointsGif,
+        "File",
+        "Export Save Points as GIF...",
+        true,
+    ),
+    entry(
+        PaletteCommand::ExportPcm,
+        "File",
+        "Export as Raw PCM...",
+        true,
+    ),
+    entry(
+        PaletteCommand::ImportBytes,
+        "File",
+        "Import Bytes into Selection...",
+        true,
+    ),
+    entry(PaletteCommand::SaveSession, "File", "Save Session...", true),
+    entry(
+        PaletteCommand::LoadSession,
+        "File",
+        "Load Session...",
+        false,
+    ),
+    entry(
+        PaletteCommand::OpenContainingFolder,
+        "File",
+        "Open Containing Folder",
+        true,
+    ),
+    entry(
+        PaletteCommand::RevealExportedFile,
+        "File",
+        "Reveal Exported File",
+        true,
+    ),
+    entry(PaletteCommand::CloseTab, "File", "Close Tab", true),
+    entry(PaletteCommand::NextTab, "File", "Next Tab", true),
+    entry(PaletteCommand::PreviousTab, "File", "Previous Tab", true),
+    entry(PaletteCommand::Undo, "Edit", "Undo", true),
+    entry(PaletteCommand::Redo, "Edit", "Redo", true),
+    entry(
+        PaletteCommand::FindReplace,
+        "Edit",
+        "Find & Replace...",
+        true,
+    ),
+    entry(PaletteCommand::GoToOffset, "Edit", "Go to Offset...", true),
+    entry(
+        PaletteCommand::MatchingMarker,
+        "Edit",
+        "Go to Matching Marker",
+        true,
+    ),
+    entry(
+        PaletteCommand::InvertSelection,
+        "Edit",
+        "Invert Selection",
+        true,
+    ),
+    entry(PaletteCommand::TrimLeft, "Edit", "Trim to Left...", true),
+    entry(PaletteCommand::TrimRight, "Edit", "Trim to Right...", true),
+    entry(
+        PaletteCommand::ToggleSelectionLock,
+        "Edit",
+        "Toggle Selection Lock",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleMeasureDistance,
+        "Edit",
+        "Toggle Measure Distance",
+        true,
+    ),
+    entry(
+        PaletteCommand::ScanImageHeaders,
+        "Edit",
+        "Scan for Image Headers...",
+        true,
+    ),
+    entry(
+        PaletteCommand::FindByteRuns,
+        "Edit",
+        "Find Byte Runs...",
+        true,
+    ),
+    entry(
+        PaletteCommand::CompareWithFile,
+        "Edit",
+        "Compare with File...",
+        true,
+    ),
+    entry(
+        PaletteCommand::CreateSavePoint,
+        "Edit",
+        "Create Save Point",
+        true,
+    ),
+    entry(
+        PaletteCommand::NameSavePoint,
+        "Edit",
+        "Save Point As...",
+        true,
+    ),
+    entry(PaletteCommand::AddBookmark, "Edit", "Add Bookmark", true),
+    entry(PaletteCommand::HexMode, "Edit", "Hex Editing Mode", true),
+    entry(
+        PaletteCommand::AsciiMode,
+        "Edit",
+        "ASCII Editing Mode",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleWriteMode,
+        "Edit",
+        "Toggle Insert/Overwrite",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleHeaderProtection,
+        "Edit",
+        "Toggle Header Protection",
+        true,
+    ),
+    entry(PaletteCommand::Preferences, "Edit", "Preferences...", false),
+    entry(
+        PaletteCommand::RefreshPreview,
+        "View",
+        "Refresh Preview",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleLivePreview,
+        "View",
+        "Toggle Live Preview",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleComparison,
+        "View",
+        "Toggle Comparison Mode",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleEntropyMap,
+        "View",
+        "Toggle Entropy Map",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleDiffHighlight,
+        "View",
+        "Toggle Diff Highlight",
+        true,
+    ),
+    entry(
+        PaletteCommand::RandomBitFlips,
+        "Effects",
+        "Random Bit Flips",
+        true,
+    ),
+    entry(PaletteCommand::RotateBits, "Effects", "Rotate Bits", true),
+    entry(
+        PaletteCommand::XorSelection,
+        "Effects",
+        "XOR Selection",
+        true,
+    ),
+    entry(
+        PaletteCommand::ScanlineShift,
+        "Effects",
+        "Scanline Shift",
+        true,
+    ),
+    entry(
+        PaletteCommand::JpegScanGlitch,
+        "Effects",
+        "JPEG Scan Glitch",
+        true,
+    ),
+    entry(
+        PaletteCommand::RepeatLastEffect,
+        "Effects",
+        "Repeat Last Effect",
+        true,
+    ),
+    entry(
+        PaletteCommand::ToggleMacroRecording,
+        "Effects",
+        "Start/Stop Recording Macro",
+        true,
+    ),
+    entry(
+        PaletteCommand::KeyboardShortcuts,
+        "Help",
+        "Keyboard Shortcuts",
+        false,
+    ),
+];
+
+/// State for the command palette window
+#[derive(Default)]
+pub struct CommandPaletteState {
+    /// Whether the palette is visible
+    pub open: bool,
+    /// Filter text typed by the user
+    pub query: String,
+    /// Index of the highlighted row within the filtered list
+    pub selected: usize,
+    /// Focus the query field on the next frame
+    focus_query: bool,
+}
+
+impl CommandPaletteState {
+    /// Open the palette with an empty query
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+        self.focus_query = true;
+    }
+}
+
+/// Entries whose "category label" text contains every word of `query`
+/// (case-insensitive). File-only commands are left out when no file is open.
+pub fn filter_commands(query: &str, has_file: bool) -> Vec<&'static PaletteEntry> {
+    let words: Vec<String> = query.split_whitespace().map(|w| w.to_lowercase()).collect();
+    COMMANDS
+        .iter()
+        .filter(|entry| has_file || !entry.needs_file)
+        .filter(|entry| {
+            let haystack = format!("{} {}", entry.category, entry.label).to_lowercase();
+            words.iter().all(|w| haystack.contains(w.as_str()))
+        })
+        .collect()
+}
+
+/// Show the command palette. Returns the command the user picked, if any.
+pub fn show(
+    ctx: &egui::Context,
+    state: &mut CommandPaletteState,
+    has_file: bool,
+) -> Option<PaletteCommand> {
+    if !state.open {
+        return None;
+    }
+
+    let mut picked = None;
+    let mut close = false;
+
+    egui::Window::new("Command Palette")
+        .collapsible(false)
+        .resizable(false)
+        .title_bar(false)
+        .default_width(360.0)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+        .show(ctx, |ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut state.query)
+                    .hint_text("Type a command...")
+                    .desired_width(f32::INFINITY),
+            );
+            if state.focus_query {
+                response.request_focus();
+                state.focus_query = false;
+            }
+            if response.changed() {
+                state.selected = 0;
+            }
+
+            let matches = filter_commands(&state.query, has_file);
+            let (up, down, enter, escape) = ui.input(|i| {
+                (
+                    i.key_pressed(egui::Key::ArrowUp),
+                    i.key_pressed(egui::Key::ArrowDown),
+                    i.key_pressed(egui::Key::Enter),
+                    i.key_pressed(egui::Key::Escape),
+                )
+            });
+            if down && state.selected + 1 < matches.len() {
+                state.selected += 1;
+            }
+            if up {
+                state.selected = state.selected.saturating_sub(1);
+            }
+            state.selected = state.selected.min(matches.len().saturating_sub(1));
+            if enter {
+                picked = matches.get(state.selected).map(|entry| entry.command);
+            }
+            if escape {
+                close = true;
+            }
+
+            ui.separator();
+            if matches.is_empty() {
+                ui.weak("No matching commands");
+            }
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for (index, entry) in matches.iter().enumerate() {
+                        let selected = index == state.selected;
+                        let text = format!("{}: {}", entry.category, entry.label);
+                        let row = ui.selectable_label(selected, text).pointer_cursor();
+                        if selected && (up || down) {
+                            row.scroll_to_me(None);
+                        }
+                        if row.clicked() {
+                            picked = Some(entry.command);
+                        }
+                    }
+                });
+        });
+
+    if picked.is_some() || close {
+        state.open = false;
+    }
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(query: &str, has_file: bool) -> Vec<&'static str> {
+        filter_commands(query, has_file)
+            .iter()
+            .map(|entry| entry.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_query_lists_everything_available() {
+        assert_eq!(filter_commands("", true).len(), COMMANDS.len());
+        // Without a file only file-independent commands remain
+        assert_eq!(
+            labels("", false),
+            [
+                "Open...",
+                "Load Session...",
+                "Preferences...",
+                "Keyboard Shortcuts"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_words_match_category_and_label() {
+        assert_eq!(labels("UNDO", true), ["Undo"]);
+        assert_eq!(labels("effects rot", true), ["Rotate Bits"]);
+        assert_eq!(labels("export gif", true), ["Export Save Points as GIF..."]);
+        assert!(labels("no such command", true).is_empty());
+    }
+}
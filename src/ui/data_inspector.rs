@@ -0,0 +1,91 @@
+//! Data inspector: the byte under the cursor, with a bit-level editor
+
+use crate::app::DocumentState;
+use crate::editor::EditorState;
+use crate::ui::theme::AppColors;
+use eframe::egui;
+
+/// Flip bit `bit` (0 = least significant) of the byte at `offset` as one undoable edit
+fn toggle_bit(editor: &mut EditorState, offset: usize, bit: u8) {
+    if let Some(&byte) = editor.working().get(offset) {
+        editor.edit_byte(offset, byte ^ (1 << bit));
+    }
+}
+
+/// Show the cursor byte's value and one checkbox per bit (most significant first)
+pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, colors: &AppColors) {
+    let Some(editor) = &doc.editor else {
+        ui.label("No file loaded");
+        return;
+    };
+    let offset = editor.cursor();
+    let Some(&byte) = editor.working().get(offset) else {
+        ui.label("Cursor is past the end of the file");
+        return;
+    };
+    let protected = doc.is_offset_protected(offset);
+
+    egui::Grid::new("data_inspector_grid")
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("Offset");
+            ui.monospace(format!("0x{:08X}", offset));
+            ui.end_row();
+            ui.label("Value");
+            ui.monospace(format!("0x{:02X}  {}  ({})", byte, byte, byte as i8));
+            ui.end_row();
+        });
+
+    ui.add_space(4.0);
+    let mut toggled = None;
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 2.0;
+        for bit in (0..8u8).rev() {
+            let mut set = byte & (1 << bit) != 0;
+            ui.vertical(|ui| {
+                ui.weak(bit.to_string());
+                let response = ui
+                    .add_enabled(!protected, egui::Checkbox::without_text(&mut set))
+                    .on_hover_text(format!("Bit {} (value {})", bit, 1u8 << bit));
+                if response.changed() {
+                    toggled = Some(bit);
+                }
+            });
+            if bit == 4 {
+                ui.add_space(6.0);
+            }
+        }
+    });
+    if protected {
+        ui.colored_label(colors.warning_text, "Protected");
+    }
+
+    if let (Some(bit), Some(editor)) = (toggled, &mut doc.editor) {
+        toggle_bit(editor, offset, bit);
+        doc.preview.mark_dirty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_bit_is_undoable() {
+        let mut editor = EditorState::new(vec![0b0000_0001, 0xFF]);
+        toggle_bit(&mut editor, 0, 7);
+        assert_eq!(editor.working()[0], 0b1000_0001);
+        toggle_bit(&mut editor, 1, 0);
+        assert_eq!(editor.working()[1], 0xFE);
+
+        // Quick successive toggles coalesce like typed edits
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0b0000_0001, 0xFF]);
+        assert!(!editor.is_modified());
+
+        // Out-of-range offsets are ignored
+        toggle_bit(&mut editor, 5, 0);
+        assert_eq!(editor.working(), &[0b0000_0001, 0xFF]);
+    }
+}
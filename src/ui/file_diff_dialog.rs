@@ -0,0 +1,126 @@
+//! "Compare with File" results window
+
+use crate::app::{DocumentState, UiState};
+use crate::ui::PointerCursor;
+use eframe::egui;
+
+/// Most differing ranges listed at once (navigation still reaches all of them)
+const MAX_LISTED_RANGES: usize = 1000;
+
+/// Select a differing range and scroll the hex view to it
+fn jump_to(doc: &mut DocumentState, ui_state: &mut UiState, range: std::ops::Range<usize>) {
+    if let Some(editor) = &mut doc.editor {
+        editor.set_selection(range.start, range.end);
+        ui_state.pending_hex_scroll = Some(range.start);
+    }
+}
+
+/// Show the comparison results window (differing ranges, prev/next navigation)
+pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState) {
+    if !ui_state.file_diff.dialog_open {
+        return;
+    }
+    let Some(generation) = doc.editor.as_ref().map(|e| e.edit_generation()) else {
+        return;
+    };
+
+    let mut open = true;
+    let mut jump = None;
+    let mut step = None;
+    let mut recompare = false;
+    let colors = ui_state.colors;
+
+    egui::Window::new("Compare with File")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(340.0)
+        .show(ctx, |ui| {
+            let diff = &ui_state.file_diff;
+            ui.label(format!("Comparing with {}", diff.other_name));
+            if let Some(err) = &diff.error {
+                ui.colored_label(colors.error_text, err);
+                return;
+            }
+            if diff.ranges().is_empty() {
+                ui.label("No differences: the files are identical");
+            } else {
+                ui.label(format!(
+                    "{} differing range(s), {} byte(s)",
+                    diff.ranges().len(),
+                    diff.differing_bytes()
+                ));
+            }
+            if diff.is_stale(generation) {
+                ui.horizontal(|ui| {
+                    ui.colored_label(colors.warning_text, "Buffer edited since comparing");
+                    if ui.button("Re-compare").pointer_cursor().clicked() {
+                        recompare = true;
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                let has_ranges = !diff.ranges().is_empty();
+                if ui
+                    .add_enabled(has_ranges, egui::Button::new("\u{25C0} Previous"))
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    step = Some(false);
+                }
+                if ui
+                    .add_enabled(has_ranges, egui::Button::new("Next \u{25B6}"))
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    step = Some(true);
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (index, range) in diff.ranges().iter().take(MAX_LISTED_RANGES).enumerate() {
+                        let text = format!(
+                            "0x{:08X}-0x{:08X} ({} bytes)",
+                            range.start,
+                            range.end - 1,
+                            range.len()
+                        );
+                        let selected = diff.current() == Some(index);
+                        if ui
+                            .selectable_label(selected, egui::RichText::new(text).monospace())
+                            .pointer_cursor()
+                            .clicked()
+                        {
+                            jump = Some(index);
+                        }
+                    }
+                    if diff.ranges().len() > MAX_LISTED_RANGES {
+                        ui.weak(format!(
+                            "... {} more (use Next/Previous)",
+                            diff.ranges().len() - MAX_LISTED_RANGES
+                        ));
+                    }
+                });
+        });
+
+    if recompare {
+        if let Some(editor) = &doc.editor {
+            ui_state.file_diff.recompare(editor.working(), generation);
+        }
+    }
+    let target = match (jump, step) {
+        (Some(index), _) => ui_state.file_diff.select(index),
+        (None, Some(forward)) => ui_state.file_diff.step(forward),
+        _ => None,
+    };
+    if let Some(range) = target {
+        jump_to(doc, ui_state, range);
+    }
+    if !open {
+        ui_state.file_diff.dialog_open = false;
+    }
+}
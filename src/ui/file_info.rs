@@ -0,0 +1,52 @@
+//! File info panel: sizes and checksums of the working and original buffers
+
+use crate::app::DocumentState;
+use crate::editor::checksum::BufferChecksum;
+use crate::ui::theme::AppColors;
+use eframe::egui;
+
+/// Show buffer lengths and CRC32s, refreshing the cache if the buffer changed
+pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, colors: &AppColors) {
+    let Some(editor) = &doc.editor else {
+        ui.label("No file loaded");
+        return;
+    };
+    doc.checksums.update(editor);
+
+    egui::Grid::new("file_info_grid")
+        .num_columns(3)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("");
+            ui.strong("Size");
+            ui.strong("CRC32");
+            ui.end_row();
+            checksum_row(ui, "Working", doc.checksums.working());
+            checksum_row(ui, "Original", doc.checksums.original());
+        });
+
+    if doc.checksums.matches_original() {
+        ui.label("Working buffer is identical to the original");
+    } else {
+        ui.colored_label(
+            colors.warning_text,
+            "Working buffer differs from the original",
+        );
+    }
+}
+
+/// One labeled row of the checksum grid
+fn checksum_row(ui: &mut egui::Ui, label: &str, checksum: Option<BufferChecksum>) {
+    ui.label(label);
+    match checksum {
+        Some(checksum) => {
+            ui.monospace(format!("{} bytes", checksum.len));
+            ui.monospace(format!("{:08X}", checksum.crc32));
+        }
+        None => {
+            ui.label("-");
+            ui.label("-");
+        }
+    }
+    ui.end_row();
+}
@@ -1,13 +1,19 @@
 //! Go to offset dialog UI component
 
 use crate::app::{DocumentState, UiState};
-use crate::editor::go_to_offset::parse_offset;
+use crate::editor::go_to_offset::{parse_byte_value, parse_offset, parse_range};
 use crate::ui::PointerCursor;
 use eframe::egui;
 
-/// Attempt to navigate to the offset specified in the dialog input
+/// Attempt to navigate to the offset specified in the dialog input.
+/// In range mode with both fields filled, selects the range instead.
 fn attempt_navigate(doc: &mut DocumentState, ui_state: &mut UiState) -> Result<(), String> {
-    let offset = parse_offset(&ui_state.go_to_offset_state.input_text)?;
+    let state = &ui_state.go_to_offset_state;
+    if state.select_range && !state.end_text.trim().is_empty() {
+        return attempt_select_range(doc, ui_state);
+    }
+
+    let offset = parse_offset(&state.input_text)?;
 
     let editor = doc
         .editor
@@ -27,6 +33,78 @@ fn attempt_navigate(doc: &mut DocumentState, ui_state: &mut UiState) -> Result<(
     Ok(())
 }
 
+/// Select the start/end range specified in the dialog inputs and scroll to its start
+fn attempt_select_range(doc: &mut DocumentState, ui_state: &mut UiState) -> Result<(), String> {
+    let state = &ui_state.go_to_offset_state;
+    let (start, end) = parse_range(&state.input_text, &state.end_text)?;
+
+    let editor = doc
+        .editor
+        .as_mut()
+        .ok_or_else(|| "No file loaded".to_string())?;
+
+    let file_len = editor.len();
+    if end > file_len {
+        return Err(format!(
+            "End offset 0x{:X} ({}) is beyond file size (0x{:X} / {} bytes)",
+            end - 1,
+            end - 1,
+            file_len,
+            file_len
+        ));
+    }
+
+    editor.set_selection(start, end);
+    ui_state.pending_hex_scroll = Some(start);
+    Ok(())
+}
+
+/// Move the cursor to the next (or previous) occurrence of `value`, wrapping
+/// around the ends of the file. Returns false if the byte doesn't occur at all.
+pub fn jump_to_byte(
+    doc: &mut DocumentState,
+    ui_state: &mut UiState,
+    value: u8,
+    forward: bool,
+) -> bool {
+    let Some(editor) = &mut doc.editor else {
+        return false;
+    };
+    let cursor = editor.cursor();
+    let found = if forward {
+        editor
+            .find_next_byte(cursor + 1, value)
+            .or_else(|| editor.find_next_byte(0, value))
+    } else {
+        editor
+            .find_prev_byte(cursor, value)
+            .or_else(|| editor.find_prev_byte(editor.len(), value))
+    };
+    ui_state.go_to_offset_state.last_byte = Some(value);
+
+    let Some(offset) = found else {
+        return false;
+    };
+    editor.clear_unlocked_selection();
+    editor.set_cursor(offset);
+    ui_state.pending_hex_scroll = Some(offset);
+    true
+}
+
+/// Parse the dialog's byte field and jump to the next/previous occurrence
+fn attempt_byte_jump(
+    doc: &mut DocumentState,
+    ui_state: &mut UiState,
+    forward: bool,
+) -> Result<(), String> {
+    let value = parse_byte_value(&ui_state.go_to_offset_state.byte_text)?;
+    if jump_to_byte(doc, ui_state, value, forward) {
+        Ok(())
+    } else {
+        Err(format!("Byte 0x{:02X} does not occur in the file", value))
+    }
+}
+
 /// Show the "Go to offset" dialog (modal window).
 /// Needs `DocumentState` (for editor) and `UiState` (for dialog state,
 /// colors, and the pending-scroll intent).
@@ -37,6 +115,8 @@ pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState
 
     let mut close_dialog = false;
     let mut do_navigate = false;
+    let mut jump_to_section: Option<usize> = None;
+    let mut byte_jump: Option<bool> = None;
 
     egui::Window::new("Go to Offset")
         .collapsible(false)
@@ -55,7 +135,10 @@ pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState
             );
 
             // Auto-focus the text field when dialog opens
-            if response.gained_focus() || ui_state.go_to_offset_state.input_text.is_empty() {
+            let nothing_focused = ui.memory(|m| m.focused().is_none());
+            if response.gained_focus()
+                || (ui_state.go_to_offset_state.input_text.is_empty() && nothing_focused)
+            {
                 response.request_focus();
             }
 
@@ -64,6 +147,72 @@ pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState
                 do_navigate = true;
             }
 
+            ui.add_space(4.0);
+            ui.checkbox(
+                &mut ui_state.go_to_offset_state.select_range,
+                "Select range",
+            );
+
+            if ui_state.go_to_offset_state.select_range {
+                ui.label("End offset (inclusive):");
+                let end_response = ui.add(
+                    egui::TextEdit::singleline(&mut ui_state.go_to_offset_state.end_text)
+                        .hint_text("Leave empty to just go to start")
+                        .desired_width(200.0),
+                );
+                if end_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    do_navigate = true;
+                }
+            }
+
+            // Jump straight to a parsed section
+            let sections = doc.flattened_sections();
+            if !sections.is_empty() {
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.label("Section:");
+                    egui::ComboBox::from_id_salt("go_to_section")
+                        .selected_text("Choose a section...")
+                        .width(200.0)
+                        .show_ui(ui, |ui| {
+                            for (depth, section) in &sections {
+                                let label = format!(
+                                    "{}{} (0x{:X})",
+                                    "  ".repeat(*depth),
+                                    section.name,
+                                    section.start
+                                );
+                                if ui.selectable_label(false, label).clicked() {
+                                    jump_to_section = Some(section.start);
+                                }
+                            }
+                        });
+                });
+            }
+
+            // Jump to the next/previous occurrence of a single byte
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label("Find byte:");
+                let byte_response = ui.add(
+                    egui::TextEdit::singleline(&mut ui_state.go_to_offset_state.byte_text)
+                        .hint_text("FF")
+                        .char_limit(4)
+                        .desired_width(40.0),
+                );
+                if byte_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    byte_jump = Some(true);
+                }
+                if ui.button("◀ Prev").pointer_cursor().clicked() {
+                    byte_jump = Some(false);
+                }
+                if ui.button("Next ▶").pointer_cursor().clicked() {
+                    byte_jump = Some(true);
+                }
+            })
+            .response
+            .on_hover_text("Repeat the last byte jump with Ctrl+. (next) and Ctrl+, (previous)");
+
             // Show file size hint if available
             if let Some(editor) = &doc.editor {
                 ui.add_space(4.0);
@@ -103,7 +252,19 @@ pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState
         });
 
     // Handle navigation after UI scope ends (to avoid borrow issues)
-    if do_navigate {
+    if let Some(start) = jump_to_section {
+        if let Some(editor) = &mut doc.editor {
+            editor.clear_unlocked_selection();
+            editor.set_cursor(start);
+            ui_state.pending_hex_scroll = Some(start);
+            close_dialog = true;
+        }
+    } else if let Some(forward) = byte_jump {
+        match attempt_byte_jump(doc, ui_state, forward) {
+            Ok(()) => close_dialog = true,
+            Err(e) => ui_state.go_to_offset_state.error = Some(e),
+        }
+    } else if do_navigate {
         match attempt_navigate(doc, ui_state) {
             Ok(()) => close_dialog = true,
             Err(e) => ui_state.go_to_offset_state.error = Some(e),
@@ -1,9 +1,13 @@
 //! Hex editor UI component with virtual scrolling
 
-use crate::app::{BendApp, PendingEditType};
+use crate::app::{BendApp, PendingEditType, PendingPaste};
 use crate::editor::buffer::{EditMode, NibblePosition, WriteMode};
+use crate::editor::clipboard_history::entry_label;
+use crate::editor::text_encoding::TextEncoding;
 use crate::editor::{is_printable_ascii, is_printable_ascii_char};
 use crate::formats::RiskLevel;
+use crate::keybindings::ShortcutAction;
+use crate::settings::OffsetBase;
 use crate::ui::theme::AppColors;
 use crate::ui::PointerCursor;
 use eframe::egui::{self, RichText, TextStyle};
@@ -35,8 +39,117 @@ pub struct ContextMenuState {
     pub target_offset: Option<usize>,
 }
 
-/// Number of bytes per row
-const BYTES_PER_ROW: usize = 16;
+/// Vim-style command mode ("vim motions" setting): Escape enters it, digits
+/// build a count, and the next navigation key moves (or with Shift, selects)
+/// that many times. Typing doesn't edit bytes until `i` or Escape leaves it.
+#[derive(Default)]
+pub struct MotionState {
+    /// Whether command mode is on
+    pub active: bool,
+    /// Count typed so far, applied to the next navigation key
+    pub count: Option<usize>,
+}
+
+impl MotionState {
+    /// Escape: enter command mode, drop a half-typed count, or leave
+    fn escape(&mut self) {
+        if !self.active {
+            self.active = true;
+        } else if self.count.take().is_none() {
+            self.active = false;
+        }
+    }
+
+    /// Leave command mode (and forget any count)
+    pub fn exit(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Append a typed digit to the count
+    fn push_digit(&mut self, digit: u32) {
+        let count = self.count.unwrap_or(0);
+        self.count = Some(count.saturating_mul(10).saturating_add(digit as usize));
+    }
+
+    /// Status bar text while command mode is on
+    pub fn status(&self) -> Option<String> {
+        match (self.active, self.count) {
+            (false, _) => None,
+            (true, None) => Some("COMMAND".to_string()),
+            (true, Some(count)) => Some(format!("COMMAND {}", count)),
+        }
+    }
+}
+
+/// "Measure Distance" mode: the first click picks a start byte, the second an
+/// end byte, and the span between them is highlighted and measured. Clicks
+/// don't move the cursor or touch the selection while the mode is on.
+#[derive(Default)]
+pub struct MeasureState {
+    /// Whether clicks in the hex view measure instead of moving the cursor
+    pub active: bool,
+    /// First clicked offset
+    start: Option<usize>,
+    /// Second clicked offset; a further click starts a new measurement
+    end: Option<usize>,
+}
+
+impl MeasureState {
+    /// Turn the mode on or off, forgetting any measurement
+    pub fn toggle(&mut self) {
+        let active = !self.active;
+        *self = Self {
+            active,
+            ..Self::default()
+        };
+    }
+
+    /// Leave the mode
+    pub fn exit(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Record a clicked offset: the end if a start is pending, otherwise a new start
+    fn click(&mut self, offset: usize) {
+        match (self.start, self.end) {
+            (Some(_), None) => self.end = Some(offset),
+            _ => {
+                self.start = Some(offset);
+                self.end = None;
+            }
+        }
+    }
+
+    /// Highlighted span as `[start, end)`: the start byte alone until the end is picked
+    fn span(&self) -> Option<(usize, usize)> {
+        let start = self.start?;
+        let end = self.end.unwrap_or(start);
+        Some((start.min(end), start.max(end) + 1))
+    }
+
+    /// Difference between the two picked offsets (one less than the
+    /// highlighted span's length)
+    pub fn distance(&self) -> Option<usize> {
+        Some(self.start?.abs_diff(self.end?))
+    }
+
+    /// Status bar text while the mode is on
+    pub fn status(&self) -> Option<String> {
+        if !self.active {
+            return None;
+        }
+        Some(match (self.start, self.distance()) {
+            (None, _) => "Measure: click the start byte".to_string(),
+            (Some(_), None) => "Measure: click the end byte".to_string(),
+            (Some(_), Some(distance)) => format!(
+                "Offset difference: 0x{:X} ({}); span: {} bytes",
+                distance,
+                distance,
+                distance + 1
+            ),
+        })
+    }
+}
 
 /// Number of rows to render above/below viewport for smooth scrolling
 const BUFFER_ROWS: usize = 2;
@@ -44,23 +157,39 @@ const BUFFER_ROWS: usize = 2;
 /// Spacing between offset column and hex bytes
 const OFFSET_HEX_SPACING: f32 = 8.0;
 
-/// Spacing between hex byte groups (after every 8 bytes)
+/// Number of bytes between hex group separators
+const HEX_GROUP_SIZE: usize = 8;
+
+/// Spacing between hex byte groups (after every `HEX_GROUP_SIZE` bytes)
 const HEX_GROUP_SPACING: f32 = 8.0;
 
+/// Spacing between ASCII character groups (when the separator is enabled)
+const ASCII_GROUP_SPACING: f32 = 6.0;
+
 /// Spacing between hex bytes and ASCII column
 const HEX_ASCII_SPACING: f32 = 16.0;
 
 /// Number of rows to scroll above target when jumping to an offset
 const SCROLL_BUFFER_ROWS: usize = 5;
 
+/// Height (in rows) of the zone at the top/bottom edge that auto-scrolls during a drag
+const DRAG_AUTOSCROLL_ROWS: f32 = 2.0;
+
 /// Pre-computed highlight state for a single byte
 struct ByteHighlight {
     is_cursor: bool,
     is_selected: bool,
+    /// Inside the span picked in "Measure Distance" mode
+    is_measured: bool,
     is_search_match: bool,
     is_current_match: bool,
     has_bookmark: bool,
+    /// Differs from the file in the "Compare with File" window
+    is_file_diff: bool,
+    is_diff: bool,
     is_protected: bool,
+    /// A blocked edit on this protected byte is being flashed
+    is_blocked_flash: bool,
     section_bg: Option<egui::Color32>,
 }
 
@@ -76,18 +205,24 @@ fn cursor_color_pair(write_mode: WriteMode, colors: &AppColors) -> (egui::Color3
 }
 
 /// Pick the non-cursor background color for a byte based on highlight flags.
-/// Priority: selection > current_match > search_match > bookmark > section tint.
+/// Priority: selection > measured span > current_match > search_match > bookmark > file diff > diff > section tint.
 /// Returns `None` when the byte has no applicable background. Cursor painting is
 /// handled by the caller because it uses split-nibble rendering in the hex column.
 fn byte_background_color(highlight: &ByteHighlight, colors: &AppColors) -> Option<egui::Color32> {
     if highlight.is_selected {
         Some(colors.selection_bg)
+    } else if highlight.is_measured {
+        Some(colors.measure_bg)
     } else if highlight.is_current_match {
         Some(colors.current_match_bg)
     } else if highlight.is_search_match {
         Some(colors.search_match_bg)
     } else if highlight.has_bookmark {
         Some(colors.bookmark_bg)
+    } else if highlight.is_file_diff {
+        Some(colors.file_diff_bg)
+    } else if highlight.is_diff {
+        Some(colors.diff_bg)
     } else {
         highlight.section_bg
     }
@@ -127,8 +262,10 @@ fn render_hex_byte(
     let galley = ui.fonts(|f| f.layout_no_wrap(hex.to_string(), font_id.clone(), text_color));
     let (rect, response) = ui.allocate_exact_size(galley.size(), egui::Sense::click_and_drag());
 
-    // Paint backgrounds
-    if highlight.is_cursor {
+    // Paint backgrounds (a blocked-edit flash wins over everything, even the cursor)
+    if highlight.is_blocked_flash {
+        ui.painter().rect_filled(rect, 0.0, colors.blocked_edit_bg);
+    } else if highlight.is_cursor {
         let half_width = rect.width() / 2.0;
         let (bright, dim) = cursor_color_pair(write_mode, colors);
         let (high_bg, low_bg) = if edit_mode == EditMode::Ascii {
@@ -165,7 +302,107 @@ fn render_hex_byte(
     response
 }
 
-/// Render the entire ASCII row as a single label with painter-based highlighting.
+/// Offset/value rows shown in a byte's hover tooltip
+fn byte_detail_rows(
+    offset: usize,
+    byte: u8,
+    encoding: TextEncoding,
+) -> [(&'static str, String); 5] {
+    let text = match encoding.decode(byte) {
+        Some(ch) => format!("'{}'", ch),
+        None => "(non-printable)".to_string(),
+    };
+    [
+        ("Offset", format!("0x{:08X} ({})", offset, offset)),
+        ("Hex", format!("0x{:02X}", byte)),
+        ("Decimal", byte.to_string()),
+        ("Binary", format!("{:04b} {:04b}", byte >> 4, byte & 0x0F)),
+        (encoding.label(), text),
+    ]
+}
+
+/// Tooltip body for a hovered byte: its values plus the section it belongs to
+fn show_byte_tooltip(ui: &mut egui::Ui, app: &BendApp, offset: usize, byte: u8) {
+    egui::Grid::new("byte_tooltip_grid")
+        .num_columns(2)
+        .spacing([12.0, 2.0])
+        .show(ui, |ui| {
+            for (label, value) in byte_detail_rows(offset, byte, app.ui.text_encoding) {
+                ui.label(label);
+                ui.monospace(value);
+                ui.end_row();
+            }
+            ui.label("Section");
+            match app.doc.section_at_offset(offset) {
+                Some(section) => {
+                    ui.colored_label(
+                        app.ui.colors.risk_color(section.risk),
+                        format!("{} ({})", section.name, section.risk.label()),
+                    );
+                }
+                None => {
+                    ui.label("(none)");
+                }
+            }
+            ui.end_row();
+        });
+    if app.doc.is_offset_protected(offset) {
+        ui.colored_label(app.ui.colors.warning_text, "Protected");
+    }
+}
+
+/// Fixed-width cell layout of the ASCII column, with an optional gap after
+/// every `HEX_GROUP_SIZE` characters so groups line up with the hex side
+#[derive(Clone, Copy)]
+struct AsciiLayout {
+    /// Width of each character cell (the monospace glyph advance)
+    cell_width: f32,
+    /// Extra space between groups (0 when group separators are off)
+    group_gap: f32,
+}
+
+impl AsciiLayout {
+    fn new(ui: &egui::Ui, state: &HexDisplayState) -> Self {
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        Self {
+            cell_width: ui.fonts(|f| f.glyph_width(&font_id, '0')),
+            group_gap: if state.ascii_group_separator {
+                ASCII_GROUP_SPACING
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Left edge of cell `index`, relative to the start of the column
+    fn cell_x(&self, index: usize) -> f32 {
+        index as f32 * self.cell_width + (index / HEX_GROUP_SIZE) as f32 * self.group_gap
+    }
+
+    /// Total width of a row of `count` cells
+    fn row_width(&self, count: usize) -> f32 {
+        match count {
+            0 => 0.0,
+            n => self.cell_x(n - 1) + self.cell_width,
+        }
+    }
+
+    /// Cell under horizontal position `dx` (relative to the column start).
+    /// A position inside a group gap maps to the last cell of that group.
+    fn cell_at(&self, dx: f32) -> Option<usize> {
+        if dx < 0.0 || self.cell_width <= 0.0 {
+            return None;
+        }
+        let group_width = HEX_GROUP_SIZE as f32 * self.cell_width + self.group_gap;
+        let group = (dx / group_width).floor() as usize;
+        let within = dx - group as f32 * group_width;
+        let cell = ((within / self.cell_width).floor() as usize).min(HEX_GROUP_SIZE - 1);
+        Some(group * HEX_GROUP_SIZE + cell)
+    }
+}
+
+/// Render the ASCII row with every character painted into its own fixed-width
+/// cell, so columns line up exactly regardless of glyph metrics.
 /// Returns the interaction response — click/drag/right-click detection is owned
 /// by the caller (`render_row`) so there's a single source of truth and no
 /// duplicated `interact_pointer_pos()` reads on the same response.
@@ -174,133 +411,183 @@ fn render_ascii_row(
     row_bytes: &[u8],
     row_offset: usize,
     state: &HexDisplayState,
+    layout: AsciiLayout,
     colors: &AppColors,
 ) -> egui::Response {
-    // Build the full 16-char string: real chars + NBSP padding
-    let mut text = String::with_capacity(BYTES_PER_ROW);
-    for byte in row_bytes {
-        if is_printable_ascii(*byte) {
-            text.push(*byte as char);
-        } else {
-            text.push('.');
-        }
-    }
-    let missing = BYTES_PER_ROW - row_bytes.len();
-    for _ in 0..missing {
-        text.push('\u{00A0}'); // non-breaking space (not trimmed by text layout)
-    }
-
     // Paint the row manually: egui's Label changes the text color on hover when
     // given an interactive sense, which makes the whole ASCII row "light up".
-    // Allocating the rect ourselves and painting the galley directly avoids that
+    // Allocating the rect ourselves and painting each glyph directly avoids that
     // while preserving click/drag detection for selection.
     let font_id = TextStyle::Monospace.resolve(ui.style());
-    let text_color = ui.visuals().text_color();
-    let galley = ui.fonts(|f| f.layout_no_wrap(text, font_id.clone(), text_color));
-    let (rect, response) = ui.allocate_exact_size(galley.size(), egui::Sense::click_and_drag());
-    ui.painter().galley(rect.min, galley, text_color);
-    let char_width = rect.width() / BYTES_PER_ROW as f32;
-    let font_id = TextStyle::Monospace.resolve(ui.style());
-
-    // Draw highlights and re-paint characters on top
+    let row_height = ui.fonts(|f| f.row_height(&font_id));
+    let size = egui::vec2(layout.row_width(state.bytes_per_row), row_height);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
     let (cursor_bright, cursor_dim) = cursor_color_pair(state.write_mode, colors);
 
     for (i, byte) in row_bytes.iter().enumerate() {
         let byte_offset = row_offset + i;
         let is_cursor = byte_offset == state.cursor_pos;
-        let is_selected = state
-            .selection
-            .map(|(start, end)| byte_offset >= start && byte_offset < end)
-            .unwrap_or(false);
-
-        if !is_cursor && !is_selected {
-            continue;
-        }
+        let is_selected = state.is_selected(byte_offset);
 
         let char_rect = egui::Rect::from_min_size(
-            egui::pos2(rect.min.x + i as f32 * char_width, rect.min.y),
-            egui::vec2(char_width, rect.height()),
+            egui::pos2(rect.min.x + layout.cell_x(i), rect.min.y),
+            egui::vec2(layout.cell_width, rect.height()),
         );
 
-        let display_char = if is_printable_ascii(*byte) {
-            *byte as char
-        } else {
-            '.'
-        };
+        let display_char = state.text_encoding.display_char(*byte);
 
-        if is_cursor {
+        let text_color = if is_cursor {
             let bg = if state.edit_mode == EditMode::Ascii {
                 cursor_bright
             } else {
                 cursor_dim
             };
             ui.painter().rect_filled(char_rect, 0.0, bg);
-            ui.painter().text(
-                char_rect.center(),
-                egui::Align2::CENTER_CENTER,
-                display_char,
-                font_id.clone(),
-                colors.cursor_text,
-            );
-        } else if is_selected {
-            ui.painter()
-                .rect_filled(char_rect, 0.0, colors.selection_bg);
-            ui.painter().text(
-                char_rect.center(),
-                egui::Align2::CENTER_CENTER,
-                display_char,
-                font_id.clone(),
-                ui.visuals().text_color(),
-            );
-        }
+            colors.cursor_text
+        } else {
+            if is_selected {
+                ui.painter()
+                    .rect_filled(char_rect, 0.0, colors.selection_bg);
+            }
+            ui.visuals().text_color()
+        };
+        ui.painter().text(
+            char_rect.center(),
+            egui::Align2::CENTER_CENTER,
+            display_char,
+            font_id.clone(),
+            text_color,
+        );
     }
 
     response
 }
 
-/// Navigation keys that follow the move-by-delta pattern: (key, cursor delta).
-const NAV_KEYS: &[(egui::Key, isize)] = &[
-    (egui::Key::ArrowLeft, -1),
-    (egui::Key::ArrowRight, 1),
-    (egui::Key::ArrowUp, -(BYTES_PER_ROW as isize)),
-    (egui::Key::ArrowDown, BYTES_PER_ROW as isize),
-    (egui::Key::PageUp, -(BYTES_PER_ROW as isize * 16)),
-    (egui::Key::PageDown, BYTES_PER_ROW as isize * 16),
+/// Navigation keys that follow the move-by-delta pattern: (key, byte delta, row delta).
+/// The cursor moves by `byte delta + row delta * bytes_per_row`.
+const NAV_KEYS: &[(egui::Key, isize, isize)] = &[
+    (egui::Key::ArrowLeft, -1, 0),
+    (egui::Key::ArrowRight, 1, 0),
+    (egui::Key::ArrowUp, 0, -1),
+    (egui::Key::ArrowDown, 0, 1),
+    (egui::Key::PageUp, 0, -16),
+    (egui::Key::PageDown, 0, 16),
 ];
 
 /// Handle navigation keys (arrows, page up/down, home/end) with optional selection extension.
-fn handle_navigation_keys(editor: &mut crate::editor::EditorState, i: &egui::InputState) {
+/// Arrow and page moves are repeated by a typed `count` (see `MotionState`);
+/// Shift+Left/Right with a count starts a selection of exactly `count` bytes.
+/// Returns whether any navigation key was pressed.
+///
+/// Uses `num_presses` rather than `key_pressed` so every OS key-repeat event moves the
+/// cursor while a key is held, even when several repeats land in the same frame.
+fn handle_navigation_keys(
+    editor: &mut crate::editor::EditorState,
+    i: &egui::InputState,
+    bytes_per_row: usize,
+    count: Option<usize>,
+) -> bool {
     let shift = i.modifiers.shift;
+    let fresh_selection = shift && editor.selection().is_none();
+    let mut moved = false;
 
-    for &(key, delta) in NAV_KEYS {
-        if i.key_pressed(key) {
-            if shift {
-                editor.move_cursor_with_selection(delta);
-            } else {
-                editor.clear_selection();
-                editor.move_cursor(delta);
-            }
+    for &(key, byte_delta, row_delta) in NAV_KEYS {
+        let presses = i.num_presses(key) as isize;
+        // Ctrl+Page Up/Down switch tabs by default instead
+        if presses == 0 || (i.modifiers.command && row_delta.abs() > 1) {
+            continue;
+        }
+        moved = true;
+        let delta = byte_delta + row_delta * bytes_per_row as isize;
+        let mut repeat =
+            presses.saturating_mul(count.unwrap_or(1).min(isize::MAX as usize) as isize);
+        // The cursor byte is already part of a new selection
+        if count.is_some() && fresh_selection && row_delta == 0 {
+            repeat -= 1;
+        }
+        let total = delta.saturating_mul(repeat);
+        if shift {
+            editor.move_cursor_with_selection(total);
+        } else {
+            editor.clear_unlocked_selection();
+            editor.move_cursor(total);
         }
     }
 
     // Home/End use set_cursor/extend_selection_to instead of move_cursor
     if i.key_pressed(egui::Key::Home) {
+        moved = true;
         if shift {
             editor.extend_selection_to(0);
         } else {
-            editor.clear_selection();
+            editor.clear_unlocked_selection();
             editor.set_cursor(0);
         }
     }
     if i.key_pressed(egui::Key::End) {
+        moved = true;
         let last = editor.len().saturating_sub(1);
         if shift {
             editor.extend_selection_to(last);
         } else {
-            editor.clear_selection();
+            editor.clear_unlocked_selection();
             editor.set_cursor(last);
         }
     }
+    moved
+}
+
+/// Handle selection arithmetic: Alt+Right/Left grow/shrink the selection's end
+/// by `step` bytes, Alt+Shift+Left/Right grow/shrink its start. The keys are
+/// consumed so they don't also move the cursor. Returns whether any was pressed.
+fn handle_selection_resize_keys(
+    editor: &mut crate::editor::EditorState,
+    i: &mut egui::InputState,
+    step: usize,
+) -> bool {
+    use egui::{Key, Modifiers};
+
+    let step = step.min(isize::MAX as usize) as isize;
+    let alt_shift = Modifiers::ALT | Modifiers::SHIFT;
+    let presses =
+        |i: &mut egui::InputState, modifiers, key| i.count_and_consume_key(modifiers, key) as isize;
+    // Alt+Shift first: matching is logical, so Alt alone would also take them
+    let start_delta =
+        presses(i, alt_shift, Key::ArrowLeft) - presses(i, alt_shift, Key::ArrowRight);
+    let end_delta =
+        presses(i, Modifiers::ALT, Key::ArrowRight) - presses(i, Modifiers::ALT, Key::ArrowLeft);
+    if start_delta != 0 {
+        editor.grow_selection_start(start_delta.saturating_mul(step));
+    }
+    if end_delta != 0 {
+        editor.grow_selection(end_delta.saturating_mul(step));
+    }
+    start_delta != 0 || end_delta != 0
+}
+
+/// Handle command-mode keys: Escape and `i` to switch modes, digits for the
+/// count. Returns whether command mode is on, in which case typing must not
+/// edit the buffer this frame.
+fn handle_motion_keys(motion: &mut MotionState, i: &egui::InputState) -> bool {
+    if i.key_pressed(egui::Key::Escape) {
+        motion.escape();
+    }
+    if !motion.active {
+        return false;
+    }
+    for event in &i.events {
+        if let egui::Event::Text(text) = event {
+            for c in text.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    motion.push_digit(digit);
+                } else if c == 'i' {
+                    motion.exit();
+                }
+            }
+        }
+    }
+    // The `i` that leaves command mode isn't typed, so this frame stays read-only
+    true
 }
 
 /// Collected result of one frame of hex-editor edit input (text entry,
@@ -314,6 +601,30 @@ struct EditInputResult {
     /// `ui.input_mut` closure (so we can take a `&mut editor` without borrow
     /// conflicts).
     paste_text: Option<String>,
+    /// The paste was Ctrl+Shift+V: tile it across the selection
+    paste_tiled: bool,
+    /// The user tried to edit the cursor byte but it is protected
+    blocked: bool,
+    /// The user tried to edit while a background job is running
+    paused_for_job: bool,
+}
+
+/// Whether typing `c` would edit a byte in the given mode
+fn is_edit_char(c: char, mode: EditMode) -> bool {
+    match mode {
+        EditMode::Hex => c.is_ascii_hexdigit(),
+        EditMode::Ascii => is_printable_ascii_char(c),
+    }
+}
+
+/// Whether this frame's input would edit the cursor byte (typing, Backspace, Delete)
+fn edit_attempted(i: &egui::InputState, mode: EditMode) -> bool {
+    i.key_pressed(egui::Key::Backspace)
+        || i.key_pressed(egui::Key::Delete)
+        || i.events.iter().any(|event| {
+            matches!(event, egui::Event::Text(text)
+                if text.chars().any(|c| is_edit_char(c, mode)))
+        })
 }
 
 /// Handle edit input: text entry, backspace, delete, and paste.
@@ -329,6 +640,7 @@ fn handle_edit_input(
     let mut result = EditInputResult::default();
 
     if cursor_protected {
+        result.blocked = edit_attempted(i, current_edit_mode);
         return result;
     }
 
@@ -392,6 +704,7 @@ fn handle_edit_input(
             }
             egui::Event::Paste(text) => {
                 result.paste_text = Some(text.clone());
+                result.paste_tiled = i.modifiers.shift;
             }
             _ => {}
         }
@@ -420,6 +733,8 @@ struct RowResult {
     drag_current_offset: Option<usize>,
     /// The byte offset of a secondary-click target (for opening the context menu).
     context_menu_offset: Option<usize>,
+    /// Row whose offset label was clicked (selects the whole row).
+    row_clicked: Option<usize>,
 }
 
 impl RowResult {
@@ -439,19 +754,39 @@ impl RowResult {
         if other.context_menu_offset.is_some() {
             self.context_menu_offset = other.context_menu_offset;
         }
+        if other.row_clicked.is_some() {
+            self.row_clicked = other.row_clicked;
+        }
     }
 }
 
 /// Cached display state for the hex editor, read from BendApp once per frame
 struct HexDisplayState {
+    bytes_per_row: usize,
     total_bytes: usize,
     total_rows: usize,
     cursor_pos: usize,
     cursor_nibble: NibblePosition,
-    selection: Option<(usize, usize)>,
+    /// Every selected range (several after Ctrl+drag), sorted and merged
+    selections: Vec<(usize, usize)>,
     edit_mode: EditMode,
     write_mode: WriteMode,
     cursor_protected: bool,
+    /// Whether the ASCII column leaves a gap after every `HEX_GROUP_SIZE` chars
+    ascii_group_separator: bool,
+    /// Base the offset column is shown in
+    offset_base: OffsetBase,
+    /// Character set of the ASCII column
+    text_encoding: TextEncoding,
+}
+
+impl HexDisplayState {
+    /// Whether `offset` falls inside any selected range
+    fn is_selected(&self, offset: usize) -> bool {
+        self.selections
+            .iter()
+            .any(|&(start, end)| offset >= start && offset < end)
+    }
 }
 
 /// Pre-computed highlight lookup data for search matches and bookmarks
@@ -459,6 +794,8 @@ struct HighlightLookup<'a> {
     app: &'a BendApp,
     current_match_offset: Option<usize>,
     pattern_len: usize,
+    blocked_offset: Option<usize>,
+    measured: Option<(usize, usize)>,
 }
 
 impl<'a> HighlightLookup<'a> {
@@ -466,6 +803,8 @@ impl<'a> HighlightLookup<'a> {
         Self {
             current_match_offset: app.ui.search_state.current_match_offset(),
             pattern_len: app.ui.search_state.pattern_length(),
+            blocked_offset: app.ui.recent_blocked_edit().map(|(offset, _)| offset),
+            measured: app.ui.measure_state.span(),
             app,
         }
     }
@@ -473,10 +812,10 @@ impl<'a> HighlightLookup<'a> {
     fn byte_highlight(&self, byte_offset: usize, state: &HexDisplayState) -> ByteHighlight {
         ByteHighlight {
             is_cursor: byte_offset == state.cursor_pos,
-            is_selected: state
-                .selection
-                .map(|(start, end)| byte_offset >= start && byte_offset < end)
-                .unwrap_or(false),
+            is_selected: state.is_selected(byte_offset),
+            is_measured: self
+                .measured
+                .is_some_and(|(start, end)| byte_offset >= start && byte_offset < end),
             is_search_match: self.app.ui.search_state.is_within_match(byte_offset),
             is_current_match: self
                 .current_match_offset
@@ -487,8 +826,26 @@ impl<'a> HighlightLookup<'a> {
                 .editor
                 .as_ref()
                 .is_some_and(|e| e.has_bookmark_at(byte_offset)),
+            is_file_diff: self.app.ui.file_diff.dialog_open
+                && self.app.ui.file_diff.contains(byte_offset),
+            is_diff: self.app.ui.show_diff_highlight
+                && self
+                    .app
+                    .doc
+                    .editor
+                    .as_ref()
+                    .is_some_and(|e| e.differs_at(byte_offset)),
             is_protected: self.app.doc.is_offset_protected(byte_offset),
-            section_bg: self.app.section_color_for_offset(byte_offset),
+            is_blocked_flash: self.blocked_offset == Some(byte_offset),
+            section_bg: if self.app.ui.show_entropy_map {
+                self.app
+                    .doc
+                    .entropy_map
+                    .value_at(byte_offset)
+                    .map(|e| self.app.ui.colors.entropy_bg_color(e))
+            } else {
+                self.app.section_color_for_offset(byte_offset)
+            },
         }
     }
 }
@@ -508,15 +865,20 @@ struct RowRenderContext<'a> {
 fn prepare_display_state(app: &BendApp) -> Option<HexDisplayState> {
     let editor = app.doc.editor.as_ref()?;
     let total_bytes = editor.len();
+    let bytes_per_row = app.config.settings.bytes_per_row();
     Some(HexDisplayState {
+        bytes_per_row,
         total_bytes,
-        total_rows: total_bytes.div_ceil(BYTES_PER_ROW),
+        total_rows: total_bytes.div_ceil(bytes_per_row),
         cursor_pos: editor.cursor(),
         cursor_nibble: editor.nibble(),
-        selection: editor.selection(),
+        selections: editor.selections(),
         edit_mode: editor.edit_mode(),
         write_mode: editor.write_mode(),
         cursor_protected: app.doc.is_offset_protected(editor.cursor()),
+        ascii_group_separator: app.config.settings.ascii_group_separator,
+        offset_base: app.config.settings.offset_base,
+        text_encoding: app.ui.text_encoding,
     })
 }
 
@@ -534,22 +896,30 @@ fn render_row(
     let colors = ctx.colors;
     let highlights = ctx.highlights;
     let pointer = ctx.pointer;
-    let offset = row_idx * BYTES_PER_ROW;
-    let row_end = (offset + BYTES_PER_ROW).min(state.total_bytes);
+    let offset = row_idx * state.bytes_per_row;
+    let row_end = (offset + state.bytes_per_row).min(state.total_bytes);
     let row_bytes = editor.bytes_in_range(offset, row_end);
     let mut result = RowResult::default();
 
     let row_response = ui.horizontal(|ui| {
-        // Offset column
-        ui.add(
-            egui::Label::new(RichText::new(format!("{:08X}", offset)).monospace())
-                .selectable(false),
-        );
+        // Offset column — acts as a row header: click selects the row, shift-click extends
+        let offset_resp = ui
+            .add(
+                egui::Label::new(
+                    RichText::new(state.offset_base.format_column(offset)).monospace(),
+                )
+                .selectable(false)
+                .sense(egui::Sense::click()),
+            )
+            .pointer_cursor();
+        if offset_resp.clicked() {
+            result.row_clicked = Some(row_idx);
+        }
         ui.add_space(OFFSET_HEX_SPACING);
 
         // Hex bytes
         for (i, byte) in row_bytes.iter().enumerate() {
-            if i == 8 {
+            if i > 0 && i % HEX_GROUP_SIZE == 0 {
                 ui.add_space(HEX_GROUP_SPACING);
             }
             let byte_offset = offset + i;
@@ -562,7 +932,8 @@ fn render_row(
                 state.edit_mode,
                 state.write_mode,
                 colors,
-            );
+            )
+            .on_hover_ui(|ui| show_byte_tooltip(ui, highlights.app, byte_offset, *byte));
             if response.clicked() {
                 result.cursor_move = Some((byte_offset, EditMode::Hex));
             }
@@ -583,8 +954,8 @@ fn render_row(
         }
 
         // Pad remaining space if row is incomplete (keeps ASCII column aligned).
-        for i in row_bytes.len()..BYTES_PER_ROW {
-            if i == 8 {
+        for i in row_bytes.len()..state.bytes_per_row {
+            if i > 0 && i % HEX_GROUP_SIZE == 0 {
                 ui.add_space(HEX_GROUP_SPACING);
             }
             ui.add(
@@ -601,24 +972,18 @@ fn render_row(
         // ASCII column — bracketed by non-selectable "|" pipes (commit 6b4fdaf).
         ui.spacing_mut().item_spacing.x = 0.0;
         ui.add(egui::Label::new(RichText::new("|").monospace()).selectable(false));
-        let ascii_resp = render_ascii_row(ui, &row_bytes, offset, state, colors);
+        let ascii_layout = AsciiLayout::new(ui, state);
+        let ascii_resp = render_ascii_row(ui, row_bytes, offset, state, ascii_layout, colors);
         // ASCII click/drag: map pointer x to a char index within the row.
         // `ascii_byte_at_x` skips the rect.contains() check — use it for
         // click/drag-started handlers where egui's widget association already
         // confirms the event belongs to this row, so a small y-drift between
         // press and release shouldn't drop the click.
         let ascii_byte_at_x = |x: f32| -> Option<usize> {
-            let char_width = ascii_resp.rect.width() / BYTES_PER_ROW as f32;
-            let dx = x - ascii_resp.rect.min.x;
-            if dx < 0.0 {
-                return None;
-            }
-            let char_idx = (dx / char_width).floor() as usize;
-            if char_idx < row_bytes.len() {
-                Some(offset + char_idx)
-            } else {
-                None
-            }
+            ascii_layout
+                .cell_at(x - ascii_resp.rect.min.x)
+                .filter(|&char_idx| char_idx < row_bytes.len())
+                .map(|char_idx| offset + char_idx)
         };
         // `ascii_byte_at_pointer` gates by rect.contains() — use it for
         // drag-extension where the pointer can be over any row in the editor.
@@ -681,6 +1046,62 @@ fn render_row(
     result
 }
 
+/// Offset column heading, padded to the column's width so the ruler lines up
+fn offset_header(base: OffsetBase) -> String {
+    let width = base.format_column(0).len();
+    format!("{:<width$}", "Offset", width = width)
+}
+
+/// Render the byte ruler: the same layout as `render_row`, with column indices
+/// in place of bytes and "Offset"/"ASCII" headings. The cursor's column is
+/// shown at full strength.
+fn render_ruler(ui: &mut egui::Ui, state: &HexDisplayState) {
+    let weak = ui.visuals().weak_text_color();
+    let strong = ui.visuals().strong_text_color();
+    let cursor_column = state.cursor_pos % state.bytes_per_row;
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::Label::new(
+                RichText::new(offset_header(state.offset_base))
+                    .monospace()
+                    .color(weak),
+            )
+            .selectable(false),
+        );
+        ui.add_space(OFFSET_HEX_SPACING);
+
+        for i in 0..state.bytes_per_row {
+            if i > 0 && i % HEX_GROUP_SIZE == 0 {
+                ui.add_space(HEX_GROUP_SPACING);
+            }
+            let color = if i == cursor_column { strong } else { weak };
+            ui.add(
+                egui::Label::new(RichText::new(hex_table()[i]).monospace().color(color))
+                    .selectable(false),
+            );
+        }
+        ui.add_space(HEX_ASCII_SPACING);
+
+        ui.spacing_mut().item_spacing.x = 0.0;
+        ui.add(egui::Label::new(RichText::new("|").monospace().color(weak)).selectable(false));
+        let layout = AsciiLayout::new(ui, state);
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let size = egui::vec2(
+            layout.row_width(state.bytes_per_row),
+            ui.fonts(|f| f.row_height(&font_id)),
+        );
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        ui.painter().text(
+            rect.left_center(),
+            egui::Align2::LEFT_CENTER,
+            "ASCII",
+            font_id,
+            weak,
+        );
+        ui.add(egui::Label::new(RichText::new("|").monospace().color(weak)).selectable(false));
+    });
+}
+
 /// Show the hex editor panel.
 /// Orchestrator: snapshot frame inputs → compute scroll target → run virtual
 /// scrolling, calling `render_row` per visible row → apply the merged
@@ -690,8 +1111,21 @@ pub fn show(ui: &mut egui::Ui, app: &mut BendApp) {
         return;
     };
 
+    if app.ui.show_entropy_map {
+        if let Some(editor) = &app.doc.editor {
+            app.doc
+                .entropy_map
+                .update(editor.working(), editor.edit_generation());
+        }
+    }
+
+    // Scoped to this panel: everything below (row height included) reads the monospace style
+    ui.style_mut().text_styles.insert(
+        TextStyle::Monospace,
+        egui::FontId::monospace(app.config.settings.hex_font_size()),
+    );
     let row_height = ui.text_style_height(&TextStyle::Monospace);
-    let shift_held = ui.input(|i| i.modifiers.shift);
+    let (shift_held, command_held) = ui.input(|i| (i.modifiers.shift, i.modifiers.command));
 
     // Snapshot pointer + drag state once so `render_row` sees a consistent view.
     let drag_id = egui::Id::new("hex_editor_drag");
@@ -706,21 +1140,44 @@ pub fn show(ui: &mut egui::Ui, app: &mut BendApp) {
         .ui
         .pending_hex_scroll
         .take()
-        .map(|byte_offset| byte_offset / BYTES_PER_ROW);
+        .map(|byte_offset| byte_offset / state.bytes_per_row);
     let initial_scroll_offset: Option<f32> = scroll_to_row.map(|target_row| {
         (target_row.saturating_sub(SCROLL_BUFFER_ROWS) as f32 * row_height).max(0.0)
     });
 
+    // Follow a cursor that moved since last frame (e.g. typing past the last
+    // visible row). Explicit scroll targets above take precedence, and a
+    // cursor that stays put never pulls the view back after manual scrolling.
+    let followed_id = egui::Id::new("hex_editor_followed_cursor");
+    let last_cursor: Option<usize> = ui.data(|d| d.get_temp(followed_id));
+    ui.data_mut(|d| d.insert_temp(followed_id, state.cursor_pos));
+    let follow_row = (app.config.settings.follow_cursor
+        && scroll_to_row.is_none()
+        && last_cursor.is_some_and(|last| last != state.cursor_pos))
+    .then_some(state.cursor_pos / state.bytes_per_row);
+
     let colors = app.ui.colors;
     let highlights = HighlightLookup::new(app);
 
+    // Byte ruler: a fixed strip above the rows that follows their horizontal
+    // scroll (as of last frame) so the columns stay lined up
+    let ruler_scroll_id = egui::Id::new("hex_ruler_scroll_x");
+    let ruler_scroll_x: f32 = ui.data(|d| d.get_temp(ruler_scroll_id).unwrap_or(0.0));
+    egui::ScrollArea::horizontal()
+        .id_salt("hex_ruler")
+        .auto_shrink([false, true])
+        .enable_scrolling(false)
+        .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysHidden)
+        .horizontal_scroll_offset(ruler_scroll_x)
+        .show(ui, |ui| render_ruler(ui, &state));
+
     let mut scroll_area = egui::ScrollArea::both().auto_shrink([false; 2]);
     if let Some(offset_y) = initial_scroll_offset {
         scroll_area = scroll_area.vertical_scroll_offset(offset_y);
     }
 
     let mut result = RowResult::default();
-    scroll_area.show_viewport(ui, |ui, viewport| {
+    let scroll_output = scroll_area.show_viewport(ui, |ui, viewport| {
         let first_visible_row = (viewport.min.y / row_height).floor() as usize;
         let last_visible_row =
             ((viewport.max.y / row_height).ceil() as usize).min(state.total_rows);
@@ -754,14 +1211,41 @@ pub fn show(ui: &mut egui::Ui, app: &mut BendApp) {
                 rows_after as f32 * row_height,
             ));
         }
+
+        if let Some(row) = follow_row {
+            let delta = follow_cursor_delta(row, viewport.min.y, viewport.max.y, row_height);
+            if delta != 0.0 {
+                ui.scroll_with_delta(egui::vec2(0.0, delta));
+                ui.ctx().request_repaint();
+            }
+        }
+
+        // Auto-scroll while drag-selecting near the top/bottom edge
+        if pointer.drag_active && pointer.primary_down {
+            if let Some(pos) = pointer.pointer_pos {
+                let clip = ui.clip_rect();
+                let delta = drag_autoscroll_delta(pos.y, clip.top(), clip.bottom(), row_height);
+                if delta != 0.0 {
+                    ui.scroll_with_delta(egui::vec2(0.0, delta));
+                    ui.ctx().request_repaint();
+                }
+            }
+        }
     });
 
+    let scroll_x = scroll_output.state.offset.x;
+    if scroll_x != ruler_scroll_x {
+        ui.data_mut(|d| d.insert_temp(ruler_scroll_id, scroll_x));
+        ui.ctx().request_repaint();
+    }
+
     handle_row_interactions(
         ui,
         app,
         result,
         &RowInteractionContext {
             shift_held,
+            command_held,
             primary_down: pointer.primary_down,
             drag_id,
         },
@@ -775,16 +1259,54 @@ pub fn show(ui: &mut egui::Ui, app: &mut BendApp) {
             risk_level,
         });
     }
+    if keyboard_result.blocked_edit {
+        app.ui.flag_blocked_edit(state.cursor_pos);
+        ui.ctx().request_repaint();
+    }
 
     show_context_menu(ui, app);
 }
 
+/// Scroll delta for a drag whose pointer is at `pointer_y` in a viewport
+/// spanning `top..bottom`. Speed grows the deeper the pointer is into the edge
+/// zone (or past the edge). Positive values scroll up, matching
+/// `Ui::scroll_with_delta`.
+fn drag_autoscroll_delta(pointer_y: f32, top: f32, bottom: f32, row_height: f32) -> f32 {
+    let zone = row_height * DRAG_AUTOSCROLL_ROWS;
+    if pointer_y < top + zone {
+        (top + zone - pointer_y).min(zone * 2.0) / 2.0
+    } else if pointer_y > bottom - zone {
+        -(pointer_y - (bottom - zone)).min(zone * 2.0) / 2.0
+    } else {
+        0.0
+    }
+}
+
+/// Scroll delta that brings row `row` fully into a viewport spanning
+/// `top..bottom` (content coordinates), moving as little as possible. Zero if
+/// the row is already visible. Positive values scroll up, matching
+/// `Ui::scroll_with_delta`.
+fn follow_cursor_delta(row: usize, top: f32, bottom: f32, row_height: f32) -> f32 {
+    let row_top = row as f32 * row_height;
+    let row_bottom = row_top + row_height;
+    if row_top < top {
+        top - row_top
+    } else if row_bottom > bottom {
+        -(row_bottom - bottom).min(row_top - top)
+    } else {
+        0.0
+    }
+}
+
 /// Per-frame inputs needed to apply a `RowResult` to editor + UI state.
 /// Symmetric with `RowRenderContext` — bundle the snapshot once at the call
 /// site so the function signature stays narrow.
 struct RowInteractionContext {
     /// Whether shift was held this frame (extends selection on click).
     shift_held: bool,
+    /// Whether Ctrl/Cmd was held this frame (a click or drag adds another
+    /// range instead of replacing the selection).
+    command_held: bool,
     /// Whether the primary mouse button was down this frame (used to decide
     /// whether to clear the in-egui-data drag flag).
     primary_down: bool,
@@ -802,13 +1324,33 @@ fn handle_row_interactions(
     result: RowResult,
     ctx: &RowInteractionContext,
 ) {
+    if app.ui.measure_state.active {
+        // Clicks pick measurement ends; drags and row clicks don't select
+        if let Some((off, _)) = result.cursor_move {
+            app.ui.measure_state.click(off);
+        }
+        if let Some(offset) = result.context_menu_offset {
+            app.ui.context_menu_state.target_offset = Some(offset);
+        }
+        return;
+    }
     if let Some((off, mode)) = result.cursor_move {
         if let Some(editor) = &mut app.doc.editor {
             editor.set_edit_mode(mode);
-            editor.set_cursor_with_selection(off, ctx.shift_held);
+            if ctx.command_held && !ctx.shift_held && !editor.selection_locked() {
+                editor.add_selection_at(off);
+            } else {
+                editor.set_cursor_with_selection(off, ctx.shift_held);
+            }
         }
     }
-    if result.start_drag {
+    // A locked selection isn't replaced by drag-selecting
+    let locked = app
+        .doc
+        .editor
+        .as_ref()
+        .is_some_and(|e| e.selection_locked());
+    if result.start_drag && !locked {
         ui.data_mut(|d| d.insert_temp(ctx.drag_id, true));
     }
     if let Some(off) = result.drag_current_offset {
@@ -822,12 +1364,20 @@ fn handle_row_interactions(
     if let Some(offset) = result.context_menu_offset {
         app.ui.context_menu_state.target_offset = Some(offset);
     }
+    if let Some(row) = result.row_clicked {
+        let bytes_per_row = app.config.settings.bytes_per_row();
+        if let Some(editor) = &mut app.doc.editor {
+            editor.select_row(row, bytes_per_row, ctx.shift_held);
+        }
+    }
 }
 
 /// Result of keyboard input handling
 struct KeyboardResult {
     /// Pending high-risk edit awaiting confirmation (edit_type, offset, risk_level)
     pending_high_risk_edit: Option<(PendingEditType, usize, RiskLevel)>,
+    /// An edit on the protected cursor byte was refused this frame
+    blocked_edit: bool,
 }
 
 /// Handle keyboard input for navigation and editing
@@ -837,10 +1387,13 @@ fn handle_keyboard_input(
     cursor_pos: usize,
     cursor_protected: bool,
 ) -> KeyboardResult {
-    // Don't process hex editor input when any egui widget (text field, etc.) has focus
-    if ui.ctx().memory(|m| m.focused().is_some()) {
+    // Don't process hex editor input when any egui widget (text field, etc.) has
+    // focus, or while Preferences is waiting for a key to bind
+    if ui.ctx().memory(|m| m.focused().is_some()) || app.ui.settings_dialog_state.is_capturing_key()
+    {
         return KeyboardResult {
             pending_high_risk_edit: None,
+            blocked_edit: false,
         };
     }
 
@@ -855,6 +1408,15 @@ fn handle_keyboard_input(
         .as_ref()
         .map(|e| e.edit_mode())
         .unwrap_or(EditMode::Hex);
+    let bytes_per_row = app.config.settings.bytes_per_row();
+    let selection_step = app.config.settings.selection_step.max(1);
+    let vim_motions =
+        app.config.settings.vim_motions && app.ui.context_menu_state.target_offset.is_none();
+    let keybindings = &app.config.settings.keybindings;
+    let grow_at_eof = app.config.settings.grow_on_overwrite_at_eof;
+    let job_running = app.is_job_running();
+    let motion = &mut app.ui.motion_state;
+    let measure = &mut app.ui.measure_state;
 
     let (edit_result, copy_requested) = ui.input_mut(|i| {
         let Some(editor) = &mut app.doc.editor else {
@@ -862,18 +1424,49 @@ fn handle_keyboard_input(
         };
 
         let ctrl = i.modifiers.ctrl || i.modifiers.mac_cmd;
+        editor.set_grow_at_eof(grow_at_eof);
 
-        // Ctrl+I / Cmd+I toggles Insert/Overwrite mode
-        if ctrl && i.key_pressed(egui::Key::I) {
+        // Escape leaves "Measure Distance" mode before command mode sees it
+        if measure.active && i.consume_key(egui::Modifiers::NONE, egui::Key::Escape) {
+            measure.exit();
+        }
+
+        // Ctrl+I / Cmd+I (by default) toggles Insert/Overwrite mode
+        if keybindings.pressed(ShortcutAction::ToggleWriteMode, i) {
             editor.toggle_write_mode();
         }
 
-        // Detect copy request (Cmd+C / Ctrl+C)
-        let copy_requested = i.events.iter().any(|e| matches!(e, egui::Event::Copy))
-            || (ctrl && i.key_pressed(egui::Key::C));
+        // Detect copy request (Cmd+C / Ctrl+C). Shift is excluded because
+        // Ctrl+Shift+C toggles comparison mode but still emits Event::Copy.
+        let copy_requested = !i.modifiers.shift
+            && (i.events.iter().any(|e| matches!(e, egui::Event::Copy))
+                || (ctrl && i.key_pressed(egui::Key::C)));
 
-        // Navigation keys (arrows, page up/down, home/end)
-        handle_navigation_keys(editor, i);
+        let command_mode = vim_motions && handle_motion_keys(motion, i);
+
+        // Navigation keys (arrows, page up/down, home/end); a typed count is used up
+        let count = motion.count.filter(|&n| command_mode && n > 0);
+        let step = selection_step.saturating_mul(count.unwrap_or(1));
+        if handle_selection_resize_keys(editor, i, step) {
+            motion.count = None;
+        }
+        if handle_navigation_keys(editor, i, bytes_per_row, count) {
+            motion.count = None;
+        }
+        if command_mode {
+            return (EditInputResult::default(), copy_requested);
+        }
+
+        // Edits wait for a background job, whose result would otherwise be discarded
+        if job_running {
+            let paused_for_job = edit_attempted(i, current_edit_mode)
+                || i.events.iter().any(|e| matches!(e, egui::Event::Paste(_)));
+            let result = EditInputResult {
+                paused_for_job,
+                ..Default::default()
+            };
+            return (result, copy_requested);
+        }
 
         // Edit input (text entry, backspace, delete, paste)
         let edit_result = handle_edit_input(
@@ -892,22 +1485,26 @@ fn handle_keyboard_input(
     // so that only the column matching the current edit mode ends up on the clipboard
     if copy_requested {
         if let Some(editor) = &app.doc.editor {
-            let (start, end) = editor.selection().unwrap_or((cursor_pos, cursor_pos + 1));
-            let bytes = editor.bytes_in_range(start, end);
+            let bytes = editor
+                .selected_bytes()
+                .unwrap_or_else(|| editor.bytes_in_range(cursor_pos, cursor_pos + 1).to_vec());
             let formatted = match current_edit_mode {
-                EditMode::Hex => format_bytes_as_hex(bytes),
-                EditMode::Ascii => format_bytes_as_ascii(bytes),
+                EditMode::Hex => format_bytes_as_hex(&bytes),
+                EditMode::Ascii => format_bytes_as_ascii(&bytes),
             };
             ui.output_mut(|o| o.copied_text = formatted);
+            app.ui.clipboard_history.push(&bytes);
         }
     }
 
+    if edit_result.paused_for_job {
+        app.note_edit_paused_for_job();
+    }
+
     // Handle paste outside the input closure
     if let Some(text) = edit_result.paste_text {
         if let Some(bytes) = parse_paste_input(&text, current_edit_mode) {
-            if let Some(editor) = &mut app.doc.editor {
-                apply_paste_bytes(editor, cursor_pos, &bytes);
-            }
+            request_paste(app, cursor_pos, bytes, edit_result.paste_tiled);
         }
     }
 
@@ -915,19 +1512,27 @@ fn handle_keyboard_input(
     if let Some(editor) = &mut app.doc.editor {
         if editor.take_length_changed() {
             // Re-parse file structure since offsets shifted
-            app.doc.cached_sections = crate::formats::parse_file(editor.working());
+            app.doc.reparse_sections();
         }
     }
 
     KeyboardResult {
         pending_high_risk_edit: edit_result.pending_high_risk_edit,
+        blocked_edit: edit_result.blocked,
     }
 }
 
 enum ContextAction {
     CopyHex,
     CopyAscii,
+    CopyWithOffsets,
     Paste,
+    PasteTiled,
+    /// Paste clipboard history entry N (0 = most recent)
+    PasteRecent(usize),
+    ClearRecent,
+    Duplicate,
+    InvertSelection,
     AddBookmark,
     GoToOffset,
 }
@@ -941,15 +1546,20 @@ fn show_context_menu(ui: &mut egui::Ui, app: &mut BendApp) {
     let mut close_menu = false;
     let mut action: Option<ContextAction> = None;
 
-    // Determine if we have a selection or just cursor
-    let (start, end) = app
+    // Determine if we have a selection (one or several ranges) or just cursor
+    let ranges = app
         .doc
         .editor
         .as_ref()
-        .and_then(|e| e.selection())
-        .unwrap_or((target_offset, target_offset + 1));
-
-    let byte_count = end - start;
+        .map(|e| e.selections())
+        .unwrap_or_default();
+    let has_selection = !ranges.is_empty();
+    let single_selection = ranges.len() == 1;
+    let byte_count = if has_selection {
+        ranges.iter().map(|(start, end)| end - start).sum()
+    } else {
+        1
+    };
     let label_suffix = if byte_count > 1 {
         format!(" ({} bytes)", byte_count)
     } else {
@@ -960,7 +1570,15 @@ fn show_context_menu(ui: &mut egui::Ui, app: &mut BendApp) {
     let ctx = ui.ctx().clone();
     let mouse_pos = ctx.input(|i| i.pointer.hover_pos()).unwrap_or_default();
 
-    egui::Area::new(egui::Id::new("hex_context_menu"))
+    let recent: Vec<String> = app
+        .ui
+        .clipboard_history
+        .entries()
+        .map(entry_label)
+        .collect();
+    let has_recent = !app.ui.clipboard_history.is_empty();
+
+    let menu_response = egui::Area::new(egui::Id::new("hex_context_menu"))
         .fixed_pos(mouse_pos)
         .order(egui::Order::Foreground)
         .show(&ctx, |ui| {
@@ -983,6 +1601,14 @@ fn show_context_menu(ui: &mut egui::Ui, app: &mut BendApp) {
                     action = Some(ContextAction::CopyAscii);
                     close_menu = true;
                 }
+                if ui
+                    .button(format!("Copy with Offsets{}", label_suffix))
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    action = Some(ContextAction::CopyWithOffsets);
+                    close_menu = true;
+                }
 
                 ui.separator();
 
@@ -990,6 +1616,61 @@ fn show_context_menu(ui: &mut egui::Ui, app: &mut BendApp) {
                     action = Some(ContextAction::Paste);
                     close_menu = true;
                 }
+                if ui
+                    .add_enabled(
+                        has_selection,
+                        egui::Button::new(format!("Paste Tiled{}", label_suffix)),
+                    )
+                    .on_hover_text("Repeat the clipboard bytes to fill the selection")
+                    .on_disabled_hover_text("Select bytes to fill")
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    action = Some(ContextAction::PasteTiled);
+                    close_menu = true;
+                }
+                ui.add_enabled_ui(has_recent, |ui| {
+                    ui.menu_button("Paste Recent", |ui| {
+                        for (index, label) in recent.iter().enumerate() {
+                            let text = RichText::new(label).monospace();
+                            if ui.button(text).pointer_cursor().clicked() {
+                                action = Some(ContextAction::PasteRecent(index));
+                                close_menu = true;
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                        if ui.button("Clear History").pointer_cursor().clicked() {
+                            action = Some(ContextAction::ClearRecent);
+                            close_menu = true;
+                            ui.close_menu();
+                        }
+                    })
+                    .response
+                    .on_hover_text("Paste a recently copied or pasted byte sequence")
+                    .on_disabled_hover_text("Nothing copied or pasted yet");
+                });
+                if ui
+                    .add_enabled(
+                        single_selection,
+                        egui::Button::new(format!("Duplicate{}", label_suffix)),
+                    )
+                    .on_disabled_hover_text("Select one range of bytes to duplicate")
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    action = Some(ContextAction::Duplicate);
+                    close_menu = true;
+                }
+                if ui
+                    .add_enabled(single_selection, egui::Button::new("Invert Selection"))
+                    .on_disabled_hover_text("Select one range of bytes to invert")
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    action = Some(ContextAction::InvertSelection);
+                    close_menu = true;
+                }
 
                 ui.separator();
 
@@ -1004,8 +1685,16 @@ fn show_context_menu(ui: &mut egui::Ui, app: &mut BendApp) {
             });
         });
 
-    // Close menu on click outside or Escape
-    let clicked_outside = ctx.input(|i| i.pointer.any_click() && !i.pointer.secondary_down());
+    // Close menu on click outside or Escape (a click inside may open a submenu)
+    let menu_rect = menu_response.response.rect;
+    let clicked_outside = ctx.input(|i| {
+        i.pointer.any_click()
+            && !i.pointer.secondary_down()
+            && !i
+                .pointer
+                .interact_pos()
+                .is_some_and(|pos| menu_rect.contains(pos))
+    });
     let escape_pressed = ctx.input(|i| i.key_pressed(egui::Key::Escape));
 
     if clicked_outside || escape_pressed {
@@ -1016,10 +1705,21 @@ fn show_context_menu(ui: &mut egui::Ui, app: &mut BendApp) {
     match action {
         Some(ContextAction::CopyHex) => copy_as_hex(ui, app, target_offset),
         Some(ContextAction::CopyAscii) => copy_as_ascii(ui, app, target_offset),
-        Some(ContextAction::Paste) => paste_hex(ui, app, target_offset),
+        Some(ContextAction::CopyWithOffsets) => copy_with_offsets(ui, app, target_offset),
+        Some(ContextAction::Paste) => paste_hex(ui, app, target_offset, false),
+        Some(ContextAction::PasteTiled) => paste_hex(ui, app, target_offset, true),
+        Some(ContextAction::PasteRecent(index)) => {
+            if let Some(bytes) = app.ui.clipboard_history.get(index).map(<[u8]>::to_vec) {
+                request_paste(app, target_offset, bytes, false);
+            }
+        }
+        Some(ContextAction::ClearRecent) => app.ui.clipboard_history.clear(),
+        Some(ContextAction::Duplicate) => duplicate_selection(app),
+        Some(ContextAction::InvertSelection) => app.invert_selection(),
         Some(ContextAction::AddBookmark) => {
+            let name = app.doc.default_bookmark_name(target_offset);
             if let Some(editor) = &mut app.doc.editor {
-                editor.add_bookmark(target_offset, format!("Offset 0x{:X}", target_offset));
+                editor.add_bookmark(target_offset, name);
             }
         }
         Some(ContextAction::GoToOffset) => app.ui.go_to_offset_state.open_dialog(),
@@ -1044,6 +1744,29 @@ fn format_bytes_as_hex(bytes: &[u8]) -> String {
     hex_string
 }
 
+/// Format bytes as hex dump lines of `bytes_per_row` bytes, each prefixed with
+/// its offset in `offset_base` (e.g., "00000100: FF D8 FF E0"). `base_offset` is
+/// the offset of `bytes[0]`.
+fn format_bytes_as_dump(
+    bytes: &[u8],
+    base_offset: usize,
+    bytes_per_row: usize,
+    offset_base: OffsetBase,
+) -> String {
+    bytes
+        .chunks(bytes_per_row)
+        .enumerate()
+        .map(|(i, chunk)| {
+            format!(
+                "{}: {}",
+                offset_base.format_column(base_offset + i * bytes_per_row),
+                format_bytes_as_hex(chunk)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Format bytes as ASCII string (non-printable bytes become '.')
 fn format_bytes_as_ascii(bytes: &[u8]) -> String {
     bytes
@@ -1059,46 +1782,197 @@ fn format_bytes_as_ascii(bytes: &[u8]) -> String {
 }
 
 /// Copy selected bytes as hex string to clipboard
-fn copy_as_hex(ui: &mut egui::Ui, app: &BendApp, target_offset: usize) {
+fn copy_as_hex(ui: &mut egui::Ui, app: &mut BendApp, target_offset: usize) {
     let Some(editor) = &app.doc.editor else {
         return;
     };
 
-    let (start, end) = editor
-        .selection()
-        .unwrap_or((target_offset, target_offset + 1));
-    let bytes = editor.bytes_in_range(start, end);
-    ui.output_mut(|o| o.copied_text = format_bytes_as_hex(bytes));
+    let bytes = editor.selected_bytes().unwrap_or_else(|| {
+        editor
+            .bytes_in_range(target_offset, target_offset + 1)
+            .to_vec()
+    });
+    ui.output_mut(|o| o.copied_text = format_bytes_as_hex(&bytes));
+    app.ui.clipboard_history.push(&bytes);
 }
 
 /// Copy selected bytes as ASCII string to clipboard
-fn copy_as_ascii(ui: &mut egui::Ui, app: &BendApp, target_offset: usize) {
+fn copy_as_ascii(ui: &mut egui::Ui, app: &mut BendApp, target_offset: usize) {
     let Some(editor) = &app.doc.editor else {
         return;
     };
 
-    let (start, end) = editor
-        .selection()
-        .unwrap_or((target_offset, target_offset + 1));
-    let bytes = editor.bytes_in_range(start, end);
-    ui.output_mut(|o| o.copied_text = format_bytes_as_ascii(bytes));
+    let bytes = editor.selected_bytes().unwrap_or_else(|| {
+        editor
+            .bytes_in_range(target_offset, target_offset + 1)
+            .to_vec()
+    });
+    ui.output_mut(|o| o.copied_text = format_bytes_as_ascii(&bytes));
+    app.ui.clipboard_history.push(&bytes);
+}
+
+/// Copy selected bytes as an offset-prefixed hex dump to clipboard, one
+/// block per selected range
+fn copy_with_offsets(ui: &mut egui::Ui, app: &mut BendApp, target_offset: usize) {
+    let Some(editor) = &app.doc.editor else {
+        return;
+    };
+
+    let mut ranges = editor.selections();
+    if ranges.is_empty() {
+        ranges.push((target_offset, target_offset + 1));
+    }
+    let bytes_per_row = app.config.settings.bytes_per_row();
+    let offset_base = app.config.settings.offset_base;
+    let dump = ranges
+        .iter()
+        .map(|&(start, end)| {
+            format_bytes_as_dump(
+                editor.bytes_in_range(start, end),
+                start,
+                bytes_per_row,
+                offset_base,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let bytes: Vec<u8> = ranges
+        .iter()
+        .flat_map(|&(start, end)| editor.bytes_in_range(start, end).iter().copied())
+        .collect();
+    ui.output_mut(|o| o.copied_text = dump);
+    app.ui.clipboard_history.push(&bytes);
 }
 
 /// Paste bytes from clipboard (mode-dependent)
 /// - Hex mode: parse clipboard as hex bytes ("FF 00" or "FF00")
 /// - ASCII mode: interpret clipboard as raw text, write each character's byte value
-fn paste_hex(_ui: &mut egui::Ui, app: &mut BendApp, target_offset: usize) {
+/// - Tiled: the bytes repeat to fill the selection
+fn paste_hex(_ui: &mut egui::Ui, app: &mut BendApp, target_offset: usize, tiled: bool) {
     // Read from system clipboard
     let Some(text) = read_clipboard() else {
         return;
     };
 
-    let Some(editor) = &mut app.doc.editor else {
+    let Some(editor) = &app.doc.editor else {
         return;
     };
 
     if let Some(bytes) = parse_paste_input(&text, editor.edit_mode()) {
-        apply_paste_bytes(editor, target_offset, &bytes);
+        request_paste(app, target_offset, bytes, tiled);
+    }
+}
+
+/// Paste immediately, or queue a confirmation if the paste exceeds the configured threshold
+fn request_paste(app: &mut BendApp, offset: usize, bytes: Vec<u8>, tiled: bool) {
+    app.ui.clipboard_history.push(&bytes);
+    if let Some(blocked) = tiled.then(|| protected_tiled_target(app)).flatten() {
+        log::warn!("Paste Tiled blocked: range overlaps a protected section");
+        app.ui.flag_blocked_edit(blocked);
+        return;
+    }
+    let threshold = app.config.settings.paste_confirm_threshold;
+    let written = app
+        .doc
+        .editor
+        .as_ref()
+        .map_or(bytes.len(), |editor| paste_len(editor, bytes.len(), tiled));
+    if threshold > 0 && written > threshold {
+        app.ui.dialogs.pending_paste = Some(PendingPaste {
+            offset,
+            bytes,
+            tiled,
+        });
+    } else if let Some(editor) = &mut app.doc.editor {
+        apply_paste_bytes(editor, offset, &bytes, tiled);
+    }
+}
+
+/// First protected byte a tiled paste over the selected ranges would overwrite
+fn protected_tiled_target(app: &BendApp) -> Option<usize> {
+    let editor = app.doc.editor.as_ref()?;
+    let protected = app.doc.protected_ranges();
+    editor.selections().into_iter().find_map(|(start, end)| {
+        protected
+            .iter()
+            .find(|range| range.start < end && range.end > start)
+            .map(|range| range.start.max(start))
+    })
+}
+
+/// Bytes a paste of `len` bytes writes: a tiled paste fills every selected
+/// range, so it writes their total length instead
+fn paste_len(editor: &crate::editor::EditorState, len: usize, tiled: bool) -> usize {
+    let selected: usize = if tiled {
+        editor
+            .selections()
+            .iter()
+            .map(|(start, end)| end - start)
+            .sum()
+    } else {
+        0
+    };
+    if selected > 0 {
+        selected
+    } else {
+        len
+    }
+}
+
+/// Describe what a paste of `len` bytes at `offset` will do to a file of `file_len` bytes
+pub(crate) fn describe_paste(
+    len: usize,
+    offset: usize,
+    file_len: usize,
+    write_mode: WriteMode,
+) -> String {
+    match write_mode {
+        WriteMode::Insert => format!(
+            "Insert {} bytes at 0x{:08X} (file grows from {} to {} bytes).",
+            len,
+            offset,
+            file_len,
+            file_len + len
+        ),
+        WriteMode::Overwrite => {
+            let written = len.min(file_len.saturating_sub(offset));
+            let mut text = format!("Overwrite {} bytes starting at 0x{:08X}.", written, offset);
+            if written < len {
+                text.push_str(&format!(
+                    " {} bytes past the end of the file will be dropped.",
+                    len - written
+                ));
+            }
+            text
+        }
+    }
+}
+
+/// Describe a tiled paste of a `pattern_len`-byte pattern over `len` selected bytes
+/// (across every range) from `start`
+pub(crate) fn describe_tiled_paste(pattern_len: usize, start: usize, len: usize) -> String {
+    format!(
+        "Fill {} selected bytes starting at 0x{:08X} with the {}-byte pattern repeated.",
+        len, start, pattern_len
+    )
+}
+
+/// Insert a copy of the selection right after it, then reparse and refresh the
+/// preview. Does nothing with several selected ranges.
+fn duplicate_selection(app: &mut BendApp) {
+    let Some((_, end)) = app.doc.editor.as_ref().and_then(|e| e.single_selection()) else {
+        return;
+    };
+    if app.doc.is_offset_protected(end) {
+        log::warn!("Duplicate blocked: insertion point is in a protected section");
+        return;
+    }
+    let Some(editor) = &mut app.doc.editor else {
+        return;
+    };
+    if editor.duplicate_selection() && editor.take_length_changed() {
+        app.doc.reparse_sections();
+        app.doc.preview.mark_dirty();
     }
 }
 
@@ -1117,9 +1991,34 @@ fn parse_paste_input(text: &str, mode: EditMode) -> Option<Vec<u8>> {
     }
 }
 
-/// Apply parsed bytes at the given offset, respecting write mode
-fn apply_paste_bytes(editor: &mut crate::editor::EditorState, offset: usize, bytes: &[u8]) {
-    if editor.write_mode() == WriteMode::Insert {
+/// `pattern` repeated (and cut off) to exactly `len` bytes
+fn tile_pattern(pattern: &[u8], len: usize) -> Vec<u8> {
+    pattern.iter().copied().cycle().take(len).collect()
+}
+
+/// Apply parsed bytes at the given offset, respecting write mode. A `tiled`
+/// paste instead overwrites every selected range with `bytes` repeated, as one
+/// undoable edit (falling back to a normal paste without a selection).
+pub(crate) fn apply_paste_bytes(
+    editor: &mut crate::editor::EditorState,
+    offset: usize,
+    bytes: &[u8],
+    tiled: bool,
+) {
+    let ranges = if tiled {
+        editor.selections()
+    } else {
+        Vec::new()
+    };
+    if let [(start, end)] = ranges.as_slice() {
+        editor.replace_bytes(*start, &tile_pattern(bytes, end - start));
+    } else if !ranges.is_empty() {
+        let spans: Vec<_> = ranges
+            .iter()
+            .map(|&(start, end)| (start, end - start, tile_pattern(bytes, end - start)))
+            .collect();
+        editor.replace_spans(&spans);
+    } else if editor.write_mode() == WriteMode::Insert {
         editor.insert_bytes(offset, bytes);
     } else {
         for (i, byte) in bytes.iter().enumerate() {
@@ -1159,6 +2058,253 @@ fn parse_hex_input(input: &str) -> Option<Vec<u8>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ascii_layout_cells() {
+        let layout = AsciiLayout {
+            cell_width: 10.0,
+            group_gap: 5.0,
+        };
+        assert_eq!(layout.cell_x(0), 0.0);
+        assert_eq!(layout.cell_x(7), 70.0);
+        assert_eq!(layout.cell_x(8), 85.0); // after the first group gap
+        assert_eq!(layout.row_width(16), 165.0);
+
+        assert_eq!(layout.cell_at(-1.0), None);
+        assert_eq!(layout.cell_at(0.0), Some(0));
+        assert_eq!(layout.cell_at(79.0), Some(7));
+        assert_eq!(layout.cell_at(82.0), Some(7)); // inside the gap
+        assert_eq!(layout.cell_at(85.0), Some(8));
+
+        // Without a gap, every cell is the same width
+        let plain = AsciiLayout {
+            cell_width: 10.0,
+            group_gap: 0.0,
+        };
+        assert_eq!(plain.cell_at(85.0), Some(8));
+        assert_eq!(plain.row_width(16), 160.0);
+    }
+
+    #[test]
+    fn test_offset_header_matches_column_width() {
+        for base in OffsetBase::ALL {
+            let header = offset_header(base);
+            assert_eq!(header.len(), base.format_column(0).len());
+            assert!(header.starts_with("Offset"));
+        }
+    }
+
+    #[test]
+    fn test_is_edit_char() {
+        assert!(is_edit_char('a', EditMode::Hex));
+        assert!(is_edit_char('F', EditMode::Hex));
+        assert!(!is_edit_char('g', EditMode::Hex));
+        assert!(is_edit_char('g', EditMode::Ascii));
+        assert!(!is_edit_char('\n', EditMode::Ascii));
+    }
+
+    #[test]
+    fn test_byte_detail_rows() {
+        let rows = byte_detail_rows(0x1F, b'A', TextEncoding::Ascii);
+        assert_eq!(rows[0].1, "0x0000001F (31)");
+        assert_eq!(rows[1].1, "0x41");
+        assert_eq!(rows[2].1, "65");
+        assert_eq!(rows[3].1, "0100 0001");
+        assert_eq!(rows[4], ("ASCII", "'A'".to_string()));
+        assert_eq!(
+            byte_detail_rows(0, 0x00, TextEncoding::Ascii)[4].1,
+            "(non-printable)"
+        );
+        // The text row follows the ASCII pane's encoding
+        assert_eq!(
+            byte_detail_rows(0, 0xC1, TextEncoding::Ebcdic)[4],
+            ("EBCDIC", "'A'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tiled_paste_fills_selection_in_one_undo() {
+        let mut editor = crate::editor::EditorState::new(vec![0; 8]);
+        editor.set_selection(1, 6);
+        // The confirmation threshold sees the selected length, not the pattern's
+        assert_eq!(paste_len(&editor, 2, true), 5);
+        assert_eq!(paste_len(&editor, 2, false), 2);
+        apply_paste_bytes(&mut editor, 1, &[0xAB, 0xCD], true);
+        assert_eq!(editor.working(), &[0, 0xAB, 0xCD, 0xAB, 0xCD, 0xAB, 0, 0]);
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &[0; 8]);
+
+        // A pattern longer than the selection is cut off
+        assert_eq!(tile_pattern(&[1, 2, 3], 2), [1, 2]);
+        assert!(tile_pattern(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_tiled_paste_respects_protection() {
+        use crate::formats::{FileSection, RiskLevel};
+
+        let mut app = BendApp::default();
+        let mut editor = crate::editor::EditorState::new(vec![0; 8]);
+        editor.set_selection(6, 8);
+        editor.add_selection_at(2);
+        editor.extend_selection_to(4);
+        app.doc.editor = Some(editor);
+        app.doc.set_sections(Some(vec![FileSection::new(
+            "Header",
+            0,
+            3,
+            RiskLevel::High,
+        )]));
+        app.doc.header_protection = true;
+
+        request_paste(&mut app, 2, vec![0xAB], true);
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), &[0; 8]);
+        assert_eq!(
+            app.ui.recent_blocked_edit().map(|(offset, _)| offset),
+            Some(2)
+        );
+
+        app.doc.header_protection = false;
+        request_paste(&mut app, 2, vec![0xAB], true);
+        assert_eq!(
+            app.doc.editor.as_ref().unwrap().working(),
+            &[0, 0, 0xAB, 0xAB, 0xAB, 0, 0xAB, 0xAB]
+        );
+    }
+
+    #[test]
+    fn test_describe_paste() {
+        assert_eq!(
+            describe_paste(10, 0x20, 100, WriteMode::Insert),
+            "Insert 10 bytes at 0x00000020 (file grows from 100 to 110 bytes)."
+        );
+        assert_eq!(
+            describe_paste(10, 0x10, 100, WriteMode::Overwrite),
+            "Overwrite 10 bytes starting at 0x00000010."
+        );
+        assert_eq!(
+            describe_paste(10, 95, 100, WriteMode::Overwrite),
+            "Overwrite 5 bytes starting at 0x0000005F. 5 bytes past the end of the file will be dropped."
+        );
+    }
+
+    #[test]
+    fn test_motion_state_count_and_modes() {
+        let mut motion = MotionState::default();
+        assert_eq!(motion.status(), None);
+
+        motion.escape();
+        assert_eq!(motion.status().as_deref(), Some("COMMAND"));
+        motion.push_digit(1);
+        motion.push_digit(6);
+        assert_eq!(motion.count, Some(16));
+        assert_eq!(motion.status().as_deref(), Some("COMMAND 16"));
+
+        // Escape drops a half-typed count first, then leaves command mode
+        motion.escape();
+        assert!(motion.active);
+        assert_eq!(motion.count, None);
+        motion.escape();
+        assert!(!motion.active);
+
+        // Huge counts saturate instead of overflowing
+        motion.escape();
+        for _ in 0..40 {
+            motion.push_digit(9);
+        }
+        assert_eq!(motion.count, Some(usize::MAX));
+        motion.exit();
+        assert_eq!(motion.status(), None);
+    }
+
+    #[test]
+    fn test_measure_state_two_clicks() {
+        let mut measure = MeasureState::default();
+        assert_eq!(measure.status(), None);
+
+        measure.toggle();
+        assert_eq!(
+            measure.status().as_deref(),
+            Some("Measure: click the start byte")
+        );
+        measure.click(0x30);
+        assert_eq!(measure.span(), Some((0x30, 0x31)));
+        assert_eq!(measure.distance(), None);
+
+        // End before start still measures and highlights the same span
+        measure.click(0x06);
+        assert_eq!(measure.span(), Some((0x06, 0x31)));
+        assert_eq!(measure.distance(), Some(42));
+        assert_eq!(
+            measure.status().as_deref(),
+            Some("Offset difference: 0x2A (42); span: 43 bytes")
+        );
+
+        // A third click starts over
+        measure.click(0x10);
+        assert_eq!(measure.distance(), None);
+        assert_eq!(measure.span(), Some((0x10, 0x11)));
+
+        measure.toggle();
+        assert!(!measure.active);
+        assert_eq!(measure.span(), None);
+    }
+
+    #[test]
+    fn test_follow_cursor_delta() {
+        // Rows 10..20 are visible
+        assert_eq!(follow_cursor_delta(12, 100.0, 200.0, 10.0), 0.0);
+        assert_eq!(follow_cursor_delta(19, 100.0, 200.0, 10.0), 0.0);
+        // Just past the bottom scrolls down by one row, above the top scrolls up
+        assert_eq!(follow_cursor_delta(20, 100.0, 200.0, 10.0), -10.0);
+        assert_eq!(follow_cursor_delta(8, 100.0, 200.0, 10.0), 20.0);
+        // A partially visible last row is scrolled in fully
+        assert_eq!(follow_cursor_delta(19, 100.0, 195.0, 10.0), -5.0);
+        // A viewport shorter than a row keeps the row's top in view
+        assert_eq!(follow_cursor_delta(10, 100.0, 105.0, 10.0), 0.0);
+    }
+
+    #[test]
+    fn test_drag_autoscroll_delta() {
+        // Middle of the viewport: no scrolling
+        assert_eq!(drag_autoscroll_delta(250.0, 0.0, 500.0, 10.0), 0.0);
+        // Near the top scrolls up, near the bottom scrolls down
+        assert!(drag_autoscroll_delta(5.0, 0.0, 500.0, 10.0) > 0.0);
+        assert!(drag_autoscroll_delta(495.0, 0.0, 500.0, 10.0) < 0.0);
+        // Deeper into the zone is faster, but capped once well past the edge
+        assert!(
+            drag_autoscroll_delta(-10.0, 0.0, 500.0, 10.0)
+                > drag_autoscroll_delta(15.0, 0.0, 500.0, 10.0)
+        );
+        assert_eq!(
+            drag_autoscroll_delta(-500.0, 0.0, 500.0, 10.0),
+            drag_autoscroll_delta(-1000.0, 0.0, 500.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn format_bytes_as_dump_splits_rows_with_offsets() {
+        let bytes: Vec<u8> = (0..20).collect();
+        let dump = format_bytes_as_dump(&bytes, 0x100, 16, OffsetBase::Hex);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "00000100: 00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F"
+        );
+        assert_eq!(lines[1], "00000110: 10 11 12 13");
+        assert_eq!(format_bytes_as_dump(&[], 0, 16, OffsetBase::Hex), "");
+        assert_eq!(
+            format_bytes_as_dump(&bytes[..10], 0, 8, OffsetBase::Hex)
+                .lines()
+                .count(),
+            2
+        );
+
+        // Decimal offsets for users cross-referencing file sizes
+        let dump = format_bytes_as_dump(&bytes[..4], 0x100, 16, OffsetBase::Decimal);
+        assert_eq!(dump, "0000000256: 00 01 02 03");
+    }
+
     /// `RowResult::merge` is last-value-wins: a non-`None` field on the
     /// incoming row overwrites the accumulator, but a `None`/`false` does
     /// not clear an earlier value. This matches the original loop's plain
@@ -1171,6 +2317,7 @@ mod tests {
             start_drag: false,
             drag_current_offset: Some(7),
             context_menu_offset: Some(5),
+            row_clicked: Some(1),
         };
         let later = RowResult {
             cursor_move: Some((20, EditMode::Ascii)),
@@ -1178,6 +2325,7 @@ mod tests {
             drag_current_offset: Some(15),
             // Intentionally None — must not clear acc's existing value.
             context_menu_offset: None,
+            row_clicked: Some(2),
         };
 
         acc.merge(later);
@@ -1189,6 +2337,7 @@ mod tests {
         assert!(acc.start_drag);
         // context_menu_offset on later was None; acc's earlier Some is kept.
         assert_eq!(acc.context_menu_offset, Some(5));
+        assert_eq!(acc.row_clicked, Some(2));
     }
 
     /// Default + a populated row → that row's values are taken verbatim.
@@ -1200,6 +2349,7 @@ mod tests {
             start_drag: true,
             drag_current_offset: Some(42),
             context_menu_offset: Some(99),
+            row_clicked: None,
         };
 
         acc.merge(row);
@@ -1218,6 +2368,7 @@ mod tests {
             start_drag: true,
             drag_current_offset: Some(2),
             context_menu_offset: Some(3),
+            row_clicked: Some(4),
         };
 
         acc.merge(RowResult::default());
@@ -1226,5 +2377,6 @@ mod tests {
         assert!(acc.start_drag);
         assert_eq!(acc.drag_current_offset, Some(2));
         assert_eq!(acc.context_menu_offset, Some(3));
+        assert_eq!(acc.row_clicked, Some(4));
     }
 }
@@ -0,0 +1,75 @@
+//! Byte histogram UI panel
+
+use crate::app::DocumentState;
+use crate::ui::theme::AppColors;
+use eframe::egui;
+
+/// Height of the bar chart in pixels
+const CHART_HEIGHT: f32 = 80.0;
+
+/// Bar height as a fraction of the chart, log-scaled so a dominant value
+/// (e.g. zero padding) doesn't flatten every other bar
+fn bar_fraction(count: u64, max: u64) -> f32 {
+    if count == 0 || max == 0 {
+        return 0.0;
+    }
+    ((count as f64).ln_1p() / (max as f64).ln_1p()) as f32
+}
+
+/// Show the byte value histogram of the working buffer
+pub fn show(ui: &mut egui::Ui, doc: &DocumentState, colors: &AppColors) {
+    let Some(editor) = &doc.editor else {
+        ui.label("No file loaded");
+        return;
+    };
+    let histogram = editor.histogram();
+    let counts = histogram.counts();
+    let max = counts.iter().copied().max().unwrap_or(0);
+
+    let width = ui.available_width().max(128.0);
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(width, CHART_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, colors.bg_surface);
+
+    let bar_width = rect.width() / 256.0;
+    for (value, &count) in counts.iter().enumerate() {
+        let height = bar_fraction(count, max) * rect.height();
+        if height <= 0.0 {
+            continue;
+        }
+        let x = rect.left() + value as f32 * bar_width;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(x, rect.bottom() - height),
+            egui::pos2(x + bar_width.max(1.0), rect.bottom()),
+        );
+        painter.rect_filled(bar, 0.0, colors.accent);
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let value = (((pos.x - rect.left()) / bar_width) as usize).min(255);
+        response.on_hover_text(format!("0x{:02X}: {} bytes", value, counts[value]));
+    }
+
+    ui.label(
+        egui::RichText::new(format!(
+            "{} distinct values, entropy {:.2} bits/byte",
+            counts.iter().filter(|&&c| c > 0).count(),
+            histogram.entropy()
+        ))
+        .small(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_fraction() {
+        assert_eq!(bar_fraction(0, 100), 0.0);
+        assert_eq!(bar_fraction(100, 100), 1.0);
+        let small = bar_fraction(1, 1_000_000);
+        assert!(small > 0.0 && small < 0.1);
+    }
+}
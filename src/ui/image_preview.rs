@@ -1,29 +1,130 @@
 //! Image preview UI component
 
-use crate::app::PreviewState;
+use crate::app::{
+    PreviewAdjustment, PreviewScale, PreviewState, BRIGHTNESS_RANGE, CONTRAST_RANGE, GAMMA_RANGE,
+};
 use crate::ui::theme::AppColors;
 use crate::ui::PointerCursor;
 use eframe::egui;
 
 /// Show the image preview panel with optional comparison mode
 pub fn show(ui: &mut egui::Ui, preview: &mut PreviewState, colors: &AppColors) {
-    // Comparison mode toggle at the top
+    // Comparison mode and raw view toggles at the top
     ui.horizontal(|ui| {
         ui.checkbox(&mut preview.comparison_mode, "Compare with Original");
+        ui.separator();
+        for mode in PreviewScale::ALL {
+            ui.selectable_value(&mut preview.scale_mode, mode, mode.label())
+                .pointer_cursor();
+        }
+        ui.separator();
+        if ui
+            .checkbox(&mut preview.raw_view, "Raw view")
+            .on_hover_text("Show the bytes as a grayscale image, one byte per pixel")
+            .changed()
+        {
+            preview.request_refresh();
+        }
+        if preview.showing_raw_view() {
+            ui.label("Width:");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut preview.raw_view_width)
+                        .range(1..=4096)
+                        .suffix(" px"),
+                )
+                .changed()
+            {
+                preview.request_refresh();
+            }
+        }
     });
 
+    if !preview.showing_raw_view() {
+        if let Some(dimensions) = preview.dimensions_text() {
+            let resized = preview
+                .original_size
+                .is_some_and(|size| Some(size) != preview.decoded_size);
+            let label = if resized {
+                ui.colored_label(colors.warning_text, dimensions)
+            } else {
+                ui.weak(dimensions)
+            };
+            label.on_hover_text("Size of the last successful decode");
+        }
+        show_adjustment_controls(ui, preview);
+    }
+
     // Animation controls (if animated GIF is loaded)
     show_animation_controls(ui, preview);
 
     ui.add_space(4.0);
 
-    if preview.comparison_mode {
+    if preview.showing_raw_view() {
+        show_raw_view(ui, preview, colors);
+    } else if preview.comparison_mode {
         show_comparison_view(ui, preview, colors);
     } else {
         show_single_preview(ui, preview, colors);
     }
 }
 
+/// Brightness/contrast/gamma sliders. These only change how the preview is
+/// drawn; the bytes (and anything exported) stay as they are.
+fn show_adjustment_controls(ui: &mut egui::Ui, preview: &mut PreviewState) {
+    let title = if preview.adjustment.is_identity() {
+        "Adjust Display (preview only)"
+    } else {
+        "Adjust Display (preview only, active)"
+    };
+    let mut changed = false;
+    egui::CollapsingHeader::new(title)
+        .id_salt("preview_adjustment")
+        .default_open(false)
+        .show(ui, |ui| {
+            let adjustment = &mut preview.adjustment;
+            egui::Grid::new("preview_adjustment_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Brightness:");
+                    changed |= ui
+                        .add(egui::Slider::new(
+                            &mut adjustment.brightness,
+                            BRIGHTNESS_RANGE,
+                        ))
+                        .changed();
+                    ui.end_row();
+                    ui.label("Contrast:");
+                    changed |= ui
+                        .add(egui::Slider::new(&mut adjustment.contrast, CONTRAST_RANGE))
+                        .changed();
+                    ui.end_row();
+                    ui.label("Gamma:");
+                    changed |= ui
+                        .add(egui::Slider::new(&mut adjustment.gamma, GAMMA_RANGE))
+                        .changed();
+                    ui.end_row();
+                });
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!adjustment.is_identity(), egui::Button::new("Reset"))
+                    .pointer_cursor()
+                    .clicked()
+                {
+                    *adjustment = PreviewAdjustment::default();
+                    changed = true;
+                }
+                ui.weak("Does not modify the file");
+            });
+        })
+        .header_response
+        .pointer_cursor()
+        .on_hover_text("Inspect dark or subtle glitches without editing any bytes");
+    if changed {
+        preview.adjustment_changed();
+    }
+}
+
 /// Show animation controls when an animated GIF is loaded
 fn show_animation_controls(ui: &mut egui::Ui, preview: &mut PreviewState) {
     // Guard: only show controls if we have a multi-frame animation
@@ -132,7 +233,14 @@ fn show_comparison_view(ui: &mut egui::Ui, preview: &PreviewState, colors: &AppC
         // Left: Original image
         ui.vertical(|ui| {
             ui.heading("Original");
-            show_texture_scaled(ui, preview.original_texture.as_ref(), scale, max_image_size);
+            show_texture(
+                ui,
+                preview.original_texture.as_ref(),
+                preview.scale_mode,
+                scale,
+                max_image_size,
+                "preview_original",
+            );
         });
 
         ui.separator();
@@ -146,31 +254,51 @@ fn show_comparison_view(ui: &mut egui::Ui, preview: &PreviewState, colors: &AppC
                     ui.colored_label(colors.warning_text, "\u{26A0} Preview may be stale");
                 });
             }
-            show_texture_scaled(ui, preview.texture.as_ref(), scale, max_image_size);
+            show_texture(
+                ui,
+                preview.texture.as_ref(),
+                preview.scale_mode,
+                scale,
+                max_image_size,
+                "preview_current",
+            );
         });
     });
 }
 
 /// Calculate a unified scale factor so both images display at the same size
 fn calculate_unified_scale(preview: &PreviewState, max_size: egui::Vec2) -> f32 {
-    let mut scale = 1.0_f32;
-
-    // Get the texture that determines our scale
-    // Use the largest dimensions from either texture
-    if let Some(tex) = &preview.original_texture {
-        let tex_size = tex.size_vec2();
-        let tex_scale = (max_size.x / tex_size.x).min(max_size.y / tex_size.y);
-        scale = scale.min(tex_scale);
-    }
+    // The texture needing the smallest scale determines it for both
+    [&preview.original_texture, &preview.texture]
+        .into_iter()
+        .flatten()
+        .map(|tex| preview.scale_mode.scale_for(tex.size_vec2(), max_size))
+        .reduce(f32::min)
+        .unwrap_or(1.0)
+}
 
-    if let Some(tex) = &preview.texture {
-        let tex_size = tex.size_vec2();
-        let tex_scale = (max_size.x / tex_size.x).min(max_size.y / tex_size.y);
-        scale = scale.min(tex_scale);
+/// Show a texture fitted with `scale`, or at 1:1 in a scroll area in Actual Size mode
+fn show_texture(
+    ui: &mut egui::Ui,
+    texture: Option<&egui::TextureHandle>,
+    mode: PreviewScale,
+    scale: f32,
+    max_size: egui::Vec2,
+    id_salt: &str,
+) {
+    match (mode, texture) {
+        (PreviewScale::Actual, Some(tex)) => {
+            egui::ScrollArea::both()
+                .id_salt(id_salt)
+                .max_width(max_size.x)
+                .max_height(max_size.y)
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    ui.add(egui::Image::from_texture(tex).fit_to_original_size(1.0));
+                });
+        }
+        _ => show_texture_scaled(ui, texture, scale, max_size),
     }
-
-    // Don't upscale
-    scale.min(1.0)
 }
 
 /// Show a texture with the given scale factor
@@ -212,6 +340,37 @@ fn show_texture_scaled(
     }
 }
 
+/// Show the raw grayscale view of the working buffer
+fn show_raw_view(ui: &mut egui::Ui, preview: &PreviewState, colors: &AppColors) {
+    if !preview.raw_view {
+        // Automatic fallback — explain why the decoded image isn't shown
+        ui.colored_label(
+            colors.warning_text,
+            "\u{26A0} Unable to decode image, showing raw bytes",
+        );
+    }
+
+    let Some(texture) = &preview.raw_texture else {
+        ui.centered_and_justified(|ui| {
+            ui.label("Loading preview...");
+        });
+        return;
+    };
+
+    let available_size = ui.available_size();
+    let scale = preview
+        .scale_mode
+        .scale_for(texture.size_vec2(), available_size);
+    show_texture(
+        ui,
+        Some(texture),
+        preview.scale_mode,
+        scale,
+        available_size,
+        "preview_raw",
+    );
+}
+
 /// Show a single image preview (current working buffer)
 fn show_single_preview(ui: &mut egui::Ui, preview: &PreviewState, colors: &AppColors) {
     if let Some(texture) = &preview.texture {
@@ -226,12 +385,17 @@ fn show_single_preview(ui: &mut egui::Ui, preview: &PreviewState, colors: &AppCo
         }
 
         let available_size = ui.available_size();
-        let texture_size = texture.size_vec2();
-        let scale = (available_size.x / texture_size.x)
-            .min(available_size.y / texture_size.y)
-            .min(1.0);
-
-        show_texture_scaled(ui, Some(texture), scale, available_size);
+        let scale = preview
+            .scale_mode
+            .scale_for(texture.size_vec2(), available_size);
+        show_texture(
+            ui,
+            Some(texture),
+            preview.scale_mode,
+            scale,
+            available_size,
+            "preview_single",
+        );
     } else {
         // No preview available
         ui.centered_and_justified(|ui| {
@@ -1,13 +1,20 @@
 //! UI components for bend-rs
 
 pub mod bookmarks;
+pub mod byte_runs_dialog;
+pub mod command_palette;
+pub mod data_inspector;
+pub mod file_diff_dialog;
+pub mod file_info;
 pub mod go_to_offset_dialog;
 pub mod hex_editor;
+pub mod histogram;
 pub mod image_preview;
 pub mod savepoints;
 pub mod search_dialog;
 pub mod settings_dialog;
 pub mod shortcuts_dialog;
+pub mod signature_scan_dialog;
 pub mod structure_tree;
 pub mod theme;
 
@@ -3,6 +3,10 @@
 use crate::app::DocumentState;
 use crate::ui::PointerCursor;
 use eframe::egui::{self, RichText};
+use std::collections::HashMap;
+
+/// Maximum thumbnail edge length in pixels
+const THUMBNAIL_SIZE: u32 = 48;
 
 /// State for the save points panel
 #[derive(Default)]
@@ -21,6 +25,58 @@ pub struct SavePointsPanelState {
 
     /// Pending create action
     pending_create: bool,
+
+    /// Thumbnail textures keyed by save point id (None = bytes failed to decode)
+    thumbnails: HashMap<u64, Option<egui::TextureHandle>>,
+}
+
+impl SavePointsPanelState {
+    /// Drop all cached thumbnails (e.g. when a new file is opened)
+    pub fn clear_thumbnails(&mut self) {
+        self.thumbnails.clear();
+    }
+}
+
+/// Decode image bytes and downscale them to fit within `THUMBNAIL_SIZE`
+fn thumbnail_image(data: &[u8]) -> Option<egui::ColorImage> {
+    let img = image::load_from_memory(data).ok()?;
+    let rgba = img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8();
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        rgba.as_raw(),
+    ))
+}
+
+/// Show the cached thumbnail for a save point, decoding it on first use
+fn show_thumbnail(
+    ui: &mut egui::Ui,
+    doc: &DocumentState,
+    state: &mut SavePointsPanelState,
+    id: u64,
+) {
+    let thumbnail = state.thumbnails.entry(id).or_insert_with(|| {
+        let bytes = doc.editor.as_ref()?.save_point_bytes(id)?;
+        let image = thumbnail_image(&bytes)?;
+        Some(ui.ctx().load_texture(
+            format!("save_point_thumb_{}", id),
+            image,
+            egui::TextureOptions::LINEAR,
+        ))
+    });
+
+    let box_size = egui::vec2(THUMBNAIL_SIZE as f32, THUMBNAIL_SIZE as f32);
+    match thumbnail {
+        Some(texture) => {
+            let size = texture.size_vec2();
+            let scale = (box_size.x / size.x).min(box_size.y / size.y).min(1.0);
+            ui.image((texture.id(), size * scale));
+        }
+        None => {
+            ui.add_sized(box_size, egui::Label::new("\u{1F5BC}"))
+                .on_hover_text("Unable to decode this save point");
+        }
+    }
 }
 
 /// Show the save points panel
@@ -39,7 +95,7 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
         .map(|e| {
             e.save_points()
                 .iter()
-                .map(|sp| (sp.id, sp.name.clone()))
+                .map(|sp| (sp.id, sp.name.clone(), sp.note.clone()))
                 .collect()
         })
         .unwrap_or_default();
@@ -47,7 +103,7 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
     // Get which save points can be deleted
     let can_delete: Vec<_> = save_points
         .iter()
-        .map(|(id, _)| {
+        .map(|(id, _, _)| {
             doc.editor
                 .as_ref()
                 .map(|e| e.can_delete_save_point(*id))
@@ -55,6 +111,11 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
         })
         .collect();
 
+    // Drop thumbnails for save points that no longer exist (e.g. cleared by a length change)
+    state
+        .thumbnails
+        .retain(|id, _| save_points.iter().any(|(sp_id, _, _)| sp_id == id));
+
     let has_editor = doc.editor.is_some();
 
     if !has_editor {
@@ -102,12 +163,13 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
     let mut action_delete: Option<u64> = None;
     let mut action_start_rename: Option<(u64, String)> = None;
     let mut action_finish_rename: Option<(u64, String)> = None;
+    let mut action_set_note: Option<(u64, String)> = None;
 
     if save_points.is_empty() {
         ui.label(RichText::new("No save points yet").italics());
         ui.label("Create a save point to capture the current state.");
     } else {
-        for (idx, (id, name)) in save_points.iter().enumerate() {
+        for (idx, (id, name, note)) in save_points.iter().enumerate() {
             if state.editing_id == Some(*id) {
                 // Editing mode: text box + save/discard buttons below
                 ui.text_edit_singleline(&mut state.edit_buffer);
@@ -130,10 +192,18 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
             } else {
                 // Normal mode: label + action buttons in one row
                 ui.horizontal(|ui| {
-                    ui.label(name);
+                    show_thumbnail(ui, doc, state, *id);
+                    // Double-click the name to rename inline
+                    if ui
+                        .add(egui::Label::new(name).sense(egui::Sense::click()))
+                        .on_hover_text("Double-click to rename")
+                        .double_clicked()
+                    {
+                        action_start_rename = Some((*id, name.clone()));
+                    }
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        // Delete button (only for leaf)
+                        // Delete button (later save points keep their contents)
                         if can_delete.get(idx).copied().unwrap_or(false)
                             && ui
                                 .button("🗑")
@@ -167,6 +237,24 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
                 });
             }
 
+            // Expandable multi-line note, edited on a copy and applied after the loop
+            egui::CollapsingHeader::new(note_header_text(note))
+                .id_salt(("save_point_note", *id))
+                .show(ui, |ui| {
+                    let mut text = note.clone();
+                    if ui
+                        .add(
+                            egui::TextEdit::multiline(&mut text)
+                                .desired_rows(3)
+                                .desired_width(f32::INFINITY)
+                                .hint_text("Add a note..."),
+                        )
+                        .changed()
+                    {
+                        action_set_note = Some((*id, text));
+                    }
+                });
+
             ui.separator();
         }
     }
@@ -184,6 +272,7 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
         if let Some(editor) = &mut doc.editor {
             let _ = editor.delete_save_point(id); // #[must_use] result intentionally ignored — deletability already checked by UI
         }
+        state.thumbnails.remove(&id);
     }
 
     if let Some((id, name)) = action_start_rename {
@@ -196,4 +285,60 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, state: &mut SavePointsPa
             let _ = editor.rename_save_point(id, new_name); // #[must_use] result intentionally ignored — save point existence already verified by UI
         }
     }
+
+    if let Some((id, note)) = action_set_note {
+        if let Some(editor) = &mut doc.editor {
+            let _ = editor.set_save_point_note(id, note); // #[must_use] result intentionally ignored — save point existence already verified by UI
+        }
+    }
+}
+
+/// Header label for a save point's note: "Note" when empty, otherwise its first line
+fn note_header_text(note: &str) -> String {
+    const MAX_PREVIEW_CHARS: usize = 32;
+    let first_line = note.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    if first_line.is_empty() {
+        return "Note".to_string();
+    }
+    let preview: String = first_line.trim().chars().take(MAX_PREVIEW_CHARS).collect();
+    if preview.chars().count() < first_line.trim().chars().count() || note.lines().count() > 1 {
+        format!("Note: {}…", preview)
+    } else {
+        format!("Note: {}", preview)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_thumbnail_image_downscales() {
+        let img = image::RgbImage::from_pixel(200, 100, image::Rgb([255, 0, 0]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let thumb = thumbnail_image(&png).unwrap();
+        assert_eq!(thumb.size, [48, 24]);
+    }
+
+    #[test]
+    fn test_note_header_text() {
+        assert_eq!(note_header_text(""), "Note");
+        assert_eq!(note_header_text("  \n"), "Note");
+        assert_eq!(note_header_text("datamoshed"), "Note: datamoshed");
+        assert_eq!(note_header_text("first\nsecond"), "Note: first…");
+        assert_eq!(
+            note_header_text(&"x".repeat(40)),
+            format!("Note: {}…", "x".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_thumbnail_image_rejects_garbage() {
+        assert!(thumbnail_image(&[0x00, 0x01, 0x02]).is_none());
+    }
 }
@@ -1,9 +1,16 @@
 //! Search and replace dialog UI component
 
 use crate::app::BendApp;
-use crate::editor::search::{parse_hex_replace, SearchMessage, SearchMode};
+use crate::editor::buffer::WriteMode;
+use crate::editor::search::{
+    non_overlapping_matches, parse_hex_replace, SearchMessage, SearchMode, SearchScope,
+};
 use crate::ui::PointerCursor;
 use eframe::egui;
+use std::time::Instant;
+
+/// Match count above which "Bookmark All Matches" asks for confirmation
+const BOOKMARK_ALL_CONFIRM_THRESHOLD: usize = 200;
 
 /// Show the search dialog (modal window)
 pub fn show(ctx: &egui::Context, app: &mut BendApp) {
@@ -17,7 +24,9 @@ pub fn show(ctx: &egui::Context, app: &mut BendApp) {
     let mut do_replace_all = false;
     let mut do_next = false;
     let mut do_prev = false;
+    let mut do_bookmark_all = false;
     let mut navigate_to_last_after_search = false;
+    let mut query_edited = false;
 
     egui::Window::new("Search & Replace")
         .collapsible(false)
@@ -46,6 +55,7 @@ pub fn show(ctx: &egui::Context, app: &mut BendApp) {
                         })
                         .desired_width(250.0),
                 );
+                query_edited = response.changed();
                 // Auto-focus the find field when dialog opens
                 if app.ui.search_state.just_opened {
                     response.request_focus();
@@ -95,6 +105,28 @@ pub fn show(ctx: &egui::Context, app: &mut BendApp) {
                     ui.label(egui::RichText::new("Tip: Use ?? for wildcard bytes").small());
                 }
             });
+            let settings = &mut app.config.settings;
+            if ui
+                .checkbox(&mut settings.search_as_you_type, "Search as you type")
+                .on_hover_text("Update matches while typing; Enter still steps to the next match")
+                .changed()
+            {
+                settings.save();
+            }
+
+            // Search scope
+            ui.horizontal(|ui| {
+                let scope = &mut app.ui.search_state.scope;
+                ui.label("Within:");
+                ui.selectable_value(scope, SearchScope::WholeFile, "Whole file")
+                    .pointer_cursor();
+                ui.selectable_value(scope, SearchScope::Selection, "Selection")
+                    .pointer_cursor()
+                    .on_hover_text("Search within selection (whole file if nothing is selected)");
+                ui.selectable_value(scope, SearchScope::Section, "Section")
+                    .pointer_cursor()
+                    .on_hover_text("Search only the file section under the cursor");
+            });
 
             ui.add_space(8.0);
 
@@ -126,8 +158,42 @@ pub fn show(ctx: &egui::Context, app: &mut BendApp) {
                 {
                     do_replace_all = true;
                 }
+                if ui
+                    .add_enabled(has_matches, egui::Button::new("Bookmark All Matches"))
+                    .pointer_cursor()
+                    .on_hover_text("Add a bookmark at every match")
+                    .clicked()
+                {
+                    if app.ui.search_state.matches.len() > BOOKMARK_ALL_CONFIRM_THRESHOLD {
+                        app.ui.search_state.confirm_bookmark_all = true;
+                    } else {
+                        do_bookmark_all = true;
+                    }
+                }
             });
 
+            // Confirmation for bookmarking a large number of matches
+            if app.ui.search_state.confirm_bookmark_all {
+                let colors = app.ui.colors;
+                ui.add_space(4.0);
+                ui.colored_label(
+                    colors.warning_text,
+                    format!(
+                        "This will create {} bookmarks. Continue?",
+                        app.ui.search_state.matches.len()
+                    ),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Bookmark All").pointer_cursor().clicked() {
+                        do_bookmark_all = true;
+                        app.ui.search_state.confirm_bookmark_all = false;
+                    }
+                    if ui.button("Cancel").pointer_cursor().clicked() {
+                        app.ui.search_state.confirm_bookmark_all = false;
+                    }
+                });
+            }
+
             ui.add_space(8.0);
 
             // Results status
@@ -167,6 +233,10 @@ pub fn show(ctx: &egui::Context, app: &mut BendApp) {
         });
 
     // Handle actions after UI is done (to avoid borrow issues)
+    if app.config.settings.search_as_you_type && !do_search {
+        live_search(ctx, app, query_edited);
+    }
+
     if do_search {
         app.refresh_search();
         // Navigate to last match if Shift+Enter was used on first search
@@ -235,11 +305,76 @@ pub fn show(ctx: &egui::Context, app: &mut BendApp) {
         }
     }
 
+    if do_bookmark_all {
+        // Re-run the search first so bookmarks land on current match positions
+        if let Some(editor) = &app.doc.editor {
+            if app
+                .ui
+                .search_state
+                .matches_may_be_stale(editor.edit_generation())
+            {
+                app.refresh_search();
+            }
+        }
+        let added = bookmark_all_matches(app);
+        app.ui.search_state.message = Some(SearchMessage::Info(format!(
+            "Added {} bookmark{}",
+            added,
+            if added == 1 { "" } else { "s" }
+        )));
+    }
+
     if close_dialog {
         app.ui.search_state.close_dialog();
     }
 }
 
+/// Search-as-you-type: re-run the search once the query (or an option) has
+/// settled, and show the first match of a new query. Does nothing while the
+/// matches are current.
+fn live_search(ctx: &egui::Context, app: &mut BendApp, query_edited: bool) {
+    let Some(generation) = app.doc.editor.as_ref().map(|e| e.edit_generation()) else {
+        return;
+    };
+    let now = Instant::now();
+    let state = &mut app.ui.search_state;
+    if query_edited {
+        state.note_query_edited(now);
+    }
+    match state.live_search_delay(generation, now) {
+        Some(delay) if delay.is_zero() => {
+            // Re-searching after a buffer edit mustn't pull the cursor away from it
+            let query_changed = state.query_changed_since_search();
+            app.refresh_search();
+            if query_changed {
+                app.navigate_to_search_match();
+            }
+        }
+        Some(delay) => ctx.request_repaint_after(delay),
+        None => {}
+    }
+}
+
+/// Add a bookmark at every match offset, skipping offsets that already have one.
+/// Returns the number of bookmarks created.
+fn bookmark_all_matches(app: &mut BendApp) -> usize {
+    let Some(editor) = app.doc.editor.as_mut() else {
+        return 0;
+    };
+
+    let new_bookmarks: Vec<(usize, String)> = app
+        .ui
+        .search_state
+        .matches
+        .iter()
+        .enumerate()
+        .filter(|&(_, &offset)| !editor.has_bookmark_at(offset))
+        .map(|(i, &offset)| (offset, format!("Match {} (0x{:08X})", i + 1, offset)))
+        .collect();
+    // One undo step for the whole batch
+    editor.add_bookmarks(new_bookmarks)
+}
+
 /// Replace the current match
 fn replace_current(app: &mut BendApp) -> Result<(), String> {
     let current_offset = app
@@ -254,15 +389,7 @@ fn replace_current(app: &mut BendApp) -> Result<(), String> {
     }
 
     let replacement = get_replacement_bytes(app)?;
-
-    // Validate replacement length matches pattern (fixed-size buffer requires same length)
-    if replacement.len() != pattern_len {
-        return Err(format!(
-            "Replace pattern length ({}) must match search pattern length ({})",
-            replacement.len(),
-            pattern_len
-        ));
-    }
+    check_replacement_length(app, replacement.len(), pattern_len)?;
 
     // Check header protection
     if app.doc.is_range_protected(current_offset, pattern_len) {
@@ -275,7 +402,7 @@ fn replace_current(app: &mut BendApp) -> Result<(), String> {
     let editor = app.doc.editor.as_mut().ok_or("No file loaded")?;
 
     // Apply the replacement as a single undoable operation
-    editor.replace_bytes(current_offset, &replacement);
+    editor.replace_range_with(current_offset, pattern_len, &replacement);
 
     Ok(())
 }
@@ -292,15 +419,7 @@ fn replace_all(app: &mut BendApp) -> Result<usize, String> {
     }
 
     let replacement = get_replacement_bytes(app)?;
-
-    // Validate replacement length matches pattern (fixed-size buffer requires same length)
-    if replacement.len() != pattern_len {
-        return Err(format!(
-            "Replace pattern length ({}) must match search pattern length ({})",
-            replacement.len(),
-            pattern_len
-        ));
-    }
+    check_replacement_length(app, replacement.len(), pattern_len)?;
 
     // Partition matches into protected vs replaceable
     let (protected, replaceable): (Vec<usize>, Vec<usize>) = app
@@ -310,6 +429,9 @@ fn replace_all(app: &mut BendApp) -> Result<usize, String> {
         .iter()
         .partition(|&&offset| app.doc.is_range_protected(offset, pattern_len));
 
+    // Overlapping matches ("aa" in "aaa") can't all be replaced; keep the earliest
+    let replaceable = non_overlapping_matches(&replaceable, pattern_len);
+
     if replaceable.is_empty() {
         return Err(format!(
             "All {} matches are in protected header regions",
@@ -317,27 +439,58 @@ fn replace_all(app: &mut BendApp) -> Result<usize, String> {
         ));
     }
 
-    let editor = app.doc.editor.as_mut().ok_or("No file loaded")?;
-
-    // Apply all replacements as a single atomic undo/redo operation
-    // Since we require replacement to be same length, positions don't shift
-    editor.replace_all_bytes(&replaceable, &replacement);
-
     let replaced_count = replaceable.len();
     let skipped_count = protected.len();
-
-    if skipped_count > 0 {
-        app.ui.search_state.message = Some(SearchMessage::Info(format!(
+    let message = (skipped_count > 0).then(|| {
+        SearchMessage::Info(format!(
             "Replaced {} of {} matches ({} skipped in protected regions)",
             replaced_count,
             replaced_count + skipped_count,
             skipped_count
-        )));
+        ))
+    });
+
+    // Resizing replacements shift the rest of the buffer for every match,
+    // which is slow enough on large files to hand off to a worker thread
+    if replacement.len() != pattern_len
+        && app.replace_all_in_background(&replaceable, pattern_len, &replacement, message.clone())
+    {
+        return Ok(replaced_count);
+    }
+
+    let editor = app.doc.editor.as_mut().ok_or("No file loaded")?;
+
+    // Apply all replacements as a single atomic undo/redo operation
+    editor.replace_ranges_with(&replaceable, pattern_len, &replacement);
+
+    if message.is_some() {
+        app.ui.search_state.message = message;
     }
 
     Ok(replaced_count)
 }
 
+/// Variable-length replacement resizes the buffer, so it is only allowed in
+/// Insert mode; Overwrite mode keeps the fixed-size guarantee.
+fn check_replacement_length(
+    app: &BendApp,
+    replacement_len: usize,
+    pattern_len: usize,
+) -> Result<(), String> {
+    let insert_mode = app
+        .doc
+        .editor
+        .as_ref()
+        .is_some_and(|e| e.write_mode() == WriteMode::Insert);
+    if replacement_len != pattern_len && !insert_mode {
+        return Err(format!(
+            "Replace pattern length ({}) must match search pattern length ({}) in Overwrite mode (switch to Insert mode to resize)",
+            replacement_len, pattern_len
+        ));
+    }
+    Ok(())
+}
+
 /// Get replacement bytes based on current mode
 fn get_replacement_bytes(app: &BendApp) -> Result<Vec<u8>, String> {
     match app.ui.search_state.mode {
@@ -350,20 +503,20 @@ fn get_replacement_bytes(app: &BendApp) -> Result<Vec<u8>, String> {
 mod tests {
     use super::*;
     use crate::editor::buffer::EditorState;
-    use crate::editor::search::{execute_search, SearchMessage, SearchMode};
+    use crate::editor::search::{execute_search, SearchMessage, SearchMode, SearchScope};
     use crate::formats::traits::{FileSection, RiskLevel};
 
     /// Helper: create a BendApp with file data, sections, and a hex search pre-executed
     fn setup_app(data: &[u8], sections: Vec<FileSection>, query: &str, replace: &str) -> BendApp {
         let mut app = BendApp::default();
         app.doc.editor = Some(EditorState::new(data.to_vec()));
-        app.doc.cached_sections = Some(sections);
+        app.doc.set_sections(Some(sections));
         app.ui.search_state.mode = SearchMode::Hex;
         app.ui.search_state.query = query.to_string();
         app.ui.search_state.replace_with = replace.to_string();
         // Execute search to populate matches
         if let Some(editor) = &app.doc.editor {
-            execute_search(&mut app.ui.search_state, editor.working());
+            execute_search(&mut app.ui.search_state, editor.working(), 0);
         }
         app
     }
@@ -510,6 +663,102 @@ mod tests {
         assert!(app.ui.search_state.message.is_none());
     }
 
+    #[test]
+    fn test_bookmark_all_matches_skips_existing() {
+        let mut data = vec![0u8; 20];
+        data[3] = 0xFF;
+        data[8] = 0xFF;
+        data[15] = 0xFF;
+        let mut app = setup_app(&data, vec![], "FF", "");
+        app.doc
+            .editor
+            .as_mut()
+            .unwrap()
+            .add_bookmark(8, "Existing".to_string());
+
+        assert_eq!(bookmark_all_matches(&mut app), 2);
+
+        let bookmarks = app.doc.editor.as_ref().unwrap().bookmarks();
+        assert_eq!(bookmarks.all().len(), 3);
+        let names: Vec<&str> = bookmarks.all().iter().map(|b| b.name.as_str()).collect();
+        assert!(names.contains(&"Match 1 (0x00000003)"));
+        assert!(names.contains(&"Match 3 (0x0000000F)"));
+        assert!(names.contains(&"Existing"));
+
+        // The whole batch undoes in one step
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert!(editor.undo());
+        assert_eq!(editor.bookmarks().all().len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_search_respects_scope() {
+        let mut data = vec![0u8; 20];
+        data[3] = 0xFF;
+        data[12] = 0xFF;
+        data[15] = 0xFF;
+        let sections = vec![
+            FileSection::new("Header", 0, 10, RiskLevel::High),
+            FileSection::new("Data", 10, 20, RiskLevel::Safe),
+        ];
+        let mut app = setup_app(&data, sections, "FF", "");
+        assert_eq!(app.ui.search_state.matches, vec![3, 12, 15]);
+
+        app.ui.search_state.scope = SearchScope::Selection;
+        app.doc.editor.as_mut().unwrap().set_selection(10, 14);
+        app.refresh_search();
+        assert_eq!(app.ui.search_state.matches, vec![12]);
+
+        app.ui.search_state.scope = SearchScope::Section;
+        app.doc.editor.as_mut().unwrap().set_cursor(2);
+        app.refresh_search();
+        assert_eq!(app.ui.search_state.matches, vec![3]);
+    }
+
+    #[test]
+    fn test_replace_length_mismatch_requires_insert_mode() {
+        let data = b"ab-ab".to_vec();
+        let mut app = setup_app(&data, vec![], "61 62", "78 79 7A");
+        app.ui.search_state.current_match = Some(0);
+
+        let result = replace_current(&mut app);
+        assert!(result.unwrap_err().contains("Insert mode"));
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), &data);
+
+        app.doc.editor.as_mut().unwrap().toggle_write_mode();
+        assert!(replace_current(&mut app).is_ok());
+        assert_eq!(app.doc.editor.as_ref().unwrap().working(), b"xyz-ab");
+    }
+
+    #[test]
+    fn test_replace_all_variable_length() {
+        let data = b"ab-ab-ab".to_vec();
+        let mut app = setup_app(&data, vec![], "61 62", "7A");
+        app.doc.editor.as_mut().unwrap().toggle_write_mode();
+
+        assert_eq!(replace_all(&mut app).unwrap(), 3);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), b"z-z-z");
+
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &data);
+        assert!(!editor.can_undo());
+    }
+
+    #[test]
+    fn test_replace_all_skips_overlapping_matches() {
+        let data = b"aaaa".to_vec();
+        let mut app = setup_app(&data, vec![], "61 61 61", "62");
+        assert_eq!(app.ui.search_state.matches, vec![0, 1]);
+        app.doc.editor.as_mut().unwrap().toggle_write_mode();
+
+        assert_eq!(replace_all(&mut app).unwrap(), 1);
+        let editor = app.doc.editor.as_mut().unwrap();
+        assert_eq!(editor.working(), b"ba");
+        assert!(editor.undo());
+        assert_eq!(editor.working(), &data);
+    }
+
     #[test]
     fn test_replace_all_atomic_undo() {
         // Data: FF at offsets 5 and 15, no protection
@@ -1,6 +1,12 @@
 //! Settings/Preferences dialog UI component
 
-use crate::settings::{AppSettings, ThemePreference};
+use crate::formats::RiskLevel;
+use crate::keybindings::{KeyBinding, KeyBindings, ShortcutAction};
+use crate::settings::{
+    AppSettings, OffsetBase, ThemePreference, BYTES_PER_ROW_OPTIONS, HEX_FONT_SIZE_RANGE,
+    MAX_PREVIEW_DEBOUNCE_MS, MAX_RECENT_FILES_LIMIT, MAX_SELECTION_STEP,
+};
+use crate::ui::theme::AppColors;
 use crate::ui::PointerCursor;
 use eframe::egui;
 
@@ -11,6 +17,16 @@ pub struct SettingsDialogState {
     pub dialog_open: bool,
     /// Snapshot of settings when dialog opened (for change detection)
     initial_settings: Option<AppSettings>,
+    /// Text buffer for the always-protected section names (one per line)
+    protected_sections_text: String,
+    /// Text buffer for the always-allowed section names (one per line)
+    allowed_sections_text: String,
+    /// Text buffer for the working copy folder (empty = next to the source)
+    work_copy_dir_text: String,
+    /// Action waiting for its new shortcut to be pressed
+    capturing: Option<ShortcutAction>,
+    /// Why the last captured shortcut was refused
+    capture_error: Option<String>,
 }
 
 impl SettingsDialogState {
@@ -18,12 +34,141 @@ impl SettingsDialogState {
     pub fn open(&mut self, settings: &AppSettings) {
         self.dialog_open = true;
         self.initial_settings = Some(settings.clone());
+        self.protected_sections_text = settings.protected_sections.join("\n");
+        self.allowed_sections_text = settings.allowed_sections.join("\n");
+        self.work_copy_dir_text = settings
+            .work_copy_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default();
     }
 
     /// Close the settings dialog
     pub fn close(&mut self) {
         self.dialog_open = false;
         self.initial_settings = None;
+        self.capturing = None;
+        self.capture_error = None;
+    }
+
+    /// Whether the next key press is being captured as a shortcut (so it
+    /// shouldn't trigger anything else)
+    pub fn is_capturing_key(&self) -> bool {
+        self.capturing.is_some()
+    }
+}
+
+/// Parse a one-name-per-line text buffer, skipping blank lines
+fn parse_section_names(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Bind the key pressed this frame (if any) to the action being captured.
+/// A bare Escape cancels instead; a refused shortcut keeps the capture going.
+fn capture_key(ui: &egui::Ui, state: &mut SettingsDialogState, bindings: &mut KeyBindings) {
+    let Some(action) = state.capturing else {
+        return;
+    };
+    let pressed = ui.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                modifiers,
+                ..
+            } => Some((*key, *modifiers)),
+            _ => None,
+        })
+    });
+    match pressed {
+        Some((egui::Key::Escape, modifiers)) if modifiers.is_none() => {
+            state.capturing = None;
+            state.capture_error = None;
+        }
+        Some((key, modifiers)) => {
+            let binding = KeyBinding::from_modifiers(key, modifiers);
+            state.capture_error = binding.rejection();
+            if state.capture_error.is_none() {
+                bindings.set(action, vec![binding]);
+                state.capturing = None;
+            }
+        }
+        None => {}
+    }
+}
+
+/// Rebinding grid: click an action's shortcut, then press the new one.
+/// Shortcuts shared by several actions or with a fixed shortcut are flagged.
+fn show_keybindings(
+    ui: &mut egui::Ui,
+    state: &mut SettingsDialogState,
+    bindings: &mut KeyBindings,
+    warning: egui::Color32,
+) {
+    egui::Grid::new("keybindings_grid")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for action in ShortcutAction::ALL {
+                ui.label(action.label());
+                let current = bindings.bindings(action);
+                let text = if state.capturing == Some(action) {
+                    "Press a shortcut...".to_string()
+                } else if current.is_empty() {
+                    "Unbound".to_string()
+                } else {
+                    let texts: Vec<_> = current.iter().map(KeyBinding::display).collect();
+                    texts.join(", ")
+                };
+                let conflicted = current
+                    .iter()
+                    .any(|&b| !bindings.conflicts_with(action, b).is_empty());
+                let mut text = egui::RichText::new(text).monospace();
+                if conflicted {
+                    text = text.color(warning);
+                }
+                if ui
+                    .button(text)
+                    .pointer_cursor()
+                    .on_hover_text("Click, then press the new shortcut (Escape cancels)")
+                    .clicked()
+                {
+                    state.capturing = Some(action);
+                    state.capture_error = None;
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!bindings.is_default(action), egui::Button::new("Reset"))
+                        .pointer_cursor()
+                        .clicked()
+                    {
+                        bindings.reset(action);
+                    }
+                    if ui
+                        .add_enabled(!current.is_empty(), egui::Button::new("Clear"))
+                        .pointer_cursor()
+                        .clicked()
+                    {
+                        bindings.set(action, Vec::new());
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+    if let Some(error) = &state.capture_error {
+        ui.colored_label(warning, error);
+    }
+    for (binding, labels) in bindings.conflicts() {
+        ui.colored_label(
+            warning,
+            format!("{} is bound to: {}", binding.display(), labels.join(", ")),
+        );
     }
 }
 
@@ -45,6 +190,8 @@ pub fn show(
     }
 
     let mut actions: Vec<SettingsAction> = Vec::new();
+    // Escape ends a capture rather than closing the dialog
+    let was_capturing = state.is_capturing_key();
 
     egui::Window::new("Preferences")
         .collapsible(false)
@@ -52,6 +199,8 @@ pub fn show(
         .default_width(400.0)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
+            capture_key(ui, state, &mut settings.keybindings);
+
             // Appearance section
             ui.heading("Appearance");
             ui.add_space(4.0);
@@ -71,6 +220,104 @@ pub fn show(
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Bytes per row:");
+                for &width in BYTES_PER_ROW_OPTIONS {
+                    ui.selectable_value(&mut settings.bytes_per_row, width, width.to_string())
+                        .pointer_cursor();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Offsets:");
+                for base in OffsetBase::ALL {
+                    ui.selectable_value(&mut settings.offset_base, base, base.to_string())
+                        .pointer_cursor();
+                }
+            })
+            .response
+            .on_hover_text("Number base for the offset column, cursor readout, and copied offsets");
+
+            ui.horizontal(|ui| {
+                ui.label("Hex editor font size:");
+                ui.add(
+                    egui::DragValue::new(&mut settings.hex_font_size)
+                        .range(HEX_FONT_SIZE_RANGE)
+                        .speed(0.25)
+                        .suffix(" pt"),
+                );
+            });
+
+            ui.checkbox(
+                &mut settings.ascii_group_separator,
+                "Separate ASCII column into 8-byte groups",
+            )
+            .on_hover_text("Leave a small gap every 8 characters so the ASCII column lines up with the hex groups");
+
+            ui.checkbox(
+                &mut settings.highlight_unknown_regions,
+                "Highlight unknown regions",
+            )
+            .on_hover_text(
+                "Tint bytes the format parser doesn't recognize; turn off to leave them \
+                 without a background",
+            );
+
+            ui.checkbox(&mut settings.follow_cursor, "Scroll to follow the cursor")
+                .on_hover_text(
+                    "Keep the cursor in view while typing or moving it past the edge of the \
+                     hex view; turn off for a static view",
+                );
+
+            ui.checkbox(&mut settings.vim_motions, "Vim motions")
+                .on_hover_text(
+                    "Escape enters command mode: type a count, then an arrow key to move \
+                     (Shift+arrow to select) that many bytes or rows. i or Escape returns \
+                     to editing.",
+                );
+
+            ui.horizontal(|ui| {
+                ui.label("Selection step:");
+                ui.add(
+                    egui::DragValue::new(&mut settings.selection_step)
+                        .range(1..=MAX_SELECTION_STEP)
+                        .suffix(" bytes"),
+                );
+            })
+            .response
+            .on_hover_text(
+                "How far Alt+Left/Right move the selection's end \
+                 (with Shift, its start) to shrink or grow it",
+            );
+
+            ui.add_space(4.0);
+            ui.label("Section colors:")
+                .on_hover_text("Colors for each risk level in the structure tree and hex view");
+            let theme_colors = AppColors::new(ctx.style().visuals.dark_mode);
+            egui::Grid::new("risk_colors_grid")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for level in RiskLevel::ALL {
+                        ui.label(level.label()).on_hover_text(level.description());
+                        let slot = settings.risk_colors.get_mut(level);
+                        let mut rgb = slot.unwrap_or_else(|| {
+                            let c = theme_colors.risk_color(level);
+                            [c.r(), c.g(), c.b()]
+                        });
+                        if ui.color_edit_button_srgb(&mut rgb).changed() {
+                            *slot = Some(rgb);
+                        }
+                        if ui
+                            .add_enabled(slot.is_some(), egui::Button::new("Reset"))
+                            .pointer_cursor()
+                            .clicked()
+                        {
+                            *slot = None;
+                        }
+                        ui.end_row();
+                    }
+                });
+
             ui.add_space(16.0);
 
             // Editing section
@@ -95,6 +342,181 @@ pub fn show(
                  to corrupt the file (e.g., JPEG scan data headers)",
             );
 
+            ui.checkbox(
+                &mut settings.warn_on_undecodable_export,
+                "Warn before exporting an undecodable image",
+            )
+            .on_hover_text(
+                "Ask for confirmation when the working buffer no longer decodes \
+                 as an image, to catch accidental header damage",
+            );
+
+            ui.checkbox(
+                &mut settings.jump_to_decode_error,
+                "Jump to decode-error location",
+            )
+            .on_hover_text(
+                "When an edit stops the preview from decoding, move the cursor to \
+                 the first byte that differs from the original, the most likely break",
+            );
+
+            ui.checkbox(
+                &mut settings.grow_on_overwrite_at_eof,
+                "Overwrite mode extends the file at the end",
+            )
+            .on_hover_text(
+                "Typing past the last byte in Overwrite mode appends new bytes \
+                 instead of overwriting the last one again",
+            );
+
+            ui.checkbox(
+                &mut settings.mouse_history_buttons,
+                "Undo/redo with mouse back/forward buttons",
+            )
+            .on_hover_text(
+                "Use the mouse side buttons to step backward and forward through edit history",
+            );
+
+            ui.checkbox(&mut settings.work_on_copy, "Work on a copy")
+                .on_hover_text(
+                    "When opening a file, copy it to a timestamped file first and edit \
+                     that, so the original on disk is never touched",
+                );
+            ui.add_enabled_ui(settings.work_on_copy, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Copy folder:");
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut state.work_copy_dir_text)
+                            .hint_text("Next to the original"),
+                    );
+                    if response.changed() {
+                        let text = state.work_copy_dir_text.trim();
+                        settings.work_copy_dir =
+                            (!text.is_empty()).then(|| std::path::PathBuf::from(text));
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Auto save point every");
+                ui.add(
+                    egui::DragValue::new(&mut settings.auto_save_point_interval)
+                        .range(0..=100_000)
+                        .speed(10),
+                );
+                ui.label("edits");
+            })
+            .response
+            .on_hover_text(
+                "Automatically create a checkpoint save point after this many edits. \
+                 Set to 0 to disable",
+            );
+
+            ui.horizontal(|ui| {
+                ui.label("Preview refresh delay");
+                ui.add(
+                    egui::DragValue::new(&mut settings.preview_debounce_ms)
+                        .range(0..=MAX_PREVIEW_DEBOUNCE_MS)
+                        .speed(5)
+                        .suffix(" ms"),
+                );
+            })
+            .response
+            .on_hover_text(
+                "How long to wait after the last edit before re-decoding the preview. \
+                 Raise this if typing feels sluggish on large images",
+            );
+
+            ui.horizontal(|ui| {
+                ui.label("GIF export frame delay");
+                ui.add(
+                    egui::DragValue::new(&mut settings.gif_frame_delay_ms)
+                        .range(10..=10_000)
+                        .speed(10)
+                        .suffix(" ms"),
+                );
+            })
+            .response
+            .on_hover_text("Time each save point is shown when exporting save points as a GIF");
+
+            ui.horizontal(|ui| {
+                ui.label("Confirm pastes larger than");
+                ui.add(
+                    egui::DragValue::new(&mut settings.paste_confirm_threshold)
+                        .range(0..=usize::MAX)
+                        .speed(64),
+                );
+                ui.label("bytes");
+            })
+            .response
+            .on_hover_text(
+                "Ask before pasting clipboard content longer than this, to avoid \
+                 accidentally inserting a huge block. Set to 0 to never ask",
+            );
+
+            ui.horizontal(|ui| {
+                ui.label("Confirm opening files larger than");
+                ui.add(
+                    egui::DragValue::new(&mut settings.large_file_warn_mb)
+                        .range(0..=u64::MAX)
+                        .speed(16),
+                );
+                ui.label("MB");
+            })
+            .response
+            .on_hover_text(
+                "Ask before opening a file bigger than this. Bend holds the original \
+                 and the edited copy in memory, so a file needs about twice its size. \
+                 Set to 0 to never ask",
+            );
+
+            ui.add_space(8.0);
+            ui.label("Section names (one per line), checked before the risk level:")
+                .on_hover_text(
+                    "Names match the structure tree. Listing a section also covers its \
+                     children unless a child is listed in the other box",
+                );
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Always protect");
+                    if ui
+                        .add(
+                            egui::TextEdit::multiline(&mut state.protected_sections_text)
+                                .desired_rows(3)
+                                .desired_width(180.0),
+                        )
+                        .changed()
+                    {
+                        settings.protected_sections =
+                            parse_section_names(&state.protected_sections_text);
+                    }
+                });
+                ui.vertical(|ui| {
+                    ui.label("Always allow");
+                    if ui
+                        .add(
+                            egui::TextEdit::multiline(&mut state.allowed_sections_text)
+                                .desired_rows(3)
+                                .desired_width(180.0),
+                        )
+                        .changed()
+                    {
+                        settings.allowed_sections =
+                            parse_section_names(&state.allowed_sections_text);
+                    }
+                });
+            });
+
+            ui.add_space(16.0);
+
+            // Keyboard shortcuts section
+            egui::CollapsingHeader::new(egui::RichText::new("Keyboard Shortcuts").heading())
+                .id_salt("keybindings_section")
+                .show(ui, |ui| {
+                    let warning = AppColors::new(ui.visuals().dark_mode).warning_text;
+                    show_keybindings(ui, state, &mut settings.keybindings, warning);
+                });
+
             ui.add_space(16.0);
 
             // Recent files section
@@ -112,6 +534,16 @@ pub fn show(
                     actions.push(SettingsAction::ClearRecent);
                 }
             });
+            ui.horizontal(|ui| {
+                ui.label("Remember up to");
+                ui.add(
+                    egui::DragValue::new(&mut settings.max_recent_files)
+                        .range(1..=MAX_RECENT_FILES_LIMIT),
+                );
+                ui.label("recent files");
+            })
+            .response
+            .on_hover_text("Pinned files don't count toward this limit");
 
             ui.add_space(16.0);
 
@@ -139,7 +571,7 @@ pub fn show(
             });
 
             // Handle Escape to close
-            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            if !was_capturing && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
                 actions.push(SettingsAction::Close);
             }
         });
@@ -191,8 +623,21 @@ mod tests {
         assert!(state.dialog_open);
         assert!(state.initial_settings.is_some());
 
+        state.capturing = Some(ShortcutAction::Undo);
+        assert!(state.is_capturing_key());
+
         state.close();
         assert!(!state.dialog_open);
         assert!(state.initial_settings.is_none());
+        assert!(!state.is_capturing_key());
+    }
+
+    #[test]
+    fn test_parse_section_names() {
+        assert_eq!(
+            parse_section_names("Header\n\n  Global Color Table  \n"),
+            vec!["Header".to_string(), "Global Color Table".to_string()]
+        );
+        assert!(parse_section_names("   ").is_empty());
     }
 }
@@ -1,5 +1,6 @@
 //! Keyboard shortcuts help dialog
 
+use crate::keybindings::{KeyBinding, KeyBindings, ShortcutAction};
 use crate::ui::PointerCursor;
 use eframe::egui;
 
@@ -22,8 +23,8 @@ impl ShortcutsDialogState {
     }
 }
 
-/// Show the keyboard shortcuts help dialog
-pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState) {
+/// Show the keyboard shortcuts help dialog, listing the current bindings
+pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState, bindings: &KeyBindings) {
     if !state.dialog_open {
         return;
     }
@@ -31,6 +32,7 @@ pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState) {
     let mut close_dialog = false;
 
     let max_height = ctx.screen_rect().height() * 0.8;
+    let rows = |actions: &[(ShortcutAction, &'static str)]| action_rows(bindings, actions);
 
     egui::Window::new("Keyboard Shortcuts")
         .collapsible(false)
@@ -39,6 +41,8 @@ pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState) {
         .max_height(max_height)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
+            ui.weak("Most shortcuts can be rebound in Preferences.");
+            ui.add_space(4.0);
             egui::ScrollArea::vertical()
                 .auto_shrink(true)
                 .show(ui, |ui| {
@@ -47,45 +51,65 @@ pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState) {
                     shortcuts_table(
                         ui,
                         "file_ops",
-                        &[
-                            ("Ctrl+O / Cmd+O", "Open file"),
-                            ("Ctrl+E / Cmd+E", "Export file"),
-                        ],
+                        &rows(&[
+                            (ShortcutAction::CommandPalette, "Command palette"),
+                            (ShortcutAction::Open, "Open file"),
+                            (ShortcutAction::Export, "Export file"),
+                            (
+                                ShortcutAction::QuickExport,
+                                "Quick export to the last export folder",
+                            ),
+                            (ShortcutAction::CloseTab, "Close tab"),
+                            (ShortcutAction::NextTab, "Next tab"),
+                            (ShortcutAction::PreviousTab, "Previous tab"),
+                        ]),
                     );
 
                     ui.add_space(10.0);
 
                     // Edit Operations
                     ui.heading("Edit Operations");
-                    shortcuts_table(
-                        ui,
-                        "edit_ops",
-                        &[
-                            ("Ctrl+Z / Cmd+Z", "Undo"),
-                            ("Ctrl+Shift+Z / Cmd+Shift+Z", "Redo"),
-                            ("Ctrl+Y / Cmd+Y", "Redo (alternative)"),
-                            ("Ctrl+F / Cmd+F", "Find & Replace"),
-                            ("Ctrl+G / Cmd+G", "Go to offset"),
-                            ("Ctrl+S / Cmd+S", "Create save point"),
-                            ("Ctrl+D / Cmd+D", "Add bookmark at cursor"),
-                            ("Ctrl+R / Cmd+R", "Refresh preview"),
-                        ],
-                    );
+                    let mut edit_rows = rows(&[
+                        (ShortcutAction::Undo, "Undo"),
+                        (ShortcutAction::Redo, "Redo"),
+                    ]);
+                    edit_rows.push(("Mouse Back / Forward".to_string(), "Undo / Redo"));
+                    edit_rows.extend(rows(&[
+                        (ShortcutAction::FindReplace, "Find & Replace"),
+                        (ShortcutAction::GoToOffset, "Go to offset"),
+                        (
+                            ShortcutAction::MatchingMarker,
+                            "Jump between start and end of the current section",
+                        ),
+                        (ShortcutAction::CreateSavePoint, "Create save point"),
+                        (ShortcutAction::NameSavePoint, "Create named save point"),
+                        (ShortcutAction::AddBookmark, "Add bookmark at cursor"),
+                        (ShortcutAction::RefreshPreview, "Refresh preview"),
+                        (ShortcutAction::RepeatLastEffect, "Repeat last effect"),
+                    ]));
+                    shortcuts_table(ui, "edit_ops", &edit_rows);
 
                     ui.add_space(10.0);
 
                     // Navigation
                     ui.heading("Navigation");
-                    shortcuts_table(
-                        ui,
-                        "navigation",
-                        &[
-                            ("Arrow Keys", "Move cursor"),
-                            ("Page Up / Page Down", "Move cursor by 16 rows"),
-                            ("Home", "Go to start of file"),
-                            ("End", "Go to end of file"),
-                        ],
-                    );
+                    let mut navigation_rows = fixed_rows(&[
+                        ("Arrow Keys", "Move cursor"),
+                        ("Page Up / Page Down", "Move cursor by 16 rows"),
+                        ("Home", "Go to start of file"),
+                        ("End", "Go to end of file"),
+                    ]);
+                    navigation_rows.extend(rows(&[
+                        (
+                            ShortcutAction::RepeatByteJumpNext,
+                            "Jump to next occurrence of last byte",
+                        ),
+                        (
+                            ShortcutAction::RepeatByteJumpPrevious,
+                            "Jump to previous occurrence of last byte",
+                        ),
+                    ]));
+                    shortcuts_table(ui, "navigation", &navigation_rows);
 
                     ui.add_space(10.0);
 
@@ -94,47 +118,80 @@ pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState) {
                     shortcuts_table(
                         ui,
                         "selection",
-                        &[
+                        &fixed_rows(&[
                             ("Shift + Arrow Keys", "Extend selection"),
                             ("Shift + Page Up/Down", "Extend selection by 16 rows"),
                             ("Shift + Home", "Select to start"),
                             ("Shift + End", "Select to end"),
                             ("Shift + Click", "Select range"),
-                        ],
-                    );
-
-                    ui.add_space(10.0);
-
-                    // Hex Editing
-                    ui.heading("Hex Editing");
-                    shortcuts_table(
-                        ui,
-                        "hex_editing",
-                        &[
-                            ("0-9, A-F", "Edit hex value at cursor (Hex mode)"),
+                            ("Ctrl + Drag", "Add another selected range"),
+                            ("Click offset column", "Select whole row"),
+                            ("Shift + Click offset column", "Extend selection by rows"),
                             (
-                                "Any printable char",
-                                "Edit ASCII value at cursor (ASCII mode)",
+                                "Alt + Right / Left",
+                                "Grow / shrink selection end by the step",
                             ),
                             (
-                                "Ctrl+M / Cmd+M",
-                                "Toggle between Hex and ASCII editing mode",
+                                "Alt + Shift + Left / Right",
+                                "Grow / shrink selection start by the step",
                             ),
-                            ("Ctrl+I / Cmd+I", "Toggle Insert/Overwrite mode"),
                             (
-                                "Backspace",
-                                "Delete byte before cursor (Insert) / Move left (Overwrite)",
+                                "Esc, count, Shift + Arrow",
+                                "Select that many bytes/rows (Vim motions setting)",
                             ),
-                            ("Delete", "Delete byte at cursor (Insert mode)"),
-                            ("Right-click", "Context menu (copy, paste, bookmark)"),
-                        ],
+                        ]),
                     );
 
                     ui.add_space(10.0);
 
+                    // Hex Editing
+                    ui.heading("Hex Editing");
+                    let mut hex_rows = fixed_rows(&[
+                        ("0-9, A-F", "Edit hex value at cursor (Hex mode)"),
+                        (
+                            "Any printable char",
+                            "Edit ASCII value at cursor (ASCII mode)",
+                        ),
+                    ]);
+                    hex_rows.extend(rows(&[
+                        (
+                            ShortcutAction::ToggleEditMode,
+                            "Toggle between Hex and ASCII editing mode",
+                        ),
+                        (
+                            ShortcutAction::ToggleWriteMode,
+                            "Toggle Insert/Overwrite mode",
+                        ),
+                    ]));
+                    hex_rows.extend(fixed_rows(&[
+                        (
+                            "Backspace",
+                            "Delete byte before cursor (Insert) / Move left (Overwrite)",
+                        ),
+                        ("Delete", "Delete byte at cursor (Insert mode)"),
+                    ]));
+                    hex_rows.push((
+                        KeyBinding::ctrl_shift(egui::Key::V).display(),
+                        "Paste tiled: repeat the clipboard across the selection",
+                    ));
+                    hex_rows.push((
+                        "Right-click".to_string(),
+                        "Context menu (copy, paste, bookmark)",
+                    ));
+                    shortcuts_table(ui, "hex_editing", &hex_rows);
+
+                    ui.add_space(10.0);
+
                     // View
                     ui.heading("View");
-                    shortcuts_table(ui, "view", &[("F1", "Show this help screen")]);
+                    shortcuts_table(
+                        ui,
+                        "view",
+                        &rows(&[
+                            (ShortcutAction::ToggleComparison, "Toggle comparison mode"),
+                            (ShortcutAction::ShortcutsHelp, "Show this help screen"),
+                        ]),
+                    );
                 });
 
             ui.add_space(10.0);
@@ -157,14 +214,34 @@ pub fn show(ctx: &egui::Context, state: &mut ShortcutsDialogState) {
     }
 }
 
+/// Rows for `actions` with their current bindings; unbound actions are left out
+fn action_rows(
+    bindings: &KeyBindings,
+    actions: &[(ShortcutAction, &'static str)],
+) -> Vec<(String, &'static str)> {
+    actions
+        .iter()
+        .map(|&(action, description)| (bindings.display_all(action), description))
+        .filter(|(shortcut, _)| !shortcut.is_empty())
+        .collect()
+}
+
+/// Rows for shortcuts that can't be rebound
+fn fixed_rows(shortcuts: &[(&str, &'static str)]) -> Vec<(String, &'static str)> {
+    shortcuts
+        .iter()
+        .map(|&(shortcut, description)| (shortcut.to_string(), description))
+        .collect()
+}
+
 /// Render a table of keyboard shortcuts
-fn shortcuts_table(ui: &mut egui::Ui, section: &str, shortcuts: &[(&str, &str)]) {
+fn shortcuts_table(ui: &mut egui::Ui, section: &str, shortcuts: &[(String, &str)]) {
     egui::Grid::new(ui.id().with(section))
         .num_columns(2)
         .spacing([20.0, 4.0])
         .show(ui, |ui| {
             for (shortcut, description) in shortcuts {
-                ui.label(egui::RichText::new(*shortcut).monospace().strong());
+                ui.label(egui::RichText::new(shortcut).monospace().strong());
                 ui.label(*description);
                 ui.end_row();
             }
@@ -0,0 +1,103 @@
+//! "Scan for Image Headers" dialog UI component
+
+use crate::app::{DocumentState, UiState};
+use crate::formats::signatures::{scan_for_signatures, SignatureHit, MAX_SIGNATURE_HITS};
+use crate::ui::PointerCursor;
+use eframe::egui;
+
+/// State for the image header scan dialog
+#[derive(Default)]
+pub struct SignatureScanState {
+    /// Whether the dialog is visible
+    pub dialog_open: bool,
+    /// Signatures found by the last scan
+    results: Vec<SignatureHit>,
+    /// Whether the last scan stopped at `MAX_SIGNATURE_HITS`
+    truncated: bool,
+}
+
+impl SignatureScanState {
+    /// Open the dialog and scan `data` immediately
+    pub fn open(&mut self, data: &[u8]) {
+        self.dialog_open = true;
+        self.rescan(data);
+    }
+
+    /// Re-run the scan against `data`
+    fn rescan(&mut self, data: &[u8]) {
+        (self.results, self.truncated) = scan_for_signatures(data);
+    }
+}
+
+/// Show the image header scan dialog. Clicking a result moves the cursor there.
+pub fn show(ctx: &egui::Context, doc: &mut DocumentState, ui_state: &mut UiState) {
+    if !ui_state.signature_scan_state.dialog_open {
+        return;
+    }
+    let Some(editor) = &mut doc.editor else {
+        ui_state.signature_scan_state.dialog_open = false;
+        return;
+    };
+
+    let mut open = true;
+    let mut jump_to: Option<usize> = None;
+    let mut rescan = false;
+    let state = &ui_state.signature_scan_state;
+
+    egui::Window::new("Scan for Image Headers")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(260.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} signature(s) found", state.results.len()));
+                if ui.button("Rescan").pointer_cursor().clicked() {
+                    rescan = true;
+                }
+            });
+            if state.truncated {
+                ui.colored_label(
+                    ui_state.colors.warning_text,
+                    format!("Stopped after {} hits", MAX_SIGNATURE_HITS),
+                );
+            }
+            ui.separator();
+
+            if state.results.is_empty() {
+                ui.label("No BMP, JPEG, PNG, or GIF signatures in the buffer");
+                return;
+            }
+
+            let row_height = ui.text_style_height(&egui::TextStyle::Body);
+            egui::ScrollArea::vertical().max_height(300.0).show_rows(
+                ui,
+                row_height,
+                state.results.len(),
+                |ui, rows| {
+                    for hit in &state.results[rows] {
+                        let label = format!("0x{:08X}  {}", hit.offset, hit.format);
+                        if ui
+                            .selectable_label(false, egui::RichText::new(label).monospace())
+                            .pointer_cursor()
+                            .clicked()
+                        {
+                            jump_to = Some(hit.offset);
+                        }
+                    }
+                },
+            );
+        });
+
+    if let Some(offset) = jump_to {
+        editor.clear_unlocked_selection();
+        editor.set_cursor(offset);
+        ui_state.pending_hex_scroll = Some(offset);
+    }
+    if rescan {
+        ui_state.signature_scan_state.rescan(editor.working());
+    }
+    if !open {
+        ui_state.signature_scan_state.dialog_open = false;
+    }
+}
@@ -1,16 +1,38 @@
 //! Structure tree UI component for visualizing file sections
 
-use crate::app::{DocumentState, UiState};
+use crate::app::{DocumentState, SectionEffect, UiState};
 use crate::formats::{FileSection, RiskLevel};
 use crate::ui::theme::AppColors;
 use crate::ui::PointerCursor;
 use eframe::egui::{self, RichText};
 
+/// A request to apply an effect to one section's byte range (`start`, `end`, effect)
+pub type SectionGlitch = (usize, usize, SectionEffect);
+
+/// Right-click menu on a section label offering "Glitch This Section"
+fn glitch_menu(
+    response: &egui::Response,
+    section: &FileSection,
+    glitch: &mut Option<SectionGlitch>,
+) {
+    response.context_menu(|ui| {
+        ui.menu_button("Glitch This Section", |ui| {
+            for effect in SectionEffect::ALL {
+                if ui.button(effect.label()).pointer_cursor().clicked() {
+                    *glitch = Some((section.start, section.end, effect));
+                    ui.close_menu();
+                }
+            }
+        });
+    });
+}
+
 /// Show a single section in the tree
 fn show_section(
     ui: &mut egui::Ui,
     section: &FileSection,
     clicked_offset: &mut Option<usize>,
+    glitch: &mut Option<SectionGlitch>,
     current_cursor: usize,
     colors: &AppColors,
 ) {
@@ -38,6 +60,7 @@ fn show_section(
             if response.clicked() {
                 *clicked_offset = Some(section.start);
             }
+            glitch_menu(&response, section, glitch);
             // Draw a risk-colored outline around the selected section label
             if is_cursor_in_section {
                 let rect = response.rect.expand(1.0);
@@ -99,7 +122,7 @@ fn show_section(
 
                 // Show children
                 for child in &section.children {
-                    show_section(ui, child, clicked_offset, current_cursor, colors);
+                    show_section(ui, child, clicked_offset, glitch, current_cursor, colors);
                 }
             });
 
@@ -110,22 +133,29 @@ fn show_section(
         );
 
         // Make header clickable too
-        if header.header_response.pointer_cursor().clicked() {
+        let header_response = header.header_response.pointer_cursor();
+        if header_response.clicked() {
             *clicked_offset = Some(section.start);
         }
+        glitch_menu(&header_response, section, glitch);
     }
 }
 
 /// Show the structure tree panel.
 /// Needs `DocumentState` (editor + cached_sections) and `UiState` (colors +
-/// scroll intent). No BendApp-level method is called.
-pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, ui_state: &mut UiState) {
+/// scroll intent). No BendApp-level method is called; a "Glitch This Section"
+/// pick is returned for the app to apply.
+pub fn show(
+    ui: &mut egui::Ui,
+    doc: &mut DocumentState,
+    ui_state: &mut UiState,
+) -> Option<SectionGlitch> {
     // Get cursor position and check if editor exists
     let current_cursor = match &doc.editor {
         Some(editor) => editor.cursor(),
         None => {
             ui.label("No file loaded");
-            return;
+            return None;
         }
     };
 
@@ -133,38 +163,40 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, ui_state: &mut UiState)
     match &doc.cached_sections {
         None => {
             ui.label("Unable to parse file structure");
-            return;
+            return None;
         }
         Some(sections) if sections.is_empty() => {
             ui.label("No structure detected");
-            return;
+            return None;
         }
         _ => {}
     }
 
+    if let Some(format) = doc.forced_format {
+        ui.weak(format!("Parsed as {} (forced)", format.label()));
+    }
+
     // Track clicked offset for navigation
     let mut clicked_offset: Option<usize> = None;
+    let mut glitch = None;
 
     // Scope the immutable borrow of sections for UI rendering
     if let Some(sections) = &doc.cached_sections {
         let colors = ui_state.colors;
 
-        // Legend
-        ui.horizontal(|ui| {
+        // Legend (hover a level for what it means; colors are set in Preferences)
+        ui.horizontal_wrapped(|ui| {
             ui.label("Risk:");
-            for risk in [
-                RiskLevel::Safe,
-                RiskLevel::Caution,
-                RiskLevel::High,
-                RiskLevel::Critical,
-            ] {
+            for risk in RiskLevel::ALL {
                 egui::Frame::none()
                     .fill(colors.risk_bg_color(risk))
                     .rounding(egui::Rounding::same(3.0))
                     .inner_margin(egui::Margin::symmetric(4.0, 1.0))
                     .show(ui, |ui| {
                         ui.label(RichText::new(risk.label()).color(colors.hex_byte_text));
-                    });
+                    })
+                    .response
+                    .on_hover_text(risk.description());
             }
         });
 
@@ -172,7 +204,14 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, ui_state: &mut UiState)
 
         // Show sections
         for section in sections {
-            show_section(ui, section, &mut clicked_offset, current_cursor, &colors);
+            show_section(
+                ui,
+                section,
+                &mut clicked_offset,
+                &mut glitch,
+                current_cursor,
+                &colors,
+            );
         }
     }
 
@@ -183,4 +222,5 @@ pub fn show(ui: &mut egui::Ui, doc: &mut DocumentState, ui_state: &mut UiState)
         }
         ui_state.pending_hex_scroll = Some(offset);
     }
+    glitch
 }
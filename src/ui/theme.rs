@@ -5,6 +5,7 @@
 //! `app.colors` or `self.colors` instead of using inline RGB literals.
 
 use crate::formats::RiskLevel;
+use crate::settings::RiskColorOverrides;
 use eframe::egui;
 use eframe::egui::Color32;
 
@@ -29,9 +30,21 @@ pub struct AppColors {
 
     // -- Selection / search / bookmark highlights --
     pub selection_bg: Color32,
+    /// Span picked in "Measure Distance" mode
+    pub measure_bg: Color32,
     pub current_match_bg: Color32,
     pub search_match_bg: Color32,
     pub bookmark_bg: Color32,
+    /// Bytes that differ from the original file
+    pub diff_bg: Color32,
+    /// Bytes that differ from the file opened in "Compare with File"
+    pub file_diff_bg: Color32,
+    /// Brief flash on a protected byte the user tried to edit
+    pub blocked_edit_bg: Color32,
+
+    // -- Entropy overlay (low = structural, high = compressed/noisy) --
+    pub entropy_low: Color32,
+    pub entropy_high: Color32,
 
     // -- Status indicators --
     pub modified_indicator: Color32,
@@ -82,9 +95,16 @@ impl AppColors {
             cursor_dim_insert: Color32::from_rgb(40, 80, 40),
 
             selection_bg: Color32::from_rgb(30, 72, 112),
+            measure_bg: Color32::from_rgb(96, 72, 136),
             current_match_bg: Color32::from_rgb(212, 144, 48),
             search_match_bg: Color32::from_rgb(160, 160, 64),
             bookmark_bg: Color32::from_rgb(36, 120, 148),
+            diff_bg: Color32::from_rgb(112, 48, 112),
+            file_diff_bg: Color32::from_rgb(40, 112, 64),
+            blocked_edit_bg: Color32::from_rgb(176, 40, 40),
+
+            entropy_low: Color32::from_rgb(64, 112, 224),
+            entropy_high: Color32::from_rgb(224, 64, 160),
 
             modified_indicator: Color32::from_rgb(224, 168, 48),
             warning_text: Color32::from_rgb(240, 200, 80),
@@ -121,9 +141,16 @@ impl AppColors {
             cursor_dim_insert: Color32::from_rgb(144, 200, 144),
 
             selection_bg: Color32::from_rgb(104, 168, 224),
+            measure_bg: Color32::from_rgb(200, 176, 232),
             current_match_bg: Color32::from_rgb(232, 168, 64),
             search_match_bg: Color32::from_rgb(216, 208, 80),
             bookmark_bg: Color32::from_rgb(96, 192, 216),
+            diff_bg: Color32::from_rgb(224, 176, 224),
+            file_diff_bg: Color32::from_rgb(168, 224, 176),
+            blocked_edit_bg: Color32::from_rgb(240, 128, 128),
+
+            entropy_low: Color32::from_rgb(48, 96, 216),
+            entropy_high: Color32::from_rgb(208, 40, 136),
 
             modified_indicator: Color32::from_rgb(192, 136, 0),
             warning_text: Color32::from_rgb(168, 128, 0),
@@ -155,12 +182,43 @@ impl AppColors {
         }
     }
 
+    /// Replace the theme's risk colors with any the user has customized
+    pub fn with_risk_overrides(mut self, overrides: &RiskColorOverrides) -> Self {
+        for level in RiskLevel::ALL {
+            if let Some([r, g, b]) = overrides.get(level) {
+                let slot = match level {
+                    RiskLevel::Safe => &mut self.risk_safe,
+                    RiskLevel::Caution => &mut self.risk_caution,
+                    RiskLevel::High => &mut self.risk_high,
+                    RiskLevel::Critical => &mut self.risk_critical,
+                    RiskLevel::Unknown => &mut self.risk_unknown,
+                };
+                *slot = Color32::from_rgb(r, g, b);
+            }
+        }
+        self
+    }
+
     /// Translucent background color for a risk level (hex view section tint).
     pub fn risk_bg_color(&self, level: RiskLevel) -> Color32 {
         let solid = self.risk_color(level);
         Color32::from_rgba_unmultiplied(solid.r(), solid.g(), solid.b(), self.risk_bg_alpha)
     }
 
+    /// Translucent background for a normalized entropy value (0.0-1.0),
+    /// blending from `entropy_low` to `entropy_high`.
+    pub fn entropy_bg_color(&self, entropy: f32) -> Color32 {
+        let t = entropy.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        let (lo, hi) = (self.entropy_low, self.entropy_high);
+        Color32::from_rgba_unmultiplied(
+            lerp(lo.r(), hi.r()),
+            lerp(lo.g(), hi.g()),
+            lerp(lo.b(), hi.b()),
+            self.risk_bg_alpha,
+        )
+    }
+
     /// Apply surface/accent colors to egui's built-in Visuals.
     pub fn apply_to_visuals(&self, visuals: &mut egui::Visuals) {
         visuals.panel_fill = self.bg_surface;
@@ -251,6 +309,20 @@ mod tests {
         assert_eq!(c.risk_color(RiskLevel::Unknown), c.risk_unknown);
     }
 
+    #[test]
+    fn risk_overrides_replace_only_set_levels() {
+        let overrides = RiskColorOverrides {
+            critical: Some([1, 2, 3]),
+            ..Default::default()
+        };
+        let base = AppColors::light();
+        let c = base.with_risk_overrides(&overrides);
+        assert_eq!(c.risk_critical, Color32::from_rgb(1, 2, 3));
+        assert_eq!(c.risk_safe, base.risk_safe);
+        // Tints keep the theme's alpha
+        assert_eq!(c.risk_bg_color(RiskLevel::Critical).a(), 70);
+    }
+
     #[test]
     fn risk_bg_has_correct_alpha() {
         let c = AppColors::dark();